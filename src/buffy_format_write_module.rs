@@ -28,6 +28,12 @@
 use std::io::{self, Write};
 use std::path::Path;
 
+// Style codes print per the active color theme (lookup only, no allocation)
+use super::color_theme_module::{themed, themed_bytes};
+
+// Terminal column widths, to tell whether a frame row would wrap
+use super::lines_editor_module::double_width::char_display_width;
+
 /*
 ```
 print!() and println!() - are macros that use format!() internally,
@@ -331,11 +337,13 @@ fn format_u64_to_buffer<'a>(value: u64, buf: &'a mut [u8]) -> Option<&'a str> {
 ///
 /// Memory: should be all stack, no heap
 /// Concatenates ANSI codes directly into buffer.
+/// Each code goes through the active color theme (NO_COLOR, monochrome,
+/// palettes), so a code may be swapped for another or dropped entirely.
 pub fn style_to_ansi<'a>(style: BuffyStyles, buf: &'a mut [u8]) -> Option<&'a str> {
     let mut pos = 0;
 
     if style.bold {
-        let code = b"\x1b[1m";
+        let code = themed_bytes(b"\x1b[1m");
         if pos + code.len() > buf.len() {
             return None;
         }
@@ -344,7 +352,7 @@ pub fn style_to_ansi<'a>(style: BuffyStyles, buf: &'a mut [u8]) -> Option<&'a st
     }
 
     if style.underline {
-        let code = b"\x1b[4m";
+        let code = themed_bytes(b"\x1b[4m");
         if pos + code.len() > buf.len() {
             return None;
         }
@@ -353,7 +361,7 @@ pub fn style_to_ansi<'a>(style: BuffyStyles, buf: &'a mut [u8]) -> Option<&'a st
     }
 
     if style.italic {
-        let code = b"\x1b[3m";
+        let code = themed_bytes(b"\x1b[3m");
         if pos + code.len() > buf.len() {
             return None;
         }
@@ -362,7 +370,7 @@ pub fn style_to_ansi<'a>(style: BuffyStyles, buf: &'a mut [u8]) -> Option<&'a st
     }
 
     if style.dim {
-        let code = b"\x1b[2m";
+        let code = themed_bytes(b"\x1b[2m");
         if pos + code.len() > buf.len() {
             return None;
         }
@@ -371,7 +379,7 @@ pub fn style_to_ansi<'a>(style: BuffyStyles, buf: &'a mut [u8]) -> Option<&'a st
     }
 
     if let Some(fg) = style.fg_color {
-        let code = fg.as_bytes();
+        let code = themed(fg).as_bytes();
         if pos + code.len() > buf.len() {
             return None;
         }
@@ -380,7 +388,7 @@ pub fn style_to_ansi<'a>(style: BuffyStyles, buf: &'a mut [u8]) -> Option<&'a st
     }
 
     if let Some(bg) = style.bg_color {
-        let code = bg.as_bytes();
+        let code = themed(bg).as_bytes();
         if pos + code.len() > buf.len() {
             return None;
         }
@@ -570,6 +578,33 @@ fn apply_alignment<'a>(value: &str, spec: FormatSpec, buf: &'a mut [u8]) -> Opti
 /// buffy_print("{:<10} {:>5}", &[BuffyFormatArg::Str("Name"), BuffyFormatArg::U32(123)])?;
 /// ```
 pub fn buffy_print(template: &str, args: &[BuffyFormatArg]) -> io::Result<()> {
+    let mut stdout = io::stdout();
+    buffy_write(&mut stdout, template, args)
+}
+
+/// Writes formatted output to any writer (stdout, a `BuffyFrame`, a file).
+///
+/// ## Project Context
+/// The underlying mechanism of `buffy_print()`. Renderers that draw into a
+/// `BuffyFrame` (to emit only changed rows) use this with the frame as the
+/// writer; everything else behaves exactly like `buffy_print()`.
+///
+/// Memory: should be all stack, no heap
+/// (The writer itself may buffer, e.g. a `BuffyFrame` keeps its rows.)
+///
+/// ## Parameters
+/// - writer: Destination implementing `Write`
+/// - template: Format string with {} or {:<N}/{:>N}/{:^N} placeholders
+/// - args: Slice of BuffyFormatArg values (max 8)
+///
+/// ## Returns
+/// - Ok(()): Successfully written
+/// - Err(io::Error): Write failed or format error
+pub fn buffy_write<W: Write>(
+    writer: &mut W,
+    template: &str,
+    args: &[BuffyFormatArg],
+) -> io::Result<()> {
     const MAX_ARGS: usize = 8;
 
     if args.len() > MAX_ARGS {
@@ -579,7 +614,6 @@ pub fn buffy_print(template: &str, args: &[BuffyFormatArg]) -> io::Result<()> {
         ));
     }
 
-    let mut stdout = io::stdout();
     let mut arg_index = 0;
     let mut pos = 0;
 
@@ -595,7 +629,7 @@ pub fn buffy_print(template: &str, args: &[BuffyFormatArg]) -> io::Result<()> {
 
             // Write literal text before placeholder
             if brace_pos > 0 {
-                stdout.write_all(template[pos..absolute_brace].as_bytes())?;
+                writer.write_all(template[pos..absolute_brace].as_bytes())?;
             }
 
             // Find closing brace
@@ -768,17 +802,17 @@ pub fn buffy_print(template: &str, args: &[BuffyFormatArg]) -> io::Result<()> {
                     let ansi = style_to_ansi(style, &mut style_buf).ok_or_else(|| {
                         io::Error::new(io::ErrorKind::Other, "BuffyStyles conversion failed")
                     })?;
-                    stdout.write_all(ansi.as_bytes())?;
+                    writer.write_all(ansi.as_bytes())?;
                 }
 
                 // Apply alignment and write
                 let aligned = apply_alignment(value_str, spec, &mut align_buf)
                     .ok_or_else(|| io::Error::new(io::ErrorKind::Other, "Alignment failed"))?;
-                stdout.write_all(aligned.as_bytes())?;
+                writer.write_all(aligned.as_bytes())?;
 
                 // Reset style if needed
                 if has_style {
-                    stdout.write_all(b"\x1b[0m")?;
+                    writer.write_all(themed_bytes(b"\x1b[0m"))?;
                 }
 
                 arg_index += 1;
@@ -791,7 +825,7 @@ pub fn buffy_print(template: &str, args: &[BuffyFormatArg]) -> io::Result<()> {
             }
         } else {
             // No more placeholders, write remaining literal text
-            stdout.write_all(template[pos..].as_bytes())?;
+            writer.write_all(template[pos..].as_bytes())?;
             break;
         }
     }
@@ -810,6 +844,312 @@ pub fn buffy_println(template: &str, args: &[BuffyFormatArg]) -> io::Result<()>
     stdout.flush()
 }
 
+/// Writes formatted output plus a newline to any writer (no flush).
+///
+/// Memory: should be all stack, no heap
+/// Calls buffy_write() then writes newline. Flushing is left to the caller
+/// (a `BuffyFrame` is flushed once per frame by `present`).
+pub fn buffy_writeln<W: Write>(
+    writer: &mut W,
+    template: &str,
+    args: &[BuffyFormatArg],
+) -> io::Result<()> {
+    buffy_write(writer, template, args)?;
+    writer.write_all(b"\n")
+}
+
+// =============================================================================
+// FRAME-DIFF RENDERING - DOUBLE-BUFFERED SCREEN
+// =============================================================================
+//
+// ## Project Context
+// ff's directory listing and the Lines text view used to clear the screen
+// and repaint every row for every command, which flickers over SSH and in
+// tmux. A `BuffyFrame` keeps the rows of the previous frame; the renderer
+// writes the new frame into it (it implements `Write`, rows split on '\n'),
+// then `present()` compares row by row and sends only:
+//
+// - cursor-position + row bytes + clear-to-end-of-line for changed rows
+// - the LAST row always (input is typed and echoed there)
+// - clear-to-end-of-screen (old longer frames, echoed Enter)
+//
+// all in one write. After a resize (size differs from the last frame) or
+// `invalidate()` (something else drew on the screen) the whole frame is
+// repainted from a cleared screen.
+//
+// Row positions only hold while every row fits on one screen line: a frame
+// taller than the terminal scrolls, and a row wider than it wraps. Such a
+// frame is written top to bottom like plain output instead, and the next
+// frame is repainted in full.
+//
+// ## Memory
+// The one heap user in buffy: row buffers are allocated while the first
+// frames are built and then reused (cleared, capacity kept) frame to frame.
+
+/// Clear entire screen and home the cursor
+const FRAME_CLEAR_SCREEN: &[u8] = b"\x1b[H\x1b[2J";
+
+/// Clear from cursor to end of line
+const FRAME_CLEAR_TO_LINE_END: &[u8] = b"\x1b[K";
+
+/// Clear from cursor to end of screen
+const FRAME_CLEAR_TO_SCREEN_END: &[u8] = b"\x1b[J";
+
+/// Terminal columns a frame row takes, not counting escape sequences
+///
+/// CSI sequences (`ESC [ ... final byte`, e.g. colors) take no columns;
+/// other characters count as `char_display_width` says.
+fn frame_row_display_width(row: &[u8]) -> usize {
+    let row_text = String::from_utf8_lossy(row);
+    let mut row_chars = row_text.chars();
+    let mut display_width = 0;
+
+    while let Some(character) = row_chars.next() {
+        if character == '\x1b' {
+            // Skip the sequence: ESC [ parameters final-byte, or ESC + one char
+            if row_chars.next() == Some('[') {
+                for sequence_char in row_chars.by_ref() {
+                    if ('@'..='~').contains(&sequence_char) {
+                        break;
+                    }
+                }
+            }
+            continue;
+        }
+        display_width += char_display_width(character);
+    }
+    display_width
+}
+
+/// Double-buffered screen frame that redraws only changed rows
+///
+/// ## Usage
+/// ```rust
+/// let mut frame = BuffyFrame::new();            // keep across redraws
+/// frame.begin_frame();
+/// buffy_writeln(&mut frame, "{}", &[BuffyFormatArg::Str("row one")])?;
+/// frame.write_all(b"prompt row")?;
+/// frame.present(&mut io::stdout(), Some((rows, cols)))?;
+/// ```
+#[derive(Debug, Default)]
+pub struct BuffyFrame {
+    /// Rows on screen after the last `present` (reused buffers)
+    previous_rows: Vec<Vec<u8>>,
+    /// Number of valid rows in `previous_rows`
+    previous_row_count: usize,
+    /// Rows of the frame being built (reused buffers)
+    current_rows: Vec<Vec<u8>>,
+    /// Index of the row currently being written (rows before it are complete)
+    current_row_index: usize,
+    /// Bytes for one `present`, sent with a single write
+    output_bytes: Vec<u8>,
+    /// Terminal (rows, cols) the previous frame was drawn at
+    previous_terminal_size: Option<(u16, u16)>,
+    /// Next `present` repaints every row on a cleared screen
+    needs_full_repaint: bool,
+}
+
+impl BuffyFrame {
+    /// Creates an empty frame; the first `present` is a full repaint.
+    pub fn new() -> Self {
+        BuffyFrame {
+            needs_full_repaint: true,
+            ..Default::default()
+        }
+    }
+
+    /// Starts building a new frame (previous frame is kept for comparison).
+    pub fn begin_frame(&mut self) {
+        for row in self.current_rows.iter_mut() {
+            row.clear();
+        }
+        self.current_row_index = 0;
+    }
+
+    /// Forces the next `present` to repaint everything.
+    ///
+    /// Call when something other than this frame drew on the screen
+    /// (a prompt, another view, an external program).
+    pub fn invalidate(&mut self) {
+        self.needs_full_repaint = true;
+    }
+
+    /// Number of rows in the frame being built (the last, open row included).
+    pub fn row_count(&self) -> usize {
+        self.current_row_index + 1
+    }
+
+    /// Whether the frame being built shows each row on its own screen line.
+    ///
+    /// ## Parameters
+    /// - terminal_size: (rows, cols), or None if unknown (assumed to fit)
+    ///
+    /// ## Returns
+    /// - bool: false if there are more rows than the terminal has, or a row
+    ///   is wider than it (escape sequences take no columns)
+    pub fn fits_screen(&self, terminal_size: Option<(u16, u16)>) -> bool {
+        let row_total = self.row_count();
+        match terminal_size {
+            Some((screen_rows, screen_cols)) => {
+                row_total <= screen_rows as usize
+                    && self.current_rows[..row_total.min(self.current_rows.len())]
+                        .iter()
+                        .all(|row| frame_row_display_width(row) <= screen_cols as usize)
+            }
+            None => true,
+        }
+    }
+
+    /// Appends bytes to the current row, starting new rows at '\n'.
+    /// Carriage returns are dropped: rows are positioned explicitly.
+    fn push_frame_bytes(&mut self, bytes: &[u8]) {
+        for segment_and_more in bytes.split_inclusive(|byte| *byte == b'\n') {
+            if self.current_rows.len() <= self.current_row_index {
+                self.current_rows.push(Vec::new());
+            }
+
+            let (segment, ends_row) = match segment_and_more.split_last() {
+                Some((b'\n', segment)) => (segment, true),
+                _ => (segment_and_more, false),
+            };
+
+            let row = &mut self.current_rows[self.current_row_index];
+            row.extend(segment.iter().filter(|byte| **byte != b'\r'));
+
+            if ends_row {
+                self.current_row_index += 1;
+            }
+        }
+
+        // Keep the open row allocated so comparisons can index it
+        if self.current_rows.len() <= self.current_row_index {
+            self.current_rows.push(Vec::new());
+        }
+    }
+
+    /// Builds the escape-sequence output for the current frame and makes it
+    /// the previous frame.
+    ///
+    /// ## Parameters
+    /// - terminal_size: (rows, cols) now, or None if unknown; a change
+    ///   from the previous frame forces a full repaint
+    ///
+    /// ## Returns
+    /// - (&[u8], usize): Bytes to write, and how many rows they redraw
+    pub fn build_frame_output(&mut self, terminal_size: Option<(u16, u16)>) -> (&[u8], usize) {
+        let row_total = self.row_count();
+        if self.current_rows.len() < row_total {
+            self.current_rows.resize_with(row_total, Vec::new);
+        }
+
+        let full_repaint = self.needs_full_repaint || terminal_size != self.previous_terminal_size;
+
+        self.output_bytes.clear();
+        if full_repaint {
+            self.output_bytes.extend_from_slice(FRAME_CLEAR_SCREEN);
+        }
+
+        // A frame that scrolls or wraps would put rows off their positions:
+        // write it sequentially and repaint the next frame in full
+        if !self.fits_screen(terminal_size) {
+            if !full_repaint {
+                self.output_bytes.extend_from_slice(FRAME_CLEAR_SCREEN);
+            }
+            for (row_index, row) in self.current_rows[..row_total].iter().enumerate() {
+                if row_index > 0 {
+                    self.output_bytes.extend_from_slice(b"\r\n");
+                }
+                self.output_bytes.extend_from_slice(row);
+            }
+
+            std::mem::swap(&mut self.previous_rows, &mut self.current_rows);
+            self.previous_row_count = row_total;
+            self.previous_terminal_size = terminal_size;
+            self.needs_full_repaint = true;
+            return (&self.output_bytes, row_total);
+        }
+
+        let mut rows_redrawn = 0;
+        let mut number_buffer = [0u8; 20];
+        for row_index in 0..row_total {
+            let is_last_row = row_index + 1 == row_total;
+            let row_unchanged = !full_repaint
+                && row_index < self.previous_row_count
+                && self.previous_rows[row_index] == self.current_rows[row_index];
+
+            // The last row is where input is typed and echoed: always redraw it
+            if row_unchanged && !is_last_row {
+                continue;
+            }
+
+            // Step 1: Position at (row, column 1); terminal rows are 1-based
+            self.output_bytes.extend_from_slice(b"\x1b[");
+            if let Some(row_digits) =
+                format_u64_to_buffer((row_index + 1) as u64, &mut number_buffer)
+            {
+                self.output_bytes.extend_from_slice(row_digits.as_bytes());
+            }
+            self.output_bytes.extend_from_slice(b";1H");
+
+            // Step 2: Row content, then clear whatever the old row had beyond it
+            self.output_bytes
+                .extend_from_slice(&self.current_rows[row_index]);
+            self.output_bytes.extend_from_slice(FRAME_CLEAR_TO_LINE_END);
+            rows_redrawn += 1;
+        }
+
+        // Step 3: Clear below the frame (old longer frame, echoed input)
+        if !full_repaint {
+            self.output_bytes
+                .extend_from_slice(FRAME_CLEAR_TO_SCREEN_END);
+        }
+
+        // Step 4: This frame is now what the screen shows
+        std::mem::swap(&mut self.previous_rows, &mut self.current_rows);
+        self.previous_row_count = row_total;
+        self.previous_terminal_size = terminal_size;
+        self.needs_full_repaint = false;
+
+        (&self.output_bytes, rows_redrawn)
+    }
+
+    /// Sends the current frame to the terminal in one write and flushes.
+    ///
+    /// ## Parameters
+    /// - writer: Terminal output (normally stdout)
+    /// - terminal_size: (rows, cols) now, or None if unknown
+    ///
+    /// ## Returns
+    /// - Ok(usize): Number of rows redrawn
+    /// - Err(io::Error): Write or flush failed (next frame repaints fully)
+    pub fn present<W: Write>(
+        &mut self,
+        writer: &mut W,
+        terminal_size: Option<(u16, u16)>,
+    ) -> io::Result<usize> {
+        let (frame_output, rows_redrawn) = self.build_frame_output(terminal_size);
+        let write_result = writer.write_all(frame_output).and_then(|_| writer.flush());
+        if write_result.is_err() {
+            // Screen state unknown after a failed write
+            self.needs_full_repaint = true;
+        }
+        write_result.map(|_| rows_redrawn)
+    }
+}
+
+impl Write for BuffyFrame {
+    /// Appends to the frame being built (never fails, never short).
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.push_frame_bytes(buf);
+        Ok(buf.len())
+    }
+
+    /// No-op: output happens in `present`.
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
 // /// Writes formatted output to any writer.
 // ///
 // /// Memory: should be all stack, no heap
@@ -1617,6 +1957,129 @@ pub fn buffy_get_syntax_highlight(byte_pos: usize, row_content: &str) -> SyntaxH
 // TESTS: Syntax Highlighting
 // =============================================================================
 
+#[cfg(test)]
+mod buffy_frame_tests {
+    use super::*;
+
+    /// Writes `rows` into a fresh frame build, one row per line, no trailing newline.
+    fn build_rows(frame: &mut BuffyFrame, rows: &[&str]) {
+        frame.begin_frame();
+        for (index, row) in rows.iter().enumerate() {
+            if index > 0 {
+                frame.write_all(b"\n").expect("frame write");
+            }
+            frame.write_all(row.as_bytes()).expect("frame write");
+        }
+    }
+
+    /// The first frame clears the screen and draws every row.
+    #[test]
+    fn test_first_frame_is_full_repaint() {
+        let mut frame = BuffyFrame::new();
+        build_rows(&mut frame, &["one", "two", ">> "]);
+        let (output, rows_redrawn) = frame.build_frame_output(Some((24, 80)));
+        let output = String::from_utf8_lossy(output).into_owned();
+
+        assert!(output.starts_with("\x1b[H\x1b[2J"));
+        assert!(output.contains("\x1b[1;1Hone\x1b[K"));
+        assert!(output.contains("\x1b[3;1H>> \x1b[K"));
+        assert_eq!(rows_redrawn, 3);
+    }
+
+    /// Only changed rows and the last row are redrawn, then the rest is cleared.
+    #[test]
+    fn test_unchanged_rows_are_skipped() {
+        let mut frame = BuffyFrame::new();
+        build_rows(&mut frame, &["one", "two", "three", ">> "]);
+        frame.build_frame_output(Some((24, 80)));
+
+        build_rows(&mut frame, &["one", "TWO", "three", ">> "]);
+        let (output, rows_redrawn) = frame.build_frame_output(Some((24, 80)));
+        let output = String::from_utf8_lossy(output).into_owned();
+
+        assert_eq!(output, "\x1b[2;1HTWO\x1b[K\x1b[4;1H>> \x1b[K\x1b[J");
+        assert_eq!(rows_redrawn, 2);
+    }
+
+    /// A shorter frame redraws the new last row and clears the leftovers below.
+    #[test]
+    fn test_shorter_frame_clears_below() {
+        let mut frame = BuffyFrame::new();
+        build_rows(&mut frame, &["one", "two", "three"]);
+        frame.build_frame_output(None);
+
+        build_rows(&mut frame, &["one", "two"]);
+        let (output, rows_redrawn) = frame.build_frame_output(None);
+
+        assert_eq!(output, b"\x1b[2;1Htwo\x1b[K\x1b[J");
+        assert_eq!(rows_redrawn, 1);
+    }
+
+    /// A resize or an explicit invalidate forces a full repaint.
+    #[test]
+    fn test_resize_and_invalidate_force_full_repaint() {
+        let mut frame = BuffyFrame::new();
+        build_rows(&mut frame, &["one", "two"]);
+        frame.build_frame_output(Some((24, 80)));
+
+        build_rows(&mut frame, &["one", "two"]);
+        let (_, rows_redrawn) = frame.build_frame_output(Some((30, 100)));
+        assert_eq!(rows_redrawn, 2);
+
+        frame.invalidate();
+        build_rows(&mut frame, &["one", "two"]);
+        let (output, rows_redrawn) = frame.build_frame_output(Some((30, 100)));
+        assert!(output.starts_with(FRAME_CLEAR_SCREEN));
+        assert_eq!(rows_redrawn, 2);
+    }
+
+    /// A frame taller or wider than the terminal is written sequentially,
+    /// and the frame after it is a full repaint again.
+    #[test]
+    fn test_oversized_frame_falls_back_to_sequential_repaint() {
+        let mut frame = BuffyFrame::new();
+        build_rows(&mut frame, &["one", "two", ">> "]);
+        frame.build_frame_output(Some((24, 80)));
+
+        build_rows(&mut frame, &["one", "two", "three", ">> "]);
+        assert!(!frame.fits_screen(Some((3, 80))));
+        assert!(frame.fits_screen(Some((4, 80))));
+        let (output, rows_redrawn) = frame.build_frame_output(Some((3, 80)));
+        assert_eq!(output, b"\x1b[H\x1b[2Jone\r\ntwo\r\nthree\r\n>> ");
+        assert_eq!(rows_redrawn, 4);
+
+        let wide_row = format!("\x1b[1m{}\x1b[0m", "x".repeat(11));
+        build_rows(&mut frame, &[&wide_row, ">> "]);
+        let (output, _) = frame.build_frame_output(Some((3, 10)));
+        assert!(!output.windows(3).any(|window| window == b";1H"));
+
+        // Colors take no columns, so this row fits in 11 columns
+        build_rows(&mut frame, &[&wide_row, ">> "]);
+        let (output, rows_redrawn) = frame.build_frame_output(Some((3, 11)));
+        assert!(output.starts_with(FRAME_CLEAR_SCREEN));
+        assert!(output.windows(6).any(|window| window == b"\x1b[1;1H"));
+        assert_eq!(rows_redrawn, 2);
+    }
+
+    /// buffy_write formats into a frame; carriage returns are dropped.
+    #[test]
+    fn test_buffy_write_into_frame() {
+        let mut frame = BuffyFrame::new();
+        frame.begin_frame();
+        buffy_writeln(
+            &mut frame,
+            "{:>3}. {}\r",
+            &[BuffyFormatArg::Usize(7), BuffyFormatArg::Str("x")],
+        )
+        .expect("buffy_writeln into frame");
+        assert_eq!(frame.row_count(), 2);
+
+        let (output, _) = frame.build_frame_output(None);
+        let output = String::from_utf8_lossy(output).into_owned();
+        assert!(output.contains("\x1b[1;1H  7. x\x1b[K"));
+    }
+}
+
 #[cfg(test)]
 mod syntax_highlight_tests {
     use super::*;
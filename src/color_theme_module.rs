@@ -0,0 +1,558 @@
+//! color_theme_module.rs - Color themes, NO_COLOR and monochrome output
+//!
+//! ## Project Context
+//!
+//! ff, Lines, the pasty TUI and the line-count TUI write ANSI SGR escapes
+//! ("\x1b[31m" etc.) written for a dark terminal. This module decides, once
+//! per run, what each of those standard escapes should really print:
+//!
+//! - **Color** (a named palette): the escape itself, or the palette's
+//!   replacement (e.g. the "light" palette turns yellow text blue)
+//! - **Monochrome**: bold and reverse video only - red (hotkeys, errors)
+//!   becomes bold, background highlights become reverse video, every
+//!   other color prints nothing
+//! - **Plain**: no escapes at all
+//!
+//! ## How Callers Use It
+//!
+//! Color constants are `ThemedSgr` values instead of `&str`:
+//! `const RED: ThemedSgr = ThemedSgr("\x1b[31m");` still works in
+//! `format!("{}q{}uit", RED, YELLOW)`, but prints the themed escape.
+//! Byte-slice escapes go through `themed_bytes`, and buffy's
+//! `style_to_ansi` themes `BuffyStyles`, so the Lines TUI follows too.
+//!
+//! ## Choosing the Theme (first match wins)
+//!
+//! 1. `TERM=dumb`, or stdout is not a terminal (piped): plain
+//! 2. `NO_COLOR` set and not empty (https://no-color.org): monochrome
+//! 3. `FF_THEME=name` environment variable
+//! 4. First word of `ff_data/color_theme.txt`
+//! 5. "default"
+//!
+//! A name is a built-in palette (default, light, mono, plain) or a file
+//! `ff_data/themes/{name}.txt` of `slot = SGR parameters` lines, e.g.
+//! `yellow = 38;5;130`, applied on top of the default palette.
+//!
+//! ## Memory
+//!
+//! The theme is built once (a few small Strings) and stored in a OnceLock;
+//! every lookup after that returns a `&'static str` with no allocation.
+
+use std::fmt;
+use std::fs;
+use std::io::{self, IsTerminal};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+// Palettes and the theme choice live in ff_data, next to the executable
+use super::ff_file_fantastic_module::FF_DATA_DIRECTORY_NAME;
+
+/// File in ff_data whose first word names the theme to use
+pub const COLOR_THEME_CONFIG_FILENAME: &str = "color_theme.txt";
+
+/// Subdirectory of ff_data holding user palettes, one `{name}.txt` per theme
+pub const COLOR_THEME_PALETTES_DIRECTORY_NAME: &str = "themes";
+
+/// Environment variable naming a theme (overrides color_theme.txt)
+pub const COLOR_THEME_ENVIRONMENT_VARIABLE: &str = "FF_THEME";
+
+/// Built-in theme names
+pub const BUILT_IN_THEME_NAMES: &[&str] = &["default", "light", "mono", "plain"];
+
+/// Longest palette file read (a palette is a few dozen short lines)
+const MAX_PALETTE_FILE_BYTES: u64 = 16 * 1024;
+
+/// Every standard SGR escape the program writes, with its palette slot name
+///
+/// A palette line `slot = parameters` replaces the escape for that slot.
+const STANDARD_SGR_SLOTS: &[(&str, &str)] = &[
+    ("reset", "\x1b[0m"),
+    ("bold", "\x1b[1m"),
+    ("dim", "\x1b[2m"),
+    ("italic", "\x1b[3m"),
+    ("underline", "\x1b[4m"),
+    ("blink", "\x1b[5m"),
+    ("reverse", "\x1b[7m"),
+    ("hidden", "\x1b[8m"),
+    ("black", "\x1b[30m"),
+    ("red", "\x1b[31m"),
+    ("green", "\x1b[32m"),
+    ("yellow", "\x1b[33m"),
+    ("blue", "\x1b[34m"),
+    ("magenta", "\x1b[35m"),
+    ("cyan", "\x1b[36m"),
+    ("white", "\x1b[37m"),
+    ("default_fg", "\x1b[39m"),
+    ("bg_black", "\x1b[40m"),
+    ("bg_red", "\x1b[41m"),
+    ("bg_green", "\x1b[42m"),
+    ("bg_yellow", "\x1b[43m"),
+    ("bg_blue", "\x1b[44m"),
+    ("bg_magenta", "\x1b[45m"),
+    ("bg_cyan", "\x1b[46m"),
+    ("bg_white", "\x1b[47m"),
+    ("default_bg", "\x1b[49m"),
+    ("gray", "\x1b[90m"),
+    ("bright_white", "\x1b[97m"),
+];
+
+/// The built-in "light" palette: readable on white/light backgrounds
+const LIGHT_PALETTE_TEXT: &str = "\
+# Built-in light palette (dark text for light backgrounds)
+yellow = 34
+green = 38;5;28
+cyan = 38;5;30
+white = 30
+bright_white = 30
+gray = 38;5;240
+bg_white = 43
+";
+
+/// How escapes are printed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorMode {
+    /// Colors from a palette
+    Color,
+    /// Bold and reverse video only
+    Monochrome,
+    /// No escapes at all
+    Plain,
+}
+
+/// A resolved theme: what each standard escape prints
+#[derive(Debug, Clone)]
+pub struct ColorTheme {
+    /// Theme name, as chosen (e.g. "light", "mono")
+    pub name: String,
+    /// Color, monochrome or plain
+    pub mode: ColorMode,
+    /// Replacement escape for each entry of STANDARD_SGR_SLOTS (same order)
+    slot_escapes: Vec<String>,
+}
+
+impl ColorTheme {
+    /// The default palette: every escape prints as written
+    pub fn default_palette() -> ColorTheme {
+        ColorTheme {
+            name: String::from("default"),
+            mode: ColorMode::Color,
+            slot_escapes: STANDARD_SGR_SLOTS
+                .iter()
+                .map(|(_, escape)| escape.to_string())
+                .collect(),
+        }
+    }
+
+    /// Bold and reverse video only
+    ///
+    /// Red marks hotkeys and errors, so it becomes bold; background colors
+    /// mark the cursor and selections, so they become reverse video.
+    pub fn monochrome() -> ColorTheme {
+        let slot_escapes = STANDARD_SGR_SLOTS
+            .iter()
+            .map(|(slot_name, escape)| match *slot_name {
+                "reset" | "bold" | "reverse" => escape.to_string(),
+                "red" => String::from("\x1b[1m"),
+                "default_bg" => String::from("\x1b[27m"),
+                background if background.starts_with("bg_") => String::from("\x1b[7m"),
+                _ => String::new(),
+            })
+            .collect();
+
+        ColorTheme {
+            name: String::from("mono"),
+            mode: ColorMode::Monochrome,
+            slot_escapes,
+        }
+    }
+
+    /// No escapes at all (dumb terminals, piped output)
+    pub fn plain() -> ColorTheme {
+        ColorTheme {
+            name: String::from("plain"),
+            mode: ColorMode::Plain,
+            slot_escapes: vec![String::new(); STANDARD_SGR_SLOTS.len()],
+        }
+    }
+
+    /// Builds a palette from `slot = SGR parameters` lines on top of the default
+    ///
+    /// # Arguments
+    /// * `name` - Theme name to record
+    /// * `palette_text` - Palette file contents; `#` starts a comment
+    ///
+    /// # Returns
+    /// * `Ok(ColorTheme)` - The palette
+    /// * `Err(String)` - First bad line: unknown slot, or parameters that are
+    ///   not digits separated by ';' (so a palette cannot inject other escapes)
+    pub fn from_palette_text(name: &str, palette_text: &str) -> Result<ColorTheme, String> {
+        let mut theme = ColorTheme::default_palette();
+        theme.name = name.to_string();
+
+        for (line_index, raw_line) in palette_text.lines().enumerate() {
+            // Step 1: Drop comments and blank lines
+            let line = raw_line.split('#').next().unwrap_or("").trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            // Step 2: slot = parameters
+            let (slot_name, parameters) = match line.split_once('=') {
+                Some((slot_name, parameters)) => (slot_name.trim(), parameters.trim()),
+                None => {
+                    return Err(format!(
+                        "line {}: expected 'slot = parameters'",
+                        line_index + 1
+                    ));
+                }
+            };
+
+            let slot_index = STANDARD_SGR_SLOTS
+                .iter()
+                .position(|(standard_name, _)| *standard_name == slot_name)
+                .ok_or_else(|| format!("line {}: unknown slot '{}'", line_index + 1, slot_name))?;
+
+            // Step 3: Only SGR parameters: digits separated by ';' (or empty = print nothing)
+            let parameters_are_valid = parameters.split(';').all(|parameter| {
+                !parameter.is_empty() && parameter.bytes().all(|b| b.is_ascii_digit())
+            });
+            theme.slot_escapes[slot_index] = if parameters.is_empty() {
+                String::new()
+            } else if parameters_are_valid {
+                format!("\x1b[{}m", parameters)
+            } else {
+                return Err(format!(
+                    "line {}: '{}' is not SGR parameters (e.g. 33 or 38;5;130)",
+                    line_index + 1,
+                    parameters
+                ));
+            };
+        }
+
+        Ok(theme)
+    }
+
+    /// What a standard escape prints in this theme
+    ///
+    /// # Arguments
+    /// * `standard_escape` - One of the escapes the program writes, e.g. "\x1b[33m"
+    ///
+    /// # Returns
+    /// * `&str` - The themed escape; an escape not in the slot table prints
+    ///   unchanged in color mode and as nothing in monochrome/plain mode
+    pub fn themed<'a>(&'a self, standard_escape: &'a str) -> &'a str {
+        match STANDARD_SGR_SLOTS
+            .iter()
+            .position(|(_, escape)| *escape == standard_escape)
+        {
+            Some(slot_index) => &self.slot_escapes[slot_index],
+            None if self.mode == ColorMode::Color => standard_escape,
+            None => "",
+        }
+    }
+}
+
+/// A standard escape that prints as the active theme's version of it
+///
+/// # Purpose
+/// Drop-in for `const RED: &str = "\x1b[31m";` - format arguments such as
+/// `println!("{}Error{}", RED, RESET)` keep working unchanged.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ThemedSgr(pub &'static str);
+
+impl ThemedSgr {
+    /// The themed escape as a string slice
+    pub fn as_str(self) -> &'static str {
+        themed(self.0)
+    }
+}
+
+impl fmt::Display for ThemedSgr {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str(self.as_str())
+    }
+}
+
+/// The theme for this run, chosen on first use
+static ACTIVE_COLOR_THEME: OnceLock<ColorTheme> = OnceLock::new();
+
+/// Returns the active theme (loading it on first call)
+pub fn active_color_theme() -> &'static ColorTheme {
+    ACTIVE_COLOR_THEME.get_or_init(load_active_color_theme)
+}
+
+/// What a standard escape prints in the active theme
+///
+/// # Arguments
+/// * `standard_escape` - e.g. "\x1b[31m"
+///
+/// # Returns
+/// * `&'static str` - The themed escape (possibly empty)
+pub fn themed(standard_escape: &'static str) -> &'static str {
+    active_color_theme().themed(standard_escape)
+}
+
+/// Byte-slice form of `themed`, for escapes written with `write_all`
+///
+/// # Arguments
+/// * `standard_escape` - e.g. b"\x1b[31m"
+///
+/// # Returns
+/// * `&'static [u8]` - The themed escape bytes (possibly empty)
+pub fn themed_bytes(standard_escape: &'static [u8]) -> &'static [u8] {
+    match std::str::from_utf8(standard_escape) {
+        Ok(escape_text) => themed(escape_text).as_bytes(),
+        Err(_) => standard_escape,
+    }
+}
+
+/// Picks the theme name from the environment and the configured choice
+///
+/// # Arguments
+/// * `term` - Value of TERM, if set
+/// * `no_color` - Value of NO_COLOR, if set
+/// * `stdout_is_terminal` - False when output is piped or redirected
+/// * `requested_theme` - FF_THEME, else the word in color_theme.txt
+///
+/// # Returns
+/// * `String` - "plain", "mono", the requested name, or "default"
+pub fn select_color_theme_name(
+    term: Option<&str>,
+    no_color: Option<&str>,
+    stdout_is_terminal: bool,
+    requested_theme: Option<&str>,
+) -> String {
+    if term == Some("dumb") || !stdout_is_terminal {
+        return String::from("plain");
+    }
+    if no_color.is_some_and(|value| !value.is_empty()) {
+        return String::from("mono");
+    }
+    match requested_theme.map(str::trim) {
+        Some(name) if !name.is_empty() => name.to_string(),
+        _ => String::from("default"),
+    }
+}
+
+/// Returns `{executable_directory}/ff_data`, if the executable can be located
+fn ff_data_directory() -> Option<PathBuf> {
+    let executable_path = std::env::current_exe().ok()?;
+    Some(executable_path.parent()?.join(FF_DATA_DIRECTORY_NAME))
+}
+
+/// Reads the theme name from `ff_data/color_theme.txt` (first word, `#` comments)
+fn read_configured_theme_name(ff_data_directory: &Path) -> Option<String> {
+    let config_text =
+        fs::read_to_string(ff_data_directory.join(COLOR_THEME_CONFIG_FILENAME)).ok()?;
+    config_text
+        .lines()
+        .map(|line| line.split('#').next().unwrap_or("").trim())
+        .find(|line| !line.is_empty())
+        .and_then(|line| line.split_whitespace().next())
+        .map(str::to_string)
+}
+
+/// Builds a theme by name: a built-in, or `ff_data/themes/{name}.txt`
+///
+/// # Arguments
+/// * `theme_name` - Built-in name or palette file stem
+/// * `ff_data_directory` - Where to look for palette files
+///
+/// # Returns
+/// * `Ok(ColorTheme)` - The theme
+/// * `Err(String)` - Unknown name, unreadable or invalid palette file
+pub fn load_color_theme_by_name(
+    theme_name: &str,
+    ff_data_directory: Option<&Path>,
+) -> Result<ColorTheme, String> {
+    match theme_name {
+        "default" => return Ok(ColorTheme::default_palette()),
+        "light" => return ColorTheme::from_palette_text("light", LIGHT_PALETTE_TEXT),
+        "mono" | "monochrome" => return Ok(ColorTheme::monochrome()),
+        "plain" | "none" => return Ok(ColorTheme::plain()),
+        _ => {}
+    }
+
+    // Palette file names are plain words: no paths out of the themes folder
+    let is_plain_word = theme_name
+        .chars()
+        .all(|name_char| name_char.is_ascii_alphanumeric() || name_char == '-' || name_char == '_');
+    if !is_plain_word {
+        return Err(format!(
+            "theme name '{}' must be letters, digits, - or _",
+            theme_name
+        ));
+    }
+
+    let ff_data_directory =
+        ff_data_directory.ok_or_else(|| String::from("ff_data directory not found"))?;
+    let palette_path = ff_data_directory
+        .join(COLOR_THEME_PALETTES_DIRECTORY_NAME)
+        .join(format!("{}.txt", theme_name));
+
+    let palette_size = fs::metadata(&palette_path)
+        .map_err(|e| format!("{}: {}", palette_path.display(), e))?
+        .len();
+    if palette_size > MAX_PALETTE_FILE_BYTES {
+        return Err(format!(
+            "{}: palette file too large",
+            palette_path.display()
+        ));
+    }
+
+    let palette_text = fs::read_to_string(&palette_path)
+        .map_err(|e| format!("{}: {}", palette_path.display(), e))?;
+    ColorTheme::from_palette_text(theme_name, &palette_text)
+        .map_err(|e| format!("{}: {}", palette_path.display(), e))
+}
+
+/// Chooses and loads the theme for this run
+///
+/// An unusable theme choice falls back to the default palette with a
+/// warning on stderr; ff never fails to start because of a theme.
+fn load_active_color_theme() -> ColorTheme {
+    // Tests compare exact escape bytes, whatever the test runner's terminal
+    if cfg!(test) {
+        return ColorTheme::default_palette();
+    }
+
+    let ff_data_directory = ff_data_directory();
+    let requested_theme = std::env::var(COLOR_THEME_ENVIRONMENT_VARIABLE)
+        .ok()
+        .or_else(|| {
+            ff_data_directory
+                .as_deref()
+                .and_then(read_configured_theme_name)
+        });
+
+    let theme_name = select_color_theme_name(
+        std::env::var("TERM").ok().as_deref(),
+        std::env::var("NO_COLOR").ok().as_deref(),
+        io::stdout().is_terminal(),
+        requested_theme.as_deref(),
+    );
+
+    match load_color_theme_by_name(&theme_name, ff_data_directory.as_deref()) {
+        Ok(theme) => theme,
+        Err(e) => {
+            eprintln!(
+                "Warning: color theme '{}' not loaded ({}); using default (built-in: {})",
+                theme_name,
+                e,
+                BUILT_IN_THEME_NAMES.join(", ")
+            );
+            ColorTheme::default_palette()
+        }
+    }
+}
+
+#[cfg(test)]
+mod color_theme_tests {
+    use super::*;
+
+    /// TERM=dumb and piped output win over NO_COLOR, which wins over a chosen theme
+    #[test]
+    fn test_select_color_theme_name() {
+        assert_eq!(select_color_theme_name(None, None, true, None), "default");
+        assert_eq!(
+            select_color_theme_name(Some("xterm"), None, true, Some("light")),
+            "light"
+        );
+        assert_eq!(
+            select_color_theme_name(Some("xterm"), Some("1"), true, Some("light")),
+            "mono"
+        );
+        // An empty NO_COLOR does not count
+        assert_eq!(
+            select_color_theme_name(None, Some(""), true, Some(" light ")),
+            "light"
+        );
+        assert_eq!(
+            select_color_theme_name(Some("dumb"), Some("1"), true, Some("light")),
+            "plain"
+        );
+        assert_eq!(
+            select_color_theme_name(Some("xterm"), None, false, Some("light")),
+            "plain"
+        );
+    }
+
+    /// Monochrome keeps bold/reverse, turns red bold and backgrounds reverse
+    #[test]
+    fn test_monochrome_and_plain_themes() {
+        let mono = ColorTheme::monochrome();
+        assert_eq!(mono.themed("\x1b[0m"), "\x1b[0m");
+        assert_eq!(mono.themed("\x1b[1m"), "\x1b[1m");
+        assert_eq!(mono.themed("\x1b[31m"), "\x1b[1m");
+        assert_eq!(mono.themed("\x1b[33m"), "");
+        assert_eq!(mono.themed("\x1b[47m"), "\x1b[7m");
+        assert_eq!(mono.themed("\x1b[38;5;196m"), "");
+
+        let plain = ColorTheme::plain();
+        assert_eq!(plain.themed("\x1b[0m"), "");
+        assert_eq!(plain.themed("\x1b[7m"), "");
+    }
+
+    /// Palette lines replace slots; bad lines are rejected
+    #[test]
+    fn test_palette_text() {
+        let theme = ColorTheme::from_palette_text(
+            "custom",
+            "# my palette\n yellow = 38;5;130 \nred=91 # bright red\ndim =\n",
+        )
+        .expect("valid palette");
+        assert_eq!(theme.mode, ColorMode::Color);
+        assert_eq!(theme.themed("\x1b[33m"), "\x1b[38;5;130m");
+        assert_eq!(theme.themed("\x1b[31m"), "\x1b[91m");
+        assert_eq!(theme.themed("\x1b[2m"), "");
+        // Untouched slots and unknown escapes print as written
+        assert_eq!(theme.themed("\x1b[32m"), "\x1b[32m");
+        assert_eq!(theme.themed("\x1b[38;5;196m"), "\x1b[38;5;196m");
+
+        assert!(ColorTheme::from_palette_text("x", "purple = 35").is_err());
+        assert!(ColorTheme::from_palette_text("x", "red = 31m\x1b[2J").is_err());
+        assert!(ColorTheme::from_palette_text("x", "red = 3;;1").is_err());
+        assert!(ColorTheme::from_palette_text("x", "red 31").is_err());
+    }
+
+    /// Built-in names load without files; unknown names need a palette file
+    #[test]
+    fn test_load_color_theme_by_name() {
+        for name in BUILT_IN_THEME_NAMES {
+            assert!(load_color_theme_by_name(name, None).is_ok(), "{}", name);
+        }
+        let light = load_color_theme_by_name("light", None).expect("light");
+        assert_eq!(light.themed("\x1b[33m"), "\x1b[34m");
+
+        assert!(load_color_theme_by_name("solarized", None).is_err());
+        assert!(load_color_theme_by_name("../etc", Some(Path::new("/tmp"))).is_err());
+
+        // A palette file in ff_data/themes
+        let ff_data = std::env::temp_dir().join(format!("ff_theme_test_{}", std::process::id()));
+        let themes = ff_data.join(COLOR_THEME_PALETTES_DIRECTORY_NAME);
+        fs::create_dir_all(&themes).expect("create themes dir");
+        fs::write(themes.join("ocean.txt"), "yellow = 36\n").expect("write palette");
+        fs::write(
+            ff_data.join(COLOR_THEME_CONFIG_FILENAME),
+            "# pick one\n ocean  \n",
+        )
+        .expect("write config");
+
+        assert_eq!(
+            read_configured_theme_name(&ff_data).as_deref(),
+            Some("ocean")
+        );
+        let ocean = load_color_theme_by_name("ocean", Some(&ff_data)).expect("ocean");
+        assert_eq!(ocean.themed("\x1b[33m"), "\x1b[36m");
+
+        let _ = fs::remove_dir_all(&ff_data);
+    }
+
+    /// ThemedSgr formats as the active theme (the default palette under test)
+    #[test]
+    fn test_themed_sgr_display() {
+        const RED: ThemedSgr = ThemedSgr("\x1b[31m");
+        assert_eq!(format!("{}x", RED), "\x1b[31mx");
+        assert_eq!(themed_bytes(b"\x1b[0m"), b"\x1b[0m");
+    }
+}
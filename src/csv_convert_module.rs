@@ -0,0 +1,926 @@
+// src/csv_convert_module.rs
+
+//! CSV conversion and projection for rows_and_columns
+//!
+//! This module rewrites a CSV file into another shape: a JSON array, JSON Lines,
+//! a GitHub Markdown table, or CSV with a different delimiter. Columns can be
+//! selected and reordered by name, and rows can be kept or dropped with simple
+//! filter expressions such as `age>=30` or `city=Paris`.
+//!
+//! # Core Responsibilities
+//! - Parse conversion formats and row filter expressions
+//! - Split CSV records, including quoted fields with commas and `""` escapes
+//! - Write JSON values typed from the detected `CsvColumnDataType`
+//! - Quote and escape fields correctly for each output format
+//!
+//! # Design Philosophy
+//! - Streaming: one input line is read and written at a time, so file size
+//!   is limited by disk space, not memory
+//! - Types come from the same sample-based detection used by `-rc` analysis,
+//!   applied to quote-aware fields from the first rows of the file
+//! - A value that does not fit its column type is written as a string rather
+//!   than failing the whole conversion
+
+use std::fs::File;
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+use super::csv_processor_module::{
+    analyze_csv_file_without_saving_metadata,
+    detect_column_data_type,
+    escape_json_string,
+    parse_csv_line_into_fields,
+    CsvColumnDataType,
+};
+
+// Import our error handling system
+use super::error_types_module::{
+    RowsAndColumnsResult,
+    create_file_system_error,
+    create_configuration_error,
+};
+
+/// Number of leading data rows buffered to detect column types
+const CONVERSION_TYPE_SAMPLE_ROWS: usize = 10;
+
+/// Output formats supported by CSV conversion
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CsvConversionFormat {
+    /// A single JSON array of row objects
+    JsonArray,
+
+    /// One JSON object per line (JSONL / NDJSON)
+    JsonLines,
+
+    /// GitHub-flavored Markdown table
+    MarkdownTable,
+
+    /// Delimited text with the given field delimiter (CSV, TSV, ...)
+    DelimitedText(char),
+}
+
+impl CsvConversionFormat {
+    /// Creates a conversion format from a user-facing name
+    ///
+    /// # Arguments
+    /// * `format_name` - e.g. `json`, `jsonl`, `md`, `csv`, `tsv`, `psv`, `ssv`
+    ///
+    /// # Returns
+    /// * `Option<CsvConversionFormat>` - The format or None if unknown
+    pub fn from_name(format_name: &str) -> Option<CsvConversionFormat> {
+        match format_name.trim().to_lowercase().as_str() {
+            "json" => Some(CsvConversionFormat::JsonArray),
+            "jsonl" | "ndjson" => Some(CsvConversionFormat::JsonLines),
+            "md" | "markdown" => Some(CsvConversionFormat::MarkdownTable),
+            "csv" => Some(CsvConversionFormat::DelimitedText(',')),
+            "tsv" => Some(CsvConversionFormat::DelimitedText('\t')),
+            "psv" => Some(CsvConversionFormat::DelimitedText('|')),
+            "ssv" => Some(CsvConversionFormat::DelimitedText(';')),
+            _ => None,
+        }
+    }
+
+    /// File extension used for converted output files
+    ///
+    /// # Returns
+    /// * `&str` - Extension without the leading dot
+    pub fn file_extension(&self) -> &str {
+        match self {
+            CsvConversionFormat::JsonArray => "json",
+            CsvConversionFormat::JsonLines => "jsonl",
+            CsvConversionFormat::MarkdownTable => "md",
+            CsvConversionFormat::DelimitedText('\t') => "tsv",
+            CsvConversionFormat::DelimitedText(',') => "csv",
+            CsvConversionFormat::DelimitedText(_) => "txt",
+        }
+    }
+}
+
+/// Comparison operators for row filters
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RowFilterOperator {
+    Equal,
+    NotEqual,
+    GreaterThan,
+    GreaterOrEqual,
+    LessThan,
+    LessOrEqual,
+    /// Case-insensitive substring match
+    Contains,
+}
+
+/// A single row filter such as `age>=30`
+///
+/// Comparisons are numeric when both sides parse as numbers and textual
+/// otherwise. All filters given for a conversion must match for a row to be kept.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RowFilter {
+    /// Column the filter reads
+    pub column_name: String,
+    /// How the field is compared
+    pub operator: RowFilterOperator,
+    /// Value the field is compared against
+    pub comparison_value: String,
+}
+
+/// Operator spellings, two-character forms first so `>=` wins over `>`
+const ROW_FILTER_OPERATOR_SPELLINGS: &[(&str, RowFilterOperator)] = &[
+    ("!=", RowFilterOperator::NotEqual),
+    (">=", RowFilterOperator::GreaterOrEqual),
+    ("<=", RowFilterOperator::LessOrEqual),
+    ("=", RowFilterOperator::Equal),
+    (">", RowFilterOperator::GreaterThan),
+    ("<", RowFilterOperator::LessThan),
+    ("~", RowFilterOperator::Contains),
+];
+
+impl RowFilter {
+    /// Parses a filter expression of the form `column OP value`
+    ///
+    /// Supported operators: `=`, `!=`, `>`, `>=`, `<`, `<=`, `~` (contains).
+    /// Surrounding double quotes around the value are removed.
+    ///
+    /// # Arguments
+    /// * `filter_expression` - e.g. `age>=30`, `city = Paris`, `name~lu`
+    ///
+    /// # Returns
+    /// * `RowsAndColumnsResult<RowFilter>` - Parsed filter
+    ///
+    /// # Errors
+    /// * `RowsAndColumnsError::ConfigurationError` - If no operator or column name is found
+    pub fn parse(filter_expression: &str) -> RowsAndColumnsResult<RowFilter> {
+        // Step 1: Find the leftmost operator (longest spelling at that position)
+        let mut found_operator: Option<(usize, &str, RowFilterOperator)> = None;
+        for (operator_text, operator) in ROW_FILTER_OPERATOR_SPELLINGS {
+            if let Some(position) = filter_expression.find(operator_text) {
+                let is_better = match found_operator {
+                    Some((best_position, best_text, _)) => {
+                        position < best_position
+                            || (position == best_position && operator_text.len() > best_text.len())
+                    }
+                    None => true,
+                };
+                if is_better {
+                    found_operator = Some((position, operator_text, *operator));
+                }
+            }
+        }
+
+        let (operator_position, operator_text, operator) = found_operator.ok_or_else(|| {
+            create_configuration_error(&format!(
+                "Row filter '{}' has no operator (use =, !=, >, >=, <, <= or ~)",
+                filter_expression
+            ))
+        })?;
+
+        // Step 2: Split into column name and comparison value
+        let column_name = filter_expression[..operator_position].trim().to_string();
+        let raw_value = filter_expression[operator_position + operator_text.len()..].trim();
+        let comparison_value = raw_value
+            .strip_prefix('"')
+            .and_then(|inner| inner.strip_suffix('"'))
+            .unwrap_or(raw_value)
+            .to_string();
+
+        if column_name.is_empty() {
+            return Err(create_configuration_error(&format!(
+                "Row filter '{}' has no column name",
+                filter_expression
+            )));
+        }
+
+        Ok(RowFilter {
+            column_name,
+            operator,
+            comparison_value,
+        })
+    }
+
+    /// Checks whether a field value passes this filter
+    ///
+    /// # Arguments
+    /// * `field_value` - The raw field value from the row
+    ///
+    /// # Returns
+    /// * `bool` - True if the row should be kept as far as this filter is concerned
+    fn matches(&self, field_value: &str) -> bool {
+        let trimmed_value = field_value.trim();
+
+        if self.operator == RowFilterOperator::Contains {
+            return trimmed_value
+                .to_lowercase()
+                .contains(&self.comparison_value.to_lowercase());
+        }
+
+        // Numeric comparison when both sides are numbers, text otherwise
+        let ordering = match (
+            trimmed_value.parse::<f64>(),
+            self.comparison_value.parse::<f64>(),
+        ) {
+            (Ok(field_number), Ok(comparison_number)) => {
+                match field_number.partial_cmp(&comparison_number) {
+                    Some(ordering) => ordering,
+                    None => return false,
+                }
+            }
+            _ => trimmed_value.cmp(self.comparison_value.as_str()),
+        };
+
+        match self.operator {
+            RowFilterOperator::Equal => ordering.is_eq(),
+            RowFilterOperator::NotEqual => ordering.is_ne(),
+            RowFilterOperator::GreaterThan => ordering.is_gt(),
+            RowFilterOperator::GreaterOrEqual => ordering.is_ge(),
+            RowFilterOperator::LessThan => ordering.is_lt(),
+            RowFilterOperator::LessOrEqual => ordering.is_le(),
+            RowFilterOperator::Contains => unreachable!("handled above"),
+        }
+    }
+}
+
+/// Everything needed to convert one CSV file
+#[derive(Debug, Clone, PartialEq)]
+pub struct CsvConversionOptions {
+    /// Output format
+    pub output_format: CsvConversionFormat,
+    /// Columns to write, in output order; empty means all columns as-is
+    pub selected_column_names: Vec<String>,
+    /// Filters that must all match for a row to be written
+    pub row_filters: Vec<RowFilter>,
+}
+
+/// Row counts from a finished conversion
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CsvConversionSummary {
+    /// Data rows written to the output
+    pub rows_written: usize,
+    /// Data rows dropped by row filters
+    pub rows_filtered_out: usize,
+}
+
+/// Maps selected column names to header indices
+///
+/// # Arguments
+/// * `header_names` - Column names from the CSV header (or generated names)
+/// * `selected_column_names` - Requested columns in output order; empty for all
+///
+/// # Returns
+/// * `RowsAndColumnsResult<Vec<usize>>` - Header index for each output column
+///
+/// # Errors
+/// * `RowsAndColumnsError::ConfigurationError` - If a requested column does not exist
+fn resolve_output_column_indices(
+    header_names: &[String],
+    selected_column_names: &[String],
+) -> RowsAndColumnsResult<Vec<usize>> {
+    if selected_column_names.is_empty() {
+        return Ok((0..header_names.len()).collect());
+    }
+
+    selected_column_names
+        .iter()
+        .map(|selected_name| find_column_index(header_names, selected_name))
+        .collect()
+}
+
+/// Finds a column by name (exact match first, then case-insensitive)
+///
+/// # Arguments
+/// * `header_names` - Column names from the CSV header
+/// * `column_name` - The name to look up
+///
+/// # Returns
+/// * `RowsAndColumnsResult<usize>` - Index of the column
+///
+/// # Errors
+/// * `RowsAndColumnsError::ConfigurationError` - If no column has that name
+fn find_column_index(header_names: &[String], column_name: &str) -> RowsAndColumnsResult<usize> {
+    let wanted_name = column_name.trim();
+
+    header_names
+        .iter()
+        .position(|header_name| header_name.trim() == wanted_name)
+        .or_else(|| {
+            header_names
+                .iter()
+                .position(|header_name| header_name.trim().eq_ignore_ascii_case(wanted_name))
+        })
+        .ok_or_else(|| {
+            create_configuration_error(&format!(
+                "Unknown column '{}'. Available columns: {}",
+                wanted_name,
+                header_names
+                    .iter()
+                    .map(|header_name| header_name.trim())
+                    .collect::<Vec<&str>>()
+                    .join(", ")
+            ))
+        })
+}
+
+/// Formats a field as a JSON value typed by its column data type
+///
+/// Empty fields become `null`. Values that do not fit the column type are
+/// written as JSON strings so no data is lost.
+///
+/// # Arguments
+/// * `field_value` - The raw field value
+/// * `column_data_type` - Detected type of the field's column
+///
+/// # Returns
+/// * `String` - JSON value text
+fn format_typed_json_value(field_value: &str, column_data_type: &CsvColumnDataType) -> String {
+    let trimmed_value = field_value.trim();
+
+    if trimmed_value.is_empty() {
+        return "null".to_string();
+    }
+
+    let typed_value = match column_data_type {
+        CsvColumnDataType::Integer => trimmed_value
+            .parse::<i64>()
+            .ok()
+            .map(|integer_value| integer_value.to_string())
+            .or_else(|| format_typed_json_value_as_float(trimmed_value)),
+        CsvColumnDataType::Float => format_typed_json_value_as_float(trimmed_value),
+        CsvColumnDataType::Boolean => match trimmed_value.to_lowercase().as_str() {
+            "true" | "yes" | "1" | "t" | "y" => Some("true".to_string()),
+            "false" | "no" | "0" | "f" | "n" => Some("false".to_string()),
+            _ => None,
+        },
+        CsvColumnDataType::String => None,
+    };
+
+    typed_value.unwrap_or_else(|| format!("\"{}\"", escape_json_string(field_value)))
+}
+
+/// Formats a value as a JSON number if it is a finite float
+///
+/// # Arguments
+/// * `trimmed_value` - The trimmed field value
+///
+/// # Returns
+/// * `Option<String>` - JSON number text, or None if not a finite number
+fn format_typed_json_value_as_float(trimmed_value: &str) -> Option<String> {
+    trimmed_value
+        .parse::<f64>()
+        .ok()
+        .filter(|float_value| float_value.is_finite())
+        .map(|float_value| format!("{}", float_value))
+}
+
+/// Detects a type for each column from a sample of parsed rows
+///
+/// # Arguments
+/// * `sample_rows` - Leading data rows, already split into fields
+/// * `column_count` - Number of columns in the header
+///
+/// # Returns
+/// * `Vec<CsvColumnDataType>` - One detected type per column
+fn detect_sample_column_types(sample_rows: &[Vec<String>], column_count: usize) -> Vec<CsvColumnDataType> {
+    (0..column_count)
+        .map(|column_index| {
+            let column_samples: Vec<String> = sample_rows
+                .iter()
+                .filter_map(|row_fields| row_fields.get(column_index))
+                .map(|field_value| field_value.trim().to_string())
+                .filter(|field_value| !field_value.is_empty())
+                .collect();
+            detect_column_data_type(&column_samples)
+        })
+        .collect()
+}
+
+/// Quotes a field for delimited output when it needs quoting
+///
+/// # Arguments
+/// * `field_value` - The raw field value
+/// * `delimiter` - Output field delimiter
+///
+/// # Returns
+/// * `String` - The field, quoted with `""` escapes if it contains the
+///   delimiter, a quote, or a line break
+pub fn format_delimited_field(field_value: &str, delimiter: char) -> String {
+    if field_value.contains(delimiter)
+        || field_value.contains('"')
+        || field_value.contains('\n')
+        || field_value.contains('\r')
+    {
+        format!("\"{}\"", field_value.replace('"', "\"\""))
+    } else {
+        field_value.to_string()
+    }
+}
+
+/// Escapes a value for use in a Markdown table cell
+///
+/// # Arguments
+/// * `field_value` - The raw field value
+///
+/// # Returns
+/// * `String` - Value with `|` and backslashes escaped and line breaks as spaces
+fn escape_markdown_table_cell(field_value: &str) -> String {
+    field_value
+        .trim()
+        .replace('\\', "\\\\")
+        .replace('|', "\\|")
+        .replace(['\r', '\n'], " ")
+}
+
+/// Writes one output row in the chosen format
+///
+/// # Arguments
+/// * `output_writer` - Destination
+/// * `options` - Conversion options (format)
+/// * `output_names` - Output column names, in order
+/// * `output_types` - Output column types, in order
+/// * `output_values` - Field values, in output order
+/// * `is_first_row` - Whether this is the first data row (JSON array commas)
+///
+/// # Returns
+/// * `std::io::Result<()>` - Write result
+fn write_converted_row<W: Write>(
+    output_writer: &mut W,
+    options: &CsvConversionOptions,
+    output_names: &[String],
+    output_types: &[CsvColumnDataType],
+    output_values: &[&str],
+    is_first_row: bool,
+) -> std::io::Result<()> {
+    match options.output_format {
+        CsvConversionFormat::JsonArray | CsvConversionFormat::JsonLines => {
+            let object_members: Vec<String> = output_names
+                .iter()
+                .zip(output_types)
+                .zip(output_values)
+                .map(|((column_name, column_type), field_value)| {
+                    format!(
+                        "\"{}\": {}",
+                        escape_json_string(column_name),
+                        format_typed_json_value(field_value, column_type)
+                    )
+                })
+                .collect();
+            let json_object = format!("{{{}}}", object_members.join(", "));
+
+            if options.output_format == CsvConversionFormat::JsonLines {
+                writeln!(output_writer, "{}", json_object)
+            } else if is_first_row {
+                write!(output_writer, "\n  {}", json_object)
+            } else {
+                write!(output_writer, ",\n  {}", json_object)
+            }
+        }
+        CsvConversionFormat::MarkdownTable => {
+            let table_cells: Vec<String> = output_values
+                .iter()
+                .map(|field_value| escape_markdown_table_cell(field_value))
+                .collect();
+            writeln!(output_writer, "| {} |", table_cells.join(" | "))
+        }
+        CsvConversionFormat::DelimitedText(delimiter) => {
+            let delimited_fields: Vec<String> = output_values
+                .iter()
+                .map(|field_value| format_delimited_field(field_value, delimiter))
+                .collect();
+            writeln!(output_writer, "{}", delimited_fields.join(&delimiter.to_string()))
+        }
+    }
+}
+
+/// Streams CSV records from a reader into another format
+///
+/// This is the core of conversion; `convert_csv_file` adds file handling and
+/// header detection around it. The first `CONVERSION_TYPE_SAMPLE_ROWS` data
+/// rows are held in memory to detect column types; every later row is written
+/// as soon as it is read.
+///
+/// # Arguments
+/// * `csv_reader` - Buffered CSV input, positioned at the first line
+/// * `has_header_row` - Whether the first line holds column names
+/// * `options` - Format, column selection and row filters
+/// * `output_writer` - Destination for converted text
+///
+/// # Returns
+/// * `RowsAndColumnsResult<CsvConversionSummary>` - Rows written and filtered out
+///
+/// # Errors
+/// * `RowsAndColumnsError::FileSystemError` - If reading or writing fails
+/// * `RowsAndColumnsError::ConfigurationError` - If a selected or filtered column is unknown
+pub fn convert_csv_records<R: BufRead, W: Write>(
+    csv_reader: R,
+    has_header_row: bool,
+    options: &CsvConversionOptions,
+    output_writer: &mut W,
+) -> RowsAndColumnsResult<CsvConversionSummary> {
+    let write_error = |io_error| create_file_system_error("Failed to write converted output", io_error);
+    let mut csv_lines = csv_reader.lines();
+    let mut summary = CsvConversionSummary::default();
+
+    // Step 1: Read column names from the header, or generate them
+    let mut pending_first_line: Option<String> = None;
+    let header_names: Vec<String> = match csv_lines.next() {
+        Some(first_line_result) => {
+            let first_line = first_line_result.map_err(|io_error| {
+                create_file_system_error("Failed to read first line of CSV file", io_error)
+            })?;
+            let first_fields = parse_csv_line_into_fields(&first_line);
+
+            if has_header_row {
+                first_fields.iter().map(|field| field.trim().to_string()).collect()
+            } else {
+                let generated_names = (0..first_fields.len())
+                    .map(|column_index| format!("column_{}", column_index + 1))
+                    .collect();
+                pending_first_line = Some(first_line);
+                generated_names
+            }
+        }
+        None => Vec::new(),
+    };
+
+    // Step 2: Resolve selected columns and filter columns up front
+    let output_indices = resolve_output_column_indices(&header_names, &options.selected_column_names)?;
+    let filter_indices: Vec<usize> = options.row_filters
+        .iter()
+        .map(|row_filter| find_column_index(&header_names, &row_filter.column_name))
+        .collect::<RowsAndColumnsResult<Vec<usize>>>()?;
+
+    // Step 3: Buffer the leading rows and detect column types from them
+    let first_data_line_number = if has_header_row { 2 } else { 1 };
+    let mut data_lines = pending_first_line
+        .into_iter()
+        .map(Ok)
+        .chain(csv_lines)
+        .enumerate()
+        .map(|(line_offset, line_result)| {
+            line_result.map_err(|io_error| {
+                create_file_system_error(
+                    &format!("Failed to read CSV line {}", line_offset + first_data_line_number),
+                    io_error
+                )
+            })
+        })
+        .filter(|line_result| {
+            line_result.as_ref().map(|csv_line| !csv_line.trim().is_empty()).unwrap_or(true)
+        });
+
+    let mut sample_rows: Vec<Vec<String>> = Vec::new();
+    for line_result in data_lines.by_ref().take(CONVERSION_TYPE_SAMPLE_ROWS) {
+        sample_rows.push(parse_csv_line_into_fields(&line_result?));
+    }
+
+    let column_data_types = detect_sample_column_types(&sample_rows, header_names.len());
+    let conversion_layout = ConversionLayout {
+        output_names: output_indices
+            .iter()
+            .map(|&column_index| header_names[column_index].clone())
+            .collect(),
+        output_types: output_indices
+            .iter()
+            .map(|&column_index| column_data_types[column_index].clone())
+            .collect(),
+        output_indices,
+        filter_indices,
+    };
+
+    // Step 4: Write the format preamble
+    match options.output_format {
+        CsvConversionFormat::JsonArray => {
+            write!(output_writer, "[").map_err(write_error)?;
+        }
+        CsvConversionFormat::JsonLines => {}
+        CsvConversionFormat::MarkdownTable => {
+            let header_cells: Vec<String> = conversion_layout.output_names
+                .iter()
+                .map(|column_name| escape_markdown_table_cell(column_name))
+                .collect();
+            let alignment_cells: Vec<&str> = conversion_layout.output_types
+                .iter()
+                .map(|column_type| match column_type {
+                    CsvColumnDataType::Integer | CsvColumnDataType::Float => "---:",
+                    _ => "---",
+                })
+                .collect();
+            writeln!(output_writer, "| {} |", header_cells.join(" | ")).map_err(write_error)?;
+            writeln!(output_writer, "| {} |", alignment_cells.join(" | ")).map_err(write_error)?;
+        }
+        CsvConversionFormat::DelimitedText(delimiter) => {
+            if has_header_row {
+                let header_fields: Vec<String> = conversion_layout.output_names
+                    .iter()
+                    .map(|column_name| format_delimited_field(column_name, delimiter))
+                    .collect();
+                writeln!(output_writer, "{}", header_fields.join(&delimiter.to_string()))
+                    .map_err(write_error)?;
+            }
+        }
+    }
+
+    // Step 5: Write the buffered rows, then stream the rest one line at a time
+    for field_values in &sample_rows {
+        convert_one_record(output_writer, options, &conversion_layout, field_values, &mut summary)
+            .map_err(write_error)?;
+    }
+
+    for line_result in data_lines {
+        let field_values = parse_csv_line_into_fields(&line_result?);
+        convert_one_record(output_writer, options, &conversion_layout, &field_values, &mut summary)
+            .map_err(write_error)?;
+    }
+
+    // Step 6: Close the format
+    if options.output_format == CsvConversionFormat::JsonArray {
+        let closing_text = if summary.rows_written == 0 { "]\n" } else { "\n]\n" };
+        write!(output_writer, "{}", closing_text).map_err(write_error)?;
+    }
+
+    output_writer.flush().map_err(write_error)?;
+
+    Ok(summary)
+}
+
+/// Column layout shared by every converted row
+struct ConversionLayout {
+    /// Input column index for each output column
+    output_indices: Vec<usize>,
+    /// Input column index for each row filter
+    filter_indices: Vec<usize>,
+    /// Output column names, in order
+    output_names: Vec<String>,
+    /// Output column types, in order
+    output_types: Vec<CsvColumnDataType>,
+}
+
+/// Applies row filters to one record and writes it if kept
+///
+/// # Arguments
+/// * `output_writer` - Destination
+/// * `options` - Conversion options (format and filters)
+/// * `conversion_layout` - Output columns and filter columns
+/// * `field_values` - The record's fields; missing trailing fields count as empty
+/// * `summary` - Running row counts, updated here
+///
+/// # Returns
+/// * `std::io::Result<()>` - Write result
+fn convert_one_record<W: Write>(
+    output_writer: &mut W,
+    options: &CsvConversionOptions,
+    conversion_layout: &ConversionLayout,
+    field_values: &[String],
+    summary: &mut CsvConversionSummary,
+) -> std::io::Result<()> {
+    let field_at = |column_index: usize| field_values.get(column_index).map(String::as_str).unwrap_or("");
+
+    let row_is_kept = options.row_filters
+        .iter()
+        .zip(&conversion_layout.filter_indices)
+        .all(|(row_filter, &column_index)| row_filter.matches(field_at(column_index)));
+
+    if !row_is_kept {
+        summary.rows_filtered_out += 1;
+        return Ok(());
+    }
+
+    let output_values: Vec<&str> = conversion_layout.output_indices
+        .iter()
+        .map(|&column_index| field_at(column_index))
+        .collect();
+
+    write_converted_row(
+        output_writer,
+        options,
+        &conversion_layout.output_names,
+        &conversion_layout.output_types,
+        &output_values,
+        summary.rows_written == 0,
+    )?;
+
+    summary.rows_written += 1;
+    Ok(())
+}
+
+/// Converts a CSV file into another format
+///
+/// Runs the usual structure analysis to decide whether the file has a header
+/// (without saving it: the metadata file, which `-validate` checks against,
+/// is left alone), then streams the file through `convert_csv_records`.
+///
+/// # Arguments
+/// * `csv_file_path` - Path to the CSV file
+/// * `options` - Format, column selection and row filters
+/// * `output_writer` - Destination for converted text (file or stdout)
+///
+/// # Returns
+/// * `RowsAndColumnsResult<CsvConversionSummary>` - Rows written and filtered out
+///
+/// # Errors
+/// * `RowsAndColumnsError::FileSystemError` - If the CSV cannot be read or output written
+/// * `RowsAndColumnsError::CsvProcessingError` - If the CSV is empty
+/// * `RowsAndColumnsError::ConfigurationError` - If a selected or filtered column is unknown
+pub fn convert_csv_file<W: Write>(
+    csv_file_path: &PathBuf,
+    options: &CsvConversionOptions,
+    output_writer: &mut W,
+) -> RowsAndColumnsResult<CsvConversionSummary> {
+    // Step 1: Detect whether the first line is a header
+    let csv_analysis_results = analyze_csv_file_without_saving_metadata(csv_file_path)?;
+
+    // Step 2: Stream the conversion
+    let csv_file = File::open(csv_file_path)
+        .map_err(|io_error| {
+            create_file_system_error(
+                &format!("Failed to open CSV file for conversion: {}", csv_file_path.display()),
+                io_error
+            )
+        })?;
+
+    convert_csv_records(
+        BufReader::new(csv_file),
+        csv_analysis_results.has_header_row,
+        options,
+        output_writer,
+    )
+}
+
+/// Determines where a converted file is saved
+///
+/// Output goes next to the CSV as `{stem}_converted_{timestamp}.{ext}` so
+/// earlier conversions are never overwritten.
+///
+/// # Arguments
+/// * `csv_file_path` - Path to the source CSV file
+/// * `output_format` - Conversion format (chooses the extension)
+///
+/// # Returns
+/// * `RowsAndColumnsResult<PathBuf>` - Path for the converted file
+///
+/// # Errors
+/// * `RowsAndColumnsError::ConfigurationError` - If the CSV name or system time is unusable
+pub fn determine_conversion_output_path(
+    csv_file_path: &Path,
+    output_format: CsvConversionFormat,
+) -> RowsAndColumnsResult<PathBuf> {
+    let csv_directory = csv_file_path.parent()
+        .ok_or_else(|| create_configuration_error("Cannot determine CSV file directory"))?;
+
+    let csv_stem = csv_file_path.file_stem()
+        .and_then(|stem| stem.to_str())
+        .ok_or_else(|| create_configuration_error("Cannot determine CSV filename"))?;
+
+    let timestamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_err(|time_error| create_configuration_error(&format!("System time error: {}", time_error)))?
+        .as_secs();
+
+    Ok(csv_directory.join(format!(
+        "{}_converted_{}.{}",
+        csv_stem,
+        timestamp,
+        output_format.file_extension()
+    )))
+}
+
+#[cfg(test)]
+mod csv_convert_tests {
+    use super::*;
+    use std::io::Cursor;
+
+    /// Converts CSV text in memory and returns the output text
+    fn convert_text(
+        csv_text: &str,
+        has_header_row: bool,
+        options: &CsvConversionOptions,
+    ) -> (String, CsvConversionSummary) {
+        let mut output_bytes = Vec::new();
+        let summary = convert_csv_records(
+            Cursor::new(csv_text),
+            has_header_row,
+            options,
+            &mut output_bytes,
+        )
+        .expect("conversion succeeds");
+        (String::from_utf8(output_bytes).expect("utf-8 output"), summary)
+    }
+
+    fn options_for(output_format: CsvConversionFormat) -> CsvConversionOptions {
+        CsvConversionOptions {
+            output_format,
+            selected_column_names: Vec::new(),
+            row_filters: Vec::new(),
+        }
+    }
+
+    const PEOPLE_CSV: &str = "id,name,score,active\n1,\"Lu, Luna\",9.5,yes\n2,Max,,no\n3,\"Say \"\"hi\"\"\",7,y\n";
+
+    #[test]
+    fn test_parse_csv_line_into_fields_handles_quotes() {
+        assert_eq!(parse_csv_line_into_fields("a,b,,c"), vec!["a", "b", "", "c"]);
+        assert_eq!(parse_csv_line_into_fields("\"x, y\",2"), vec!["x, y", "2"]);
+        assert_eq!(parse_csv_line_into_fields("\"say \"\"hi\"\"\",z"), vec!["say \"hi\"", "z"]);
+        assert_eq!(parse_csv_line_into_fields(""), vec![""]);
+    }
+
+    #[test]
+    fn test_row_filter_parsing() {
+        let filter = RowFilter::parse("age >= 30").unwrap();
+        assert_eq!(filter.column_name, "age");
+        assert_eq!(filter.operator, RowFilterOperator::GreaterOrEqual);
+        assert_eq!(filter.comparison_value, "30");
+
+        let filter = RowFilter::parse("city!=\"New York\"").unwrap();
+        assert_eq!(filter.operator, RowFilterOperator::NotEqual);
+        assert_eq!(filter.comparison_value, "New York");
+
+        assert_eq!(RowFilter::parse("name~lu").unwrap().operator, RowFilterOperator::Contains);
+        assert!(RowFilter::parse("no operator here").is_err());
+        assert!(RowFilter::parse("=5").is_err());
+    }
+
+    #[test]
+    fn test_row_filter_numeric_and_text_comparison() {
+        let numeric_filter = RowFilter::parse("score>8").unwrap();
+        assert!(numeric_filter.matches("9.5"));
+        assert!(!numeric_filter.matches("10e-1"));
+        assert!(!numeric_filter.matches(""));
+
+        // "10" > "8" numerically, though not as text
+        assert!(numeric_filter.matches("10"));
+
+        let equal_filter = RowFilter::parse("score=1").unwrap();
+        assert!(equal_filter.matches("1.0"));
+
+        let contains_filter = RowFilter::parse("name~LU").unwrap();
+        assert!(contains_filter.matches("Luna"));
+        assert!(!contains_filter.matches("Max"));
+    }
+
+    #[test]
+    fn test_json_array_uses_column_types() {
+        let (output, summary) = convert_text(
+            PEOPLE_CSV,
+            true,
+            &options_for(CsvConversionFormat::JsonArray),
+        );
+
+        assert_eq!(summary.rows_written, 3);
+        assert!(output.starts_with("[\n  {\"id\": 1, \"name\": \"Lu, Luna\", \"score\": 9.5, \"active\": true},"));
+        assert!(output.contains("{\"id\": 2, \"name\": \"Max\", \"score\": null, \"active\": false}"));
+        assert!(output.contains("\"name\": \"Say \\\"hi\\\"\""));
+        assert!(output.ends_with("}\n]\n"));
+    }
+
+    #[test]
+    fn test_jsonl_with_selection_and_filter() {
+        let mut options = options_for(CsvConversionFormat::JsonLines);
+        options.selected_column_names = vec!["active".to_string(), "ID".to_string()];
+        options.row_filters = vec![RowFilter::parse("score>=7").unwrap()];
+
+        let (output, summary) = convert_text(PEOPLE_CSV, true, &options);
+
+        assert_eq!(output, "{\"active\": true, \"id\": 1}\n{\"active\": true, \"id\": 3}\n");
+        assert_eq!(summary.rows_written, 2);
+        assert_eq!(summary.rows_filtered_out, 1);
+    }
+
+    #[test]
+    fn test_markdown_table_and_delimited_output() {
+        let mut options = options_for(CsvConversionFormat::MarkdownTable);
+        options.selected_column_names = vec!["name".to_string(), "score".to_string()];
+        let (markdown, _) = convert_text("name,score\na|b,12\nc,3.5\n", true, &options);
+        assert_eq!(markdown, "| name | score |\n| --- | ---: |\n| a\\|b | 12 |\n| c | 3.5 |\n");
+
+        let (tab_separated, _) = convert_text(
+            PEOPLE_CSV,
+            true,
+            &options_for(CsvConversionFormat::DelimitedText('\t')),
+        );
+        let tsv_lines: Vec<&str> = tab_separated.lines().collect();
+        assert_eq!(tsv_lines[0], "id\tname\tscore\tactive");
+        assert_eq!(tsv_lines[1], "1\tLu, Luna\t9.5\tyes");
+        assert_eq!(tsv_lines[3], "3\t\"Say \"\"hi\"\"\"\t7\ty");
+    }
+
+    #[test]
+    fn test_headerless_input_and_unknown_columns() {
+        let (output, _) = convert_text(
+            "5,apple\n6,pear\n",
+            false,
+            &options_for(CsvConversionFormat::JsonLines),
+        );
+        assert_eq!(
+            output,
+            "{\"column_1\": 5, \"column_2\": \"apple\"}\n{\"column_1\": 6, \"column_2\": \"pear\"}\n"
+        );
+
+        let mut options = options_for(CsvConversionFormat::JsonArray);
+        options.selected_column_names = vec!["missing".to_string()];
+        let result = convert_csv_records(
+            Cursor::new("a,b\n1,2\n"),
+            true,
+            &options,
+            &mut Vec::new(),
+        );
+        assert!(result.is_err());
+    }
+}
@@ -21,8 +21,9 @@
 
 use std::fs::File;
 use std::io::{BufRead, BufReader};
-use std::path::{PathBuf};
-use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::collections::{HashMap, HashSet};
+use std::hash::{DefaultHasher, Hash, Hasher};
 
 // Import our error handling system
 use super::error_types_module::{
@@ -37,6 +38,21 @@ use super::error_types_module::{
 const CSV_SAMPLE_ROWS_FOR_TYPE_DETECTION: usize = 10;
 const METADATA_FILE_EXTENSION: &str = "csv_metadata.toml";
 
+/// Configuration constants for schema validation against saved metadata
+const VALIDATION_MAX_ROW_FAILURE_EXAMPLES: usize = 20;
+const VALIDATION_EMPTY_RATIO_TOLERANCE: f64 = 0.05;
+
+/// Configuration constants for ASCII distribution plots
+pub const DEFAULT_HISTOGRAM_BIN_COUNT: usize = 10;
+pub const MAX_HISTOGRAM_BIN_COUNT: usize = 50;
+const DISTRIBUTION_PLOT_BAR_WIDTH: usize = 40;
+const DISTRIBUTION_PLOT_TOP_CATEGORIES: usize = 10;
+const DISTRIBUTION_PLOT_CATEGORY_LABEL_WIDTH: usize = 16;
+
+/// Configuration constants for cross-column analysis
+const CANDIDATE_KEY_PAIR_SEARCH_MAX_COLUMNS: usize = 30;
+const MAX_DUPLICATE_ROW_GROUPS_REPORTED: usize = 20;
+
 /// Represents the detected data type for a CSV column
 ///
 /// This enum covers the MVP data types that the system can detect
@@ -70,22 +86,41 @@ impl CsvColumnDataType {
         }
     }
 
-    // /// Creates a data type from a TOML string representation
-    // ///
-    // /// # Arguments
-    // /// * `toml_string` - The string representation from TOML file
-    // ///
-    // /// # Returns
-    // /// * `Option<CsvColumnDataType>` - The data type or None if invalid
-    // pub fn from_toml_string(toml_string: &str) -> Option<CsvColumnDataType> {
-    //     match toml_string.to_lowercase().as_str() {
-    //         "boolean" | "bool" => Some(CsvColumnDataType::Boolean),
-    //         "integer" | "int" => Some(CsvColumnDataType::Integer),
-    //         "float" | "decimal" | "number" => Some(CsvColumnDataType::Float),
-    //         "string" | "text" | "str" => Some(CsvColumnDataType::String),
-    //         _ => None,
-    //     }
-    // }
+    /// Creates a data type from a TOML string representation
+    ///
+    /// # Arguments
+    /// * `toml_string` - The string representation from TOML file
+    ///
+    /// # Returns
+    /// * `Option<CsvColumnDataType>` - The data type or None if invalid
+    pub fn from_toml_string(toml_string: &str) -> Option<CsvColumnDataType> {
+        match toml_string.to_lowercase().as_str() {
+            "boolean" | "bool" => Some(CsvColumnDataType::Boolean),
+            "integer" | "int" => Some(CsvColumnDataType::Integer),
+            "float" | "decimal" | "number" => Some(CsvColumnDataType::Float),
+            "string" | "text" | "str" => Some(CsvColumnDataType::String),
+            _ => None,
+        }
+    }
+
+    /// Checks whether a single non-empty field value fits this data type
+    ///
+    /// Uses the same parsing rules as type detection, so a value that would
+    /// have been counted toward a type during detection is accepted here.
+    ///
+    /// # Arguments
+    /// * `field_value` - The trimmed, non-empty field value to check
+    ///
+    /// # Returns
+    /// * `bool` - True if the value is valid for this data type
+    pub fn accepts_value(&self, field_value: &str) -> bool {
+        match self {
+            CsvColumnDataType::Boolean => is_boolean_value(&field_value.to_lowercase()),
+            CsvColumnDataType::Integer => field_value.parse::<i64>().is_ok(),
+            CsvColumnDataType::Float => field_value.parse::<f64>().is_ok(),
+            CsvColumnDataType::String => true,
+        }
+    }
 }
 
 /// Information about a detected CSV column
@@ -158,15 +193,46 @@ pub struct CsvAnalysisResults {
 /// * `RowsAndColumnsError::CsvProcessingError` - If CSV parsing fails
 /// * `RowsAndColumnsError::MetadataError` - If metadata file operations fail
 pub fn analyze_csv_file_structure_and_types(csv_file_path: &PathBuf) -> RowsAndColumnsResult<CsvAnalysisResults> {
+    // Step 1: Analyze without touching any file
+    let csv_analysis_results = analyze_csv_file_without_saving_metadata(csv_file_path)?;
+
+    // Step 2: Create or update metadata file
+    create_or_update_metadata_file(
+        &csv_analysis_results.metadata_file_path,
+        &csv_analysis_results.column_information_list,
+    )?;
+
+    // println!("  Metadata file updated");
+
+    Ok(csv_analysis_results)
+}
+
+/// Analyzes a CSV file like `analyze_csv_file_structure_and_types`, but
+/// writes nothing
+///
+/// Read-only uses (the headless report, conversions) must leave the
+/// metadata file alone: it is the schema `-validate` checks against.
+///
+/// # Arguments
+/// * `csv_file_path` - Absolute path to the CSV file to analyze
+///
+/// # Returns
+/// * `RowsAndColumnsResult<CsvAnalysisResults>` - Complete analysis results;
+///   `metadata_file_path` is where the metadata file is (or would be)
+///
+/// # Errors
+/// * `RowsAndColumnsError::FileSystemError` - If file access fails
+/// * `RowsAndColumnsError::CsvProcessingError` - If CSV parsing fails
+pub fn analyze_csv_file_without_saving_metadata(csv_file_path: &PathBuf) -> RowsAndColumnsResult<CsvAnalysisResults> {
     // println!("🔍 Analyzing CSV file structure...");
 
     // Step 1: Read and analyze the CSV file structure
     let (has_header_row, column_count, data_row_count) = analyze_csv_basic_structure(csv_file_path)?;
 
-    println!("    Structure of Data:");
-    println!("    Columns: {}", column_count);
-    println!("    Data rows: {}", data_row_count);
-    println!("    Has header: {}", has_header_row);
+    eprintln!("    Structure of Data:");
+    eprintln!("    Columns: {}", column_count);
+    eprintln!("    Data rows: {}", data_row_count);
+    eprintln!("    Has header: {}", has_header_row);
 
     // Step 2: Analyze column data types and content
     let column_information_list = analyze_csv_column_types_and_content(
@@ -187,11 +253,6 @@ pub fn analyze_csv_file_structure_and_types(csv_file_path: &PathBuf) -> RowsAndC
     //     println!(" Will create metadata file: {}", metadata_file_path.display());
     // }
 
-    // Step 4: Create or update metadata file
-    create_or_update_metadata_file(&metadata_file_path, &column_information_list)?;
-
-    // println!("  Metadata file updated");
-
     // Return complete analysis results
     Ok(CsvAnalysisResults {
         has_header_row,
@@ -304,7 +365,7 @@ fn detect_csv_header_row(
     if first_fields.len() != expected_column_count ||
        second_fields.len() != expected_column_count {
         // Inconsistent column counts - this is suspicious but proceed
-        println!("  Warning: Inconsistent column counts detected");
+        eprintln!("  Warning: Inconsistent column counts detected");
     }
 
     // Heuristic: if first line contains non-numeric values and second line
@@ -479,20 +540,48 @@ fn analyze_csv_column_types_and_content(
     Ok(column_info_list)
 }
 
-/// Parses a CSV line into individual field values
+/// Parses a CSV line into individual field values, honoring double-quoted fields
 ///
-/// For MVP, this uses simple comma splitting. Could be enhanced later
-/// for proper CSV parsing with quoted fields and escaped commas.
+/// Quoted fields may contain commas, and `""` inside quotes is a literal
+/// quote. Records spanning several lines are not supported; the line is
+/// treated as complete.
 ///
 /// # Arguments
-/// * `csv_line` - The CSV line to parse
+/// * `csv_line` - One line of the CSV file, without the line ending
 ///
 /// # Returns
-/// * `Vec<String>` - List of field values
-fn parse_csv_line_into_fields(csv_line: &str) -> Vec<String> {
-    csv_line.split(',')
-        .map(|field| field.to_string())
-        .collect()
+/// * `Vec<String>` - Unquoted field values
+pub fn parse_csv_line_into_fields(csv_line: &str) -> Vec<String> {
+    let mut field_values = Vec::new();
+    let mut current_field = String::new();
+    let mut inside_quotes = false;
+    let mut line_chars = csv_line.chars().peekable();
+
+    while let Some(current_char) = line_chars.next() {
+        match current_char {
+            '"' if inside_quotes => {
+                if line_chars.peek() == Some(&'"') {
+                    // Escaped quote inside a quoted field
+                    current_field.push('"');
+                    line_chars.next();
+                } else {
+                    inside_quotes = false;
+                }
+            }
+            '"' if current_field.trim().is_empty() => {
+                // Opening quote; drop any spaces before it
+                current_field.clear();
+                inside_quotes = true;
+            }
+            ',' if !inside_quotes => {
+                field_values.push(std::mem::take(&mut current_field));
+            }
+            other_char => current_field.push(other_char),
+        }
+    }
+
+    field_values.push(current_field);
+    field_values
 }
 
 /// Detects the most likely data type for a column based on sample values
@@ -505,7 +594,7 @@ fn parse_csv_line_into_fields(csv_line: &str) -> Vec<String> {
 ///
 /// # Returns
 /// * `CsvColumnDataType` - The detected data type
-fn detect_column_data_type(sample_values: &[String]) -> CsvColumnDataType {
+pub fn detect_column_data_type(sample_values: &[String]) -> CsvColumnDataType {
     if sample_values.is_empty() {
         return CsvColumnDataType::String;
     }
@@ -566,7 +655,7 @@ fn is_boolean_value(value: &str) -> bool {
 ///
 /// # Returns
 /// * `RowsAndColumnsResult<PathBuf>` - Path to metadata file or error
-fn determine_metadata_file_path(csv_file_path: &PathBuf) -> RowsAndColumnsResult<PathBuf> {
+pub fn determine_metadata_file_path(csv_file_path: &Path) -> RowsAndColumnsResult<PathBuf> {
     let csv_filename_stem = csv_file_path.file_stem()
         .and_then(|stem| stem.to_str())
         .ok_or_else(|| {
@@ -626,7 +715,7 @@ fn create_or_update_metadata_file(
     for column_info in column_information_list {
         let column_section = format!("column_{}", column_info.column_index + 1);
         toml_content.push_str(&format!("[{}]\n", column_section));
-        toml_content.push_str(&format!("name = \"{}\"\n", column_info.column_name));
+        toml_content.push_str(&format!("name = \"{}\"\n", escape_toml_string_value(&column_info.column_name)));
         toml_content.push_str(&format!("data_type = \"{}\"\n", column_info.detected_data_type.to_toml_string()));
         toml_content.push_str(&format!("column_index = {}\n", column_info.column_index));
         toml_content.push_str(&format!("non_empty_values = {}\n", column_info.non_empty_value_count));
@@ -646,6 +735,257 @@ fn create_or_update_metadata_file(
     Ok(())
 }
 
+/// Escapes a value for use inside a double-quoted TOML string
+///
+/// # Arguments
+/// * `raw_value` - The unescaped text
+///
+/// # Returns
+/// * `String` - Text with backslashes and double quotes escaped
+fn escape_toml_string_value(raw_value: &str) -> String {
+    raw_value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// A single scalar value read from a metadata TOML file
+///
+/// The metadata format only ever writes integers, strings and booleans,
+/// so the reader does not support arrays, tables-in-values or dates.
+#[derive(Debug, Clone, PartialEq)]
+enum MetadataTomlValue {
+    /// Bare integer such as `column_index = 3`
+    Integer(i64),
+
+    /// Double-quoted string such as `name = "price"`
+    Text(String),
+
+    /// Bare boolean such as `has_header = true`
+    Boolean(bool),
+}
+
+/// Parses the right-hand side of a `key = value` metadata line
+///
+/// # Arguments
+/// * `raw_value` - Text after the `=` sign, already trimmed
+///
+/// # Returns
+/// * `Option<MetadataTomlValue>` - Parsed value, or None if not a supported scalar
+fn parse_metadata_toml_value(raw_value: &str) -> Option<MetadataTomlValue> {
+    if let Some(quoted_body) = raw_value.strip_prefix('"') {
+        let mut unescaped_text = String::new();
+        let mut body_chars = quoted_body.chars();
+
+        while let Some(current_char) = body_chars.next() {
+            match current_char {
+                '\\' => match body_chars.next()? {
+                    'n' => unescaped_text.push('\n'),
+                    't' => unescaped_text.push('\t'),
+                    escaped_char => unescaped_text.push(escaped_char),
+                },
+                '"' => {
+                    // Only a trailing comment may follow the closing quote
+                    let remainder = body_chars.as_str().trim();
+                    if remainder.is_empty() || remainder.starts_with('#') {
+                        return Some(MetadataTomlValue::Text(unescaped_text));
+                    }
+                    return None;
+                }
+                other_char => unescaped_text.push(other_char),
+            }
+        }
+
+        // Reached end of line without a closing quote
+        return None;
+    }
+
+    // Bare values may carry a trailing comment
+    let bare_value = raw_value.split('#').next().unwrap_or("").trim();
+
+    match bare_value {
+        "true" => Some(MetadataTomlValue::Boolean(true)),
+        "false" => Some(MetadataTomlValue::Boolean(false)),
+        _ => bare_value.parse::<i64>().ok().map(MetadataTomlValue::Integer),
+    }
+}
+
+/// Parses metadata TOML text back into column information
+///
+/// This is a minimal reader for exactly the format written by
+/// `create_or_update_metadata_file`: top-level keys followed by one
+/// `[column_N]` table per column. Sample values are not stored in the
+/// metadata file, so they are returned empty.
+///
+/// # Arguments
+/// * `toml_text` - Full contents of a metadata TOML file
+///
+/// # Returns
+/// * `RowsAndColumnsResult<Vec<CsvColumnInformation>>` - Columns sorted by index
+///
+/// # Errors
+/// * `RowsAndColumnsError::ConfigurationError` - If a line cannot be parsed,
+///   a column table is missing a required key, or `total_columns` disagrees
+///   with the number of column tables
+pub fn parse_metadata_toml_string(toml_text: &str) -> RowsAndColumnsResult<Vec<CsvColumnInformation>> {
+    let mut declared_total_columns: Option<i64> = None;
+    let mut column_tables: Vec<(String, HashMap<String, MetadataTomlValue>)> = Vec::new();
+
+    for (line_index, raw_line) in toml_text.lines().enumerate() {
+        let line_number = line_index + 1;
+        let trimmed_line = raw_line.trim();
+
+        if trimmed_line.is_empty() || trimmed_line.starts_with('#') {
+            continue;
+        }
+
+        // Table header: [column_N]
+        if trimmed_line.starts_with('[') {
+            let table_name = trimmed_line
+                .strip_prefix('[')
+                .and_then(|rest| rest.strip_suffix(']'))
+                .map(|name| name.trim())
+                .filter(|name| !name.is_empty())
+                .ok_or_else(|| {
+                    create_configuration_error(&format!(
+                        "Metadata TOML line {}: malformed table header '{}'",
+                        line_number, trimmed_line
+                    ))
+                })?;
+
+            column_tables.push((table_name.to_string(), HashMap::new()));
+            continue;
+        }
+
+        // Key/value pair
+        let (raw_key, raw_value) = trimmed_line.split_once('=').ok_or_else(|| {
+            create_configuration_error(&format!(
+                "Metadata TOML line {}: expected 'key = value', found '{}'",
+                line_number, trimmed_line
+            ))
+        })?;
+
+        let key = raw_key.trim();
+        let value = parse_metadata_toml_value(raw_value.trim()).ok_or_else(|| {
+            create_configuration_error(&format!(
+                "Metadata TOML line {}: unsupported value for '{}'",
+                line_number, key
+            ))
+        })?;
+
+        match column_tables.last_mut() {
+            Some((_, table_values)) => {
+                table_values.insert(key.to_string(), value);
+            }
+            None => {
+                // Top-level keys: only total_columns is meaningful
+                if key == "total_columns" {
+                    match value {
+                        MetadataTomlValue::Integer(count) => declared_total_columns = Some(count),
+                        _ => {
+                            return Err(create_configuration_error(&format!(
+                                "Metadata TOML line {}: total_columns must be an integer",
+                                line_number
+                            )));
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    // Convert each column table into column information
+    let mut column_information_list = Vec::new();
+
+    for (table_name, table_values) in &column_tables {
+        let column_name = match table_values.get("name") {
+            Some(MetadataTomlValue::Text(name)) => name.clone(),
+            _ => {
+                return Err(create_configuration_error(&format!(
+                    "Metadata table [{}] is missing a string 'name'",
+                    table_name
+                )));
+            }
+        };
+
+        let detected_data_type = match table_values.get("data_type") {
+            Some(MetadataTomlValue::Text(type_name)) => {
+                CsvColumnDataType::from_toml_string(type_name).ok_or_else(|| {
+                    create_configuration_error(&format!(
+                        "Metadata table [{}] has unknown data_type '{}'",
+                        table_name, type_name
+                    ))
+                })?
+            }
+            _ => {
+                return Err(create_configuration_error(&format!(
+                    "Metadata table [{}] is missing a string 'data_type'",
+                    table_name
+                )));
+            }
+        };
+
+        let column_index = match table_values.get("column_index") {
+            Some(MetadataTomlValue::Integer(index)) if *index >= 0 => *index as usize,
+            _ => {
+                return Err(create_configuration_error(&format!(
+                    "Metadata table [{}] is missing a non-negative 'column_index'",
+                    table_name
+                )));
+            }
+        };
+
+        // Counts are informational; treat absent values as zero
+        let read_count = |key: &str| match table_values.get(key) {
+            Some(MetadataTomlValue::Integer(count)) if *count >= 0 => *count as usize,
+            _ => 0,
+        };
+
+        column_information_list.push(CsvColumnInformation {
+            column_index,
+            column_name,
+            detected_data_type,
+            non_empty_value_count: read_count("non_empty_values"),
+            empty_value_count: read_count("empty_values"),
+            sample_values: Vec::new(),
+        });
+    }
+
+    if let Some(total_columns) = declared_total_columns
+        && total_columns as usize != column_information_list.len()
+    {
+        return Err(create_configuration_error(&format!(
+            "Metadata declares total_columns = {} but contains {} column tables",
+            total_columns,
+            column_information_list.len()
+        )));
+    }
+
+    column_information_list.sort_by_key(|column_info| column_info.column_index);
+
+    Ok(column_information_list)
+}
+
+/// Reads a metadata TOML file from disk and parses its column information
+///
+/// # Arguments
+/// * `metadata_file_path` - Path to an existing `.csv_metadata.toml` file
+///
+/// # Returns
+/// * `RowsAndColumnsResult<Vec<CsvColumnInformation>>` - Saved column schema
+///
+/// # Errors
+/// * `RowsAndColumnsError::FileSystemError` - If the file cannot be read
+/// * `RowsAndColumnsError::ConfigurationError` - If the contents cannot be parsed
+pub fn read_metadata_file(metadata_file_path: &Path) -> RowsAndColumnsResult<Vec<CsvColumnInformation>> {
+    let toml_text = std::fs::read_to_string(metadata_file_path)
+        .map_err(|io_error| {
+            create_file_system_error(
+                &format!("Failed to read metadata file: {}", metadata_file_path.display()),
+                io_error
+            )
+        })?;
+
+    parse_metadata_toml_string(&toml_text)
+}
+
 /// Enhanced statistical information for numerical columns
 ///
 /// This structure contains comprehensive statistical measures similar to
@@ -675,6 +1015,25 @@ pub struct NumericalColumnStatistics {
 
     /// Percentage of missing/empty values
     pub missing_percentage: f64,
+
+    /// Equal-width histogram of the parsed values
+    pub histogram: NumericalHistogram,
+}
+
+/// Equal-width histogram for a numerical column
+///
+/// Bin `i` covers `[range_start + i * bin_width, range_start + (i + 1) * bin_width)`,
+/// with the last bin closed so the maximum value is included.
+#[derive(Debug, Clone)]
+pub struct NumericalHistogram {
+    /// Lower edge of the first bin (the column minimum)
+    pub range_start: f64,
+
+    /// Width of every bin (0.0 when all values are equal)
+    pub bin_width: f64,
+
+    /// Number of values falling into each bin
+    pub bin_counts: Vec<usize>,
 }
 
 /// Value frequency information for categorical columns
@@ -746,21 +1105,119 @@ pub struct EnhancedCsvColumnInformation {
     pub categorical_statistics: Option<CategoricalColumnStatistics>,
 }
 
+/// Distinct-value count and uniqueness for one column
+#[derive(Debug, Clone)]
+pub struct ColumnCardinality {
+    /// Column name
+    pub column_name: String,
+
+    /// Number of distinct non-empty values
+    pub distinct_value_count: usize,
+
+    /// Number of non-empty values
+    pub non_empty_value_count: usize,
+
+    /// True if every row has a value and no value repeats (candidate key)
+    pub is_unique: bool,
+}
+
+/// A group of rows that are exact duplicates of each other
+#[derive(Debug, Clone)]
+pub struct DuplicateRowGroup {
+    /// CSV line number of the first occurrence
+    pub first_line_number: usize,
+
+    /// CSV line numbers of the later, duplicate occurrences
+    pub duplicate_line_numbers: Vec<usize>,
+}
+
+/// Relationships between columns, computed across whole rows
+///
+/// Correlation matrices are indexed by `numeric_column_names`; an entry is
+/// None when fewer than two rows have both values or a column is constant.
+#[derive(Debug, Clone, Default)]
+pub struct CrossColumnAnalysis {
+    /// Names of the continuous columns used for correlations, in column order
+    pub numeric_column_names: Vec<String>,
+
+    /// Pearson correlation coefficients (linear relationship)
+    pub pearson_correlation_matrix: Vec<Vec<Option<f64>>>,
+
+    /// Spearman rank correlation coefficients (monotonic relationship)
+    pub spearman_correlation_matrix: Vec<Vec<Option<f64>>>,
+
+    /// Cardinality for every column, in column order
+    pub column_cardinalities: Vec<ColumnCardinality>,
+
+    /// Column pairs that are unique together but not individually
+    pub unique_column_pairs: Vec<(String, String)>,
+
+    /// Whether the pair search was skipped because there were too many columns
+    pub column_pair_search_skipped: bool,
+
+    /// Number of data rows examined
+    pub total_rows_checked: usize,
+
+    /// Number of rows that repeat an earlier row exactly
+    pub duplicate_row_count: usize,
+
+    /// Groups of duplicate rows (limited to the first few groups)
+    pub duplicate_row_groups: Vec<DuplicateRowGroup>,
+}
+
+/// Complete output of the enhanced statistical analysis
+///
+/// Per-column statistics plus the cross-column analysis.
+#[derive(Debug, Clone)]
+pub struct EnhancedCsvAnalysisResults {
+    /// Enhanced information for each column, in column order
+    pub column_results: Vec<EnhancedCsvColumnInformation>,
+
+    /// Correlations, candidate keys and duplicate rows
+    pub cross_column_analysis: CrossColumnAnalysis,
+}
+
 /// Performs enhanced statistical analysis on CSV columns
 ///
 /// This function provides comprehensive pandas-style statistical analysis
-/// including appropriate measures for categorical and continuous data types.
+/// including appropriate measures for categorical and continuous data types,
+/// plus cross-column analysis: Pearson and Spearman correlation matrices,
+/// cardinality and candidate keys, and exact duplicate rows.
 ///
 /// # Arguments
 /// * `csv_file_path` - Path to the CSV file to analyze
 /// * `basic_analysis_results` - Results from basic CSV structure analysis
 ///
 /// # Returns
-/// * `RowsAndColumnsResult<Vec<EnhancedCsvColumnInformation>>` - Enhanced column analysis
+/// * `RowsAndColumnsResult<EnhancedCsvAnalysisResults>` - Enhanced column and cross-column analysis
 pub fn perform_enhanced_statistical_analysis(
     csv_file_path: &PathBuf,
     basic_analysis_results: &CsvAnalysisResults,
-) -> RowsAndColumnsResult<Vec<EnhancedCsvColumnInformation>> {
+) -> RowsAndColumnsResult<EnhancedCsvAnalysisResults> {
+    perform_enhanced_statistical_analysis_with_histogram_bins(
+        csv_file_path,
+        basic_analysis_results,
+        DEFAULT_HISTOGRAM_BIN_COUNT,
+    )
+}
+
+/// Performs enhanced statistical analysis with a chosen histogram bin count
+///
+/// Same as `perform_enhanced_statistical_analysis`, but numerical columns get
+/// a histogram with `histogram_bin_count` bins (clamped to 1..=MAX_HISTOGRAM_BIN_COUNT).
+///
+/// # Arguments
+/// * `csv_file_path` - Path to the CSV file to analyze
+/// * `basic_analysis_results` - Results from basic CSV structure analysis
+/// * `histogram_bin_count` - Number of equal-width bins for numerical histograms
+///
+/// # Returns
+/// * `RowsAndColumnsResult<EnhancedCsvAnalysisResults>` - Enhanced column and cross-column analysis
+pub fn perform_enhanced_statistical_analysis_with_histogram_bins(
+    csv_file_path: &PathBuf,
+    basic_analysis_results: &CsvAnalysisResults,
+    histogram_bin_count: usize,
+) -> RowsAndColumnsResult<EnhancedCsvAnalysisResults> {
     // println!("📊 Performing enhanced statistical analysis...");
 
     let mut enhanced_column_info_list = Vec::new();
@@ -782,7 +1239,7 @@ pub fn perform_enhanced_statistical_analysis(
         // Generate appropriate statistics based on field type
         let (numerical_statistics, categorical_statistics) = match field_type {
             CsvFieldType::Continuous => {
-                let numerical_stats = calculate_numerical_statistics(column_values)?;
+                let numerical_stats = calculate_numerical_statistics(column_values, histogram_bin_count)?;
                 (Some(numerical_stats), None)
             }
             CsvFieldType::Categorical => {
@@ -801,9 +1258,19 @@ pub fn perform_enhanced_statistical_analysis(
         enhanced_column_info_list.push(enhanced_column_info);
     }
 
-    println!("  Data analysis complete: OK");
+    // Relationships between columns need row-aligned values
+    let cross_column_analysis = perform_cross_column_analysis(
+        csv_file_path,
+        basic_analysis_results,
+        &enhanced_column_info_list,
+    )?;
+
+    eprintln!("  Data analysis complete: OK");
 
-    Ok(enhanced_column_info_list)
+    Ok(EnhancedCsvAnalysisResults {
+        column_results: enhanced_column_info_list,
+        cross_column_analysis,
+    })
 }
 
 /// Collects all values from CSV columns for comprehensive statistical analysis
@@ -881,10 +1348,14 @@ fn determine_field_type(detected_data_type: &CsvColumnDataType) -> CsvFieldType
 ///
 /// # Arguments
 /// * `column_values` - All values from the column
+/// * `histogram_bin_count` - Number of bins for the column histogram
 ///
 /// # Returns
 /// * `RowsAndColumnsResult<NumericalColumnStatistics>` - Complete numerical analysis
-fn calculate_numerical_statistics(column_values: &[String]) -> RowsAndColumnsResult<NumericalColumnStatistics> {
+fn calculate_numerical_statistics(
+    column_values: &[String],
+    histogram_bin_count: usize,
+) -> RowsAndColumnsResult<NumericalColumnStatistics> {
     // Parse all numerical values, filtering out empty/invalid ones
     let mut numerical_values = Vec::new();
     let mut empty_count = 0;
@@ -938,6 +1409,9 @@ fn calculate_numerical_statistics(column_values: &[String]) -> RowsAndColumnsRes
         0.0
     };
 
+    // Histogram reuses the already sorted values
+    let histogram = build_numerical_histogram(&numerical_values, histogram_bin_count);
+
     Ok(NumericalColumnStatistics {
         min_value,
         q1_value,
@@ -947,9 +1421,166 @@ fn calculate_numerical_statistics(column_values: &[String]) -> RowsAndColumnsRes
         mean_value,
         standard_deviation,
         missing_percentage,
+        histogram,
     })
 }
 
+/// Builds an equal-width histogram from sorted numerical values
+///
+/// # Arguments
+/// * `sorted_values` - Sorted numerical values (ascending)
+/// * `bin_count` - Requested number of bins, clamped to 1..=MAX_HISTOGRAM_BIN_COUNT
+///
+/// # Returns
+/// * `NumericalHistogram` - Bin edges and counts; a single bin if all values are equal
+pub fn build_numerical_histogram(sorted_values: &[f64], bin_count: usize) -> NumericalHistogram {
+    let bin_count = bin_count.clamp(1, MAX_HISTOGRAM_BIN_COUNT);
+
+    let (Some(&range_start), Some(&range_end)) = (sorted_values.first(), sorted_values.last()) else {
+        return NumericalHistogram {
+            range_start: 0.0,
+            bin_width: 0.0,
+            bin_counts: vec![0; bin_count],
+        };
+    };
+
+    // All values identical: one bin holds everything
+    if range_end <= range_start {
+        return NumericalHistogram {
+            range_start,
+            bin_width: 0.0,
+            bin_counts: vec![sorted_values.len()],
+        };
+    }
+
+    let bin_width = (range_end - range_start) / bin_count as f64;
+    let mut bin_counts = vec![0usize; bin_count];
+
+    for value in sorted_values {
+        let bin_index = (((value - range_start) / bin_width) as usize).min(bin_count - 1);
+        bin_counts[bin_index] += 1;
+    }
+
+    NumericalHistogram {
+        range_start,
+        bin_width,
+        bin_counts,
+    }
+}
+
+/// Scales a count to a bar of '#' characters
+///
+/// Non-zero counts always get at least one character so small bins stay visible.
+///
+/// # Arguments
+/// * `count` - The count to draw
+/// * `max_count` - The largest count in the chart (full bar width)
+/// * `bar_width` - Width of a full bar in characters
+///
+/// # Returns
+/// * `String` - The bar, padded with spaces to `bar_width`
+fn render_ascii_bar(count: usize, max_count: usize, bar_width: usize) -> String {
+    let filled_width = if max_count == 0 || count == 0 {
+        0
+    } else {
+        ((count * bar_width) / max_count).max(1)
+    };
+
+    format!("{}{}", "#".repeat(filled_width), " ".repeat(bar_width - filled_width))
+}
+
+/// Renders a numerical histogram as fixed-width ASCII lines
+///
+/// # Arguments
+/// * `histogram` - The histogram to draw
+///
+/// # Returns
+/// * `Vec<String>` - One line per bin: `[start, end) ##### count`
+pub fn render_numerical_histogram_lines(histogram: &NumericalHistogram) -> Vec<String> {
+    let max_count = histogram.bin_counts.iter().copied().max().unwrap_or(0);
+    let last_bin_index = histogram.bin_counts.len().saturating_sub(1);
+
+    histogram.bin_counts
+        .iter()
+        .enumerate()
+        .map(|(bin_index, &count)| {
+            let bin_start = histogram.range_start + bin_index as f64 * histogram.bin_width;
+            let bin_end = bin_start + histogram.bin_width;
+            let closing_bracket = if bin_index == last_bin_index { ']' } else { ')' };
+
+            format!(
+                "[{:>12.3}, {:>12.3}{} {} {}",
+                bin_start,
+                bin_end,
+                closing_bracket,
+                render_ascii_bar(count, max_count, DISTRIBUTION_PLOT_BAR_WIDTH),
+                count
+            )
+        })
+        .collect()
+}
+
+/// Renders the most frequent categorical values as a fixed-width ASCII bar chart
+///
+/// # Arguments
+/// * `categorical_statistics` - Statistics whose frequencies are already sorted descending
+///
+/// # Returns
+/// * `Vec<String>` - One line per top value, plus a trailing "more values" line if truncated
+pub fn render_categorical_bar_chart_lines(categorical_statistics: &CategoricalColumnStatistics) -> Vec<String> {
+    let value_frequencies = &categorical_statistics.value_frequencies;
+    let max_count = value_frequencies.first().map(|freq| freq.count).unwrap_or(0);
+
+    let mut chart_lines: Vec<String> = value_frequencies
+        .iter()
+        .take(DISTRIBUTION_PLOT_TOP_CATEGORIES)
+        .map(|freq| {
+            // Truncate by characters so multi-byte labels never split
+            let mut label: String = freq.value.chars().take(DISTRIBUTION_PLOT_CATEGORY_LABEL_WIDTH).collect();
+            if freq.value.chars().count() > DISTRIBUTION_PLOT_CATEGORY_LABEL_WIDTH {
+                label.pop();
+                label.push('~');
+            }
+
+            format!(
+                "{:<width$} {} {} ({:.1}%)",
+                label,
+                render_ascii_bar(freq.count, max_count, DISTRIBUTION_PLOT_BAR_WIDTH),
+                freq.count,
+                freq.percentage,
+                width = DISTRIBUTION_PLOT_CATEGORY_LABEL_WIDTH
+            )
+        })
+        .collect();
+
+    if value_frequencies.len() > DISTRIBUTION_PLOT_TOP_CATEGORIES {
+        chart_lines.push(format!(
+            "... and {} more values",
+            value_frequencies.len() - DISTRIBUTION_PLOT_TOP_CATEGORIES
+        ));
+    }
+
+    chart_lines
+}
+
+/// Renders the distribution plot appropriate for a column's field type
+///
+/// # Arguments
+/// * `column_info` - Enhanced column information with statistics
+///
+/// # Returns
+/// * `Vec<String>` - Histogram lines for continuous columns, bar chart lines for
+///   categorical columns, or empty if no statistics are available
+pub fn render_column_distribution_plot_lines(column_info: &EnhancedCsvColumnInformation) -> Vec<String> {
+    if let Some(ref num_stats) = column_info.numerical_statistics {
+        render_numerical_histogram_lines(&num_stats.histogram)
+    } else if let Some(ref cat_stats) = column_info.categorical_statistics {
+        render_categorical_bar_chart_lines(cat_stats)
+    } else {
+        Vec::new()
+    }
+}
+
 /// Calculates percentile value from sorted numerical data
 ///
 /// # Arguments
@@ -1018,8 +1649,9 @@ fn calculate_categorical_statistics(column_values: &[String]) -> RowsAndColumnsR
         })
         .collect();
 
-    // Sort by count (descending)
-    value_frequencies.sort_by(|a, b| b.count.cmp(&a.count));
+    // Sort by count (descending), ties by value so the mode and any
+    // headless output do not depend on HashMap iteration order
+    value_frequencies.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.value.cmp(&b.value)));
 
     // Find mode (most common value)
     let (mode_value, mode_percentage) = if let Some(most_frequent) = value_frequencies.first() {
@@ -1045,26 +1677,321 @@ fn calculate_categorical_statistics(column_values: &[String]) -> RowsAndColumnsR
     })
 }
 
-/// Saves CSV analysis summary information to a TOML-formatted report file
+/// Computes correlations, cardinality, candidate keys and duplicate rows
 ///
-/// This function creates a new report file and writes the overview/summary section
-/// including file metadata, basic structure information, and column type counts.
-/// The file is created in the same directory as the source CSV file.
+/// Reads the CSV row by row so that values stay aligned across columns.
+/// Rows with fewer fields than the header are padded with empty values.
+/// Row and value identity use 64-bit hashes (`DefaultHasher`) rather than
+/// storing full text, so memory stays proportional to rows x columns x 8 bytes.
 ///
 /// # Arguments
-/// * `csv_file_path` - Absolute path to the analyzed CSV file
-/// * `analysis_results` - Basic CSV structure and column information
-/// * `directory_paths` - Application directory paths for storage references
+/// * `csv_file_path` - Path to the CSV file
+/// * `basic_analysis_results` - Header and column count information
+/// * `enhanced_column_results` - Per-column results, used to pick numeric columns
 ///
 /// # Returns
-/// * `RowsAndColumnsResult<PathBuf>` - Path to the created report file or error
-///
-/// # Errors
-/// * `RowsAndColumnsError::FileSystemError` - If file creation or writing fails
-/// * `RowsAndColumnsError::ConfigurationError` - If paths cannot be determined
-pub fn save_analysis_summary_to_file(
+/// * `RowsAndColumnsResult<CrossColumnAnalysis>` - Cross-column findings
+fn perform_cross_column_analysis(
     csv_file_path: &PathBuf,
-    analysis_results: &CsvAnalysisResults,
+    basic_analysis_results: &CsvAnalysisResults,
+    enhanced_column_results: &[EnhancedCsvColumnInformation],
+) -> RowsAndColumnsResult<CrossColumnAnalysis> {
+    let column_count = basic_analysis_results.total_column_count;
+
+    let csv_file = File::open(csv_file_path)
+        .map_err(|io_error| {
+            create_file_system_error(
+                &format!("Failed to open CSV file for cross-column analysis: {}", csv_file_path.display()),
+                io_error
+            )
+        })?;
+
+    let mut csv_lines = BufReader::new(csv_file).lines();
+    let first_data_line_number = if basic_analysis_results.has_header_row {
+        csv_lines.next();
+        2
+    } else {
+        1
+    };
+
+    // Column indices of continuous columns, for correlations
+    let numeric_column_indices: Vec<usize> = enhanced_column_results
+        .iter()
+        .filter(|column_info| column_info.field_type == CsvFieldType::Continuous)
+        .map(|column_info| column_info.basic_info.column_index)
+        .filter(|column_index| *column_index < column_count)
+        .collect();
+
+    let mut numeric_values_by_row: Vec<Vec<Option<f64>>> = Vec::new();
+    // Per-row value hashes (None for empty), for cardinality and key search
+    let mut value_hashes_by_row: Vec<Vec<Option<u64>>> = Vec::new();
+    let mut first_line_by_row_fingerprint: HashMap<u64, usize> = HashMap::new();
+    let mut duplicate_lines_by_first_line: HashMap<usize, Vec<usize>> = HashMap::new();
+    let mut duplicate_row_count = 0;
+
+    for (row_offset, line_result) in csv_lines.enumerate() {
+        let csv_line_number = first_data_line_number + row_offset;
+        let csv_line = line_result.map_err(|io_error| {
+            create_file_system_error("Failed to read CSV line during cross-column analysis", io_error)
+        })?;
+
+        let mut field_values = parse_csv_line_into_fields(&csv_line);
+        field_values.resize(column_count, String::new());
+        let trimmed_fields: Vec<&str> = field_values.iter().map(|field| field.trim()).collect();
+
+        // Exact duplicate detection via row fingerprint
+        let row_fingerprint = hash_value(&trimmed_fields);
+        match first_line_by_row_fingerprint.get(&row_fingerprint) {
+            Some(&first_line_number) => {
+                duplicate_row_count += 1;
+                duplicate_lines_by_first_line
+                    .entry(first_line_number)
+                    .or_default()
+                    .push(csv_line_number);
+            }
+            None => {
+                first_line_by_row_fingerprint.insert(row_fingerprint, csv_line_number);
+            }
+        }
+
+        value_hashes_by_row.push(
+            trimmed_fields
+                .iter()
+                .map(|field| if field.is_empty() { None } else { Some(hash_value(field)) })
+                .collect()
+        );
+
+        numeric_values_by_row.push(
+            numeric_column_indices
+                .iter()
+                .map(|&column_index| trimmed_fields[column_index].parse::<f64>().ok())
+                .collect()
+        );
+    }
+
+    let total_rows_checked = value_hashes_by_row.len();
+
+    // Correlation matrices over pairwise-complete rows
+    let numeric_column_count = numeric_column_indices.len();
+    let mut pearson_correlation_matrix = vec![vec![None; numeric_column_count]; numeric_column_count];
+    let mut spearman_correlation_matrix = vec![vec![None; numeric_column_count]; numeric_column_count];
+
+    for first_numeric in 0..numeric_column_count {
+        for second_numeric in first_numeric..numeric_column_count {
+            let (first_values, second_values): (Vec<f64>, Vec<f64>) = numeric_values_by_row
+                .iter()
+                .filter_map(|row_values| match (row_values[first_numeric], row_values[second_numeric]) {
+                    (Some(first_value), Some(second_value)) => Some((first_value, second_value)),
+                    _ => None,
+                })
+                .unzip();
+
+            let pearson = calculate_pearson_correlation(&first_values, &second_values);
+            let spearman = calculate_pearson_correlation(
+                &calculate_average_ranks(&first_values),
+                &calculate_average_ranks(&second_values),
+            );
+
+            pearson_correlation_matrix[first_numeric][second_numeric] = pearson;
+            pearson_correlation_matrix[second_numeric][first_numeric] = pearson;
+            spearman_correlation_matrix[first_numeric][second_numeric] = spearman;
+            spearman_correlation_matrix[second_numeric][first_numeric] = spearman;
+        }
+    }
+
+    // Column names by index, falling back to generated names
+    let column_name_for = |column_index: usize| {
+        basic_analysis_results.column_information_list
+            .get(column_index)
+            .map(|column_info| column_info.column_name.clone())
+            .unwrap_or_else(|| format!("column_{}", column_index + 1))
+    };
+
+    // Single-column cardinality
+    let mut column_cardinalities = Vec::with_capacity(column_count);
+    for column_index in 0..column_count {
+        let mut distinct_hashes = HashSet::new();
+        let mut non_empty_value_count = 0;
+
+        for row_hashes in &value_hashes_by_row {
+            if let Some(value_hash) = row_hashes[column_index] {
+                non_empty_value_count += 1;
+                distinct_hashes.insert(value_hash);
+            }
+        }
+
+        column_cardinalities.push(ColumnCardinality {
+            column_name: column_name_for(column_index),
+            distinct_value_count: distinct_hashes.len(),
+            non_empty_value_count,
+            is_unique: total_rows_checked > 0
+                && non_empty_value_count == total_rows_checked
+                && distinct_hashes.len() == total_rows_checked,
+        });
+    }
+
+    // Column pairs unique together (only when neither is unique alone)
+    let mut unique_column_pairs = Vec::new();
+    let column_pair_search_skipped = column_count > CANDIDATE_KEY_PAIR_SEARCH_MAX_COLUMNS;
+
+    if !column_pair_search_skipped && total_rows_checked > 0 {
+        for first_column in 0..column_count {
+            if column_cardinalities[first_column].is_unique
+                || column_cardinalities[first_column].non_empty_value_count < total_rows_checked
+            {
+                continue;
+            }
+
+            for second_column in (first_column + 1)..column_count {
+                if column_cardinalities[second_column].is_unique
+                    || column_cardinalities[second_column].non_empty_value_count < total_rows_checked
+                {
+                    continue;
+                }
+
+                let mut distinct_pair_hashes = HashSet::with_capacity(total_rows_checked);
+                let pair_is_unique = value_hashes_by_row.iter().all(|row_hashes| {
+                    distinct_pair_hashes.insert((row_hashes[first_column], row_hashes[second_column]))
+                });
+
+                if pair_is_unique {
+                    unique_column_pairs.push((column_name_for(first_column), column_name_for(second_column)));
+                }
+            }
+        }
+    }
+
+    // Duplicate groups in file order, limited for the report
+    let mut duplicate_row_groups: Vec<DuplicateRowGroup> = duplicate_lines_by_first_line
+        .into_iter()
+        .map(|(first_line_number, duplicate_line_numbers)| DuplicateRowGroup {
+            first_line_number,
+            duplicate_line_numbers,
+        })
+        .collect();
+    duplicate_row_groups.sort_by_key(|group| group.first_line_number);
+    duplicate_row_groups.truncate(MAX_DUPLICATE_ROW_GROUPS_REPORTED);
+
+    Ok(CrossColumnAnalysis {
+        numeric_column_names: numeric_column_indices.iter().map(|&column_index| column_name_for(column_index)).collect(),
+        pearson_correlation_matrix,
+        spearman_correlation_matrix,
+        column_cardinalities,
+        unique_column_pairs,
+        column_pair_search_skipped,
+        total_rows_checked,
+        duplicate_row_count,
+        duplicate_row_groups,
+    })
+}
+
+/// Hashes any hashable value into a 64-bit fingerprint
+///
+/// # Arguments
+/// * `value` - The value to fingerprint
+///
+/// # Returns
+/// * `u64` - Hash from the standard library `DefaultHasher`
+fn hash_value<T: Hash + ?Sized>(value: &T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Calculates the Pearson correlation coefficient of two equal-length series
+///
+/// # Arguments
+/// * `first_values` - First series
+/// * `second_values` - Second series, paired by position with the first
+///
+/// # Returns
+/// * `Option<f64>` - Coefficient in -1.0..=1.0, or None if fewer than two
+///   pairs or either series has zero variance
+fn calculate_pearson_correlation(first_values: &[f64], second_values: &[f64]) -> Option<f64> {
+    let pair_count = first_values.len().min(second_values.len());
+    if pair_count < 2 {
+        return None;
+    }
+
+    let first_mean = first_values[..pair_count].iter().sum::<f64>() / pair_count as f64;
+    let second_mean = second_values[..pair_count].iter().sum::<f64>() / pair_count as f64;
+
+    let mut covariance_sum = 0.0;
+    let mut first_variance_sum = 0.0;
+    let mut second_variance_sum = 0.0;
+
+    for (first_value, second_value) in first_values.iter().zip(second_values) {
+        let first_deviation = first_value - first_mean;
+        let second_deviation = second_value - second_mean;
+        covariance_sum += first_deviation * second_deviation;
+        first_variance_sum += first_deviation * first_deviation;
+        second_variance_sum += second_deviation * second_deviation;
+    }
+
+    if first_variance_sum == 0.0 || second_variance_sum == 0.0 {
+        return None;
+    }
+
+    Some((covariance_sum / (first_variance_sum.sqrt() * second_variance_sum.sqrt())).clamp(-1.0, 1.0))
+}
+
+/// Converts values to ranks (1-based), giving tied values their average rank
+///
+/// Used to compute Spearman correlation as the Pearson correlation of ranks.
+///
+/// # Arguments
+/// * `values` - Values in their original order
+///
+/// # Returns
+/// * `Vec<f64>` - Rank of each value, in the original order
+fn calculate_average_ranks(values: &[f64]) -> Vec<f64> {
+    let mut sorted_positions: Vec<usize> = (0..values.len()).collect();
+    sorted_positions.sort_by(|a, b| values[*a].partial_cmp(&values[*b]).unwrap_or(std::cmp::Ordering::Equal));
+
+    let mut ranks = vec![0.0; values.len()];
+    let mut group_start = 0;
+
+    while group_start < sorted_positions.len() {
+        // Extend the group over equal values
+        let mut group_end = group_start + 1;
+        while group_end < sorted_positions.len()
+            && values[sorted_positions[group_end]] == values[sorted_positions[group_start]]
+        {
+            group_end += 1;
+        }
+
+        // Ranks group_start+1 ..= group_end share their average
+        let average_rank = (group_start + 1 + group_end) as f64 / 2.0;
+        for &position in &sorted_positions[group_start..group_end] {
+            ranks[position] = average_rank;
+        }
+
+        group_start = group_end;
+    }
+
+    ranks
+}
+
+/// Saves CSV analysis summary information to a TOML-formatted report file
+///
+/// This function creates a new report file and writes the overview/summary section
+/// including file metadata, basic structure information, and column type counts.
+/// The file is created in the same directory as the source CSV file.
+///
+/// # Arguments
+/// * `csv_file_path` - Absolute path to the analyzed CSV file
+/// * `analysis_results` - Basic CSV structure and column information
+/// * `directory_paths` - Application directory paths for storage references
+///
+/// # Returns
+/// * `RowsAndColumnsResult<PathBuf>` - Path to the created report file or error
+///
+/// # Errors
+/// * `RowsAndColumnsError::FileSystemError` - If file creation or writing fails
+/// * `RowsAndColumnsError::ConfigurationError` - If paths cannot be determined
+pub fn save_analysis_summary_to_file(
+    csv_file_path: &PathBuf,
+    analysis_results: &CsvAnalysisResults,
 ) -> RowsAndColumnsResult<PathBuf> {
     // Determine report file path in same directory as CSV
     let csv_parent_directory = csv_file_path.parent()
@@ -1091,6 +2018,35 @@ pub fn save_analysis_summary_to_file(
     let report_filename = format!("{}_analysis_report_{}.toml", csv_stem, timestamp);
     let report_file_path = csv_parent_directory.join(report_filename);
 
+    // Build TOML content
+    let toml_content = render_analysis_summary_toml(csv_file_path, analysis_results, &timestamp);
+
+    // Write content to file
+    std::fs::write(&report_file_path, toml_content)
+        .map_err(|io_error| create_file_system_error(
+            &format!("Failed to write analysis report to {}", report_file_path.display()),
+            io_error
+        ))?;
+
+    Ok(report_file_path)
+}
+
+/// Renders the overview/summary section of an analysis report as TOML text
+///
+/// Shared by the report file writer and the headless `--format toml` output.
+///
+/// # Arguments
+/// * `csv_file_path` - Absolute path to the analyzed CSV file
+/// * `analysis_results` - Basic CSV structure and column information
+/// * `timestamp` - Report timestamp (seconds since the Unix epoch)
+///
+/// # Returns
+/// * `String` - The `[metadata]`, `[summary]` and `[file_paths]` tables
+pub fn render_analysis_summary_toml(
+    csv_file_path: &Path,
+    analysis_results: &CsvAnalysisResults,
+    timestamp: &str,
+) -> String {
     // Get file size if available
     let file_size_bytes = std::fs::metadata(csv_file_path)
         .map(|metadata| metadata.len())
@@ -1107,7 +2063,6 @@ pub fn save_analysis_summary_to_file(
         }
     }
 
-    // Build TOML content
     let mut toml_content = String::new();
 
     // Metadata section
@@ -1134,14 +2089,7 @@ pub fn save_analysis_summary_to_file(
     // toml_content.push_str(&format!("storage_directory = \"{}\"\n", directory_paths.csv_imports_directory.display()));
     toml_content.push_str("\n");
 
-    // Write content to file
-    std::fs::write(&report_file_path, toml_content)
-        .map_err(|io_error| create_file_system_error(
-            &format!("Failed to write analysis report to {}", report_file_path.display()),
-            io_error
-        ))?;
-
-    Ok(report_file_path)
+    toml_content
 }
 
 /// Appends detailed column-by-column analysis to the TOML report file
@@ -1149,10 +2097,12 @@ pub fn save_analysis_summary_to_file(
 /// This function adds detailed statistical analysis for each column including
 /// numerical statistics (min, max, quartiles, mean, stdev) for continuous columns
 /// and categorical statistics (unique values, mode, value distributions) for categorical columns.
+/// A `[cross_column]` section with correlations, candidate keys and duplicate
+/// rows follows the per-column tables.
 ///
 /// # Arguments
 /// * `report_file_path` - Path to the existing report file to append to
-/// * `enhanced_analysis_results` - Vector of enhanced column information with statistics
+/// * `enhanced_analysis_results` - Enhanced column and cross-column analysis
 ///
 /// # Returns
 /// * `RowsAndColumnsResult<()>` - Success or error
@@ -1161,7 +2111,7 @@ pub fn save_analysis_summary_to_file(
 /// * `RowsAndColumnsError::FileSystemError` - If file reading or writing fails
 pub fn save_analysis_details_to_file(
     report_file_path: &PathBuf,
-    enhanced_analysis_results: &Vec<EnhancedCsvColumnInformation>,
+    enhanced_analysis_results: &EnhancedCsvAnalysisResults,
 ) -> RowsAndColumnsResult<()> {
     // Read existing content
     let mut toml_content = std::fs::read_to_string(report_file_path)
@@ -1170,14 +2120,40 @@ pub fn save_analysis_details_to_file(
             io_error
         ))?;
 
+    // Append column and cross-column details
+    toml_content.push_str(&render_analysis_details_toml(enhanced_analysis_results));
+
+    // Write updated content back to file
+    std::fs::write(report_file_path, toml_content)
+        .map_err(|io_error| create_file_system_error(
+            &format!("Failed to write detailed analysis to {}", report_file_path.display()),
+            io_error
+        ))?;
+
+    Ok(())
+}
+
+/// Renders the detailed column and cross-column analysis as TOML text
+///
+/// Shared by the report file writer and the headless `--format toml` output.
+///
+/// # Arguments
+/// * `enhanced_analysis_results` - Enhanced column and cross-column analysis
+///
+/// # Returns
+/// * `String` - The `[columns.*]` and `[cross_column*]` tables
+pub fn render_analysis_details_toml(enhanced_analysis_results: &EnhancedCsvAnalysisResults) -> String {
+    let column_results = &enhanced_analysis_results.column_results;
+    let mut toml_content = String::new();
+
     // Add columns header if we have columns to report
-    if !enhanced_analysis_results.is_empty() {
+    if !column_results.is_empty() {
         toml_content.push_str("[columns]\n");
         toml_content.push_str("\n");
     }
 
     // Process each column
-    for column_info in enhanced_analysis_results {
+    for column_info in column_results {
         let column_name = &column_info.basic_info.column_name;
         let safe_column_name = column_name.replace(" ", "_").replace(".", "_");
 
@@ -1202,6 +2178,22 @@ pub fn save_analysis_details_to_file(
             toml_content.push_str(&format!("mean_value = {:.3}\n", num_stats.mean_value));
             toml_content.push_str(&format!("standard_deviation = {:.3}\n", num_stats.standard_deviation));
             toml_content.push_str(&format!("missing_percentage = {:.1}\n", num_stats.missing_percentage));
+
+            // Histogram data plus an ASCII plot as TOML comments
+            toml_content.push_str(&format!("histogram_range_start = {:.3}\n", num_stats.histogram.range_start));
+            toml_content.push_str(&format!("histogram_bin_width = {:.3}\n", num_stats.histogram.bin_width));
+            toml_content.push_str(&format!(
+                "histogram_bin_counts = [{}]\n",
+                num_stats.histogram.bin_counts
+                    .iter()
+                    .map(|count| count.to_string())
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ));
+            toml_content.push_str("# histogram:\n");
+            for plot_line in render_numerical_histogram_lines(&num_stats.histogram) {
+                toml_content.push_str(&format!("#   {}\n", plot_line));
+            }
         }
 
         // Add categorical statistics if categorical
@@ -1232,6 +2224,12 @@ pub fn save_analysis_details_to_file(
                 ));
             }
             toml_content.push_str("]\n");
+
+            // ASCII bar chart of top values as TOML comments
+            toml_content.push_str("# top values:\n");
+            for plot_line in render_categorical_bar_chart_lines(cat_stats) {
+                toml_content.push_str(&format!("#   {}\n", plot_line));
+            }
         }
 
         // Sample values
@@ -1247,12 +2245,1191 @@ pub fn save_analysis_details_to_file(
         toml_content.push_str("\n");
     }
 
-    // Write updated content back to file
-    std::fs::write(report_file_path, toml_content)
+    // Cross-column section
+    append_cross_column_analysis_toml(&mut toml_content, &enhanced_analysis_results.cross_column_analysis);
+
+    toml_content
+}
+
+/// Appends the cross-column analysis as TOML tables
+///
+/// Correlation matrices are written one row per numeric column, keyed by the
+/// quoted column name; `nan` marks coefficients that could not be computed.
+///
+/// # Arguments
+/// * `toml_content` - Report text to append to
+/// * `cross_column_analysis` - Cross-column findings
+fn append_cross_column_analysis_toml(toml_content: &mut String, cross_column_analysis: &CrossColumnAnalysis) {
+    let quoted_name = |name: &str| format!("\"{}\"", escape_toml_string_value(name));
+
+    toml_content.push_str("[cross_column]\n");
+    toml_content.push_str(&format!("total_rows_checked = {}\n", cross_column_analysis.total_rows_checked));
+    toml_content.push_str(&format!(
+        "numeric_columns = [{}]\n",
+        cross_column_analysis.numeric_column_names
+            .iter()
+            .map(|name| quoted_name(name))
+            .collect::<Vec<String>>()
+            .join(", ")
+    ));
+    toml_content.push_str(&format!(
+        "unique_columns = [{}]\n",
+        cross_column_analysis.column_cardinalities
+            .iter()
+            .filter(|cardinality| cardinality.is_unique)
+            .map(|cardinality| quoted_name(&cardinality.column_name))
+            .collect::<Vec<String>>()
+            .join(", ")
+    ));
+    toml_content.push_str(&format!(
+        "unique_column_pairs = [{}]\n",
+        cross_column_analysis.unique_column_pairs
+            .iter()
+            .map(|(first_name, second_name)| format!("[{}, {}]", quoted_name(first_name), quoted_name(second_name)))
+            .collect::<Vec<String>>()
+            .join(", ")
+    ));
+    if cross_column_analysis.column_pair_search_skipped {
+        toml_content.push_str(&format!(
+            "# column pair search skipped: more than {} columns\n",
+            CANDIDATE_KEY_PAIR_SEARCH_MAX_COLUMNS
+        ));
+    }
+    toml_content.push_str(&format!("duplicate_row_count = {}\n", cross_column_analysis.duplicate_row_count));
+    toml_content.push('\n');
+
+    // Correlation matrices
+    for (table_name, correlation_matrix) in [
+        ("pearson_correlation", &cross_column_analysis.pearson_correlation_matrix),
+        ("spearman_correlation", &cross_column_analysis.spearman_correlation_matrix),
+    ] {
+        if cross_column_analysis.numeric_column_names.is_empty() {
+            break;
+        }
+
+        toml_content.push_str(&format!("[cross_column.{}]\n", table_name));
+        for (row_name, matrix_row) in cross_column_analysis.numeric_column_names.iter().zip(correlation_matrix) {
+            toml_content.push_str(&format!(
+                "{} = [{}]\n",
+                quoted_name(row_name),
+                matrix_row
+                    .iter()
+                    .map(|coefficient| match coefficient {
+                        Some(value) => format!("{:.3}", value),
+                        None => "nan".to_string(),
+                    })
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ));
+        }
+        toml_content.push('\n');
+    }
+
+    // Cardinality per column
+    toml_content.push_str("[cross_column.cardinality]\n");
+    for cardinality in &cross_column_analysis.column_cardinalities {
+        toml_content.push_str(&format!(
+            "{} = {{ distinct_values = {}, non_empty_values = {}, unique = {} }}\n",
+            quoted_name(&cardinality.column_name),
+            cardinality.distinct_value_count,
+            cardinality.non_empty_value_count,
+            cardinality.is_unique
+        ));
+    }
+    toml_content.push('\n');
+
+    // Duplicate row groups
+    if !cross_column_analysis.duplicate_row_groups.is_empty() {
+        toml_content.push_str("[cross_column.duplicate_rows]\n");
+        toml_content.push_str("groups = [\n");
+        for group in &cross_column_analysis.duplicate_row_groups {
+            toml_content.push_str(&format!(
+                "  {{ first_line = {}, duplicate_lines = [{}] }},\n",
+                group.first_line_number,
+                group.duplicate_line_numbers
+                    .iter()
+                    .map(|line_number| line_number.to_string())
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ));
+        }
+        toml_content.push_str("]\n\n");
+    }
+}
+
+/// Escapes text for use inside a double-quoted JSON string
+///
+/// # Arguments
+/// * `raw_text` - The unescaped text
+///
+/// # Returns
+/// * `String` - Text with quotes, backslashes and control characters escaped
+pub fn escape_json_string(raw_text: &str) -> String {
+    let mut escaped_text = String::with_capacity(raw_text.len());
+
+    for current_char in raw_text.chars() {
+        match current_char {
+            '"' => escaped_text.push_str("\\\""),
+            '\\' => escaped_text.push_str("\\\\"),
+            '\n' => escaped_text.push_str("\\n"),
+            '\r' => escaped_text.push_str("\\r"),
+            '\t' => escaped_text.push_str("\\t"),
+            control_char if (control_char as u32) < 0x20 => {
+                escaped_text.push_str(&format!("\\u{:04x}", control_char as u32));
+            }
+            other_char => escaped_text.push(other_char),
+        }
+    }
+
+    escaped_text
+}
+
+/// Formats a float as a JSON number, or `null` if it is NaN or infinite
+///
+/// # Arguments
+/// * `value` - The number to format
+///
+/// # Returns
+/// * `String` - JSON number text or `null`
+fn format_json_number(value: f64) -> String {
+    if value.is_finite() {
+        format!("{}", value)
+    } else {
+        "null".to_string()
+    }
+}
+
+/// Formats an optional correlation matrix as a JSON array of arrays
+///
+/// # Arguments
+/// * `correlation_matrix` - Square matrix with None for undefined entries
+///
+/// # Returns
+/// * `String` - JSON text such as `[[1,0.5],[0.5,1]]`
+fn format_json_correlation_matrix(correlation_matrix: &[Vec<Option<f64>>]) -> String {
+    let matrix_rows: Vec<String> = correlation_matrix
+        .iter()
+        .map(|matrix_row| {
+            let row_values: Vec<String> = matrix_row
+                .iter()
+                .map(|coefficient| coefficient.map(format_json_number).unwrap_or_else(|| "null".to_string()))
+                .collect();
+            format!("[{}]", row_values.join(","))
+        })
+        .collect();
+
+    format!("[{}]", matrix_rows.join(","))
+}
+
+/// Renders a complete analysis as a single JSON document
+///
+/// Used by the headless `ff --rc data.csv --format json` output.
+///
+/// # Arguments
+/// * `csv_file_path` - Absolute path to the analyzed CSV file
+/// * `analysis_results` - Basic CSV structure and column information
+/// * `enhanced_analysis_results` - Enhanced column and cross-column analysis
+///
+/// # Returns
+/// * `String` - Pretty-printed JSON object
+pub fn render_analysis_json(
+    csv_file_path: &Path,
+    analysis_results: &CsvAnalysisResults,
+    enhanced_analysis_results: &EnhancedCsvAnalysisResults,
+) -> String {
+    let quoted = |text: &str| format!("\"{}\"", escape_json_string(text));
+    let mut json_content = String::new();
+
+    json_content.push_str("{\n");
+    json_content.push_str(&format!("  \"csv_file\": {},\n", quoted(&csv_file_path.display().to_string())));
+    json_content.push_str(&format!(
+        "  \"metadata_file\": {},\n",
+        quoted(&analysis_results.metadata_file_path.display().to_string())
+    ));
+    json_content.push_str(&format!("  \"has_header_row\": {},\n", analysis_results.has_header_row));
+    json_content.push_str(&format!("  \"total_data_rows\": {},\n", analysis_results.total_data_row_count));
+    json_content.push_str(&format!("  \"total_columns\": {},\n", analysis_results.total_column_count));
+
+    // Per-column statistics
+    let column_objects: Vec<String> = enhanced_analysis_results.column_results
+        .iter()
+        .map(|column_info| {
+            let numerical_json = match column_info.numerical_statistics {
+                Some(ref num_stats) => format!(
+                    "{{\"min\": {}, \"q1\": {}, \"median\": {}, \"q3\": {}, \"max\": {}, \"mean\": {}, \
+                     \"standard_deviation\": {}, \"missing_percentage\": {}, \"histogram\": \
+                     {{\"range_start\": {}, \"bin_width\": {}, \"bin_counts\": [{}]}}}}",
+                    format_json_number(num_stats.min_value),
+                    format_json_number(num_stats.q1_value),
+                    format_json_number(num_stats.q2_median_value),
+                    format_json_number(num_stats.q3_value),
+                    format_json_number(num_stats.max_value),
+                    format_json_number(num_stats.mean_value),
+                    format_json_number(num_stats.standard_deviation),
+                    format_json_number(num_stats.missing_percentage),
+                    format_json_number(num_stats.histogram.range_start),
+                    format_json_number(num_stats.histogram.bin_width),
+                    num_stats.histogram.bin_counts
+                        .iter()
+                        .map(|count| count.to_string())
+                        .collect::<Vec<String>>()
+                        .join(", ")
+                ),
+                None => "null".to_string(),
+            };
+
+            let categorical_json = match column_info.categorical_statistics {
+                Some(ref cat_stats) => format!(
+                    "{{\"unique_value_count\": {}, \"missing_percentage\": {}, \"mode_value\": {}, \
+                     \"mode_percentage\": {}, \"top_values\": [{}]}}",
+                    cat_stats.unique_value_count,
+                    format_json_number(cat_stats.missing_percentage),
+                    cat_stats.mode_value.as_deref().map(quoted).unwrap_or_else(|| "null".to_string()),
+                    format_json_number(cat_stats.mode_percentage),
+                    cat_stats.value_frequencies
+                        .iter()
+                        .take(DISTRIBUTION_PLOT_TOP_CATEGORIES)
+                        .map(|freq| format!(
+                            "{{\"value\": {}, \"count\": {}, \"percentage\": {}}}",
+                            quoted(&freq.value),
+                            freq.count,
+                            format_json_number(freq.percentage)
+                        ))
+                        .collect::<Vec<String>>()
+                        .join(", ")
+                ),
+                None => "null".to_string(),
+            };
+
+            format!(
+                "    {{\n      \"index\": {},\n      \"name\": {},\n      \"data_type\": {},\n      \
+                 \"field_type\": {},\n      \"non_empty_values\": {},\n      \"empty_values\": {},\n      \
+                 \"numerical_statistics\": {},\n      \"categorical_statistics\": {}\n    }}",
+                column_info.basic_info.column_index,
+                quoted(&column_info.basic_info.column_name),
+                quoted(column_info.basic_info.detected_data_type.to_toml_string()),
+                quoted(match column_info.field_type {
+                    CsvFieldType::Continuous => "continuous",
+                    CsvFieldType::Categorical => "categorical",
+                }),
+                column_info.basic_info.non_empty_value_count,
+                column_info.basic_info.empty_value_count,
+                numerical_json,
+                categorical_json
+            )
+        })
+        .collect();
+
+    json_content.push_str("  \"columns\": [\n");
+    json_content.push_str(&column_objects.join(",\n"));
+    json_content.push_str("\n  ],\n");
+
+    // Cross-column analysis
+    let cross_column = &enhanced_analysis_results.cross_column_analysis;
+    let quoted_list = |names: &[String]| {
+        names.iter().map(|name| quoted(name)).collect::<Vec<String>>().join(", ")
+    };
+
+    json_content.push_str("  \"cross_column\": {\n");
+    json_content.push_str(&format!("    \"total_rows_checked\": {},\n", cross_column.total_rows_checked));
+    json_content.push_str(&format!(
+        "    \"numeric_columns\": [{}],\n",
+        quoted_list(&cross_column.numeric_column_names)
+    ));
+    json_content.push_str(&format!(
+        "    \"pearson_correlation\": {},\n",
+        format_json_correlation_matrix(&cross_column.pearson_correlation_matrix)
+    ));
+    json_content.push_str(&format!(
+        "    \"spearman_correlation\": {},\n",
+        format_json_correlation_matrix(&cross_column.spearman_correlation_matrix)
+    ));
+    json_content.push_str(&format!(
+        "    \"cardinality\": [{}],\n",
+        cross_column.column_cardinalities
+            .iter()
+            .map(|cardinality| format!(
+                "{{\"column\": {}, \"distinct_values\": {}, \"non_empty_values\": {}, \"unique\": {}}}",
+                quoted(&cardinality.column_name),
+                cardinality.distinct_value_count,
+                cardinality.non_empty_value_count,
+                cardinality.is_unique
+            ))
+            .collect::<Vec<String>>()
+            .join(", ")
+    ));
+    json_content.push_str(&format!(
+        "    \"unique_column_pairs\": [{}],\n",
+        cross_column.unique_column_pairs
+            .iter()
+            .map(|(first_name, second_name)| format!("[{}, {}]", quoted(first_name), quoted(second_name)))
+            .collect::<Vec<String>>()
+            .join(", ")
+    ));
+    json_content.push_str(&format!(
+        "    \"column_pair_search_skipped\": {},\n",
+        cross_column.column_pair_search_skipped
+    ));
+    json_content.push_str(&format!("    \"duplicate_row_count\": {},\n", cross_column.duplicate_row_count));
+    json_content.push_str(&format!(
+        "    \"duplicate_row_groups\": [{}]\n",
+        cross_column.duplicate_row_groups
+            .iter()
+            .map(|group| format!(
+                "{{\"first_line\": {}, \"duplicate_lines\": [{}]}}",
+                group.first_line_number,
+                group.duplicate_line_numbers
+                    .iter()
+                    .map(|line_number| line_number.to_string())
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ))
+            .collect::<Vec<String>>()
+            .join(", ")
+    ));
+    json_content.push_str("  }\n");
+    json_content.push_str("}\n");
+
+    json_content
+}
+
+/// Renders a complete analysis as plain, human-readable text
+///
+/// Used by the headless `ff --rc data.csv --format text` output. Includes the
+/// same ASCII histograms and bar charts as the report file.
+///
+/// # Arguments
+/// * `csv_file_path` - Absolute path to the analyzed CSV file
+/// * `analysis_results` - Basic CSV structure and column information
+/// * `enhanced_analysis_results` - Enhanced column and cross-column analysis
+///
+/// # Returns
+/// * `String` - Multi-line text report
+pub fn render_analysis_text(
+    csv_file_path: &Path,
+    analysis_results: &CsvAnalysisResults,
+    enhanced_analysis_results: &EnhancedCsvAnalysisResults,
+) -> String {
+    let mut text_content = String::new();
+
+    text_content.push_str(&format!("File: {}\n", csv_file_path.display()));
+    text_content.push_str(&format!(
+        "Rows: {}  Columns: {}  Header: {}\n\n",
+        analysis_results.total_data_row_count,
+        analysis_results.total_column_count,
+        if analysis_results.has_header_row { "yes" } else { "no" }
+    ));
+
+    for column_info in &enhanced_analysis_results.column_results {
+        text_content.push_str(&format!(
+            "{}. {} ({}, {})  non-empty {}, empty {}\n",
+            column_info.basic_info.column_index + 1,
+            column_info.basic_info.column_name,
+            column_info.basic_info.detected_data_type.to_toml_string(),
+            format!("{:?}", column_info.field_type).to_lowercase(),
+            column_info.basic_info.non_empty_value_count,
+            column_info.basic_info.empty_value_count
+        ));
+
+        if let Some(ref num_stats) = column_info.numerical_statistics {
+            text_content.push_str(&format!(
+                "   min {:.3}  q1 {:.3}  median {:.3}  q3 {:.3}  max {:.3}\n",
+                num_stats.min_value,
+                num_stats.q1_value,
+                num_stats.q2_median_value,
+                num_stats.q3_value,
+                num_stats.max_value
+            ));
+            text_content.push_str(&format!(
+                "   mean {:.3}  stdev {:.3}  missing {:.1}%\n",
+                num_stats.mean_value,
+                num_stats.standard_deviation,
+                num_stats.missing_percentage
+            ));
+        }
+
+        if let Some(ref cat_stats) = column_info.categorical_statistics {
+            text_content.push_str(&format!(
+                "   unique {}  mode {} ({:.1}%)  missing {:.1}%\n",
+                cat_stats.unique_value_count,
+                cat_stats.mode_value.as_deref().unwrap_or("-"),
+                cat_stats.mode_percentage,
+                cat_stats.missing_percentage
+            ));
+        }
+
+        for plot_line in render_column_distribution_plot_lines(column_info) {
+            text_content.push_str(&format!("   {}\n", plot_line));
+        }
+        text_content.push('\n');
+    }
+
+    // Cross-column summary
+    let cross_column = &enhanced_analysis_results.cross_column_analysis;
+    let unique_column_names: Vec<&str> = cross_column.column_cardinalities
+        .iter()
+        .filter(|cardinality| cardinality.is_unique)
+        .map(|cardinality| cardinality.column_name.as_str())
+        .collect();
+    let unique_pair_names: Vec<String> = cross_column.unique_column_pairs
+        .iter()
+        .map(|(first_name, second_name)| format!("({}, {})", first_name, second_name))
+        .collect();
+
+    text_content.push_str("Cross-column:\n");
+    text_content.push_str(&format!("   unique columns: {}\n", if unique_column_names.is_empty() {
+        "-".to_string()
+    } else {
+        unique_column_names.join(", ")
+    }));
+    text_content.push_str(&format!("   unique column pairs: {}\n", if unique_pair_names.is_empty() {
+        "-".to_string()
+    } else {
+        unique_pair_names.join(", ")
+    }));
+    text_content.push_str(&format!("   duplicate rows: {}\n", cross_column.duplicate_row_count));
+
+    for (matrix_title, correlation_matrix) in [
+        ("Pearson correlation", &cross_column.pearson_correlation_matrix),
+        ("Spearman correlation", &cross_column.spearman_correlation_matrix),
+    ] {
+        if cross_column.numeric_column_names.is_empty() {
+            break;
+        }
+
+        text_content.push_str(&format!("   {}:\n", matrix_title));
+        for (row_name, matrix_row) in cross_column.numeric_column_names.iter().zip(correlation_matrix) {
+            let row_cells: Vec<String> = matrix_row
+                .iter()
+                .map(|coefficient| match coefficient {
+                    Some(value) => format!("{:>7.3}", value),
+                    None => format!("{:>7}", "-"),
+                })
+                .collect();
+            text_content.push_str(&format!("     {:<16} {}\n", row_name, row_cells.join(" ")));
+        }
+    }
+
+    text_content
+}
+
+/// A column whose detected type no longer matches the saved metadata type
+#[derive(Debug, Clone)]
+pub struct CsvColumnTypeDrift {
+    /// Column name in the new CSV
+    pub column_name: String,
+
+    /// Data type recorded in the saved metadata
+    pub saved_data_type: CsvColumnDataType,
+
+    /// Data type detected in the new CSV
+    pub observed_data_type: CsvColumnDataType,
+}
+
+/// One field that failed its saved column type
+#[derive(Debug, Clone)]
+pub struct CsvRowTypeFailure {
+    /// 1-based line number in the CSV file (header counts as line 1)
+    pub csv_line_number: usize,
+
+    /// Column name in the new CSV
+    pub column_name: String,
+
+    /// Data type the saved metadata expects
+    pub expected_data_type: CsvColumnDataType,
+
+    /// The offending field value
+    pub field_value: String,
+}
+
+/// A column whose empty-value ratio grew beyond tolerance
+#[derive(Debug, Clone)]
+pub struct CsvEmptyRatioChange {
+    /// Column name in the new CSV
+    pub column_name: String,
+
+    /// Empty-value ratio (0.0 to 1.0) implied by the saved metadata
+    pub saved_empty_ratio: f64,
+
+    /// Empty-value ratio (0.0 to 1.0) observed in the new CSV
+    pub observed_empty_ratio: f64,
+}
+
+/// Results of checking a CSV file against its saved metadata schema
+///
+/// Structural changes, type drift and per-row type failures make the
+/// validation fail. Empty-value ratio changes are reported as warnings
+/// only, since missing data is common and not a schema change by itself.
+#[derive(Debug, Clone, Default)]
+pub struct CsvSchemaValidationReport {
+    /// Column names present in the CSV but not in the metadata
+    pub added_column_names: Vec<String>,
+
+    /// Column names present in the metadata but not in the CSV
+    pub removed_column_names: Vec<String>,
+
+    /// (saved name, new name) pairs for columns renamed at the same position
+    pub renamed_columns: Vec<(String, String)>,
+
+    /// Columns whose detected type changed
+    pub type_drift_list: Vec<CsvColumnTypeDrift>,
+
+    /// Number of data rows with at least one field failing its column type
+    pub rows_failing_type_count: usize,
+
+    /// First few failing fields, for the report
+    pub row_type_failure_examples: Vec<CsvRowTypeFailure>,
+
+    /// Columns whose empty-value ratio increased beyond tolerance
+    pub empty_ratio_changes: Vec<CsvEmptyRatioChange>,
+
+    /// Number of data rows checked
+    pub total_data_rows_checked: usize,
+}
+
+impl CsvSchemaValidationReport {
+    /// Whether the CSV conforms to the saved schema
+    ///
+    /// # Returns
+    /// * `bool` - True if there are no structural, type or row failures
+    pub fn passed(&self) -> bool {
+        self.added_column_names.is_empty()
+            && self.removed_column_names.is_empty()
+            && self.renamed_columns.is_empty()
+            && self.type_drift_list.is_empty()
+            && self.rows_failing_type_count == 0
+    }
+}
+
+/// Validates a CSV file against a saved metadata schema
+///
+/// The CSV is analyzed with the same structure and type detection used by
+/// `analyze_csv_file_structure_and_types`, but the metadata file is only
+/// read, never rewritten, so the saved schema stays the reference.
+///
+/// Columns are matched by name. A saved column that is missing by name is
+/// treated as renamed when the new CSV has an unknown name at the same index.
+///
+/// # Arguments
+/// * `csv_file_path` - Absolute path to the CSV file to validate
+/// * `metadata_file_path` - Path to the saved `.csv_metadata.toml` file
+///
+/// # Returns
+/// * `RowsAndColumnsResult<CsvSchemaValidationReport>` - Validation findings
+///
+/// # Errors
+/// * `RowsAndColumnsError::FileSystemError` - If either file cannot be read
+/// * `RowsAndColumnsError::CsvProcessingError` - If the CSV is empty
+/// * `RowsAndColumnsError::ConfigurationError` - If the metadata cannot be parsed
+pub fn validate_csv_against_metadata_file(
+    csv_file_path: &PathBuf,
+    metadata_file_path: &Path,
+) -> RowsAndColumnsResult<CsvSchemaValidationReport> {
+    // Step 1: Load the saved schema
+    let saved_columns = read_metadata_file(metadata_file_path)?;
+
+    // Step 2: Analyze the new CSV without touching the metadata file
+    let (has_header_row, column_count, _data_row_count) = analyze_csv_basic_structure(csv_file_path)?;
+    let observed_columns = analyze_csv_column_types_and_content(
+        csv_file_path,
+        has_header_row,
+        column_count
+    )?;
+
+    let mut validation_report = CsvSchemaValidationReport::default();
+
+    // Step 3: Match columns by name, then by position for renames
+    // Maps observed column index -> saved column used as reference
+    let mut observed_to_saved: Vec<Option<&CsvColumnInformation>> = vec![None; observed_columns.len()];
+
+    for (observed_index, observed_column) in observed_columns.iter().enumerate() {
+        observed_to_saved[observed_index] = saved_columns
+            .iter()
+            .find(|saved_column| saved_column.column_name == observed_column.column_name);
+    }
+
+    for saved_column in &saved_columns {
+        let found_by_name = observed_columns
+            .iter()
+            .any(|observed_column| observed_column.column_name == saved_column.column_name);
+
+        if found_by_name {
+            continue;
+        }
+
+        // Same position, previously unknown name: a rename
+        let rename_candidate_index = saved_column.column_index;
+        let is_rename = rename_candidate_index < observed_columns.len()
+            && observed_to_saved[rename_candidate_index].is_none();
+
+        if is_rename {
+            observed_to_saved[rename_candidate_index] = Some(saved_column);
+            validation_report.renamed_columns.push((
+                saved_column.column_name.clone(),
+                observed_columns[rename_candidate_index].column_name.clone(),
+            ));
+        } else {
+            validation_report.removed_column_names.push(saved_column.column_name.clone());
+        }
+    }
+
+    for (observed_index, observed_column) in observed_columns.iter().enumerate() {
+        if observed_to_saved[observed_index].is_none() {
+            validation_report.added_column_names.push(observed_column.column_name.clone());
+        }
+    }
+
+    // Step 4: Type drift on matched columns
+    for (observed_index, observed_column) in observed_columns.iter().enumerate() {
+        if let Some(saved_column) = observed_to_saved[observed_index]
+            && saved_column.detected_data_type != observed_column.detected_data_type
+        {
+            validation_report.type_drift_list.push(CsvColumnTypeDrift {
+                column_name: observed_column.column_name.clone(),
+                saved_data_type: saved_column.detected_data_type.clone(),
+                observed_data_type: observed_column.detected_data_type.clone(),
+            });
+        }
+    }
+
+    // Step 5: Check every data row against the saved column types
+    let csv_file = File::open(csv_file_path)
+        .map_err(|io_error| {
+            create_file_system_error(
+                &format!("Failed to open CSV file for validation: {}", csv_file_path.display()),
+                io_error
+            )
+        })?;
+
+    let mut csv_lines = BufReader::new(csv_file).lines();
+    let first_data_line_number = if has_header_row {
+        csv_lines.next();
+        2
+    } else {
+        1
+    };
+
+    let mut observed_empty_counts = vec![0usize; observed_columns.len()];
+
+    for (row_offset, line_result) in csv_lines.enumerate() {
+        let csv_line_number = first_data_line_number + row_offset;
+        let csv_line = line_result.map_err(|io_error| {
+            create_file_system_error(
+                &format!("Failed to read CSV line {} during validation", csv_line_number),
+                io_error
+            )
+        })?;
+
+        let field_values = parse_csv_line_into_fields(&csv_line);
+        let mut row_has_type_failure = false;
+
+        for (column_index, field_value) in field_values.iter().enumerate() {
+            if column_index >= observed_columns.len() {
+                continue; // Skip extra fields, as in analysis
+            }
+
+            let trimmed_value = field_value.trim();
+
+            if trimmed_value.is_empty() {
+                observed_empty_counts[column_index] += 1;
+                continue;
+            }
+
+            if let Some(saved_column) = observed_to_saved[column_index]
+                && !saved_column.detected_data_type.accepts_value(trimmed_value)
+            {
+                row_has_type_failure = true;
+
+                if validation_report.row_type_failure_examples.len() < VALIDATION_MAX_ROW_FAILURE_EXAMPLES {
+                    validation_report.row_type_failure_examples.push(CsvRowTypeFailure {
+                        csv_line_number,
+                        column_name: observed_columns[column_index].column_name.clone(),
+                        expected_data_type: saved_column.detected_data_type.clone(),
+                        field_value: trimmed_value.to_string(),
+                    });
+                }
+            }
+        }
+
+        if row_has_type_failure {
+            validation_report.rows_failing_type_count += 1;
+        }
+
+        validation_report.total_data_rows_checked += 1;
+    }
+
+    // Step 6: Empty-value ratios that grew beyond tolerance
+    for (observed_index, observed_column) in observed_columns.iter().enumerate() {
+        let Some(saved_column) = observed_to_saved[observed_index] else {
+            continue;
+        };
+
+        let saved_total = saved_column.non_empty_value_count + saved_column.empty_value_count;
+        let saved_empty_ratio = if saved_total > 0 {
+            saved_column.empty_value_count as f64 / saved_total as f64
+        } else {
+            0.0
+        };
+
+        let observed_empty_ratio = if validation_report.total_data_rows_checked > 0 {
+            observed_empty_counts[observed_index] as f64 / validation_report.total_data_rows_checked as f64
+        } else {
+            0.0
+        };
+
+        if observed_empty_ratio - saved_empty_ratio > VALIDATION_EMPTY_RATIO_TOLERANCE {
+            validation_report.empty_ratio_changes.push(CsvEmptyRatioChange {
+                column_name: observed_column.column_name.clone(),
+                saved_empty_ratio,
+                observed_empty_ratio,
+            });
+        }
+    }
+
+    Ok(validation_report)
+}
+
+/// Saves a schema validation report to a TOML-formatted summary file
+///
+/// The file is created in the same directory as the source CSV file,
+/// alongside the analysis report files.
+///
+/// # Arguments
+/// * `csv_file_path` - Absolute path to the validated CSV file
+/// * `metadata_file_path` - Path to the metadata file used as the schema
+/// * `validation_report` - Findings from `validate_csv_against_metadata_file`
+///
+/// # Returns
+/// * `RowsAndColumnsResult<PathBuf>` - Path to the created validation file or error
+///
+/// # Errors
+/// * `RowsAndColumnsError::FileSystemError` - If file creation or writing fails
+/// * `RowsAndColumnsError::ConfigurationError` - If paths cannot be determined
+pub fn save_validation_report_to_file(
+    csv_file_path: &Path,
+    metadata_file_path: &Path,
+    validation_report: &CsvSchemaValidationReport,
+) -> RowsAndColumnsResult<PathBuf> {
+    // Determine report file path in same directory as CSV
+    let csv_parent_directory = csv_file_path.parent()
+        .ok_or_else(|| RowsAndColumnsError::ConfigurationError {
+            configuration_issue_description: "Cannot determine parent directory of CSV file".to_string(),
+        })?;
+
+    let csv_stem = csv_file_path.file_stem()
+        .and_then(|stem| stem.to_str())
+        .ok_or_else(|| RowsAndColumnsError::ConfigurationError {
+            configuration_issue_description: "Cannot determine CSV filename".to_string(),
+        })?;
+
+    let timestamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_err(|e| RowsAndColumnsError::ConfigurationError {
+            configuration_issue_description: format!("System time error: {}", e),
+        })?
+        .as_secs();
+
+    let report_filename = format!("{}_validation_report_{}.toml", csv_stem, timestamp);
+    let report_file_path = csv_parent_directory.join(report_filename);
+
+    // Build TOML content
+    let mut toml_content = String::new();
+
+    // Metadata section
+    toml_content.push_str("[metadata]\n");
+    toml_content.push_str(&format!("timestamp = \"{}\"\n", timestamp));
+    toml_content.push_str(&format!("csv_file = \"{}\"\n", csv_file_path.display()));
+    toml_content.push_str(&format!("metadata_file = \"{}\"\n", metadata_file_path.display()));
+    toml_content.push_str("report_generated_by = \"rows_and_columns\"\n\n");
+
+    // Summary section
+    let quoted_list = |names: &[String]| {
+        names.iter()
+            .map(|name| format!("\"{}\"", escape_toml_string_value(name)))
+            .collect::<Vec<String>>()
+            .join(", ")
+    };
+
+    toml_content.push_str("[validation]\n");
+    toml_content.push_str(&format!(
+        "result = \"{}\"\n",
+        if validation_report.passed() { "PASS" } else { "FAIL" }
+    ));
+    toml_content.push_str(&format!("data_rows_checked = {}\n", validation_report.total_data_rows_checked));
+    toml_content.push_str(&format!("added_columns = [{}]\n", quoted_list(&validation_report.added_column_names)));
+    toml_content.push_str(&format!("removed_columns = [{}]\n", quoted_list(&validation_report.removed_column_names)));
+    toml_content.push_str(&format!("type_drift_columns = {}\n", validation_report.type_drift_list.len()));
+    toml_content.push_str(&format!("rows_failing_column_type = {}\n", validation_report.rows_failing_type_count));
+    toml_content.push_str(&format!("empty_ratio_warnings = {}\n", validation_report.empty_ratio_changes.len()));
+
+    toml_content.push_str("renamed_columns = [\n");
+    for (saved_name, new_name) in &validation_report.renamed_columns {
+        toml_content.push_str(&format!(
+            "  {{ from = \"{}\", to = \"{}\" }},\n",
+            escape_toml_string_value(saved_name),
+            escape_toml_string_value(new_name)
+        ));
+    }
+    toml_content.push_str("]\n\n");
+
+    // Type drift details
+    if !validation_report.type_drift_list.is_empty() {
+        toml_content.push_str("[type_drift]\n");
+        toml_content.push_str("columns = [\n");
+        for drift in &validation_report.type_drift_list {
+            toml_content.push_str(&format!(
+                "  {{ column = \"{}\", saved = \"{}\", observed = \"{}\" }},\n",
+                escape_toml_string_value(&drift.column_name),
+                drift.saved_data_type.to_toml_string(),
+                drift.observed_data_type.to_toml_string()
+            ));
+        }
+        toml_content.push_str("]\n\n");
+    }
+
+    // Row failure examples
+    if !validation_report.row_type_failure_examples.is_empty() {
+        toml_content.push_str("[row_type_failures]\n");
+        toml_content.push_str("examples = [\n");
+        for failure in &validation_report.row_type_failure_examples {
+            toml_content.push_str(&format!(
+                "  {{ line = {}, column = \"{}\", expected = \"{}\", value = \"{}\" }},\n",
+                failure.csv_line_number,
+                escape_toml_string_value(&failure.column_name),
+                failure.expected_data_type.to_toml_string(),
+                escape_toml_string_value(&failure.field_value)
+            ));
+        }
+        if validation_report.row_type_failure_examples.len() < validation_report.rows_failing_type_count {
+            toml_content.push_str(&format!(
+                "  # ... and more failing rows ({} rows in total)\n",
+                validation_report.rows_failing_type_count
+            ));
+        }
+        toml_content.push_str("]\n\n");
+    }
+
+    // Empty ratio warnings
+    if !validation_report.empty_ratio_changes.is_empty() {
+        toml_content.push_str("[empty_ratio_warnings]\n");
+        toml_content.push_str("columns = [\n");
+        for change in &validation_report.empty_ratio_changes {
+            toml_content.push_str(&format!(
+                "  {{ column = \"{}\", saved_percentage = {:.1}, observed_percentage = {:.1} }},\n",
+                escape_toml_string_value(&change.column_name),
+                change.saved_empty_ratio * 100.0,
+                change.observed_empty_ratio * 100.0
+            ));
+        }
+        toml_content.push_str("]\n\n");
+    }
+
+    // Write content to file
+    std::fs::write(&report_file_path, toml_content)
         .map_err(|io_error| create_file_system_error(
-            &format!("Failed to write detailed analysis to {}", report_file_path.display()),
+            &format!("Failed to write validation report to {}", report_file_path.display()),
             io_error
         ))?;
 
-    Ok(())
+    Ok(report_file_path)
+}
+
+#[cfg(test)]
+mod metadata_schema_tests {
+    use super::*;
+
+    /// Writes a CSV file into a fresh temporary directory for one test
+    fn write_temp_csv(test_name: &str, csv_text: &str) -> PathBuf {
+        let test_directory = std::env::temp_dir()
+            .join(format!("rc_metadata_schema_tests_{}_{}", test_name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&test_directory);
+        std::fs::create_dir_all(&test_directory).expect("create test directory");
+
+        let csv_path = test_directory.join("data.csv");
+        std::fs::write(&csv_path, csv_text).expect("write test csv");
+        csv_path
+    }
+
+    #[test]
+    fn test_metadata_round_trip_through_reader() {
+        let csv_path = write_temp_csv("round_trip", "id,\"name\",price\n1,a,1.5\n2,b,2.5\n3,,3.5\n");
+        let analysis = analyze_csv_file_structure_and_types(&csv_path).expect("analysis");
+
+        let saved_columns = read_metadata_file(&analysis.metadata_file_path).expect("read back");
+
+        assert_eq!(saved_columns.len(), analysis.column_information_list.len());
+        for (saved, original) in saved_columns.iter().zip(&analysis.column_information_list) {
+            assert_eq!(saved.column_name, original.column_name);
+            assert_eq!(saved.detected_data_type, original.detected_data_type);
+            assert_eq!(saved.column_index, original.column_index);
+            assert_eq!(saved.empty_value_count, original.empty_value_count);
+        }
+    }
+
+    #[test]
+    fn test_parse_metadata_rejects_count_mismatch() {
+        let toml_text = "total_columns = 2\n\n[column_1]\nname = \"a\"\ndata_type = \"integer\"\ncolumn_index = 0\n";
+        assert!(parse_metadata_toml_string(toml_text).is_err());
+    }
+
+    #[test]
+    fn test_parse_metadata_rejects_unknown_type() {
+        let toml_text = "[column_1]\nname = \"a\"\ndata_type = \"date\"\ncolumn_index = 0\n";
+        assert!(parse_metadata_toml_string(toml_text).is_err());
+    }
+
+    #[test]
+    fn test_validation_passes_for_unchanged_csv() {
+        let csv_path = write_temp_csv("unchanged", "id,score\n1,10\n2,20\n3,30\n");
+        let analysis = analyze_csv_file_structure_and_types(&csv_path).expect("analysis");
+
+        let report = validate_csv_against_metadata_file(&csv_path, &analysis.metadata_file_path)
+            .expect("validation");
+
+        assert!(report.passed());
+        assert_eq!(report.total_data_rows_checked, 3);
+    }
+
+    #[test]
+    fn test_validation_reports_schema_changes_and_bad_rows() {
+        let csv_path = write_temp_csv("changed", "id,score,city\n1,10,x\n2,20,y\n3,30,z\n");
+        let analysis = analyze_csv_file_structure_and_types(&csv_path).expect("analysis");
+
+        // Rename 'score' and 'city' in place, append 'more', and break one id
+        std::fs::write(&csv_path, "id,points,town,more\n1,10,a,b\nx,20,c,d\n3,30,e,f\n")
+            .expect("rewrite csv");
+
+        let report = validate_csv_against_metadata_file(&csv_path, &analysis.metadata_file_path)
+            .expect("validation");
+
+        assert!(!report.passed());
+        assert_eq!(
+            report.renamed_columns,
+            vec![
+                ("score".to_string(), "points".to_string()),
+                ("city".to_string(), "town".to_string()),
+            ]
+        );
+        assert_eq!(report.added_column_names, vec!["more".to_string()]);
+        assert!(report.removed_column_names.is_empty());
+        assert_eq!(report.rows_failing_type_count, 1);
+        assert_eq!(report.row_type_failure_examples[0].csv_line_number, 3);
+        assert_eq!(report.row_type_failure_examples[0].field_value, "x");
+    }
+
+    #[test]
+    fn test_validation_reports_removed_column() {
+        let csv_path = write_temp_csv("removed", "id,score,city\n1,10,x\n2,20,y\n");
+        let analysis = analyze_csv_file_structure_and_types(&csv_path).expect("analysis");
+
+        std::fs::write(&csv_path, "id,score\n1,10\n2,20\n").expect("rewrite csv");
+
+        let report = validate_csv_against_metadata_file(&csv_path, &analysis.metadata_file_path)
+            .expect("validation");
+
+        assert!(!report.passed());
+        assert_eq!(report.removed_column_names, vec!["city".to_string()]);
+    }
+}
+
+#[cfg(test)]
+mod distribution_plot_tests {
+    use super::*;
+
+    #[test]
+    fn test_histogram_counts_every_value_once() {
+        let sorted_values = vec![0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 10.0];
+        let histogram = build_numerical_histogram(&sorted_values, 5);
+
+        assert_eq!(histogram.bin_counts.len(), 5);
+        assert_eq!(histogram.bin_counts.iter().sum::<usize>(), sorted_values.len());
+        assert_eq!(histogram.range_start, 0.0);
+        assert_eq!(histogram.bin_width, 2.0);
+        // Maximum lands in the closed last bin
+        assert_eq!(histogram.bin_counts, vec![2, 2, 2, 2, 2]);
+    }
+
+    #[test]
+    fn test_histogram_constant_values_use_single_bin() {
+        let histogram = build_numerical_histogram(&[3.0, 3.0, 3.0], 10);
+        assert_eq!(histogram.bin_counts, vec![3]);
+        assert_eq!(histogram.bin_width, 0.0);
+    }
+
+    #[test]
+    fn test_histogram_bin_count_is_clamped() {
+        let sorted_values = vec![1.0, 2.0];
+        assert_eq!(build_numerical_histogram(&sorted_values, 0).bin_counts.len(), 1);
+        assert_eq!(
+            build_numerical_histogram(&sorted_values, MAX_HISTOGRAM_BIN_COUNT + 5).bin_counts.len(),
+            MAX_HISTOGRAM_BIN_COUNT
+        );
+    }
+
+    #[test]
+    fn test_histogram_lines_are_fixed_width() {
+        let histogram = build_numerical_histogram(&[1.0, 2.0, 2.0, 9.0], 4);
+        let plot_lines = render_numerical_histogram_lines(&histogram);
+
+        assert_eq!(plot_lines.len(), 4);
+        // Bars are padded, so only the trailing count can change the width
+        let bar_end_positions: Vec<usize> = plot_lines
+            .iter()
+            .map(|line| line.rfind(' ').unwrap())
+            .collect();
+        assert!(bar_end_positions.windows(2).all(|pair| pair[0] == pair[1]));
+        assert!(plot_lines[3].contains(']'));
+        assert!(plot_lines[0].contains(&"#".repeat(DISTRIBUTION_PLOT_BAR_WIDTH)));
+    }
+
+    #[test]
+    fn test_categorical_bar_chart_truncates_to_top_values() {
+        let column_values: Vec<String> = (0..DISTRIBUTION_PLOT_TOP_CATEGORIES + 3)
+            .flat_map(|index| vec![format!("value_{}", index); index + 1])
+            .collect();
+        let categorical_statistics = calculate_categorical_statistics(&column_values).unwrap();
+
+        let chart_lines = render_categorical_bar_chart_lines(&categorical_statistics);
+
+        assert_eq!(chart_lines.len(), DISTRIBUTION_PLOT_TOP_CATEGORIES + 1);
+        assert!(chart_lines[0].starts_with("value_12"));
+        assert!(chart_lines.last().unwrap().contains("3 more values"));
+    }
+}
+
+#[cfg(test)]
+mod cross_column_analysis_tests {
+    use super::*;
+
+    /// Writes a CSV file into a fresh temporary directory and analyzes it fully
+    fn analyze_temp_csv(test_name: &str, csv_text: &str) -> EnhancedCsvAnalysisResults {
+        let test_directory = std::env::temp_dir()
+            .join(format!("rc_cross_column_tests_{}_{}", test_name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&test_directory);
+        std::fs::create_dir_all(&test_directory).expect("create test directory");
+
+        let csv_path = test_directory.join("data.csv");
+        std::fs::write(&csv_path, csv_text).expect("write test csv");
+
+        let basic_results = analyze_csv_file_structure_and_types(&csv_path).expect("basic analysis");
+        perform_enhanced_statistical_analysis(&csv_path, &basic_results).expect("enhanced analysis")
+    }
+
+    #[test]
+    fn test_pearson_perfect_linear_relationships() {
+        let rising = [1.0, 2.0, 3.0, 4.0];
+        let doubled = [2.0, 4.0, 6.0, 8.0];
+        let falling = [4.0, 3.0, 2.0, 1.0];
+
+        assert!((calculate_pearson_correlation(&rising, &doubled).unwrap() - 1.0).abs() < 1e-9);
+        assert!((calculate_pearson_correlation(&rising, &falling).unwrap() + 1.0).abs() < 1e-9);
+        assert_eq!(calculate_pearson_correlation(&rising, &[5.0, 5.0, 5.0, 5.0]), None);
+        assert_eq!(calculate_pearson_correlation(&[1.0], &[2.0]), None);
+    }
+
+    #[test]
+    fn test_average_ranks_handle_ties() {
+        assert_eq!(calculate_average_ranks(&[10.0, 30.0, 20.0, 20.0]), vec![1.0, 4.0, 2.5, 2.5]);
+    }
+
+    #[test]
+    fn test_spearman_detects_monotonic_nonlinear_relationship() {
+        let results = analyze_temp_csv("spearman", "x,y\n1,1\n2,4\n3,9\n4,16\n5,1000\n");
+        let cross_column = &results.cross_column_analysis;
+
+        assert_eq!(cross_column.numeric_column_names, vec!["x".to_string(), "y".to_string()]);
+        let spearman = cross_column.spearman_correlation_matrix[0][1].unwrap();
+        let pearson = cross_column.pearson_correlation_matrix[0][1].unwrap();
+        assert!((spearman - 1.0).abs() < 1e-9);
+        assert!(pearson < spearman);
+    }
+
+    #[test]
+    fn test_candidate_keys_and_duplicate_rows() {
+        let results = analyze_temp_csv(
+            "keys",
+            "id,region,year,amount\n1,north,2020,5\n2,north,2021,6\n3,south,2020,7\n4,south,2021,8\n",
+        );
+        let cross_column = &results.cross_column_analysis;
+
+        let unique_names: Vec<&str> = cross_column.column_cardinalities
+            .iter()
+            .filter(|cardinality| cardinality.is_unique)
+            .map(|cardinality| cardinality.column_name.as_str())
+            .collect();
+        assert_eq!(unique_names, vec!["id", "amount"]);
+        assert!(cross_column.unique_column_pairs.contains(&("region".to_string(), "year".to_string())));
+        assert_eq!(cross_column.duplicate_row_count, 0);
+
+        let duplicated = analyze_temp_csv("duplicates", "a,b\n1,x\n2,y\n1,x\n1,x\n");
+        let cross_column = &duplicated.cross_column_analysis;
+        assert_eq!(cross_column.duplicate_row_count, 2);
+        assert_eq!(cross_column.duplicate_row_groups.len(), 1);
+        assert_eq!(cross_column.duplicate_row_groups[0].first_line_number, 2);
+        assert_eq!(cross_column.duplicate_row_groups[0].duplicate_line_numbers, vec![4, 5]);
+    }
+}
+
+#[cfg(test)]
+mod headless_output_tests {
+    use super::*;
+
+    #[test]
+    fn test_escape_json_string_handles_quotes_and_controls() {
+        assert_eq!(escape_json_string("plain"), "plain");
+        assert_eq!(escape_json_string("say \"hi\""), "say \\\"hi\\\"");
+        assert_eq!(escape_json_string("a\\b\nc\t"), "a\\\\b\\nc\\t");
+        assert_eq!(escape_json_string("\u{1}"), "\\u0001");
+        assert_eq!(format_json_number(f64::NAN), "null");
+        assert_eq!(format_json_number(1.5), "1.5");
+    }
+
+    #[test]
+    fn test_categorical_ties_are_ordered_by_value() {
+        let column_values: Vec<String> = ["pear", "fig", "apple", "fig", "pear", "kiwi"]
+            .iter()
+            .map(|value| value.to_string())
+            .collect();
+        let categorical_statistics = calculate_categorical_statistics(&column_values).unwrap();
+
+        let ordered_values: Vec<&str> = categorical_statistics
+            .value_frequencies
+            .iter()
+            .map(|frequency| frequency.value.as_str())
+            .collect();
+        assert_eq!(ordered_values, ["fig", "pear", "apple", "kiwi"]);
+        assert_eq!(categorical_statistics.mode_value.as_deref(), Some("fig"));
+    }
+
+    #[test]
+    fn test_render_analysis_json_and_text() {
+        let test_directory = std::env::temp_dir()
+            .join(format!("rc_headless_output_tests_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&test_directory);
+        std::fs::create_dir_all(&test_directory).expect("create test directory");
+
+        let csv_path = test_directory.join("data.csv");
+        std::fs::write(&csv_path, "id,name\n1,Luna\\L\n2,Max\n2,Max\n").expect("write test csv");
+
+        let basic_results = analyze_csv_file_structure_and_types(&csv_path).expect("basic analysis");
+        let enhanced_results = perform_enhanced_statistical_analysis(&csv_path, &basic_results)
+            .expect("enhanced analysis");
+
+        let json_output = render_analysis_json(&csv_path, &basic_results, &enhanced_results);
+        assert!(json_output.starts_with("{\n"));
+        assert!(json_output.trim_end().ends_with('}'));
+        assert!(json_output.contains(&format!(
+            "\"total_data_rows\": {}",
+            basic_results.total_data_row_count
+        )));
+        assert!(json_output.contains("\"name\": \"id\""));
+        assert!(json_output.contains("\"duplicate_row_count\": 1"));
+        assert!(json_output.contains("\"Luna\\\\L\""));
+
+        let text_output = render_analysis_text(&csv_path, &basic_results, &enhanced_results);
+        assert!(text_output.contains("Columns: 2  Header: yes"));
+        assert!(text_output.contains("duplicate rows: 1"));
+
+        let _ = std::fs::remove_dir_all(&test_directory);
+    }
 }
@@ -9,6 +9,19 @@
 use std::fmt;
 use std::io;
 
+/// Process exit codes for the headless `ff --rc` command line
+///
+/// These values are part of the scripting interface and must stay stable.
+pub const EXIT_CODE_SUCCESS: i32 = 0;
+/// Invalid or missing command line arguments
+pub const EXIT_CODE_USAGE_ERROR: i32 = 2;
+/// `RowsAndColumnsError::FileSystemError`
+pub const EXIT_CODE_FILE_SYSTEM_ERROR: i32 = 3;
+/// `RowsAndColumnsError::CsvProcessingError`
+pub const EXIT_CODE_CSV_PROCESSING_ERROR: i32 = 4;
+/// `RowsAndColumnsError::ConfigurationError`
+pub const EXIT_CODE_CONFIGURATION_ERROR: i32 = 5;
+
 /// Primary error type for all rows_and_columns operations
 ///
 /// This enum covers all categories of errors that can occur during CSV processing,
@@ -97,6 +110,20 @@ impl fmt::Display for RowsAndColumnsError {
     }
 }
 
+impl RowsAndColumnsError {
+    /// Returns the stable process exit code for this error category
+    ///
+    /// # Returns
+    /// * `i32` - One of the `EXIT_CODE_*` constants
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            RowsAndColumnsError::FileSystemError { .. } => EXIT_CODE_FILE_SYSTEM_ERROR,
+            RowsAndColumnsError::CsvProcessingError { .. } => EXIT_CODE_CSV_PROCESSING_ERROR,
+            RowsAndColumnsError::ConfigurationError { .. } => EXIT_CODE_CONFIGURATION_ERROR,
+        }
+    }
+}
+
 impl std::error::Error for RowsAndColumnsError {
     /// Returns the underlying source of this error, if any
     ///
@@ -233,6 +260,27 @@ mod tests {
         }
     }
 
+    /// Test that each error category maps to its documented exit code
+    #[test]
+    fn test_exit_codes_are_stable() {
+        let io_error = io::Error::new(io::ErrorKind::NotFound, "File not found");
+        assert_eq!(create_file_system_error("Reading", io_error).exit_code(), 3);
+        assert_eq!(create_csv_processing_error("Parsing", None, None).exit_code(), 4);
+        assert_eq!(create_configuration_error("Config").exit_code(), 5);
+
+        // All codes are distinct from success and usage errors
+        let codes = [
+            EXIT_CODE_SUCCESS,
+            EXIT_CODE_USAGE_ERROR,
+            EXIT_CODE_FILE_SYSTEM_ERROR,
+            EXIT_CODE_CSV_PROCESSING_ERROR,
+            EXIT_CODE_CONFIGURATION_ERROR,
+        ];
+        for (index, code) in codes.iter().enumerate() {
+            assert!(!codes[index + 1..].contains(code));
+        }
+    }
+
     /// Test helper function for creating configuration errors
     #[test]
     fn test_create_configuration_error_helper() {
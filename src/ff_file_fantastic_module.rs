@@ -2,6 +2,7 @@
 
 use std::collections::{HashMap, HashSet, VecDeque};
 use std::env;
+use std::ffi::{OsStr, OsString};
 use std::fs::{self};
 use std::io::{self, Write};
 use std::path::{Path, PathBuf};
@@ -10,7 +11,10 @@ use std::sync::OnceLock;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 /// Module: analyze rows and colums of data file
-use super::rows_and_columns_module::rc_analyze_datafile_save_results_to_resultsfile;
+use super::rows_and_columns_module::{
+    rc_analyze_datafile_save_results_to_resultsfile, rc_convert_datafile_interactive,
+    rc_import_spreadsheet_interactive, rc_run_headless_cli, rc_validate_datafile_against_metadata,
+};
 
 // Module: Share Source
 use super::source_it_module::{SourcedFile, handle_sourceit_command};
@@ -20,7 +24,34 @@ use super::row_line_count_tui_module::show_minimal_linecount_tui;
 // mod ribbon_external_counter_module;
 use super::ribbon_external_counter_module::CascadingHexCounter;
 
-use super::lines_editor_module::{LinesError, lines_full_file_editor};
+use super::lines_editor_module::{LinesError, count_lines_in_file, lines_full_file_editor};
+// Terminal cell widths (CJK, emoji, combining marks), shared with Lines
+use super::lines_editor_module::double_width::{char_display_width, str_display_width};
+
+// Real window size and resize notifications (TIOCGWINSZ / SIGWINCH)
+use super::raw_terminal_x86_module::{
+    MouseEvent, MouseEventKind, RawKey, install_window_resize_handler, query_terminal_window_size,
+    read_raw_key, read_raw_key_or_resize, take_window_resized, wait_for_stdin_or_resize,
+};
+
+// Frame-diff rendering: redraw only the listing rows that changed
+use super::buffy_format_write_module::BuffyFrame;
+
+// File preview: Lines-style highlighting and hex-dump glyphs
+use super::buffy_format_write_module::{
+    SyntaxHighlight, buffy_get_syntax_highlight, buffy_is_plain_text_extension,
+};
+use super::lines_editor_module::byte_to_display_char;
+use super::raw_terminal_x86_module::format_byte_info;
+
+// Color themes: escapes print per the active theme (NO_COLOR, TERM=dumb, mono)
+use super::color_theme_module::ThemedSgr;
+
+// Clean exit on Ctrl-C / SIGTERM (flag polled by the main loop)
+use super::raw_terminal_x86_module::{
+    install_termination_handler, pending_termination_signal, reset_terminal_display_state,
+    restore_default_termination_handling, termination_exit_code, termination_signal_name,
+};
 
 /// ff - A minimal file manager in Rust
 /// use -> cargo build --profile release-performance
@@ -374,17 +405,22 @@ const MAX_NAME_LENGTH_DEFAULT: usize = 55;
 
 const MAX_TUI_CHAR_LENGTH_DEFAULT: usize = 80;
 
+/// Terminal height the default items-per-page is sized for
+const TUI_ROWS_DEFAULT: usize = 24;
+
 /// 16 allows an extra line for cases where there cwd/PWD path
 /// is long enough to wrap around, a safer default
 const ITEMS_PER_PAGE_DEFAULT: u8 = 16;
 const FILENAME_SUFFIX_LENGTH: usize = 5;
 
-const RESET: &str = "\x1b[0m";
-const RED: &str = "\x1b[31m";
-const GREEN: &str = "\x1b[32m";
-const YELLOW: &str = "\x1b[33m";
+const RESET: ThemedSgr = ThemedSgr("\x1b[0m");
+const RED: ThemedSgr = ThemedSgr("\x1b[31m");
+const GREEN: ThemedSgr = ThemedSgr("\x1b[32m");
+const YELLOW: ThemedSgr = ThemedSgr("\x1b[33m");
+const BOLD: ThemedSgr = ThemedSgr("\x1b[1m");
+const REVERSE: ThemedSgr = ThemedSgr("\x1b[7m");
+const MAGENTA: ThemedSgr = ThemedSgr("\x1b[35m");
 // const BLUE: &str = "\x1b[34m";
-// const BOLD: &str = "\x1b[1m";
 // const ITALIC: &str = "\x1b[3m";
 // const UNDERLINE: &str = "\x1b[4m";
 
@@ -496,6 +532,13 @@ pub enum FileFantasticError {
     /// ```
     AlreadyExists(PathBuf),
 
+    /// A directory tree has more entries than an operation will read
+    ///
+    /// # Usage
+    /// When reading a whole tree into memory (directory compare) would
+    /// go past a fixed limit; the user should pick a smaller directory
+    TooManyEntries { path: PathBuf, limit: usize },
+
     /// Wraps a LinesError from the lines editor module
     ///
     /// # Usage
@@ -536,6 +579,12 @@ impl std::fmt::Display for FileFantasticError {
                     path.display()
                 )
             }
+            Self::TooManyEntries { path, limit } => write!(
+                f,
+                "{} has more than {} entries; pick a smaller directory",
+                path.display(),
+                limit
+            ),
             Self::LinesError(err) => write!(f, "Lines editor error: {}", err),
         }
     }
@@ -554,6 +603,7 @@ impl std::error::Error for FileFantasticError {
             Self::EditorLaunchFailed(_) => None,
             Self::UnsupportedPlatform => None,
             Self::LevenshteinError { .. } => None,
+            Self::TooManyEntries { .. } => None,
             Self::LinesError(err) => Some(err),
             // _ => None,
         }
@@ -861,7 +911,8 @@ fn apply_format_spec(value: &str, spec: &FormatSpec) -> String {
         None => return value.to_string(), // No width, return as-is
     };
 
-    let value_len = value.len();
+    // Width in terminal columns, so CJK/emoji inserts still line up
+    let value_len = str_display_width(value);
 
     if value_len >= width {
         // Value already meets or exceeds width
@@ -1260,6 +1311,96 @@ pub fn copy_directory_from_stack(
     Ok(final_destination)
 }
 
+/// Creates a symbolic link in a directory, pointing at a stack item
+///
+/// # Purpose
+/// Get-Send "Link" action: instead of copying a file or directory from the
+/// stack, place a symlink to it in the current directory. The stack item
+/// itself is left untouched.
+///
+/// # Arguments
+/// * `link_target_path` - The stack item the new link will point to
+/// * `destination_directory` - The directory where the link is created
+///
+/// # Returns
+/// * `Result<PathBuf>` - The path of the newly created link
+///
+/// # Naming
+/// The link takes the target's name. If that name is already taken in the
+/// destination (including by another, possibly broken, link), the link is
+/// named `{name}_link_{timestamp}` instead; nothing is ever overwritten.
+///
+/// # Platform Notes
+/// - Unix: `std::os::unix::fs::symlink`
+/// - Windows: `symlink_file` or `symlink_dir` (may need Developer Mode
+///   or administrator rights)
+/// - Other platforms: `UnsupportedPlatform`
+///
+/// # Error Handling
+/// - Validates the target exists
+/// - Validates the destination is an existing directory
+/// - Reports permission and other IO errors from link creation
+pub fn create_symlink_to_stack_item(
+    link_target_path: &Path,
+    destination_directory: &Path,
+) -> Result<PathBuf> {
+    // Step 1: Validate target and destination
+    if !link_target_path.exists() {
+        return Err(FileFantasticError::NotFound(link_target_path.to_path_buf()));
+    }
+
+    if !destination_directory.is_dir() {
+        return Err(FileFantasticError::InvalidName(format!(
+            "{} is not a directory",
+            destination_directory.display()
+        )));
+    }
+
+    // Step 2: Choose a link name that does not replace anything
+    let target_name = link_target_path.file_name().ok_or_else(|| {
+        FileFantasticError::InvalidName(format!(
+            "Cannot determine name from: {}",
+            link_target_path.display()
+        ))
+    })?;
+
+    let mut link_path = destination_directory.join(target_name);
+    // symlink_metadata so an existing broken link also counts as taken
+    if fs::symlink_metadata(&link_path).is_ok() {
+        link_path = destination_directory.join(join_os_name(
+            "",
+            target_name,
+            &format!("_link_{}", generate_archive_timestamp()),
+        ));
+        if fs::symlink_metadata(&link_path).is_ok() {
+            return Err(FileFantasticError::AlreadyExists(link_path));
+        }
+    }
+
+    // Step 3: Create the link for this platform
+    #[cfg(unix)]
+    {
+        std::os::unix::fs::symlink(link_target_path, &link_path).map_err(FileFantasticError::Io)?;
+        Ok(link_path)
+    }
+
+    #[cfg(windows)]
+    {
+        let link_result = if link_target_path.is_dir() {
+            std::os::windows::fs::symlink_dir(link_target_path, &link_path)
+        } else {
+            std::os::windows::fs::symlink_file(link_target_path, &link_path)
+        };
+        link_result.map_err(FileFantasticError::Io)?;
+        Ok(link_path)
+    }
+
+    #[cfg(not(any(unix, windows)))]
+    {
+        Err(FileFantasticError::UnsupportedPlatform)
+    }
+}
+
 /// Internal function to recursively copy directory contents
 ///
 /// # Purpose
@@ -2191,6 +2332,26 @@ fn calculate_items_per_page(
 mod calculate_items_per_page_tests {
     use super::*;
 
+    /// Detected window offsets combine with the user's tall/wide setting
+    #[test]
+    fn test_combine_size_adjustment() {
+        // 100-column window (+20) with wide-5 gives +15
+        assert_eq!(combine_size_adjustment(20, 5, false), (15, true));
+        // 20-row window (-4) with tall+1 gives -3
+        assert_eq!(combine_size_adjustment(-4, 1, true), (3, false));
+        // No terminal detected: user setting unchanged
+        assert_eq!(combine_size_adjustment(0, 7, false), (7, false));
+        assert_eq!(combine_size_adjustment(0, 0, true), (0, true));
+        // Saturates instead of overflowing
+        assert_eq!(combine_size_adjustment(10, u16::MAX, true), (u16::MAX, true));
+
+        let (extra_rows_magnitude, extra_rows_positive) = combine_size_adjustment(16, 0, true);
+        assert_eq!(
+            calculate_items_per_page(extra_rows_magnitude, extra_rows_positive),
+            ITEMS_PER_PAGE_DEFAULT as u16 + 16
+        );
+    }
+
     /// Test default behavior with no adjustment
     #[test]
     fn test_calculate_items_per_page_no_adjustment() {
@@ -2324,6 +2485,60 @@ mod calculate_items_per_page_tests {
     }
 }
 
+/// Measures how much the real terminal differs from the 80x24 defaults
+///
+/// # Purpose
+/// The default name width and items per page are sized for an 80x24
+/// terminal. This returns the difference to the actual window (queried
+/// with TIOCGWINSZ) so layouts can grow or shrink with the window.
+///
+/// # Returns
+/// * `(i32, i32)` - (extra columns, extra rows); negative when the window is
+///   smaller, and (0, 0) when the size cannot be queried (not a terminal)
+fn detected_terminal_size_offsets() -> (i32, i32) {
+    match query_terminal_window_size() {
+        Ok(window_size) => (
+            window_size.cols as i32 - MAX_TUI_CHAR_LENGTH_DEFAULT as i32,
+            window_size.rows as i32 - TUI_ROWS_DEFAULT as i32,
+        ),
+        Err(_) => (0, 0),
+    }
+}
+
+/// Adds a detected window-size offset to a user's tall/wide adjustment
+///
+/// # Arguments
+/// * `detected_offset` - Rows or columns the window differs from the default
+/// * `adjustment_magnitude` - The user's tall+N / wide+N magnitude
+/// * `adjustment_direction_true_is_positive_false_is_negative` - The user's direction
+///
+/// # Returns
+/// * `(u16, bool)` - Combined magnitude and direction, in the form
+///   `calculate_name_width` and `calculate_items_per_page` take
+///
+/// # Example
+/// ```rust
+/// // 100-column window (+20) with wide-5 gives +15
+/// assert_eq!(combine_size_adjustment(20, 5, false), (15, true));
+/// ```
+fn combine_size_adjustment(
+    detected_offset: i32,
+    adjustment_magnitude: u16,
+    adjustment_direction_true_is_positive_false_is_negative: bool,
+) -> (u16, bool) {
+    let user_offset = if adjustment_direction_true_is_positive_false_is_negative {
+        adjustment_magnitude as i32
+    } else {
+        -(adjustment_magnitude as i32)
+    };
+
+    let combined_offset = detected_offset + user_offset;
+    (
+        combined_offset.unsigned_abs().min(u16::MAX as u32) as u16,
+        combined_offset >= 0,
+    )
+}
+
 /// Calculates name width from NavigationState for convenience
 ///
 /// # Purpose
@@ -2331,6 +2546,16 @@ mod calculate_items_per_page_tests {
 /// and calculates the actual name column width. Reduces code duplication
 /// throughout the display functions.
 ///
+/// # Window Size
+/// The user's wide+N / wide-N setting is an offset on top of the detected
+/// window width (see `detected_terminal_size_offsets`), so the column
+/// follows the window when it is resized.
+///
+/// # Optional Columns
+/// Columns turned on with `cols` (see `ListingColumns::extra_width`) are
+/// budgeted here: the name column shrinks by their width, down to the
+/// minimum `calculate_name_width` enforces.
+///
 /// # Arguments
 /// * `nav_state` - Reference to current navigation state containing TUI settings
 ///
@@ -2348,10 +2573,17 @@ mod calculate_items_per_page_tests {
 ///          index, name, size, modified, width = width);
 /// ```
 fn calculate_name_width_from_state(nav_state: &NavigationState) -> u16 {
-    calculate_name_width(
+    let (extra_columns, _) = detected_terminal_size_offsets();
+
+    // Optional columns (cols perm owner ...) take their width from the name column
+    let optional_columns_width = nav_state.listing_columns.extra_width() as i32;
+
+    let (width_adjustment, width_direction) = combine_size_adjustment(
+        extra_columns - optional_columns_width,
         nav_state.tui_wide_adjustment,
         nav_state.tui_wide_direction_sign,
-    )
+    );
+    calculate_name_width(width_adjustment, width_direction)
 }
 
 /// Calculates items per page from NavigationState for convenience
@@ -2361,6 +2593,10 @@ fn calculate_name_width_from_state(nav_state: &NavigationState) -> u16 {
 /// and calculates the actual items per page. Reduces code duplication
 /// throughout the pagination logic.
 ///
+/// # Window Size
+/// The user's tall+N / tall-N setting is an offset on top of the detected
+/// window height, like the width in `calculate_name_width_from_state`.
+///
 /// # Arguments
 /// * `nav_state` - Reference to current navigation state containing TUI settings
 ///
@@ -2377,10 +2613,13 @@ fn calculate_name_width_from_state(nav_state: &NavigationState) -> u16 {
 /// let mut dir_view = DirectoryView::new(&entries, items_per_page);
 /// ```
 fn calculate_items_per_page_from_state(nav_state: &NavigationState) -> u16 {
-    calculate_items_per_page(
+    let (_, extra_rows) = detected_terminal_size_offsets();
+    let (height_adjustment, height_direction) = combine_size_adjustment(
+        extra_rows,
         nav_state.tui_tall_adjustment,
         nav_state.tui_tall_direction_sign,
-    )
+    );
+    calculate_items_per_page(height_adjustment, height_direction)
 }
 
 /// Creates a zip archive of a directory with timestamped filename and optional custom name
@@ -2442,36 +2681,31 @@ fn create_directory_zip_archive(
         )));
     }
 
-    // Extract source directory name
-    let source_directory_name = source_directory_path
-        .file_name()
-        .ok_or_else(|| {
-            FileFantasticError::InvalidName(format!(
-                "Cannot determine directory name from: {}",
-                source_directory_path.display()
-            ))
-        })?
-        .to_string_lossy()
-        .to_string();
+    // Extract source directory name (raw, so the zip is named after the real directory)
+    let source_directory_name = source_directory_path.file_name().ok_or_else(|| {
+        FileFantasticError::InvalidName(format!(
+            "Cannot determine directory name from: {}",
+            source_directory_path.display()
+        ))
+    })?;
 
     // Generate timestamped zip filename with optional custom name
     let timestamp = generate_archive_timestamp();
     let zip_filename = if let Some(custom_name) = custom_name_addition {
         if custom_name.trim().is_empty() {
             // Empty custom name, use standard format
-            format!("{}_{}.zip", source_directory_name, timestamp)
+            join_os_name("", source_directory_name, &format!("_{}.zip", timestamp))
         } else {
             // Include custom name before timestamp
-            format!(
-                "{}_{}_{}.zip",
+            join_os_name(
+                "",
                 source_directory_name,
-                custom_name.trim(),
-                timestamp
+                &format!("_{}_{}.zip", custom_name.trim(), timestamp),
             )
         }
     } else {
         // No custom name, use standard format
-        format!("{}_{}.zip", source_directory_name, timestamp)
+        join_os_name("", source_directory_name, &format!("_{}.zip", timestamp))
     };
 
     let zip_destination_path = destination_directory_path.join(&zip_filename);
@@ -2916,7 +3150,7 @@ impl SavedNavigationState {
         // Extract the directory name from the path
         let path_name = path
             .file_name()
-            .map(|name| name.to_string_lossy().to_string())
+            .map(escape_name_for_display)
             .unwrap_or_else(|| "root".to_string());
 
         // Get timestamp seconds since epoch for uniqueness
@@ -3011,7 +3245,7 @@ impl SavedNavigationState {
         // Combine all elements into description
         format!(
             "{}{}{}",
-            path.file_name().unwrap_or_default().to_string_lossy(),
+            escape_name_for_display(path.file_name().unwrap_or_default()),
             filter_desc,
             sort_desc
         )
@@ -3557,8 +3791,10 @@ impl NavigationStateManager {
             current_directory_entries,
             current_directory_path,
             None,
-            nav_state.current_filter,
+            "",  // Menus show no tab bar
+            &[], // and a flat listing
             nav_state,
+            &mut BuffyFrame::new(),
         )
         .map_err(|e| FileFantasticError::Io(e))?;
 
@@ -3586,11 +3822,8 @@ impl NavigationStateManager {
         if let Ok(number) = input.parse::<usize>() {
             if let Some(item_info) = nav_state.lookup_item(number) {
                 // Extract item name for display purposes
-                let item_name = item_info
-                    .item_path
-                    .file_name()
-                    .unwrap_or_default()
-                    .to_string_lossy();
+                let item_name =
+                    escape_name_for_display(item_info.item_path.file_name().unwrap_or_default());
 
                 // WORKFLOW STEP 2: Ask for confirmation based on item type
                 let confirmation_message = if item_info.item_type == FileSystemItemType::Directory {
@@ -3787,23 +4020,20 @@ impl NavigationStateManager {
             )));
         }
 
-        // Get the file name without extension for the archive name
-        let file_stem = file_path
-            .file_stem()
-            .ok_or_else(|| {
-                FileFantasticError::InvalidName("Could not extract file name".to_string())
-            })?
-            .to_string_lossy();
+        // Get the file name without extension for the archive name (raw bytes kept)
+        let file_stem = file_path.file_stem().ok_or_else(|| {
+            FileFantasticError::InvalidName("Could not extract file name".to_string())
+        })?;
 
         // Generate full timestamp
         let timestamp = createarchive_timestamp_with_precision(SystemTime::now(), true);
 
         // Build the archive name with optional prefix
-        let archive_name = if let Some(prefix) = custom_prefix {
-            format!("{}{}_{}.zip", prefix, file_stem, timestamp)
-        } else {
-            format!("{}_{}.zip", file_stem, timestamp)
-        };
+        let archive_name = join_os_name(
+            custom_prefix.unwrap_or(""),
+            file_stem,
+            &format!("_{}.zip", timestamp),
+        );
 
         // Create full path for the archive
         let archive_path = archive_directory.join(archive_name);
@@ -3837,28 +4067,24 @@ impl NavigationStateManager {
             return Err(FileFantasticError::NotFound(file_path.to_path_buf()));
         }
 
-        // Get file name components
-        let file_stem = file_path
-            .file_stem()
-            .ok_or_else(|| {
-                FileFantasticError::InvalidName("Could not extract file name".to_string())
-            })?
-            .to_string_lossy();
-
-        let extension = file_path
-            .extension()
-            .map(|ext| format!(".{}", ext.to_string_lossy()))
-            .unwrap_or_else(|| String::new());
+        // Get file name components (raw bytes kept)
+        let file_stem = file_path.file_stem().ok_or_else(|| {
+            FileFantasticError::InvalidName("Could not extract file name".to_string())
+        })?;
 
         // Generate full timestamp
         let timestamp = createarchive_timestamp_with_precision(SystemTime::now(), true);
 
         // Build the archive name with optional prefix
-        let archive_name = if let Some(prefix) = custom_prefix {
-            format!("{}{}_{}{}", prefix, file_stem, timestamp, extension)
-        } else {
-            format!("{}_{}{}", file_stem, timestamp, extension)
-        };
+        let mut archive_name = join_os_name(
+            custom_prefix.unwrap_or(""),
+            file_stem,
+            &format!("_{}", timestamp),
+        );
+        if let Some(extension) = file_path.extension() {
+            archive_name.push(".");
+            archive_name.push(extension);
+        }
 
         // Create full path for the archive
         let archive_path = archive_directory.join(archive_name);
@@ -3949,8 +4175,10 @@ impl NavigationStateManager {
             current_directory_entries,
             current_directory_path,
             None, // Pagination info handled by main navigation
-            nav_state.current_filter,
+            "",   // Menus show no tab bar
+            &[],  // and a flat listing
             nav_state,
+            &mut BuffyFrame::new(),
         )
         .map_err(|e| FileFantasticError::Io(e))?;
 
@@ -3980,11 +4208,8 @@ impl NavigationStateManager {
             // Use navigation state's lookup to find the selected item
             if let Some(item_info) = nav_state.lookup_item(number) {
                 // Extract item name for display purposes
-                let item_name = item_info
-                    .item_path
-                    .file_name()
-                    .unwrap_or_default()
-                    .to_string_lossy();
+                let item_name =
+                    escape_name_for_display(item_info.item_path.file_name().unwrap_or_default());
 
                 // STEP 4: Route to appropriate stack based on item type
                 match item_info.item_type {
@@ -4204,9 +4429,12 @@ impl NavigationStateManager {
         // Display files in reverse order (most recent first) for user-friendly numbering
         for (i, file) in self.file_path_stack.iter().enumerate().rev() {
             println!(
-                "{}. {}",
-                self.file_path_stack.len() - i,
-                file.file_name().unwrap_or_default().to_string_lossy()
+                "{}",
+                fit_menu_line(format!(
+                    "{}. {}",
+                    self.file_path_stack.len() - i,
+                    escape_name_for_display(file.file_name().unwrap_or_default())
+                ))
             );
         }
 
@@ -4232,7 +4460,7 @@ impl NavigationStateManager {
             if let Some(file) = self.file_path_stack.last() {
                 println!(
                     "Retrieved: {}",
-                    file.file_name().unwrap_or_default().to_string_lossy()
+                    escape_name_for_display(file.file_name().unwrap_or_default())
                 );
                 return Ok(Some(file.clone()));
             }
@@ -4248,7 +4476,7 @@ impl NavigationStateManager {
                 if let Some(file) = self.file_path_stack.get(actual_index) {
                     println!(
                         "Retrieved: {}",
-                        file.file_name().unwrap_or_default().to_string_lossy()
+                        escape_name_for_display(file.file_name().unwrap_or_default())
                     );
                     return Ok(Some(file.clone()));
                 } else {
@@ -4328,9 +4556,12 @@ impl NavigationStateManager {
         // Display directories in reverse order (most recent first)
         for (i, dir) in self.directory_path_stack.iter().enumerate().rev() {
             println!(
-                "{}. {}/",
-                self.directory_path_stack.len() - i,
-                dir.display()
+                "{}",
+                fit_menu_line(format!(
+                    "{}. {}/",
+                    self.directory_path_stack.len() - i,
+                    dir.display()
+                ))
             );
         }
 
@@ -4478,7 +4709,10 @@ impl NavigationStateManager {
         println!("\n=== Pocket Dimensions ===");
         // Display all dimensions with numbers and descriptions
         for (i, (nickname, state)) in dimensions.iter().enumerate() {
-            println!("{}. {} - {}", i + 1, nickname, state.description);
+            println!(
+                "{}",
+                fit_menu_line(format!("{}. {} - {}", i + 1, nickname, state.description))
+            );
         }
 
         print!("Select pocket dimension number ('c' to cancel): ");
@@ -4572,9 +4806,11 @@ impl NavigationStateManager {
         println!("5. View stacks & pocket dimensions");
         println!("6. Archive file/directory 'a': zip/timestamp");
         println!("7. Clear all stacks");
+        println!("8. Link: make a symlink here TO a stack item");
+        println!("9. Send: copy an item here TO another tab");
         // println!("--  --");
         println!();
-        print!("Select Action (1-7)  or (b)ack / empty-Enter ");
+        print!("Select Action (1-9)  or (b)ack / empty-Enter ");
         io::stdout()
             .flush()
             .map_err(|e| FileFantasticError::Io(e))?;
@@ -4593,6 +4829,8 @@ impl NavigationStateManager {
             "5" => Ok(GetSendModeAction::ViewStacks),
             "6" => Ok(GetSendModeAction::ArchiveSelection),
             "7" => Ok(GetSendModeAction::ClearAll),
+            "8" => Ok(GetSendModeAction::LinkItemFromStack),
+            "9" => Ok(GetSendModeAction::SendItemToTab),
             "" | "b" => Ok(GetSendModeAction::ReturnToBrowser), // Default to return
             _ => {
                 println!("Invalid selection.");
@@ -4620,6 +4858,9 @@ impl NavigationStateManager {
 /// - `SavePocketDimension` - Initiate pocket dimension saving workflow
 /// - `GoToPocketDimension` - Initiate pocket dimension selection and restoration
 /// - `ViewStacks` - Display current status and contents of all collections
+/// - `ArchiveSelection` - Archive the selected item with a timestamp
+/// - `LinkItemFromStack` - Create a symlink here to a stack item
+/// - `SendItemToTab` - Copy an item here to another tab's directory
 /// - `ClearAll` - Initiate cleanup workflow with confirmation
 /// - `ReturnToBrowser` - Exit Get-Send-Mode and return to normal file browsing
 ///
@@ -4665,6 +4906,12 @@ pub enum GetSendModeAction {
     /// Archive the currently selected item (file copy or directory zip with timestamp)
     ArchiveSelection,
 
+    /// Create a symlink in the current directory pointing at a stack item
+    LinkItemFromStack,
+
+    /// Copy an item from the current directory into another tab's directory
+    SendItemToTab,
+
     /// Clear all stacks and pocket dimensions
     /// Triggers the cleanup workflow with user confirmation
     ClearAll,
@@ -4775,7 +5022,7 @@ fn generate_archive_timestamp() -> String {
 ///     Err(e) => eprintln!("Failed to process archive directory: {}", e),
 /// }
 /// ```
-fn ensure_archive_directory_exists(parent_directory: &PathBuf) -> Result<PathBuf> {
+pub fn ensure_archive_directory_exists(parent_directory: &PathBuf) -> Result<PathBuf> {
     // Step 1: Check if the parent directory itself is already named "archive"
     // This prevents creating nested archive directories like archive/archive/
     if let Some(dir_name) = parent_directory.file_name() {
@@ -4843,11 +5090,12 @@ fn ensure_archive_directory_exists(parent_directory: &PathBuf) -> Result<PathBuf
 /// while ensuring uniqueness.
 ///
 /// # Arguments
-/// * `original_filename` - The original name of the file
+/// * `original_filename` - The original name of the file (raw, may be invalid UTF-8)
 /// * `timestamp` - The timestamp string to insert
 ///
 /// # Returns
-/// * `String` - New filename with timestamp inserted before extension
+/// * `OsString` - New filename with timestamp inserted before extension;
+///   the original name's bytes are kept as they are
 ///
 /// # Filename Transformation Rules
 /// - Files with extensions: "name.ext" → "name_timestamp.ext"
@@ -4860,29 +5108,31 @@ fn ensure_archive_directory_exists(parent_directory: &PathBuf) -> Result<PathBuf
 /// let timestamp = "2025_01_15_14_30_45";
 ///
 /// assert_eq!(
-///     generate_archive_filename("cats.toml", timestamp),
+///     generate_archive_filename(OsStr::new("cats.toml"), timestamp),
 ///     "cats_2025_01_15_14_30_45.toml"
 /// );
 ///
 /// assert_eq!(
-///     generate_archive_filename("README", timestamp),
+///     generate_archive_filename(OsStr::new("README"), timestamp),
 ///     "README_2025_01_15_14_30_45"
 /// );
 ///
 /// assert_eq!(
-///     generate_archive_filename("data.tar.gz", timestamp),
+///     generate_archive_filename(OsStr::new("data.tar.gz"), timestamp),
 ///     "data.tar_2025_01_15_14_30_45.gz"
 /// );
 /// ```
-fn generate_archive_filename(original_filename: &str, timestamp: &str) -> String {
-    // Find the last dot to separate name from extension
-    if let Some(dot_position) = original_filename.rfind('.') {
-        // Split into name and extension
-        let (name_part, extension_part) = original_filename.split_at(dot_position);
-        format!("{}_{}{}", name_part, timestamp, extension_part)
-    } else {
+pub fn generate_archive_filename(original_filename: &OsStr, timestamp: &str) -> OsString {
+    // Split at the last dot (Path rules: a leading dot is not an extension)
+    let original_path = Path::new(original_filename);
+    match (original_path.file_stem(), original_path.extension()) {
+        (Some(name_part), Some(extension_part)) => {
+            let mut archive_filename = join_os_name("", name_part, &format!("_{}.", timestamp));
+            archive_filename.push(extension_part);
+            archive_filename
+        }
         // No extension found, just append timestamp
-        format!("{}_{}", original_filename, timestamp)
+        _ => join_os_name("", original_filename, &format!("_{}", timestamp)),
     }
 }
 
@@ -4946,25 +5196,24 @@ fn copy_file_with_archive_handling(
         )));
     }
 
-    // Extract source filename
-    let source_filename = source_file_path
-        .file_name()
-        .ok_or_else(|| {
-            FileFantasticError::InvalidName(format!(
-                "Cannot determine filename from: {}",
-                source_file_path.display()
-            ))
-        })?
-        .to_string_lossy()
-        .to_string();
+    // Extract source filename (raw: the copy keeps the exact same name)
+    let source_filename = source_file_path.file_name().ok_or_else(|| {
+        FileFantasticError::InvalidName(format!(
+            "Cannot determine filename from: {}",
+            source_file_path.display()
+        ))
+    })?;
 
     // Determine destination path
-    let primary_destination_path = destination_directory.join(&source_filename);
+    let primary_destination_path = destination_directory.join(source_filename);
 
     // Check if file exists at destination
     let final_destination_path = if primary_destination_path.exists() {
         // File exists, use safe archive strategy with temp files
-        println!("File '{}' already exists in destination.", source_filename);
+        println!(
+            "File '{}' already exists in destination.",
+            escape_name_for_display(source_filename)
+        );
         println!("Creating safe archive of existing file...");
 
         // Generate timestamp for unique temp names
@@ -4973,7 +5222,11 @@ fn copy_file_with_archive_handling(
         // Step 1: Create temporary backup of the existing file (for rollback if needed)
         // Add process ID to make temp names more unique
         let pid = std::process::id();
-        let temp_backup_filename = format!("backup_{}_{}_{}", pid, timestamp, source_filename);
+        let temp_backup_filename = join_os_name(
+            &format!("backup_{}_{}_", pid, timestamp),
+            source_filename,
+            "",
+        );
         let temp_backup_path = destination_directory.join(&temp_backup_filename);
 
         // Check if temp backup already exists (very unlikely but possible)
@@ -5021,7 +5274,11 @@ fn copy_file_with_archive_handling(
         }
 
         // Step 2: Copy new file with temporary name
-        let temp_new_filename = format!("newfile_{}_{}_{}", pid, timestamp, source_filename);
+        let temp_new_filename = join_os_name(
+            &format!("newfile_{}_{}_", pid, timestamp),
+            source_filename,
+            "",
+        );
         let temp_new_path = destination_directory.join(&temp_new_filename);
 
         // Check if temp new file already exists
@@ -5067,7 +5324,7 @@ fn copy_file_with_archive_handling(
         };
 
         // Step 4: Move the existing file to archive (from primary location)
-        let archive_filename = generate_archive_filename(&source_filename, &timestamp);
+        let archive_filename = generate_archive_filename(source_filename, &timestamp);
         let archive_destination_path = archive_directory_path.join(&archive_filename);
 
         // Check if archive destination already exists
@@ -5326,9 +5583,15 @@ impl<'a> DirectoryView<'a> {
 
     /// Returns only entries for current page
     fn current_page_entries(&self) -> &[FileSystemEntry] {
-        let start = self.current_page * self.items_per_page;
+        &self.entries[self.current_page_range()]
+    }
+
+    /// Index range of the current page in the full list (for parallel
+    /// per-row data such as the tree listing's guide lines)
+    fn current_page_range(&self) -> std::ops::Range<usize> {
+        let start = (self.current_page * self.items_per_page).min(self.entries.len());
         let end = (start + self.items_per_page).min(self.entries.len());
-        &self.entries[start..end]
+        start..end
     }
 
     /// Moves to next page, returns success
@@ -5500,8 +5763,8 @@ impl<'a> DirectoryView<'a> {
 /// ```
 #[derive(Debug, Clone)]
 pub struct FuzzySearchResult {
-    /// Name of the matching item
-    item_name: String,
+    /// Name of the matching item, exactly as the OS gave it
+    item_name: OsString,
     /// Full path to the item
     item_path: PathBuf,
     /// Levenshtein distance score
@@ -6587,105 +6850,665 @@ fn format_tui_adjustments(
     (tall_display, wide_display)
 }
 
-static LINE_COUNT_LOOKUP: OnceLock<HashMap<&'static str, ()>> = OnceLock::new();
+/// Width of the "Perm" column: file type letter + 9 permission letters
+const PERMISSIONS_COLUMN_WIDTH: usize = 10;
 
-fn get_line_count_options() -> &'static HashMap<&'static str, ()> {
-    LINE_COUNT_LOOKUP.get_or_init(|| {
-        HashMap::from([
-            ("--line-count", ()),
-            ("--row-count", ()),
-            ("--line-counts", ()),
-            ("--row-counts", ()),
-            ("--linecounts", ()),
-            ("--rowcounts", ()),
-            ("--lines-count", ()),
-            ("--rows-count", ()),
-            ("--count-rows", ()),
-            ("--count-lines", ()),
-            ("--linescounts", ()),
-            ("--rowscounts", ()),
-            ("--linecount", ()),
-            ("--rowcount", ()),
-            ("--linescount", ()),
-            ("--rowscount", ()),
-            ("--countrows", ()),
-            ("--countlines", ()),
-        ])
-    })
-}
+/// Width of the "Owner" column: user and group names, 8 characters each
+const OWNER_COLUMN_WIDTH: usize = 17;
 
-/// Processes user input and returns the corresponding NavigationAction
+/// Longest user or group name shown before truncating with '~'
+const OWNER_NAME_MAX_CHARS: usize = 8;
+
+/// Width of the "Modified" column when showing exact ISO timestamps
+const ISO_TIMESTAMP_COLUMN_WIDTH: usize = 19;
+
+/// Width of the default "Modified" column (time, month-day time, or date)
+const MODIFIED_COLUMN_WIDTH: usize = 11;
+
+/// Width of the "Ext" column
+const EXTENSION_COLUMN_WIDTH: usize = 6;
+
+/// Width of the "Link" column ("-> " plus the symlink target)
+const LINK_TARGET_COLUMN_WIDTH: usize = 24;
+
+/// Suffix after a symbolic link's name in the listing (before any '/')
+const SYMLINK_NAME_MARKER: &str = "@";
+
+/// Suffix after a broken symbolic link's name (its target does not exist)
+const BROKEN_SYMLINK_NAME_MARKER: &str = "@!";
+
+/// Optional listing columns, names as typed after "cols"
+const LISTING_COLUMN_NAMES: &[&str] = &["perm", "owner", "iso", "ext", "link"];
+
+/// Which optional columns the directory listing shows
 ///
 /// # Purpose
-/// This function serves as the central command interpreter for File Fantastic,
-/// translating user text input into specific actions the application should take.
-/// It handles commands, item selection, and search functionality in one unified interface.
+/// The listing always shows number, name, size and modified time. These
+/// extra columns are chosen with the `cols` command and stay on until
+/// changed. Their width comes out of the name column (see
+/// `calculate_name_width_from_state`), so rows still fit the window.
 ///
-/// # Arguments
-/// * `input` - The user's input string
-/// * `nav_state` - Current navigation state containing lookup table
-/// * `all_entries` - Complete list of directory entries
+/// # Fields
+/// * `permissions` - "perm": Unix type and mode bits, e.g. `drwxr-xr-x`
+/// * `owner` - "owner": user and group names (from /etc/passwd, /etc/group)
+/// * `iso_timestamp` - "iso": exact `YYYY-MM-DD HH:MM:SS` (UTC) modified time
+/// * `extension` - "ext": file extension, e.g. `rs`
+/// * `link_target` - "link": where a symbolic link points
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+struct ListingColumns {
+    permissions: bool,
+    owner: bool,
+    iso_timestamp: bool,
+    extension: bool,
+    link_target: bool,
+}
+
+impl ListingColumns {
+    /// Characters the enabled columns add to a listing row
+    ///
+    /// # Returns
+    /// * `usize` - Extra width, including one separating space per column;
+    ///   the ISO timestamp counts only its width beyond the default column
+    fn extra_width(&self) -> usize {
+        let mut extra_width = 0;
+        if self.permissions {
+            extra_width += PERMISSIONS_COLUMN_WIDTH + 1;
+        }
+        if self.owner {
+            extra_width += OWNER_COLUMN_WIDTH + 1;
+        }
+        if self.iso_timestamp {
+            extra_width += ISO_TIMESTAMP_COLUMN_WIDTH - MODIFIED_COLUMN_WIDTH;
+        }
+        if self.extension {
+            extra_width += EXTENSION_COLUMN_WIDTH + 1;
+        }
+        if self.link_target {
+            extra_width += LINK_TARGET_COLUMN_WIDTH + 1;
+        }
+        extra_width
+    }
+
+    /// Names of the enabled columns, for feedback messages
+    ///
+    /// # Returns
+    /// * `String` - e.g. "perm owner", or "none"
+    fn describe(&self) -> String {
+        let enabled = [
+            self.permissions,
+            self.owner,
+            self.iso_timestamp,
+            self.extension,
+            self.link_target,
+        ];
+        let names: Vec<&str> = LISTING_COLUMN_NAMES
+            .iter()
+            .zip(enabled)
+            .filter(|(_, is_enabled)| *is_enabled)
+            .map(|(name, _)| *name)
+            .collect();
+        if names.is_empty() {
+            String::from("none")
+        } else {
+            names.join(" ")
+        }
+    }
+}
+
+/// Parses the `cols` command that picks optional listing columns
 ///
-/// # Returns
-/// * `Result<NavigationAction>` - The determined action to take or error with context
+/// # Command Format
+/// - `cols` or `cols none` - Back to the default columns
+/// - `cols all` - Every optional column
+/// - `cols perm owner iso ext link` - Exactly the named columns (any order)
 ///
-/// # Command Processing Rules
-/// 1. Empty input triggers refresh action
-/// 2. Single-character inputs are checked against command mappings:
-///    - `q` -> Quit
-///    - `b` -> Parent directory
-///    - `t` -> Open terminal
-///    - `n/s/m` -> Sort commands
-///    - `d/f/a` -> Filter commands
-/// 3. Numeric inputs are treated as item selection
-///    - Validates against current display lookup table
-///    - Returns appropriate action based on item type (file/directory)
-/// 4. Multi-character non-numeric inputs trigger fuzzy search
-///    - Search results are displayed for user selection
-///    - User can then select from results by number
+/// # Arguments
+/// * `input` - The trimmed user input string
 ///
-/// # Error Handling
-/// - Validates user input
-/// - Handles IO errors during search result display
-/// - Validates selected numbers against available items
-/// - Returns specific FileFantasticError types with context
+/// # Returns
+/// * `None` - Input is not a `cols` command
+/// * `Some(Ok(ListingColumns))` - The columns to show
+/// * `Some(Err(String))` - A `cols` command naming an unknown column
 ///
 /// # Examples
 /// ```rust
-/// / Process user input and take appropriate action
-/// match process_user_input(&input, &nav_state, &all_entries)? {
-///     NavigationAction::Quit => break,
-///     NavigationAction::ChangeDirectory(path) => current_directory = path,
-///     NavigationAction::OpenFile(path) => handle_file_open(&path)?,
-///     NavigationAction::Sort(c) => nav_state.toggle_sort(c),
-///     NavigationAction::OpenNewTerminal => open_new_terminal(&current_directory)?,
-///     NavigationAction::Filter(c) => nav_state.set_filter(c),
-///     NavigationAction::Refresh => { /* reload directory */ },
-///     NavigationAction::ParentDirectory => {
-///         if let Some(parent) = current_directory.parent() {
-///             current_directory = parent.to_path_buf();
-///         }
-///     },
-///     NavigationAction::Invalid => println!("Invalid input"),
-/// }
+/// assert!(parse_listing_columns_command("columnist").is_none());
+/// assert!(parse_listing_columns_command("cols perm ext").unwrap().is_ok());
+/// assert!(parse_listing_columns_command("cols color").unwrap().is_err());
 /// ```
-fn process_user_input(
+fn parse_listing_columns_command(
     input: &str,
-    nav_state: &NavigationState,
-    all_entries: &[FileSystemEntry],
-    current_directory_path: &Path,
-) -> Result<NavigationAction> {
-    let input = input.trim();
-
-    // Handle empty input first - refresh and clear filters
-    if input.is_empty() {
-        return Ok(NavigationAction::Refresh);
+) -> Option<std::result::Result<ListingColumns, String>> {
+    let mut words = input.split_whitespace();
+    if words.next() != Some("cols") {
+        return None;
     }
 
-    // Convert to lowercase for case-insensitive commands
-    let lowercase_input = input.to_lowercase();
+    let mut columns = ListingColumns::default();
+    for word in words {
+        match word {
+            "none" => columns = ListingColumns::default(),
+            "all" => {
+                columns = ListingColumns {
+                    permissions: true,
+                    owner: true,
+                    iso_timestamp: true,
+                    extension: true,
+                    link_target: true,
+                }
+            }
+            "perm" => columns.permissions = true,
+            "owner" => columns.owner = true,
+            "iso" => columns.iso_timestamp = true,
+            "ext" => columns.extension = true,
+            "link" => columns.link_target = true,
+            unknown => {
+                return Some(Err(format!(
+                    "Unknown column '{}'. Columns: {} (or all, none)",
+                    unknown,
+                    LISTING_COLUMN_NAMES.join(" ")
+                )));
+            }
+        }
+    }
+    Some(Ok(columns))
+}
 
-    // Handle single-character commands first
+/// Formats Unix mode bits like `ls -l`, e.g. `drwxr-xr-x`
+///
+/// # Arguments
+/// * `mode` - st_mode: file type bits and permission bits
+///
+/// # Returns
+/// * `String` - Type letter (`-` file, `d` dir, `l` link, `c`/`b` device,
+///   `p` fifo, `s` socket, `?` other) then rwx for user, group and other;
+///   setuid/setgid/sticky show as s/S and t/T in the execute positions
+fn format_unix_mode_bits(mode: u32) -> String {
+    let type_letter = match mode & 0o170000 {
+        0o100000 => '-',
+        0o040000 => 'd',
+        0o120000 => 'l',
+        0o020000 => 'c',
+        0o060000 => 'b',
+        0o010000 => 'p',
+        0o140000 => 's',
+        _ => '?',
+    };
+
+    // (read bit, write bit, execute bit, special bit, letter when special)
+    let permission_triples = [
+        (0o400, 0o200, 0o100, 0o4000, 's'),
+        (0o040, 0o020, 0o010, 0o2000, 's'),
+        (0o004, 0o002, 0o001, 0o1000, 't'),
+    ];
+
+    let mut formatted = String::with_capacity(PERMISSIONS_COLUMN_WIDTH);
+    formatted.push(type_letter);
+    for (read_bit, write_bit, execute_bit, special_bit, special_letter) in permission_triples {
+        formatted.push(if mode & read_bit != 0 { 'r' } else { '-' });
+        formatted.push(if mode & write_bit != 0 { 'w' } else { '-' });
+        let is_executable = mode & execute_bit != 0;
+        formatted.push(match (mode & special_bit != 0, is_executable) {
+            (true, true) => special_letter,
+            (true, false) => special_letter.to_ascii_uppercase(),
+            (false, true) => 'x',
+            (false, false) => '-',
+        });
+    }
+    formatted
+}
+
+/// Formats a modification time as an exact `YYYY-MM-DD HH:MM:SS` (UTC)
+///
+/// # Arguments
+/// * `timestamp` - SystemTime to format
+///
+/// # Returns
+/// * `String` - 19-character timestamp, or "????-??-?? ??:??:??" before 1970
+fn format_iso_timestamp(timestamp: SystemTime) -> String {
+    match timestamp.duration_since(UNIX_EPOCH) {
+        Ok(duration) => {
+            let secs = duration.as_secs();
+            let (year, month, day, hour, minute) = seconds_to_components(secs);
+            format!(
+                "{}-{:02}-{:02} {:02}:{:02}:{:02}",
+                year,
+                month,
+                day,
+                hour,
+                minute,
+                secs % 60
+            )
+        }
+        Err(_) => String::from("????-??-?? ??:??:??"),
+    }
+}
+
+/// Parses /etc/passwd or /etc/group text into an id -> name table
+///
+/// # Arguments
+/// * `database_text` - Lines of `name:password:id:...` (both files share
+///   this layout for the first three fields)
+///
+/// # Returns
+/// * `HashMap<u32, String>` - First name seen for each id; malformed
+///   lines and comments are skipped
+fn parse_id_name_table(database_text: &str) -> HashMap<u32, String> {
+    let mut id_names = HashMap::new();
+    for line in database_text.lines() {
+        if line.starts_with('#') {
+            continue;
+        }
+        let mut fields = line.split(':');
+        let (Some(name), Some(_password), Some(id_text)) =
+            (fields.next(), fields.next(), fields.next())
+        else {
+            continue;
+        };
+        if let Ok(id) = id_text.trim().parse::<u32>() {
+            id_names.entry(id).or_insert_with(|| name.to_string());
+        }
+    }
+    id_names
+}
+
+/// Shortens a column cell to `max_chars`, marking the cut with '~'
+///
+/// # Arguments
+/// * `text` - Cell text (user name, extension, ...)
+/// * `max_chars` - Column width in terminal columns (at least 1)
+///
+/// # Returns
+/// * `String` - `text` unchanged if it fits, else its start plus '~'
+fn truncate_column_cell(text: &str, max_chars: usize) -> String {
+    if str_display_width(text) <= max_chars {
+        return text.to_string();
+    }
+    let mut truncated = take_display_width_prefix(text, max_chars.saturating_sub(1));
+    truncated.push('~');
+    truncated
+}
+
+/// User names by id, read once from /etc/passwd
+static USER_NAMES_BY_ID: OnceLock<HashMap<u32, String>> = OnceLock::new();
+
+/// Group names by id, read once from /etc/group
+static GROUP_NAMES_BY_ID: OnceLock<HashMap<u32, String>> = OnceLock::new();
+
+/// Looks up a user or group name, falling back to the number
+///
+/// # Arguments
+/// * `id` - User or group id (None off Unix)
+/// * `names_by_id` - Table from `parse_id_name_table`
+///
+/// # Returns
+/// * `String` - Name (truncated to OWNER_NAME_MAX_CHARS with '~'),
+///   the id as a number if unknown (e.g. LDAP users), or "-"
+fn owner_name_for_id(id: Option<u32>, names_by_id: &HashMap<u32, String>) -> String {
+    let Some(id) = id else {
+        return String::from("-");
+    };
+    match names_by_id.get(&id) {
+        Some(name) => truncate_column_cell(name, OWNER_NAME_MAX_CHARS),
+        None => truncate_column_cell(&id.to_string(), OWNER_NAME_MAX_CHARS),
+    }
+}
+
+/// Formats the "Owner" column: user and group names
+///
+/// # Arguments
+/// * `entry` - Directory entry with owner/group ids
+///
+/// # Returns
+/// * `String` - e.g. "alice    staff   " (OWNER_COLUMN_WIDTH wide)
+fn format_owner_column(entry: &FileSystemEntry) -> String {
+    format!(
+        "{} {}",
+        pad_to_display_width(
+            &owner_name_for_id(entry.owner_user_id, user_names_by_id()),
+            OWNER_NAME_MAX_CHARS
+        ),
+        pad_to_display_width(
+            &owner_name_for_id(entry.owner_group_id, group_names_by_id()),
+            OWNER_NAME_MAX_CHARS
+        )
+    )
+}
+
+/// User names by id (/etc/passwd is read on first use)
+fn user_names_by_id() -> &'static HashMap<u32, String> {
+    USER_NAMES_BY_ID
+        .get_or_init(|| parse_id_name_table(&fs::read_to_string("/etc/passwd").unwrap_or_default()))
+}
+
+/// Group names by id (/etc/group is read on first use)
+fn group_names_by_id() -> &'static HashMap<u32, String> {
+    GROUP_NAMES_BY_ID
+        .get_or_init(|| parse_id_name_table(&fs::read_to_string("/etc/group").unwrap_or_default()))
+}
+
+/// Builds the optional column cells that follow "Modified" in a listing row
+///
+/// # Arguments
+/// * `entry` - Directory entry to describe
+/// * `columns` - Which optional columns are on
+///
+/// # Returns
+/// * `String` - Space-separated cells (empty when no optional column is on);
+///   the ISO timestamp is not included, it replaces the Modified cell
+fn format_optional_column_cells(entry: &FileSystemEntry, columns: &ListingColumns) -> String {
+    let mut cells = String::new();
+
+    if columns.permissions {
+        let permissions = match entry.unix_mode_bits {
+            Some(mode) => format_unix_mode_bits(mode),
+            None => String::from("-"),
+        };
+        cells.push_str(&format!(
+            " {:<width$}",
+            permissions,
+            width = PERMISSIONS_COLUMN_WIDTH
+        ));
+    }
+
+    if columns.owner {
+        cells.push(' ');
+        cells.push_str(&format_owner_column(entry));
+    }
+
+    if columns.extension {
+        let extension = if entry.is_directory {
+            String::new()
+        } else {
+            Path::new(&entry.file_system_item_name)
+                .extension()
+                .map(escape_name_for_display)
+                .unwrap_or_default()
+        };
+        cells.push(' ');
+        cells.push_str(&pad_to_display_width(
+            &truncate_column_cell(&extension, EXTENSION_COLUMN_WIDTH),
+            EXTENSION_COLUMN_WIDTH,
+        ));
+    }
+
+    if columns.link_target {
+        let link_display = match &entry.symlink_target {
+            Some(target) => truncate_filename_for_display(
+                format!("-> {}", escape_path_for_display(target)),
+                LINK_TARGET_COLUMN_WIDTH,
+            ),
+            None => String::new(),
+        };
+        cells.push(' ');
+        cells.push_str(&link_display);
+    }
+
+    cells
+}
+
+/// Formats an entry's name for the Name column of the listing
+///
+/// # Arguments
+/// * `entry` - Directory entry to name
+///
+/// # Returns
+/// * `String` - The name with its markers:
+///   - `name/` for a directory
+///   - `name@` for a link to a file, `name@/` for a link to a directory
+///   - `name@!` for a broken link
+fn format_entry_display_name(entry: &FileSystemEntry) -> String {
+    let link_marker = if entry.is_broken_symlink {
+        BROKEN_SYMLINK_NAME_MARKER
+    } else if entry.is_symlink {
+        SYMLINK_NAME_MARKER
+    } else {
+        ""
+    };
+    let directory_marker = if entry.is_directory { "/" } else { "" };
+
+    format!(
+        "{}{}{}",
+        escape_name_for_display(&entry.file_system_item_name),
+        link_marker,
+        directory_marker
+    )
+}
+
+/// Builds the header cells for the optional columns (see `format_optional_column_cells`)
+fn format_optional_column_headers(columns: &ListingColumns) -> String {
+    let mut headers = String::new();
+    if columns.permissions {
+        headers.push_str(&format!(
+            " {:<width$}",
+            "Perm",
+            width = PERMISSIONS_COLUMN_WIDTH
+        ));
+    }
+    if columns.owner {
+        headers.push_str(&format!(
+            " {:<width$}",
+            "Owner Group",
+            width = OWNER_COLUMN_WIDTH
+        ));
+    }
+    if columns.extension {
+        headers.push_str(&format!(
+            " {:<width$}",
+            "Ext",
+            width = EXTENSION_COLUMN_WIDTH
+        ));
+    }
+    if columns.link_target {
+        headers.push_str(" Link");
+    }
+    headers
+}
+
+#[cfg(test)]
+mod listing_columns_tests {
+    use super::*;
+
+    /// "cols" picks exactly the named columns; bare "cols" resets; unknown names are errors
+    #[test]
+    fn test_parse_listing_columns_command() {
+        assert!(parse_listing_columns_command("columns").is_none());
+        assert!(parse_listing_columns_command("tall+5").is_none());
+
+        let columns = parse_listing_columns_command("cols ext perm")
+            .expect("cols command")
+            .expect("valid columns");
+        assert!(columns.permissions && columns.extension);
+        assert!(!columns.owner && !columns.iso_timestamp && !columns.link_target);
+        assert_eq!(columns.describe(), "perm ext");
+
+        let reset = parse_listing_columns_command("cols")
+            .expect("cols command")
+            .expect("valid columns");
+        assert_eq!(reset, ListingColumns::default());
+        assert_eq!(reset.describe(), "none");
+
+        let all = parse_listing_columns_command("cols all")
+            .expect("cols command")
+            .expect("valid columns");
+        assert_eq!(all.describe(), "perm owner iso ext link");
+
+        assert!(
+            parse_listing_columns_command("cols perm colour")
+                .expect("cols command")
+                .is_err()
+        );
+    }
+
+    /// Optional columns take their width from the name column, not past the minimum
+    #[test]
+    fn test_listing_columns_extra_width() {
+        assert_eq!(ListingColumns::default().extra_width(), 0);
+
+        let iso_only = ListingColumns {
+            iso_timestamp: true,
+            ..ListingColumns::default()
+        };
+        assert_eq!(
+            iso_only.extra_width(),
+            ISO_TIMESTAMP_COLUMN_WIDTH - MODIFIED_COLUMN_WIDTH
+        );
+
+        let perm_and_ext = ListingColumns {
+            permissions: true,
+            extension: true,
+            ..ListingColumns::default()
+        };
+        assert_eq!(
+            perm_and_ext.extra_width(),
+            PERMISSIONS_COLUMN_WIDTH + 1 + EXTENSION_COLUMN_WIDTH + 1
+        );
+
+        // Name column narrows by the budget (80-column default window)
+        let (magnitude, direction) =
+            combine_size_adjustment(-(perm_and_ext.extra_width() as i32), 0, true);
+        assert_eq!(
+            calculate_name_width(magnitude, direction) as usize,
+            MAX_NAME_LENGTH_DEFAULT - perm_and_ext.extra_width()
+        );
+    }
+
+    /// Mode bits format like ls -l, including setuid/setgid/sticky
+    #[test]
+    fn test_format_unix_mode_bits() {
+        assert_eq!(format_unix_mode_bits(0o100644), "-rw-r--r--");
+        assert_eq!(format_unix_mode_bits(0o040755), "drwxr-xr-x");
+        assert_eq!(format_unix_mode_bits(0o120777), "lrwxrwxrwx");
+        assert_eq!(format_unix_mode_bits(0o104755), "-rwsr-xr-x");
+        assert_eq!(format_unix_mode_bits(0o102644), "-rw-r-Sr--");
+        assert_eq!(format_unix_mode_bits(0o041777), "drwxrwxrwt");
+    }
+
+    /// ISO timestamps are exact to the second (UTC)
+    #[test]
+    fn test_format_iso_timestamp() {
+        let timestamp = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        assert_eq!(format_iso_timestamp(timestamp), "2023-11-14 22:13:20");
+        assert_eq!(format_iso_timestamp(UNIX_EPOCH), "1970-01-01 00:00:00");
+    }
+
+    /// /etc/passwd and /etc/group lines map ids to names; ids without names show as numbers
+    #[test]
+    fn test_owner_names() {
+        let passwd = "# comment\nroot:x:0:0:root:/root:/bin/bash\nbroken line\nalexandria:x:1000:1000::/home/a:/bin/sh\n";
+        let user_names = parse_id_name_table(passwd);
+        assert_eq!(user_names.len(), 2);
+        assert_eq!(owner_name_for_id(Some(0), &user_names), "root");
+        assert_eq!(owner_name_for_id(Some(1000), &user_names), "alexand~");
+        assert_eq!(owner_name_for_id(Some(4242), &user_names), "4242");
+        assert_eq!(owner_name_for_id(None, &user_names), "-");
+
+        let group_names = parse_id_name_table("wheel:x:10:root,alice\n");
+        assert_eq!(group_names.get(&10).map(String::as_str), Some("wheel"));
+    }
+}
+
+static LINE_COUNT_LOOKUP: OnceLock<HashMap<&'static str, ()>> = OnceLock::new();
+
+fn get_line_count_options() -> &'static HashMap<&'static str, ()> {
+    LINE_COUNT_LOOKUP.get_or_init(|| {
+        HashMap::from([
+            ("--line-count", ()),
+            ("--row-count", ()),
+            ("--line-counts", ()),
+            ("--row-counts", ()),
+            ("--linecounts", ()),
+            ("--rowcounts", ()),
+            ("--lines-count", ()),
+            ("--rows-count", ()),
+            ("--count-rows", ()),
+            ("--count-lines", ()),
+            ("--linescounts", ()),
+            ("--rowscounts", ()),
+            ("--linecount", ()),
+            ("--rowcount", ()),
+            ("--linescount", ()),
+            ("--rowscount", ()),
+            ("--countrows", ()),
+            ("--countlines", ()),
+        ])
+    })
+}
+
+/// Processes user input and returns the corresponding NavigationAction
+///
+/// # Purpose
+/// This function serves as the central command interpreter for File Fantastic,
+/// translating user text input into specific actions the application should take.
+/// It handles commands, item selection, and search functionality in one unified interface.
+///
+/// # Arguments
+/// * `input` - The user's input string
+/// * `nav_state` - Current navigation state containing lookup table
+/// * `all_entries` - Complete list of directory entries
+///
+/// # Returns
+/// * `Result<NavigationAction>` - The determined action to take or error with context
+///
+/// # Command Processing Rules
+/// 1. Empty input triggers refresh action
+/// 2. Single-character inputs are checked against command mappings:
+///    - `q` -> Quit
+///    - `b` -> Parent directory
+///    - `t` -> Open terminal
+///    - `n/s/m` -> Sort commands
+///    - `d/f/a` -> Filter commands
+///    - `back`/`fwd` -> Directory history, `z {fragment}` -> Frecency jump
+///    - `tab`, `tab {N}`, `tabn`/`tabp`, `tabc` -> Open, switch, cycle, close tabs
+/// 3. Numeric inputs are treated as item selection
+///    - Validates against current display lookup table
+///    - Returns appropriate action based on item type (file/directory)
+/// 4. Multi-character non-numeric inputs trigger fuzzy search
+///    - Search results are displayed for user selection
+///    - User can then select from results by number
+///
+/// # Error Handling
+/// - Validates user input
+/// - Handles IO errors during search result display
+/// - Validates selected numbers against available items
+/// - Returns specific FileFantasticError types with context
+///
+/// # Examples
+/// ```rust
+/// / Process user input and take appropriate action
+/// match process_user_input(&input, &nav_state, &all_entries)? {
+///     NavigationAction::Quit => break,
+///     NavigationAction::ChangeDirectory(path) => current_directory = path,
+///     NavigationAction::OpenFile(path) => handle_file_open(&path)?,
+///     NavigationAction::Sort(c) => nav_state.toggle_sort(c),
+///     NavigationAction::OpenNewTerminal => open_new_terminal(&current_directory)?,
+///     NavigationAction::Filter(c) => nav_state.set_filter(c),
+///     NavigationAction::Refresh => { /* reload directory */ },
+///     NavigationAction::ParentDirectory => {
+///         if let Some(parent) = current_directory.parent() {
+///             current_directory = parent.to_path_buf();
+///         }
+///     },
+///     NavigationAction::Invalid => println!("Invalid input"),
+/// }
+/// ```
+fn process_user_input(
+    input: &str,
+    nav_state: &NavigationState,
+    all_entries: &[FileSystemEntry],
+    current_directory_path: &Path,
+) -> Result<NavigationAction> {
+    let input = input.trim();
+
+    // Handle empty input first - refresh and clear filters
+    if input.is_empty() {
+        return Ok(NavigationAction::Refresh);
+    }
+
+    // Convert to lowercase for case-insensitive commands
+    let lowercase_input = input.to_lowercase();
+
+    // Handle single-character commands first
     if input.len() == 1 {
         match lowercase_input.as_str() {
             "q" => return Ok(NavigationAction::Quit),
@@ -6705,6 +7528,13 @@ fn process_user_input(
 
     // command flags that are more than one character long
     match lowercase_input.as_str() {
+        "back" | "bk" => return Ok(NavigationAction::HistoryBack),
+        "fwd" | "fw" => return Ok(NavigationAction::HistoryForward),
+        "tab" => return Ok(NavigationAction::OpenTab),
+        "tabc" => return Ok(NavigationAction::CloseTab),
+        "tabn" => return Ok(NavigationAction::CycleTab(true)),
+        "tabp" => return Ok(NavigationAction::CycleTab(false)),
+        "tree" => return Ok(NavigationAction::ToggleTreeView),
         "vsplit" | "-vsplit" => return Ok(NavigationAction::VsplitTmux),
         "hsplit" | "-hsplit" => return Ok(NavigationAction::HsplitTmux),
         "--help" => return Ok(NavigationAction::GoToHelpMenuMode),
@@ -6715,6 +7545,36 @@ fn process_user_input(
         _ => {}
     }
 
+    // Switch tabs: "tab 2"
+    if let Some(tab_number) = lowercase_input
+        .strip_prefix("tab ")
+        .and_then(|number| number.trim().parse::<usize>().ok())
+    {
+        return Ok(NavigationAction::SwitchToTab(tab_number));
+    }
+
+    // Expand/collapse in the tree listing: "tree 3"
+    if let Some(item_number) = lowercase_input
+        .strip_prefix("tree ")
+        .and_then(|number| number.trim().parse::<usize>().ok())
+    {
+        return Ok(NavigationAction::ToggleTreeExpansion(item_number));
+    }
+
+    // Directory compare: "cmp" alone, or "cmp {tab number or path}" (path keeps its case)
+    if lowercase_input == "cmp" || lowercase_input.starts_with("cmp ") {
+        return Ok(NavigationAction::CompareDirectories(
+            input.trim()[3..].trim().to_string(),
+        ));
+    }
+
+    // Frecency jump: "z" alone, or "z {fragment}" (fragment keeps its case)
+    if lowercase_input == "z" || lowercase_input.starts_with("z ") {
+        return Ok(NavigationAction::FrecencyJump(
+            input[1..].trim().to_string(),
+        ));
+    }
+
     // if selection number + --return-path
     if lowercase_input.contains("--return-path") {
         // Split the input and try to extract the number before --return-path
@@ -6737,12 +7597,38 @@ fn process_user_input(
         }
     }
 
+    // Quick preview ("3 -p", "3 --preview") and info panel ("3 -i", "3 --info")
+    let input_words: Vec<&str> = lowercase_input.split_whitespace().collect();
+    if let [item_number, item_flag] = input_words[..]
+        && matches!(item_flag, "-p" | "--preview" | "-i" | "--info")
+        && let Some(item_info) = item_number
+            .parse::<usize>()
+            .ok()
+            .and_then(|number| nav_state.lookup_item(number))
+    {
+        let item_path = item_info.item_path.clone();
+        return Ok(if matches!(item_flag, "-i" | "--info") {
+            NavigationAction::ShowItemInfo(item_path)
+        } else {
+            NavigationAction::PreviewItem(item_path)
+        });
+    }
+
     // In process_user_input, check for TUI commands after single-char but before number parsing:
     if let Some(adjustment_action) = parse_tui_adjustment_command(input) {
         return Ok(NavigationAction::AdjustTuiSize(adjustment_action));
     }
 
-    // Try to parse as number for direct selection
+    // Optional listing columns: "cols perm owner iso ext link"
+    match parse_listing_columns_command(&lowercase_input) {
+        Some(Ok(listing_columns)) => {
+            return Ok(NavigationAction::SetListingColumns(listing_columns));
+        }
+        Some(Err(message)) => return Ok(NavigationAction::InvalidListingColumns(message)),
+        None => {}
+    }
+
+    // Try to parse as number for direct selection
     // This will be used as a fallback when not handled by pagination
     if let Ok(number) = input.parse::<usize>() {
         if let Some(item_info) = nav_state.lookup_item(number) {
@@ -7303,6 +8189,68 @@ enum NavigationAction {
     /// up from current directory
     ParentDirectory,
 
+    /// Return to the previously shown directory (history, not the parent)
+    ///
+    /// Generated by "back" or "bk"
+    HistoryBack,
+
+    /// Undo a "back"
+    ///
+    /// Generated by "fwd" or "fw"
+    HistoryForward,
+
+    /// Jump to the best frecency match for a fragment
+    ///
+    /// Generated by "z {fragment}"; a bare "z" holds an empty fragment
+    /// and lists the most visited directories to pick from
+    FrecencyJump(String),
+
+    /// Open a new tab on the current directory
+    ///
+    /// Generated by "tab"
+    OpenTab,
+
+    /// Close the current tab
+    ///
+    /// Generated by "tabc"
+    CloseTab,
+
+    /// Show another tab (1-based number, as in the tab bar)
+    ///
+    /// Generated by "tab {number}"
+    SwitchToTab(usize),
+
+    /// Show the next (true) or previous (false) tab
+    ///
+    /// Generated by "tabn" / "tabp"
+    CycleTab(bool),
+
+    /// Show a file or directory without leaving ff
+    ///
+    /// Generated by "{number} -p" or "{number} --preview"
+    PreviewItem(PathBuf),
+
+    /// Show size, times, mode, owner, inode and content type of an item
+    ///
+    /// Generated by "{number} -i" or "{number} --info"
+    ShowItemInfo(PathBuf),
+
+    /// Switch between the flat listing and the tree listing
+    ///
+    /// Generated by "tree"
+    ToggleTreeView,
+
+    /// Expand or collapse a directory in the tree listing (display number)
+    ///
+    /// Generated by "tree {number}"; also switches the tree listing on
+    ToggleTreeExpansion(usize),
+
+    /// Compare the current directory with another, side by side
+    ///
+    /// Generated by "cmp" (the next tab), "cmp {tab number}" or
+    /// "cmp {path}" (relative to the current directory)
+    CompareDirectories(String),
+
     /// Open the specified file with editor prompt
     ///
     /// Generated when user selects a file by number
@@ -7371,6 +8319,12 @@ enum NavigationAction {
     /// Generated by commands like "tall+5", "wide-10", etc.
     AdjustTuiSize(TuiAdjustmentAction),
 
+    /// Show these optional listing columns (from "cols perm owner ...")
+    SetListingColumns(ListingColumns),
+
+    /// A "cols" command naming an unknown column; holds the message to show
+    InvalidListingColumns(String),
+
     /// archive mode
     ArchiveModeShortcut,
 
@@ -7556,6 +8510,17 @@ struct DisplayedItemInfo {
 /// * `file_system_item_size_in_bytes` - Size of the file in bytes (0 for directories)
 /// * `file_system_item_last_modified_time` - Last modification time as a SystemTime
 /// * `is_directory` - Boolean flag indicating if this entry is a directory
+/// * `unix_mode_bits` - File type and permission bits (None off Unix)
+/// * `owner_user_id` - Owning user id (None off Unix)
+/// * `owner_group_id` - Owning group id (None off Unix)
+/// * `symlink_target` - Where a symbolic link points (None if not a link)
+/// * `is_symlink` - The entry itself is a symbolic link
+/// * `is_broken_symlink` - A symbolic link whose target does not exist
+///
+/// # Symbolic Links
+/// A link is listed under its own name, but size, modified time and
+/// `is_directory` describe its target, so a link to a directory can be
+/// entered like a directory. A broken link keeps its own (lstat) values.
 ///
 /// # Usage Context
 /// Instances are created during directory reading and used for:
@@ -7568,18 +8533,18 @@ struct DisplayedItemInfo {
 /// # Example
 /// ```rust
 /// / Creating a FileSystemEntry from directory read results
-/// let entry = FileSystemEntry {
-///     file_system_item_name: dir_entry.file_name().to_string_lossy().to_string(),
-///     file_system_item_path: dir_entry.path(),
-///     file_system_item_size_in_bytes: metadata.len(),
-///     file_system_item_last_modified_time: metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH),
-///     is_directory: metadata.is_dir(),
-/// };
+/// / (DirEntry::metadata does not follow symlinks)
+/// let entry = build_file_system_entry(
+///     dir_entry.file_name(),
+///     dir_entry.path(),
+///     &dir_entry.metadata()?,
+/// );
 /// ```
 #[derive(Debug, Clone)]
 pub struct FileSystemEntry {
-    /// The complete name of the file or directory
-    file_system_item_name: String,
+    /// The complete name of the file or directory, exactly as the OS gave it
+    /// (may be invalid UTF-8; show it with `escape_name_for_display`)
+    file_system_item_name: OsString,
 
     /// The full path to the file or directory
     file_system_item_path: std::path::PathBuf,
@@ -7592,6 +8557,634 @@ pub struct FileSystemEntry {
 
     /// Boolean flag indicating if this entry is a directory
     is_directory: bool,
+
+    /// File type and permission bits, e.g. 0o100644 (None off Unix)
+    unix_mode_bits: Option<u32>,
+
+    /// Owning user id, shown by name in the "owner" column (None off Unix)
+    owner_user_id: Option<u32>,
+
+    /// Owning group id, shown by name in the "owner" column (None off Unix)
+    owner_group_id: Option<u32>,
+
+    /// Target of a symbolic link, as stored in the link (None if not a link)
+    symlink_target: Option<PathBuf>,
+
+    /// True if this entry is a symbolic link (size/time/type describe the target)
+    is_symlink: bool,
+
+    /// True if this entry is a symbolic link whose target does not exist
+    is_broken_symlink: bool,
+}
+
+/// Builds a FileSystemEntry, resolving symbolic links to their targets
+///
+/// # Purpose
+/// One place that turns a directory item into an entry, so the listing,
+/// the recursive crawl and searches agree on how links are shown.
+///
+/// # Arguments
+/// * `file_name` - Name of the item as listed (raw, not lossily converted)
+/// * `path` - Full path of the item
+/// * `link_metadata` - Metadata NOT following symlinks (DirEntry::metadata, lstat)
+///
+/// # Returns
+/// * `FileSystemEntry` - For a link: target's size, modified time and
+///   directory flag, the link's own mode bits and owner; for a broken
+///   link: the link's own values and `is_broken_symlink` set
+fn build_file_system_entry(
+    file_name: OsString,
+    path: PathBuf,
+    link_metadata: &fs::Metadata,
+) -> FileSystemEntry {
+    let is_symlink = link_metadata.file_type().is_symlink();
+
+    // Step 1: For a link, read what it points at (follows the link)
+    let target_metadata = if is_symlink {
+        fs::metadata(&path).ok()
+    } else {
+        None
+    };
+    let is_broken_symlink = is_symlink && target_metadata.is_none();
+    let described_metadata = target_metadata.as_ref().unwrap_or(link_metadata);
+
+    // Step 2: Mode bits and owner belong to the item itself (an 'l' in perm)
+    let (unix_mode_bits, owner_user_id, owner_group_id) = read_unix_mode_and_owner(link_metadata);
+    let symlink_target = read_symlink_target(link_metadata, &path);
+
+    FileSystemEntry {
+        file_system_item_name: file_name,
+        file_system_item_path: path,
+        file_system_item_size_in_bytes: described_metadata.len(),
+        file_system_item_last_modified_time: described_metadata
+            .modified()
+            .unwrap_or(SystemTime::UNIX_EPOCH),
+        is_directory: described_metadata.is_dir(),
+        unix_mode_bits,
+        owner_user_id,
+        owner_group_id,
+        symlink_target,
+        is_symlink,
+        is_broken_symlink,
+    }
+}
+
+/// Makes a file name printable without losing what its bytes were
+///
+/// # Purpose
+/// Names on old mounts or from other systems may not be valid UTF-8.
+/// `to_string_lossy` turns every bad byte into the same '�', so two
+/// different files can look identical; here each undisplayable byte is
+/// shown as `\xNN` instead. Control characters (newline, escape, ...)
+/// would break the listing or the terminal, so they are escaped too.
+/// A backslash in the name is shown doubled, so a file really named
+/// `caf\xE9.txt` (shown `caf\\xE9.txt`) is not mistaken for the escape.
+///
+/// # Arguments
+/// * `name` - File name (or path) exactly as the OS gave it
+///
+/// # Returns
+/// * `String` - Valid UTF-8 text unchanged, other bytes as `\xNN`,
+///   `\` as `\\`, control characters as in `escape_control_character`
+///
+/// # Examples
+/// ```rust
+/// // b"caf\xe9.txt" (Latin-1 "café.txt")
+/// assert_eq!(escape_name_for_display(name), "caf\\xE9.txt");
+/// ```
+#[cfg(unix)]
+fn escape_name_for_display(name: &OsStr) -> String {
+    use std::os::unix::ffi::OsStrExt;
+
+    let mut display_name = String::with_capacity(name.len());
+    for chunk in name.as_bytes().utf8_chunks() {
+        for character in chunk.valid().chars() {
+            if character.is_control() {
+                display_name.push_str(&escape_control_character(character));
+            } else if character == '\\' {
+                display_name.push_str("\\\\");
+            } else {
+                display_name.push(character);
+            }
+        }
+        for invalid_byte in chunk.invalid() {
+            display_name.push_str(&format!("\\x{:02X}", invalid_byte));
+        }
+    }
+    display_name
+}
+
+/// Makes a file name printable (non-Unix: names are Unicode, only
+/// unpaired surrogates are lost to '�'; control characters are escaped;
+/// `\` is the path separator and cannot be part of a name, so it is kept)
+#[cfg(not(unix))]
+fn escape_name_for_display(name: &OsStr) -> String {
+    name.to_string_lossy()
+        .chars()
+        .map(|character| {
+            if character.is_control() {
+                escape_control_character(character)
+            } else {
+                character.to_string()
+            }
+        })
+        .collect()
+}
+
+/// Writes a control character the way the listing shows it
+///
+/// # Arguments
+/// * `character` - A control character (`char::is_control`)
+///
+/// # Returns
+/// * `String` - `\xNN` for C0 controls and DEL (U+0000-U+001F, U+007F);
+///   `\u{NN}` for C1 controls (U+0080-U+009F), which are valid UTF-8 and
+///   must not look like the raw byte NN that `\xNN` stands for
+fn escape_control_character(character: char) -> String {
+    if character.is_ascii() {
+        format!("\\x{:02X}", character as u32)
+    } else {
+        format!("\\u{{{:X}}}", character as u32)
+    }
+}
+
+/// Makes a whole path printable, see `escape_name_for_display`
+fn escape_path_for_display(path: &Path) -> String {
+    escape_name_for_display(path.as_os_str())
+}
+
+/// Builds a new file name around an existing one without converting it
+///
+/// # Purpose
+/// Backup, archive and link names are made from the original name; going
+/// through `String` would replace bytes that are not valid UTF-8 with '�'
+/// and produce a different name than the file's.
+///
+/// # Arguments
+/// * `prefix` - Text before the name (may be empty)
+/// * `name` - Original name, kept byte for byte
+/// * `suffix` - Text after the name (may be empty)
+///
+/// # Returns
+/// * `OsString` - `prefix` + `name` + `suffix`
+fn join_os_name(prefix: &str, name: &OsStr, suffix: &str) -> OsString {
+    let mut joined = OsString::from(prefix);
+    joined.push(name);
+    joined.push(suffix);
+    joined
+}
+
+/// One unit of a file name for fuzzy matching
+///
+/// # Purpose
+/// Fuzzy search compares names unit by unit. Valid UTF-8 gives characters;
+/// a byte that is not valid UTF-8 stays a raw byte, so it only matches the
+/// same byte (typed as `\xNN`, as the listing shows it), never a '�'.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum NameUnit {
+    /// A character (lowercased for case-insensitive matching)
+    Character(char),
+    /// A byte that is not part of valid UTF-8
+    RawByte(u8),
+}
+
+/// Lowercases one character to a single character (first of its lowercase form)
+fn lowercase_character(character: char) -> char {
+    character.to_lowercase().next().unwrap_or(character)
+}
+
+/// Splits a raw file name into lowercased units for fuzzy matching
+///
+/// # Arguments
+/// * `name` - File name exactly as the OS gave it
+///
+/// # Returns
+/// * `Vec<NameUnit>` - Characters, with invalid bytes kept as `RawByte`
+#[cfg(unix)]
+fn name_units_for_matching(name: &OsStr) -> Vec<NameUnit> {
+    use std::os::unix::ffi::OsStrExt;
+
+    let mut units = Vec::with_capacity(name.len());
+    for chunk in name.as_bytes().utf8_chunks() {
+        units.extend(
+            chunk
+                .valid()
+                .chars()
+                .map(|character| NameUnit::Character(lowercase_character(character))),
+        );
+        units.extend(chunk.invalid().iter().map(|&byte| NameUnit::RawByte(byte)));
+    }
+    units
+}
+
+/// Splits a file name into lowercased units for fuzzy matching (non-Unix)
+#[cfg(not(unix))]
+fn name_units_for_matching(name: &OsStr) -> Vec<NameUnit> {
+    name.to_string_lossy()
+        .chars()
+        .map(|character| NameUnit::Character(lowercase_character(character)))
+        .collect()
+}
+
+/// Splits a typed search term into lowercased units for fuzzy matching
+///
+/// # Arguments
+/// * `search_term` - Text as the user typed it
+///
+/// # Returns
+/// * `Vec<NameUnit>` - Characters; `\xNN` (two hex digits) becomes the raw
+///   byte NN when NN is 0x80 or above, else the character NN (an escaped
+///   control character), `\u{NN}` becomes the character U+NN, and `\\`
+///   becomes one backslash, so names shown escaped in the listing can be
+///   searched by typing what is shown
+fn search_term_units_for_matching(search_term: &str) -> Vec<NameUnit> {
+    let characters: Vec<char> = search_term.chars().collect();
+    let mut units = Vec::with_capacity(characters.len());
+    let mut index = 0;
+
+    while index < characters.len() {
+        // Step 1: "\\" is a backslash of the name, as the listing shows it
+        if characters[index] == '\\' && characters.get(index + 1) == Some(&'\\') {
+            units.push(NameUnit::Character('\\'));
+            index += 2;
+            continue;
+        }
+
+        // Step 2: "\xNN" escape, as `escape_name_for_display` writes it
+        if characters[index] == '\\'
+            && characters.get(index + 1) == Some(&'x')
+            && index + 3 < characters.len()
+        {
+            let hex_digits: String = characters[index + 2..index + 4].iter().collect();
+            if let Ok(byte) = u8::from_str_radix(&hex_digits, 16) {
+                if byte >= 0x80 {
+                    // Not valid UTF-8 on its own: a raw byte of the name
+                    units.push(NameUnit::RawByte(byte));
+                } else {
+                    units.push(NameUnit::Character(lowercase_character(byte as char)));
+                }
+                index += 4;
+                continue;
+            }
+        }
+
+        // Step 3: "\u{NN}" escape (a C1 control character, valid UTF-8)
+        if characters[index] == '\\'
+            && characters.get(index + 1) == Some(&'u')
+            && characters.get(index + 2) == Some(&'{')
+            && let Some(closing_offset) = characters[index + 3..]
+                .iter()
+                .take(7)
+                .position(|&character| character == '}')
+        {
+            let hex_digits: String = characters[index + 3..index + 3 + closing_offset]
+                .iter()
+                .collect();
+            if let Some(character) = u32::from_str_radix(&hex_digits, 16)
+                .ok()
+                .and_then(char::from_u32)
+            {
+                units.push(NameUnit::Character(lowercase_character(character)));
+                index += 4 + closing_offset;
+                continue;
+            }
+        }
+
+        // Step 4: Anything else is an ordinary character
+        units.push(NameUnit::Character(lowercase_character(characters[index])));
+        index += 1;
+    }
+    units
+}
+
+#[cfg(all(test, unix))]
+mod non_utf8_name_tests {
+    use super::*;
+    use std::os::unix::ffi::{OsStrExt, OsStringExt};
+
+    /// Latin-1 "café.txt": 0xE9 on its own is not valid UTF-8
+    fn latin1_cafe_name() -> OsString {
+        OsString::from_vec(b"caf\xe9.txt".to_vec())
+    }
+
+    /// Creates an empty, uniquely named scratch directory for one test
+    fn create_name_test_dir(test_name: &str) -> PathBuf {
+        let test_dir = std::env::temp_dir().join(format!(
+            "ff_non_utf8_test_{}_{}",
+            test_name,
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&test_dir);
+        fs::create_dir_all(&test_dir).expect("Failed to create test directory");
+        test_dir
+    }
+
+    #[test]
+    fn test_escape_name_for_display() {
+        assert_eq!(escape_name_for_display(&latin1_cafe_name()), "caf\\xE9.txt");
+        assert_eq!(escape_name_for_display(OsStr::new("日本.txt")), "日本.txt");
+        assert_eq!(escape_name_for_display(OsStr::new("a\nb")), "a\\x0Ab");
+        // A literal "\xE9" in a name is not shown like the byte 0xE9
+        assert_eq!(
+            escape_name_for_display(OsStr::new("caf\\xE9.txt")),
+            "caf\\\\xE9.txt"
+        );
+        assert_ne!(
+            escape_name_for_display(OsStr::new("caf\\xE9.txt")),
+            escape_name_for_display(&latin1_cafe_name())
+        );
+        // Two different bad bytes stay distinguishable (lossy gives "��" for both)
+        assert_ne!(
+            escape_name_for_display(OsStr::from_bytes(b"\xff")),
+            escape_name_for_display(OsStr::from_bytes(b"\xfe"))
+        );
+        // A C1 control character (UTF-8 C2 85) is not shown like the raw byte 0x85
+        assert_eq!(escape_name_for_display(OsStr::new("a\u{85}b")), "a\\u{85}b");
+        assert_eq!(
+            escape_name_for_display(OsStr::from_bytes(b"a\x85b")),
+            "a\\x85b"
+        );
+    }
+
+    #[test]
+    fn test_search_term_escapes_match_raw_bytes() {
+        // Typing what the listing shows gives the same units as the raw name
+        assert_eq!(
+            search_term_units_for_matching("CAF\\xE9.TXT"),
+            name_units_for_matching(&latin1_cafe_name())
+        );
+        // Escapes below 0x80 are ordinary characters, not raw bytes
+        assert_eq!(
+            search_term_units_for_matching("\\x41"),
+            vec![NameUnit::Character('a')]
+        );
+        // A C1 control character is typed as shown, apart from the raw byte
+        assert_eq!(
+            search_term_units_for_matching("a\\u{85}b"),
+            name_units_for_matching(OsStr::new("a\u{85}b"))
+        );
+        assert_eq!(
+            search_term_units_for_matching("\\x85"),
+            vec![NameUnit::RawByte(0x85)]
+        );
+        // Incomplete escapes are typed text
+        assert_eq!(search_term_units_for_matching("\\x").len(), 2);
+        assert_eq!(search_term_units_for_matching("\\u{85").len(), 5);
+        // A doubled backslash is one backslash of the name, not an escape
+        assert_eq!(
+            search_term_units_for_matching("caf\\\\xE9.txt"),
+            name_units_for_matching(OsStr::new("caf\\xE9.txt"))
+        );
+        assert_ne!(
+            search_term_units_for_matching("caf\\\\xE9.txt"),
+            name_units_for_matching(&latin1_cafe_name())
+        );
+    }
+
+    #[test]
+    fn test_non_utf8_names_are_listed_searched_and_copied() {
+        let test_dir = create_name_test_dir("list");
+        let raw_path = test_dir.join(latin1_cafe_name());
+        fs::write(&raw_path, "bonjour").expect("Failed to write non-UTF-8 file");
+
+        // Listed with its exact name (not skipped, not replaced by '�')
+        let entries = read_directory_contents(&test_dir).expect("Read should succeed");
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].file_system_item_name, latin1_cafe_name());
+        assert_eq!(entries[0].file_system_item_path, raw_path);
+
+        let crawled = NavigationState::new()
+            .collect_entries_iterative(&test_dir, Some(0), None, None)
+            .expect("Crawl should succeed");
+        assert_eq!(crawled.len(), 1, "Recursive search must not skip the file");
+
+        // Fuzzy search on raw bytes: the escaped form matches exactly
+        let results = NavigationState::new()
+            .fuzzy_search_entries(&SearchConfig::new("caf\\xe9".to_string()), &entries);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].distance, 0);
+        assert_eq!(results[0].item_path, raw_path);
+
+        // Copy keeps the exact name
+        let destination = test_dir.join("destination");
+        fs::create_dir(&destination).expect("Failed to create destination");
+        let copied =
+            copy_file_with_archive_handling(&raw_path, &destination).expect("Copy should succeed");
+        assert_eq!(copied.file_name(), Some(latin1_cafe_name().as_os_str()));
+
+        let _ = fs::remove_dir_all(&test_dir);
+    }
+
+    #[test]
+    fn test_generate_archive_filename_keeps_raw_bytes() {
+        let timestamp = "2025_01_15_14_30_45";
+        assert_eq!(
+            generate_archive_filename(OsStr::new("cats.toml"), timestamp),
+            "cats_2025_01_15_14_30_45.toml"
+        );
+        assert_eq!(
+            generate_archive_filename(OsStr::new("README"), timestamp),
+            "README_2025_01_15_14_30_45"
+        );
+        assert_eq!(
+            generate_archive_filename(OsStr::new("data.tar.gz"), timestamp),
+            "data.tar_2025_01_15_14_30_45.gz"
+        );
+        assert_eq!(
+            generate_archive_filename(OsStr::new(".gitignore"), timestamp),
+            ".gitignore_2025_01_15_14_30_45"
+        );
+        assert_eq!(
+            generate_archive_filename(&latin1_cafe_name(), timestamp).as_bytes(),
+            b"caf\xe9_2025_01_15_14_30_45.txt"
+        );
+    }
+}
+
+/// Identifies a directory by (device, inode), following symlinks
+///
+/// # Purpose
+/// The recursive crawl records every directory it enters; a second path
+/// to the same directory (a link to a parent, a bind mount) is then
+/// listed but not entered again, so traversal cannot loop.
+///
+/// # Arguments
+/// * `directory_path` - Directory (or link to a directory)
+///
+/// # Returns
+/// * `Some((device, inode))` - Identity of the directory
+/// * `None` - Not readable, or no inode numbers on this platform
+#[cfg(unix)]
+fn directory_identity(directory_path: &Path) -> Option<(u64, u64)> {
+    use std::os::unix::fs::MetadataExt;
+    let metadata = fs::metadata(directory_path).ok()?;
+    Some((metadata.dev(), metadata.ino()))
+}
+
+/// Identifies a directory by (device, inode), following symlinks
+///
+/// # Returns
+/// * `None` - This platform has no inode numbers (links are not followed)
+#[cfg(not(unix))]
+fn directory_identity(_directory_path: &Path) -> Option<(u64, u64)> {
+    None
+}
+
+#[cfg(all(test, unix))]
+mod symlink_entry_tests {
+    use super::*;
+
+    /// Creates an empty, uniquely named scratch directory for one test
+    fn create_symlink_test_dir(test_name: &str) -> PathBuf {
+        let test_dir = std::env::temp_dir().join(format!(
+            "ff_symlink_test_{}_{}",
+            test_name,
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&test_dir);
+        fs::create_dir_all(&test_dir).expect("Failed to create test directory");
+        test_dir
+    }
+
+    /// Builds an entry the way directory listing does (link metadata, not followed)
+    fn entry_for(path: &Path) -> FileSystemEntry {
+        let link_metadata = fs::symlink_metadata(path).expect("Failed to read link metadata");
+        build_file_system_entry(
+            path.file_name()
+                .expect("Test path has a name")
+                .to_os_string(),
+            path.to_path_buf(),
+            &link_metadata,
+        )
+    }
+
+    #[test]
+    fn test_links_to_file_directory_and_missing_target() {
+        let test_dir = create_symlink_test_dir("entries");
+        let target_file = test_dir.join("target.txt");
+        let target_directory = test_dir.join("target_dir");
+        fs::write(&target_file, "twelve bytes").expect("Failed to write target file");
+        fs::create_dir(&target_directory).expect("Failed to create target directory");
+
+        std::os::unix::fs::symlink(&target_file, test_dir.join("file_link"))
+            .expect("Failed to create file link");
+        std::os::unix::fs::symlink(&target_directory, test_dir.join("dir_link"))
+            .expect("Failed to create directory link");
+        std::os::unix::fs::symlink(test_dir.join("missing"), test_dir.join("broken_link"))
+            .expect("Failed to create broken link");
+
+        // Link to a file: size comes from the target
+        let file_link = entry_for(&test_dir.join("file_link"));
+        assert!(file_link.is_symlink && !file_link.is_broken_symlink);
+        assert!(!file_link.is_directory);
+        assert_eq!(file_link.file_system_item_size_in_bytes, 12);
+        assert_eq!(file_link.symlink_target, Some(target_file.clone()));
+        assert_eq!(format_entry_display_name(&file_link), "file_link@");
+
+        // Link to a directory: navigable like a directory
+        let dir_link = entry_for(&test_dir.join("dir_link"));
+        assert!(dir_link.is_symlink && dir_link.is_directory);
+        assert_eq!(format_entry_display_name(&dir_link), "dir_link@/");
+
+        // Broken link: still listed, marked, target preserved
+        let broken_link = entry_for(&test_dir.join("broken_link"));
+        assert!(broken_link.is_symlink && broken_link.is_broken_symlink);
+        assert!(!broken_link.is_directory);
+        assert_eq!(broken_link.symlink_target, Some(test_dir.join("missing")));
+        assert_eq!(format_entry_display_name(&broken_link), "broken_link@!");
+
+        // Plain entries carry no link markers
+        let plain_file = entry_for(&target_file);
+        assert!(!plain_file.is_symlink && !plain_file.is_broken_symlink);
+        assert_eq!(format_entry_display_name(&plain_file), "target.txt");
+
+        let _ = fs::remove_dir_all(&test_dir);
+    }
+
+    #[test]
+    fn test_create_symlink_to_stack_item_never_overwrites() {
+        let test_dir = create_symlink_test_dir("create");
+        let source_directory = test_dir.join("source");
+        let destination_directory = test_dir.join("destination");
+        fs::create_dir_all(&source_directory).expect("Failed to create source directory");
+        fs::create_dir_all(&destination_directory).expect("Failed to create destination directory");
+        let stack_file = source_directory.join("notes.txt");
+        fs::write(&stack_file, "notes").expect("Failed to write stack file");
+
+        // First link takes the target's name
+        let first_link = create_symlink_to_stack_item(&stack_file, &destination_directory)
+            .expect("First link should be created");
+        assert_eq!(first_link, destination_directory.join("notes.txt"));
+        assert_eq!(
+            fs::read_link(&first_link).expect("Should be a link"),
+            stack_file
+        );
+
+        // Second link gets a timestamped name; the first is untouched
+        let second_link = create_symlink_to_stack_item(&stack_file, &destination_directory)
+            .expect("Second link should be created");
+        assert_ne!(second_link, first_link);
+        assert!(
+            second_link
+                .file_name()
+                .map(|name| name.to_string_lossy().starts_with("notes.txt_link_"))
+                .unwrap_or(false)
+        );
+        assert_eq!(fs::read_to_string(&first_link).expect("Readable"), "notes");
+
+        // Missing target is an error, not a broken link
+        let missing_result =
+            create_symlink_to_stack_item(&source_directory.join("gone"), &destination_directory);
+        assert!(matches!(
+            missing_result,
+            Err(FileFantasticError::NotFound(_))
+        ));
+
+        let _ = fs::remove_dir_all(&test_dir);
+    }
+}
+
+/// Reads Unix mode bits and owner/group ids from metadata
+///
+/// # Arguments
+/// * `metadata` - Metadata of the entry (not following symlinks)
+///
+/// # Returns
+/// * `(mode, user id, group id)` - All None on platforms without Unix metadata
+#[cfg(unix)]
+fn read_unix_mode_and_owner(metadata: &fs::Metadata) -> (Option<u32>, Option<u32>, Option<u32>) {
+    use std::os::unix::fs::MetadataExt;
+    (
+        Some(metadata.mode()),
+        Some(metadata.uid()),
+        Some(metadata.gid()),
+    )
+}
+
+/// Reads Unix mode bits and owner/group ids from metadata
+///
+/// # Returns
+/// * `(None, None, None)` - This platform has no Unix metadata
+#[cfg(not(unix))]
+fn read_unix_mode_and_owner(_metadata: &fs::Metadata) -> (Option<u32>, Option<u32>, Option<u32>) {
+    (None, None, None)
+}
+
+/// Reads where a symbolic link points
+///
+/// # Arguments
+/// * `metadata` - Metadata of the entry (not following symlinks)
+/// * `path` - Path of the entry
+///
+/// # Returns
+/// * `Some(PathBuf)` - The link's target, as stored (may be relative or dangling)
+/// * `None` - Not a symbolic link, or the link could not be read
+fn read_symlink_target(metadata: &fs::Metadata, path: &Path) -> Option<PathBuf> {
+    if metadata.file_type().is_symlink() {
+        fs::read_link(path).ok()
+    } else {
+        None
+    }
 }
 
 /// Parses user input to extract search term and flags
@@ -7672,8 +9265,9 @@ pub fn display_paginated_search_results(
     }
 
     // Calculate items per page based on TUI height adjustments
-    // Start with the default number of items per page
-    let base_items_per_page = ITEMS_PER_PAGE_DEFAULT as i16;
+    // Start with the default number of items per page, grown or shrunk to the window
+    let (extra_columns, extra_rows) = detected_terminal_size_offsets();
+    let base_items_per_page = (ITEMS_PER_PAGE_DEFAULT as i32 + extra_rows).clamp(0, i16::MAX as i32) as i16;
 
     // Apply the height adjustment based on direction
     // true = add rows (show more items), false = subtract rows (show fewer items)
@@ -7688,8 +9282,8 @@ pub fn display_paginated_search_results(
 
     // Apply width adjustment based on direction
     // true = wider (show more of filename), false = narrower (show less of filename)
-    // Start with the default width constant, convert to signed for arithmetic
-    let base_width = MAX_TUI_CHAR_LENGTH_DEFAULT as i16;
+    // Start with the detected window width (or the default), convert to signed for arithmetic
+    let base_width = (MAX_TUI_CHAR_LENGTH_DEFAULT as i32 + extra_columns).clamp(0, i16::MAX as i32) as i16;
 
     // Calculate the adjustment as signed (can be negative)
     let width_adjustment = if tui_wide_direction_sign {
@@ -7872,9 +9466,9 @@ fn display_grep_page(
 
             // Display the row with exact spacing to match header
             print!("{:<2} ", row_num); // 2 chars + 1 space
-            print!("{:<width$}", display_file, width = file_width);
+            print!("{}", pad_to_display_width(&display_file, file_width));
             print!(" {} ", line_str); // 1 + 5 + 1 spaces
-            println!("{:<width$}", display_content, width = content_width);
+            println!("{}", pad_to_display_width(&display_content, content_width));
         }
     }
 
@@ -7889,20 +9483,20 @@ fn display_grep_page(
 ///
 /// # Arguments
 /// * `text` - The text to potentially truncate
-/// * `max_width` - Maximum width in characters
+/// * `max_width` - Maximum width in terminal columns
 ///
 /// # Returns
 /// A String that fits within max_width, with "..." appended if truncated
 fn truncate_with_ellipsis(text: &str, max_width: usize) -> String {
-    if text.len() <= max_width {
+    if str_display_width(text) <= max_width {
         text.to_string()
     } else if max_width <= 3 {
         // If width is too small for ellipsis, just truncate
-        text.chars().take(max_width).collect()
+        take_display_width_prefix(text, max_width)
     } else {
         // Leave room for "..."
         let truncate_at = max_width - 3;
-        let truncated: String = text.chars().take(truncate_at).collect();
+        let truncated = take_display_width_prefix(text, truncate_at);
         format!("{}...", truncated)
     }
 }
@@ -8003,14 +9597,17 @@ fn display_fuzzy_page(
             let row_num = format!("{}", index + 1);
 
             // Truncate name if needed (reuse helper function from grep)
-            let display_name = truncate_with_ellipsis(&fuzzy_result.item_name, name_width);
+            let display_name = truncate_with_ellipsis(
+                &escape_name_for_display(&fuzzy_result.item_name),
+                name_width,
+            );
 
             // Format distance (right-aligned for better readability)
             let distance_str = format!("{:>9}", fuzzy_result.distance);
 
             // Display the row with exact spacing to match header
             print!("{:<2} ", row_num); // 2 chars + 1 space
-            print!("{:<width$}", display_name, width = name_width);
+            print!("{}", pad_to_display_width(&display_name, name_width));
             println!(" {}", distance_str); // 1 space + distance
         }
     }
@@ -8036,7 +9633,7 @@ fn display_fuzzy_page_minimal(
             println!(
                 "{}. {} (dist: {})",
                 index + 1,
-                truncate_with_ellipsis(&fuzzy_result.item_name, 20),
+                truncate_with_ellipsis(&escape_name_for_display(&fuzzy_result.item_name), 20),
                 fuzzy_result.distance
             );
         }
@@ -8245,6 +9842,13 @@ mod tests_iterative_crawl {
             tui_wide_direction_sign: true,
             current_page_index: 0,
             lines_editor_session_path: PathBuf::new(),
+            raw_key_mode: false,
+            raw_key_cursor_row: 0,
+            listing_first_item_screen_row: None,
+            listing_columns: ListingColumns::default(),
+            directory_history: DirectoryHistory::new(),
+            tree_view_mode: false,
+            tree_expanded_directories: HashSet::new(),
         }
     }
 
@@ -8312,6 +9916,34 @@ mod tests_iterative_crawl {
         );
     }
 
+    #[cfg(unix)]
+    #[test]
+    fn test_collect_entries_iterative_link_to_parent_terminates() {
+        // A link pointing back at an ancestor must not be entered again
+        let nav_state = create_test_navigation_state();
+        let test_root =
+            std::env::temp_dir().join(format!("ff_crawl_loop_test_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&test_root);
+        let child_directory = test_root.join("child");
+        fs::create_dir_all(&child_directory).expect("Failed to create test directories");
+        std::os::unix::fs::symlink(&test_root, child_directory.join("back_to_root"))
+            .expect("Failed to create loop link");
+
+        let entries = nav_state
+            .collect_entries_iterative(&test_root, None, None, None)
+            .expect("Crawl should succeed");
+
+        // "child" and "child/back_to_root" only: the link is listed, not followed
+        assert_eq!(entries.len(), 2, "Got {:?}", entries);
+        assert!(
+            entries
+                .iter()
+                .any(|entry| entry.is_symlink && entry.is_directory)
+        );
+
+        let _ = fs::remove_dir_all(&test_root);
+    }
+
     #[test]
     fn test_collect_entries_iterative_nonexistent_directory() {
         // Test handling of non-existent directory
@@ -8347,72 +9979,3443 @@ mod tests_iterative_crawl {
             Some(1), // 1 MB limit
         );
 
-        assert!(result.is_ok(), "Should succeed even with memory limit");
-        // Just verify it completes without panic
+        assert!(result.is_ok(), "Should succeed even with memory limit");
+        // Just verify it completes without panic
+    }
+
+    #[test]
+    fn test_collect_entries_iterative_combined_limits() {
+        // Test multiple limits at once
+        let nav_state = create_test_navigation_state();
+        let temp_dir = std::env::temp_dir();
+
+        let result = nav_state.collect_entries_iterative(
+            &temp_dir,
+            Some(1),  // Depth limit
+            Some(10), // Entry limit
+            Some(5),  // Memory limit (MB)
+        );
+
+        assert!(result.is_ok(), "Should succeed with combined limits");
+        let entries = result.expect("Failed to get entries");
+
+        // Should respect the entry limit
+        assert!(
+            entries.len() <= 10,
+            "Should not exceed 10 entries when limit is set, got {}",
+            entries.len()
+        );
+    }
+
+    #[test]
+    fn test_collect_entries_iterative_file_attributes() {
+        // Test that collected entries have proper attributes
+        let nav_state = create_test_navigation_state();
+        let temp_dir = std::env::temp_dir();
+
+        let result = nav_state.collect_entries_iterative(
+            &temp_dir,
+            Some(0), // Only immediate children
+            Some(5), // Limit to a few entries
+            None,
+        );
+
+        assert!(result.is_ok(), "Should collect entries successfully");
+        let entries = result.expect("Failed to get entries");
+
+        // Verify each entry has valid attributes
+        for entry in entries.iter() {
+            // Name should not be empty
+            assert!(
+                !entry.file_system_item_name.is_empty(),
+                "File name should not be empty"
+            );
+
+            // Path should exist as a string representation
+            assert!(
+                entry.file_system_item_path.to_str().is_some(),
+                "Path should be valid UTF-8"
+            );
+
+            // Last modified time should not be UNIX_EPOCH for real files
+            // (though we handle UNIX_EPOCH as fallback)
+            // Just verify it doesn't panic when accessed
+            let _ = entry.file_system_item_last_modified_time;
+
+            // is_directory should be properly set
+            let _ = entry.is_directory;
+        }
+    }
+}
+
+/// Most directories kept on each of the back and forward history stacks
+const DIRECTORY_HISTORY_MAX_ENTRIES: usize = 100;
+
+/// File in ff_data holding the frecency database of visited directories
+const FRECENCY_DATABASE_FILENAME: &str = "directory_frecency.txt";
+
+/// Most directories kept in the frecency database (lowest scores are dropped)
+const FRECENCY_DATABASE_MAX_ENTRIES: usize = 500;
+
+/// Directories listed by a bare "z" command
+const FRECENCY_LIST_LENGTH: usize = 10;
+
+/// Back/forward directory history, like a web browser's
+///
+/// # Purpose
+/// `b` only goes to the parent. This remembers where the user actually was,
+/// so "back" returns to the previous directory (wherever it was) and "fwd"
+/// undoes a "back".
+///
+/// # Behavior
+/// - Visiting a new directory pushes the old one on the back stack and
+///   clears the forward stack (as a browser does after following a link)
+/// - Going back or forward moves the current directory onto the other stack
+/// - Directories that no longer exist are skipped when going back/forward
+/// - Each stack keeps at most `DIRECTORY_HISTORY_MAX_ENTRIES` directories
+#[derive(Debug, Default)]
+struct DirectoryHistory {
+    /// Earlier directories, most recent last
+    back_stack: Vec<PathBuf>,
+
+    /// Directories left with "back", most recent last
+    forward_stack: Vec<PathBuf>,
+
+    /// Directory being shown (None before the first visit)
+    current_directory: Option<PathBuf>,
+}
+
+impl DirectoryHistory {
+    /// Creates an empty history
+    fn new() -> Self {
+        DirectoryHistory::default()
+    }
+
+    /// Records that ff is now showing `directory_path`
+    ///
+    /// # Arguments
+    /// * `directory_path` - The directory now being listed
+    ///
+    /// # Returns
+    /// * `bool` - true if this was a new visit, false if already current
+    ///   (a redraw, or the target of "back"/"fwd")
+    fn record_visit(&mut self, directory_path: &Path) -> bool {
+        if self.current_directory.as_deref() == Some(directory_path) {
+            return false;
+        }
+
+        // Step 1: The directory being left becomes the newest "back" entry
+        if let Some(previous_directory) = self.current_directory.take() {
+            push_bounded_history(&mut self.back_stack, previous_directory);
+        }
+
+        // Step 2: A fresh visit ends any "forward" path
+        self.forward_stack.clear();
+        self.current_directory = Some(directory_path.to_path_buf());
+        true
+    }
+
+    /// Steps back to the previous directory that still exists
+    ///
+    /// # Returns
+    /// * `Option<PathBuf>` - The directory to show, or None if there is none
+    fn go_back(&mut self) -> Option<PathBuf> {
+        step_directory_history(
+            &mut self.back_stack,
+            &mut self.forward_stack,
+            &mut self.current_directory,
+        )
+    }
+
+    /// Steps forward again after "back", skipping directories that are gone
+    ///
+    /// # Returns
+    /// * `Option<PathBuf>` - The directory to show, or None if there is none
+    fn go_forward(&mut self) -> Option<PathBuf> {
+        step_directory_history(
+            &mut self.forward_stack,
+            &mut self.back_stack,
+            &mut self.current_directory,
+        )
+    }
+}
+
+/// Pushes onto a history stack, dropping the oldest entry when full
+fn push_bounded_history(history_stack: &mut Vec<PathBuf>, directory_path: PathBuf) {
+    if history_stack.len() >= DIRECTORY_HISTORY_MAX_ENTRIES {
+        history_stack.remove(0);
+    }
+    history_stack.push(directory_path);
+}
+
+/// Moves one step through history (shared by back and forward)
+///
+/// # Arguments
+/// * `from_stack` - Stack to take the destination from
+/// * `to_stack` - Stack that receives the directory being left
+/// * `current_directory` - The history's current directory, updated in place
+///
+/// # Returns
+/// * `Option<PathBuf>` - The destination, or None (history unchanged apart
+///   from dropping directories that no longer exist)
+fn step_directory_history(
+    from_stack: &mut Vec<PathBuf>,
+    to_stack: &mut Vec<PathBuf>,
+    current_directory: &mut Option<PathBuf>,
+) -> Option<PathBuf> {
+    while let Some(candidate_directory) = from_stack.pop() {
+        // Deleted or renamed since: skip it rather than land on an error
+        if !candidate_directory.is_dir() {
+            continue;
+        }
+        if let Some(left_directory) = current_directory.take() {
+            push_bounded_history(to_stack, left_directory);
+        }
+        *current_directory = Some(candidate_directory.clone());
+        return Some(candidate_directory);
+    }
+    None
+}
+
+/// Seconds since the Unix epoch (0 if the clock is before 1970)
+fn unix_seconds_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or(0)
+}
+
+/// One visited directory in the frecency database
+#[derive(Debug, Clone, PartialEq)]
+struct FrecencyEntry {
+    /// Absolute path of the directory
+    directory_path: PathBuf,
+
+    /// How many times ff has entered it
+    visit_count: u64,
+
+    /// Last visit, in seconds since the Unix epoch
+    last_visit_unix_seconds: u64,
+}
+
+impl FrecencyEntry {
+    /// Frecency score: visit count weighted by how recent the last visit was
+    ///
+    /// # Arguments
+    /// * `now_unix_seconds` - Current time, seconds since the Unix epoch
+    ///
+    /// # Returns
+    /// * `u64` - Higher is better. Weights (×4 to keep integers):
+    ///   within an hour ×16, a day ×8, a week ×2, older ×1
+    fn score(&self, now_unix_seconds: u64) -> u64 {
+        let age_seconds = now_unix_seconds.saturating_sub(self.last_visit_unix_seconds);
+        let recency_weight = if age_seconds < 60 * 60 {
+            16
+        } else if age_seconds < 24 * 60 * 60 {
+            8
+        } else if age_seconds < 7 * 24 * 60 * 60 {
+            2
+        } else {
+            1
+        };
+        self.visit_count.saturating_mul(recency_weight)
+    }
+}
+
+/// Persisted database of visited directories, ranked by frecency
+///
+/// # Purpose
+/// Backs the `z <fragment>` jump: directories visited often and recently
+/// rank first, and the fragment is matched against their names with the
+/// same Levenshtein distance fuzzy search uses.
+///
+/// # File Format
+/// `ff_data/directory_frecency.txt`, one directory per line:
+/// ```text
+/// {visit_count}\t{last_visit_unix_seconds}\t{path}
+/// ```
+/// Paths are stored as raw bytes on Unix (so non-UTF-8 names survive).
+/// Malformed lines are skipped; a missing file is an empty database.
+#[derive(Debug)]
+struct FrecencyDatabase {
+    /// Known directories (unordered)
+    entries: Vec<FrecencyEntry>,
+
+    /// Where to save; None when saving is unavailable or has failed
+    database_file_path: Option<PathBuf>,
+}
+
+impl FrecencyDatabase {
+    /// Loads the database from `{executable_directory}/ff_data`
+    ///
+    /// # Returns
+    /// * `FrecencyDatabase` - Empty (and not saved) if the executable
+    ///   directory cannot be found
+    fn load_from_ff_data() -> Self {
+        match get_absolute_path_to_executable_parentdirectory() {
+            Ok(executable_directory) => FrecencyDatabase::load(
+                executable_directory
+                    .join(FF_DATA_DIRECTORY_NAME)
+                    .join(FRECENCY_DATABASE_FILENAME),
+            ),
+            Err(_) => FrecencyDatabase {
+                entries: Vec::new(),
+                database_file_path: None,
+            },
+        }
+    }
+
+    /// Loads the database from a file (missing or unreadable: empty)
+    ///
+    /// # Arguments
+    /// * `database_file_path` - File to read now and save to later
+    fn load(database_file_path: PathBuf) -> Self {
+        let entries = match fs::read(&database_file_path) {
+            Ok(file_bytes) => file_bytes
+                .split(|byte| *byte == b'\n')
+                .filter_map(parse_frecency_line)
+                .collect(),
+            Err(_) => Vec::new(),
+        };
+        FrecencyDatabase {
+            entries,
+            database_file_path: Some(database_file_path),
+        }
+    }
+
+    /// Counts a visit to `directory_path` at `now_unix_seconds`
+    ///
+    /// # Arguments
+    /// * `directory_path` - The directory entered
+    /// * `now_unix_seconds` - Current time, seconds since the Unix epoch
+    fn record_visit(&mut self, directory_path: &Path, now_unix_seconds: u64) {
+        match self
+            .entries
+            .iter_mut()
+            .find(|entry| entry.directory_path == directory_path)
+        {
+            Some(entry) => {
+                entry.visit_count = entry.visit_count.saturating_add(1);
+                entry.last_visit_unix_seconds = now_unix_seconds;
+            }
+            None => self.entries.push(FrecencyEntry {
+                directory_path: directory_path.to_path_buf(),
+                visit_count: 1,
+                last_visit_unix_seconds: now_unix_seconds,
+            }),
+        }
+
+        // Full: forget the lowest-ranked directory
+        if self.entries.len() > FRECENCY_DATABASE_MAX_ENTRIES
+            && let Some(lowest_index) = self
+                .entries
+                .iter()
+                .enumerate()
+                .min_by_key(|(_, entry)| entry.score(now_unix_seconds))
+                .map(|(index, _)| index)
+        {
+            self.entries.swap_remove(lowest_index);
+        }
+    }
+
+    /// Writes the database (via a temporary file, so a crash cannot truncate it)
+    ///
+    /// # Returns
+    /// * `Result<()>` - Ok, or the I/O error (nothing to do if saving is off)
+    fn save(&self) -> Result<()> {
+        let database_file_path = match &self.database_file_path {
+            Some(path) => path,
+            None => return Ok(()),
+        };
+
+        // Step 1: ff_data may not exist yet
+        if let Some(ff_data_directory) = database_file_path.parent() {
+            fs::create_dir_all(ff_data_directory)?;
+        }
+
+        // Step 2: One line per directory (paths with a newline cannot be stored)
+        let mut database_bytes = Vec::new();
+        for entry in &self.entries {
+            let path_bytes = match frecency_path_to_bytes(&entry.directory_path) {
+                Some(bytes) if !bytes.contains(&b'\n') => bytes,
+                _ => continue,
+            };
+            database_bytes.extend_from_slice(
+                format!("{}\t{}\t", entry.visit_count, entry.last_visit_unix_seconds).as_bytes(),
+            );
+            database_bytes.extend_from_slice(&path_bytes);
+            database_bytes.push(b'\n');
+        }
+
+        // Step 3: Write beside the database, then swap it in
+        let temporary_path = database_file_path.with_extension("txt.tmp");
+        fs::write(&temporary_path, &database_bytes)?;
+        fs::rename(&temporary_path, database_file_path)?;
+        Ok(())
+    }
+
+    /// Stops saving (after a failed save, so the warning is shown only once)
+    fn disable_saving(&mut self) {
+        self.database_file_path = None;
+    }
+
+    /// Existing directories matching `fragment`, best first
+    ///
+    /// # Arguments
+    /// * `fragment` - What the user typed after "z"
+    /// * `now_unix_seconds` - Current time, for the recency weighting
+    ///
+    /// # Returns
+    /// * `Vec<PathBuf>` - Sorted by match distance, then by frecency score
+    ///
+    /// # Matching
+    /// A directory matches with distance 0 if the fragment appears anywhere
+    /// in its path (case-insensitive). Otherwise each path component, cut to
+    /// the fragment's length as in fuzzy search, is compared with
+    /// `levenshtein_distance`; the closest component counts, and must be
+    /// within `MAX_SEARCH_DISTANCE`.
+    fn ranked_matches(&self, fragment: &str, now_unix_seconds: u64) -> Vec<PathBuf> {
+        let fragment = fragment.trim().to_lowercase();
+        if fragment.is_empty() {
+            return Vec::new();
+        }
+
+        let mut scored_matches: Vec<(usize, u64, &Path)> = self
+            .entries
+            .iter()
+            .filter(|entry| entry.directory_path.is_dir())
+            .filter_map(|entry| {
+                frecency_match_distance(&fragment, &entry.directory_path).map(|distance| {
+                    (
+                        distance,
+                        entry.score(now_unix_seconds),
+                        entry.directory_path.as_path(),
+                    )
+                })
+            })
+            .collect();
+
+        // Closest match first; among equals, the highest frecency
+        scored_matches.sort_by(|a, b| a.0.cmp(&b.0).then(b.1.cmp(&a.1)));
+        scored_matches
+            .into_iter()
+            .map(|(_, _, directory_path)| directory_path.to_path_buf())
+            .collect()
+    }
+
+    /// The highest-ranked existing directories (for a bare "z")
+    ///
+    /// # Arguments
+    /// * `now_unix_seconds` - Current time, for the recency weighting
+    /// * `limit` - How many to return at most
+    fn most_frecent(&self, now_unix_seconds: u64, limit: usize) -> Vec<PathBuf> {
+        let mut ranked_entries: Vec<&FrecencyEntry> = self
+            .entries
+            .iter()
+            .filter(|entry| entry.directory_path.is_dir())
+            .collect();
+        ranked_entries.sort_by_key(|entry| std::cmp::Reverse(entry.score(now_unix_seconds)));
+        ranked_entries
+            .into_iter()
+            .take(limit)
+            .map(|entry| entry.directory_path.clone())
+            .collect()
+    }
+}
+
+/// Lists the most frecent directories and asks which one to jump to (bare "z")
+///
+/// # Arguments
+/// * `frecency_database` - The visited-directory database
+///
+/// # Returns
+/// * `Result<Option<PathBuf>>` - The chosen directory, or None if the list
+///   is empty, the user cancelled, or the number was not valid
+fn select_frecent_directory(frecency_database: &FrecencyDatabase) -> Result<Option<PathBuf>> {
+    let frecent_directories =
+        frecency_database.most_frecent(unix_seconds_now(), FRECENCY_LIST_LENGTH);
+
+    if frecent_directories.is_empty() {
+        println!("No visited directories yet. Press Enter to continue...");
+        let _ = io::stdin().read_line(&mut String::new());
+        return Ok(None);
+    }
+
+    println!("\n=== Frequent & Recent Directories ===  (z {{fragment}} jumps directly)");
+    for (i, directory_path) in frecent_directories.iter().enumerate() {
+        println!(
+            "{}",
+            fit_menu_line(format!(
+                "{}. {}",
+                i + 1,
+                escape_path_for_display(directory_path)
+            ))
+        );
+    }
+
+    print!("Select directory number (Enter to cancel): ");
+    io::stdout().flush().map_err(FileFantasticError::Io)?;
+
+    let mut input = String::new();
+    io::stdin()
+        .read_line(&mut input)
+        .map_err(FileFantasticError::Io)?;
+
+    // Anything but a listed number cancels
+    Ok(input
+        .trim()
+        .parse::<usize>()
+        .ok()
+        .filter(|number| *number >= 1 && *number <= frecent_directories.len())
+        .map(|number| frecent_directories[number - 1].clone()))
+}
+
+/// How well `fragment` (already lowercase) matches a directory path
+///
+/// # Returns
+/// * `Option<usize>` - The edit distance (0 for a substring of the path),
+///   or None if no component is within `MAX_SEARCH_DISTANCE`
+fn frecency_match_distance(fragment: &str, directory_path: &Path) -> Option<usize> {
+    if directory_path
+        .to_string_lossy()
+        .to_lowercase()
+        .contains(fragment)
+    {
+        return Some(0);
+    }
+
+    let fragment_length = fragment.chars().count();
+    directory_path
+        .components()
+        .filter_map(|component| match component {
+            std::path::Component::Normal(name) => Some(name.to_string_lossy().to_lowercase()),
+            _ => None,
+        })
+        .filter_map(|component_name| {
+            let truncated_name: String = component_name.chars().take(fragment_length).collect();
+            levenshtein_distance(&truncated_name, fragment).ok()
+        })
+        .min()
+        .filter(|distance| *distance <= MAX_SEARCH_DISTANCE)
+}
+
+/// Parses one `{count}\t{seconds}\t{path}` database line
+fn parse_frecency_line(line_bytes: &[u8]) -> Option<FrecencyEntry> {
+    let mut fields = line_bytes.splitn(3, |byte| *byte == b'\t');
+    let visit_count = std::str::from_utf8(fields.next()?).ok()?.parse().ok()?;
+    let last_visit_unix_seconds = std::str::from_utf8(fields.next()?).ok()?.parse().ok()?;
+    let path_bytes = fields.next()?;
+    if path_bytes.is_empty() {
+        return None;
+    }
+    Some(FrecencyEntry {
+        directory_path: frecency_path_from_bytes(path_bytes)?,
+        visit_count,
+        last_visit_unix_seconds,
+    })
+}
+
+/// A path as database bytes (raw on Unix)
+#[cfg(unix)]
+fn frecency_path_to_bytes(directory_path: &Path) -> Option<Vec<u8>> {
+    use std::os::unix::ffi::OsStrExt;
+    Some(directory_path.as_os_str().as_bytes().to_vec())
+}
+
+/// A path as database bytes (UTF-8 only off Unix)
+#[cfg(not(unix))]
+fn frecency_path_to_bytes(directory_path: &Path) -> Option<Vec<u8>> {
+    directory_path.to_str().map(|path| path.as_bytes().to_vec())
+}
+
+/// A path from database bytes (raw on Unix)
+#[cfg(unix)]
+fn frecency_path_from_bytes(path_bytes: &[u8]) -> Option<PathBuf> {
+    use std::os::unix::ffi::OsStrExt;
+    Some(PathBuf::from(OsStr::from_bytes(path_bytes)))
+}
+
+/// A path from database bytes (UTF-8 only off Unix)
+#[cfg(not(unix))]
+fn frecency_path_from_bytes(path_bytes: &[u8]) -> Option<PathBuf> {
+    std::str::from_utf8(path_bytes).ok().map(PathBuf::from)
+}
+
+#[cfg(test)]
+mod directory_history_tests {
+    use super::*;
+
+    /// A fresh directory tree under the temp directory
+    fn make_test_tree(test_name: &str, subdirectories: &[&str]) -> PathBuf {
+        let root = std::env::temp_dir().join(format!(
+            "ff_history_test_{}_{}",
+            test_name,
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&root);
+        for subdirectory in subdirectories {
+            fs::create_dir_all(root.join(subdirectory)).expect("Failed to create test directory");
+        }
+        root
+    }
+
+    #[test]
+    fn test_back_and_forward() {
+        let root = make_test_tree("back_forward", &["one", "two", "three"]);
+        let (one, two, three) = (root.join("one"), root.join("two"), root.join("three"));
+
+        let mut history = DirectoryHistory::new();
+        assert!(history.record_visit(&one));
+        assert!(history.record_visit(&two));
+        assert!(!history.record_visit(&two), "A redraw is not a visit");
+        assert!(history.record_visit(&three));
+
+        assert_eq!(history.go_back(), Some(two.clone()));
+        // The main loop records the new current directory: not a new visit
+        assert!(!history.record_visit(&two));
+        assert_eq!(history.go_back(), Some(one.clone()));
+        assert_eq!(history.go_back(), None);
+        assert_eq!(history.go_forward(), Some(two.clone()));
+        assert_eq!(history.go_forward(), Some(three.clone()));
+        assert_eq!(history.go_forward(), None);
+
+        // A new visit after going back clears the forward stack
+        history.go_back();
+        history.record_visit(&one);
+        assert_eq!(history.go_forward(), None);
+        assert_eq!(history.go_back(), Some(two.clone()));
+
+        // Directories removed since are skipped
+        fs::remove_dir(&one).expect("Failed to remove test directory");
+        history.record_visit(&three);
+        history.record_visit(&one);
+        history.record_visit(&two);
+        assert_eq!(history.go_back(), Some(three));
+
+        let _ = fs::remove_dir_all(&root);
+    }
+
+    #[test]
+    fn test_frecency_ranking_and_persistence() {
+        let root = make_test_tree(
+            "frecency",
+            &["projects/rust_app", "projects/ruby_app", "music"],
+        );
+        let rust_app = root.join("projects/rust_app");
+        let ruby_app = root.join("projects/ruby_app");
+        let music = root.join("music");
+        let now = 1_000_000_000;
+
+        // Recency weighting: one recent visit beats three old ones
+        let week_old = FrecencyEntry {
+            directory_path: music.clone(),
+            visit_count: 3,
+            last_visit_unix_seconds: now - 30 * 24 * 60 * 60,
+        };
+        let fresh = FrecencyEntry {
+            visit_count: 1,
+            last_visit_unix_seconds: now,
+            ..week_old.clone()
+        };
+        assert!(fresh.score(now) > week_old.score(now));
+
+        let database_path = root.join("ff_data").join(FRECENCY_DATABASE_FILENAME);
+        let mut database = FrecencyDatabase::load(database_path.clone());
+        assert!(
+            database.entries.is_empty(),
+            "Missing file is an empty database"
+        );
+        database.record_visit(&ruby_app, now);
+        database.record_visit(&rust_app, now);
+        database.record_visit(&rust_app, now);
+        database.record_visit(&music, now);
+        database.save().expect("Save should succeed");
+
+        // Reload: counts and paths survive the round trip
+        let database = FrecencyDatabase::load(database_path.clone());
+        assert_eq!(database.entries.len(), 3);
+        assert_eq!(
+            database.most_frecent(now, FRECENCY_LIST_LENGTH).first(),
+            Some(&rust_app)
+        );
+
+        // Substring: both apps match, the more visited first
+        assert_eq!(
+            database.ranked_matches("app", now)[..2],
+            [rust_app.clone(), ruby_app.clone()]
+        );
+        // Typo: Levenshtein distance 1 from "music"
+        assert_eq!(database.ranked_matches("MUSUC", now), vec![music.clone()]);
+        // A substring match outranks a more visited fuzzy match ("rust")
+        assert_eq!(
+            database.ranked_matches("ruby", now).first(),
+            Some(&ruby_app)
+        );
+        assert!(database.ranked_matches("zzzzzz", now).is_empty());
+
+        // Malformed lines are skipped
+        fs::write(&database_path, b"oops\n2\t5\n1\t5\t/tmp\n").expect("Failed to write");
+        assert_eq!(FrecencyDatabase::load(database_path).entries.len(), 1);
+
+        let _ = fs::remove_dir_all(&root);
+    }
+}
+
+/// Most tabs open at once (tab numbers stay single digits)
+const MAX_BROWSER_TABS: usize = 9;
+
+/// Widest directory name shown for one tab in the tab bar (terminal cells)
+const TAB_BAR_LABEL_WIDTH: usize = 14;
+
+/// One tab: a directory and its own navigation state
+///
+/// Each tab keeps its own sort, filter, page, search and back/forward
+/// history; switching tabs brings all of it back.
+struct BrowserTab {
+    /// Directory the tab is showing
+    directory_path: PathBuf,
+
+    /// The tab's sort, filter, page, search and history
+    nav_state: NavigationState,
+}
+
+/// The tabs of one ff session
+///
+/// # Design
+/// The main loop works on one `current_directory_path` and one `nav_state`.
+/// Those belong to the active tab ("checked out"); its slot here is None
+/// while the other tabs are parked in theirs. Switching swaps the working
+/// state with a parked tab, so the rest of the loop never knows about tabs.
+///
+/// # Example
+/// ```text
+/// slots:  [Some(src), None, Some(data)]    active_tab_index: 1
+/// working state (current_directory_path, nav_state): tab 2
+/// ```
+struct BrowserTabs {
+    /// One slot per tab, in tab-bar order; the active tab's slot is None
+    tab_slots: Vec<Option<BrowserTab>>,
+
+    /// Index (0-based) of the tab in the working state
+    active_tab_index: usize,
+}
+
+impl BrowserTabs {
+    /// A session with one tab (the working state)
+    fn new() -> Self {
+        BrowserTabs {
+            tab_slots: vec![None],
+            active_tab_index: 0,
+        }
+    }
+
+    /// Number of open tabs
+    fn tab_count(&self) -> usize {
+        self.tab_slots.len()
+    }
+
+    /// Makes tab `target_index` (0-based) the working state
+    ///
+    /// # Arguments
+    /// * `target_index` - Tab to show
+    /// * `current_directory_path` - Working directory; swapped with the tab's
+    /// * `nav_state` - Working navigation state; swapped with the tab's
+    ///
+    /// # Returns
+    /// * `bool` - false if there is no such tab or it is already active
+    fn switch_to(
+        &mut self,
+        target_index: usize,
+        current_directory_path: &mut PathBuf,
+        nav_state: &mut NavigationState,
+    ) -> bool {
+        if target_index == self.active_tab_index {
+            return false;
+        }
+        let target_tab = match self.tab_slots.get_mut(target_index).and_then(Option::take) {
+            Some(tab) => tab,
+            None => return false,
+        };
+
+        // Park the working state in the active slot, then check out the target
+        let left_tab = BrowserTab {
+            directory_path: std::mem::replace(current_directory_path, target_tab.directory_path),
+            nav_state: std::mem::replace(nav_state, target_tab.nav_state),
+        };
+        self.tab_slots[self.active_tab_index] = Some(left_tab);
+        self.active_tab_index = target_index;
+        true
+    }
+
+    /// Switches to the next tab (`forward`) or the previous one, wrapping around
+    ///
+    /// # Returns
+    /// * `bool` - false if there is only one tab
+    fn cycle(
+        &mut self,
+        forward: bool,
+        current_directory_path: &mut PathBuf,
+        nav_state: &mut NavigationState,
+    ) -> bool {
+        let tab_count = self.tab_count();
+        let target_index = if forward {
+            (self.active_tab_index + 1) % tab_count
+        } else {
+            (self.active_tab_index + tab_count - 1) % tab_count
+        };
+        self.switch_to(target_index, current_directory_path, nav_state)
+    }
+
+    /// Opens a new tab on the current directory, right of the active one, and shows it
+    ///
+    /// # Arguments
+    /// * `current_directory_path` - Directory for the new tab (then its working directory)
+    /// * `nav_state` - The active tab's state; the new tab copies its session settings
+    ///
+    /// # Returns
+    /// * `bool` - false if `MAX_BROWSER_TABS` are already open
+    fn open_tab(
+        &mut self,
+        current_directory_path: &mut PathBuf,
+        nav_state: &mut NavigationState,
+    ) -> bool {
+        if self.tab_count() >= MAX_BROWSER_TABS {
+            return false;
+        }
+        let mut new_tab_state = nav_state.new_tab_state();
+        // The new tab's history starts where it opened, so "back" works from its first move
+        new_tab_state
+            .directory_history
+            .record_visit(current_directory_path);
+
+        let new_tab_index = self.active_tab_index + 1;
+        self.tab_slots.insert(
+            new_tab_index,
+            Some(BrowserTab {
+                directory_path: current_directory_path.clone(),
+                nav_state: new_tab_state,
+            }),
+        );
+        self.switch_to(new_tab_index, current_directory_path, nav_state)
+    }
+
+    /// Closes the active tab and shows its right neighbour (or left, if last)
+    ///
+    /// # Returns
+    /// * `bool` - false if this is the only tab (ff needs one)
+    fn close_active_tab(
+        &mut self,
+        current_directory_path: &mut PathBuf,
+        nav_state: &mut NavigationState,
+    ) -> bool {
+        if self.tab_count() <= 1 {
+            return false;
+        }
+        let closing_index = self.active_tab_index;
+        let neighbour_index = if closing_index + 1 < self.tab_count() {
+            closing_index + 1
+        } else {
+            closing_index - 1
+        };
+
+        // Step 1: Show the neighbour (this parks the closing tab in its slot)
+        self.switch_to(neighbour_index, current_directory_path, nav_state);
+
+        // Step 2: Drop the closing tab; tabs after it move one place left
+        self.tab_slots.remove(closing_index);
+        if self.active_tab_index > closing_index {
+            self.active_tab_index -= 1;
+        }
+        true
+    }
+
+    /// Every tab's directory, in tab order
+    ///
+    /// # Arguments
+    /// * `current_directory_path` - The active tab's directory (working state)
+    fn tab_directories(&self, current_directory_path: &Path) -> Vec<PathBuf> {
+        self.tab_slots
+            .iter()
+            .map(|slot| match slot {
+                Some(tab) => tab.directory_path.clone(),
+                None => current_directory_path.to_path_buf(),
+            })
+            .collect()
+    }
+
+    /// The tab bar line for the listing header, e.g. `1:src [2:data] 3:/`
+    ///
+    /// # Arguments
+    /// * `current_directory_path` - The active tab's directory
+    ///
+    /// # Returns
+    /// * `String` - Empty with a single tab (the header looks as before);
+    ///   otherwise numbered directory names, the active one in brackets and
+    ///   reverse video, shortened to the window width like a menu line
+    fn tab_bar(&self, current_directory_path: &Path) -> String {
+        if self.tab_count() <= 1 {
+            return String::new();
+        }
+
+        // Step 1: Plain labels, remembering where the active one is
+        let mut tab_labels = Vec::new();
+        let mut active_label_range = 0..0;
+        for (tab_index, directory_path) in self
+            .tab_directories(current_directory_path)
+            .iter()
+            .enumerate()
+        {
+            let directory_name = match directory_path.file_name() {
+                Some(name) => escape_name_for_display(name),
+                None => escape_path_for_display(directory_path), // "/" has no name
+            };
+            let label = format!(
+                "{}:{}",
+                tab_index + 1,
+                truncate_filename_for_display(directory_name, TAB_BAR_LABEL_WIDTH)
+            );
+            if tab_index == self.active_tab_index {
+                let label_start = tab_labels
+                    .iter()
+                    .map(|label: &String| label.len() + 1)
+                    .sum();
+                let active_label = format!("[{}]", label);
+                active_label_range = label_start..label_start + active_label.len();
+                tab_labels.push(active_label);
+            } else {
+                tab_labels.push(label);
+            }
+        }
+        let plain_tab_bar = tab_labels.join(" ");
+
+        // Step 2: Fit to the window, then reverse-video the active label
+        // if the shortened line still has it in place
+        let fitted_tab_bar = fit_menu_line(plain_tab_bar.clone());
+        if fitted_tab_bar.get(..active_label_range.end)
+            != plain_tab_bar.get(..active_label_range.end)
+        {
+            return fitted_tab_bar;
+        }
+        format!(
+            "{}{}{}{}{}",
+            &fitted_tab_bar[..active_label_range.start],
+            REVERSE,
+            &fitted_tab_bar[active_label_range.clone()],
+            RESET,
+            &fitted_tab_bar[active_label_range.end..]
+        )
+    }
+}
+
+/// Get-Send option 9: copies an item from this directory into another tab's
+///
+/// # Purpose
+/// With tabs open on a source and a destination, copying between them
+/// needs no stack round trip and no pocket dimension: pick the item here,
+/// pick the tab, and it is copied there (files and whole directories).
+///
+/// # Arguments
+/// * `nav_state` - The active tab's state (its lookup table numbers the items)
+/// * `tab_directories` - Every tab's directory, in tab order
+/// * `active_tab_index` - Which of them is this tab (0-based)
+///
+/// # Returns
+/// * `Result<()>` - Ok after the copy, a cancel, or a reported copy failure;
+///   Err only for terminal I/O errors
+///
+/// # Safety
+/// Copy only, as everywhere in Get-Send, and name clashes are handled as in
+/// Get (2): an existing file is archived before it is replaced, and an
+/// existing directory stops the copy.
+fn send_item_to_tab_interactive(
+    nav_state: &NavigationState,
+    tab_directories: &[PathBuf],
+    active_tab_index: usize,
+) -> Result<()> {
+    if tab_directories.len() < 2 {
+        println!("Only one tab is open. Open another with 'tab', then 'tab 1' to come back.");
+        return Ok(());
+    }
+
+    // Step 1: Which item (numbers as in the listing)
+    print!("\nItem number to send (or 'b' to back/cancel): ");
+    io::stdout().flush().map_err(FileFantasticError::Io)?;
+    let mut input = String::new();
+    io::stdin()
+        .read_line(&mut input)
+        .map_err(FileFantasticError::Io)?;
+    let item_info = match input
+        .trim()
+        .parse::<usize>()
+        .ok()
+        .and_then(|number| nav_state.lookup_item(number))
+    {
+        Some(item_info) => item_info,
+        None => {
+            println!("Back/Cancelled.");
+            return Ok(());
+        }
+    };
+
+    // Step 2: Which tab
+    println!("\n=== Send To Tab ===");
+    for (tab_index, directory_path) in tab_directories.iter().enumerate() {
+        let this_tab_note = if tab_index == active_tab_index {
+            " (this tab)"
+        } else {
+            ""
+        };
+        println!(
+            "{}",
+            fit_menu_line(format!(
+                "{}. {}{}",
+                tab_index + 1,
+                escape_path_for_display(directory_path),
+                this_tab_note
+            ))
+        );
+    }
+    print!("Send to tab number (or 'b' to back/cancel): ");
+    io::stdout().flush().map_err(FileFantasticError::Io)?;
+    let mut input = String::new();
+    io::stdin()
+        .read_line(&mut input)
+        .map_err(FileFantasticError::Io)?;
+    let destination_directory = match input.trim().parse::<usize>() {
+        Ok(tab_number) if tab_number >= 1 && tab_number - 1 != active_tab_index => {
+            match tab_directories.get(tab_number - 1) {
+                Some(directory_path) => directory_path,
+                None => {
+                    println!("No tab {}.", tab_number);
+                    return Ok(());
+                }
+            }
+        }
+        _ => {
+            println!("Back/Cancelled.");
+            return Ok(());
+        }
+    };
+
+    // Step 3: Copy (files and directories use the stack copy routines)
+    let copy_result = match item_info.item_type {
+        FileSystemItemType::File => {
+            copy_file_from_stack(&item_info.item_path, destination_directory)
+        }
+        FileSystemItemType::Directory => {
+            copy_directory_from_stack(&item_info.item_path, destination_directory)
+        }
+    };
+    match copy_result {
+        Ok(final_destination_path) => {
+            println!("✓ Sent: {}", final_destination_path.display());
+        }
+        Err(e) => eprintln!("✗ Send failed: {}", e),
+    }
+    println!("\nPress Enter to continue...");
+    let _ = io::stdin().read_line(&mut String::new());
+    Ok(())
+}
+
+#[cfg(test)]
+mod browser_tab_tests {
+    use super::*;
+
+    #[test]
+    fn test_tabs_keep_their_own_state() {
+        let mut current_directory_path = PathBuf::from("/source");
+        let mut nav_state = NavigationState::new();
+        nav_state.current_filter = Some('f');
+        nav_state.tui_tall_adjustment = 3;
+        let mut tabs = BrowserTabs::new();
+        assert_eq!(tabs.tab_bar(&current_directory_path), "");
+
+        // A new tab: same directory and session settings, fresh filter
+        assert!(tabs.open_tab(&mut current_directory_path, &mut nav_state));
+        assert_eq!(tabs.tab_count(), 2);
+        assert_eq!(tabs.active_tab_index, 1);
+        assert_eq!(current_directory_path, PathBuf::from("/source"));
+        assert_eq!(nav_state.current_filter, None);
+        assert_eq!(nav_state.tui_tall_adjustment, 3);
+        current_directory_path = PathBuf::from("/data");
+        nav_state.current_filter = Some('d');
+
+        // Back to tab 1: its directory and filter return
+        assert!(tabs.switch_to(0, &mut current_directory_path, &mut nav_state));
+        assert_eq!(current_directory_path, PathBuf::from("/source"));
+        assert_eq!(nav_state.current_filter, Some('f'));
+        assert_eq!(
+            tabs.tab_directories(&current_directory_path),
+            vec![PathBuf::from("/source"), PathBuf::from("/data")]
+        );
+        assert!(
+            tabs.tab_bar(&current_directory_path).ends_with("2:data"),
+            "Inactive tabs are plain"
+        );
+        assert!(!tabs.switch_to(0, &mut current_directory_path, &mut nav_state));
+        assert!(!tabs.switch_to(5, &mut current_directory_path, &mut nav_state));
+
+        // Cycling wraps around
+        assert!(tabs.cycle(false, &mut current_directory_path, &mut nav_state));
+        assert_eq!(current_directory_path, PathBuf::from("/data"));
+        assert!(tabs.cycle(true, &mut current_directory_path, &mut nav_state));
+        assert_eq!(current_directory_path, PathBuf::from("/source"));
+    }
+
+    #[test]
+    fn test_open_and_close_tabs() {
+        let mut current_directory_path = PathBuf::from("/one");
+        let mut nav_state = NavigationState::new();
+        let mut tabs = BrowserTabs::new();
+        assert!(
+            !tabs.close_active_tab(&mut current_directory_path, &mut nav_state),
+            "The last tab cannot be closed"
+        );
+
+        for directory in ["/two", "/three"] {
+            tabs.open_tab(&mut current_directory_path, &mut nav_state);
+            current_directory_path = PathBuf::from(directory);
+        }
+        // Tabs: /one /two [/three]; close the middle one from tab 2
+        tabs.switch_to(1, &mut current_directory_path, &mut nav_state);
+        assert!(tabs.close_active_tab(&mut current_directory_path, &mut nav_state));
+        assert_eq!(current_directory_path, PathBuf::from("/three"));
+        assert_eq!(tabs.active_tab_index, 1);
+        assert_eq!(
+            tabs.tab_directories(&current_directory_path),
+            vec![PathBuf::from("/one"), PathBuf::from("/three")]
+        );
+
+        // Closing the last tab in the bar shows its left neighbour
+        assert!(tabs.close_active_tab(&mut current_directory_path, &mut nav_state));
+        assert_eq!(current_directory_path, PathBuf::from("/one"));
+        assert_eq!(tabs.tab_count(), 1);
+        assert_eq!(tabs.active_tab_index, 0);
+
+        // At most MAX_BROWSER_TABS
+        while tabs.open_tab(&mut current_directory_path, &mut nav_state) {}
+        assert_eq!(tabs.tab_count(), MAX_BROWSER_TABS);
+    }
+}
+
+/// Most entries read from each side of a directory compare
+const MAX_COMPARE_ENTRIES: usize = 50_000;
+
+/// Directory name sync archives into; the compare skips such a directory
+/// when it holds only archived (timestamped) items
+const COMPARE_SKIPPED_ARCHIVE_DIRECTORY_NAME: &str = "archive";
+
+/// Whether a name carries an archive timestamp (`_YYYY_MM_DD_HH_MM_SS`),
+/// as `generate_archive_filename` writes it
+fn has_archive_timestamp(name: &OsStr) -> bool {
+    // 'd' stands for any digit
+    const TIMESTAMP_PATTERN: &[u8] = b"_dddd_dd_dd_dd_dd_dd";
+    name.as_encoded_bytes()
+        .windows(TIMESTAMP_PATTERN.len())
+        .any(|window| {
+            window
+                .iter()
+                .zip(TIMESTAMP_PATTERN)
+                .all(|(&byte, &pattern_byte)| match pattern_byte {
+                    b'd' => byte.is_ascii_digit(),
+                    _ => byte == pattern_byte,
+                })
+        })
+}
+
+/// Whether a directory holds only items archived by a sync (or by Get-Send)
+///
+/// # Returns
+/// * `bool` - true if it is named "archive", is not empty and every entry
+///   has an archive timestamp; a user's own `archive/` folder is false
+fn is_sync_archive_directory(directory_path: &Path) -> bool {
+    if directory_path.file_name() != Some(OsStr::new(COMPARE_SKIPPED_ARCHIVE_DIRECTORY_NAME)) {
+        return false;
+    }
+    let Ok(directory_entries) = fs::read_dir(directory_path) else {
+        return false;
+    };
+    let mut has_entries = false;
+    for directory_entry in directory_entries.flatten() {
+        if !has_archive_timestamp(&directory_entry.file_name()) {
+            return false;
+        }
+        has_entries = true;
+    }
+    has_entries
+}
+
+/// Rows of the removal preview shown before a mirror asks for "yes"
+const MIRROR_PREVIEW_ROWS: usize = 15;
+
+/// One side's entry in a directory compare
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct CompareSideInfo {
+    /// Directory (true) or file/link (false)
+    is_directory: bool,
+
+    /// Symbolic link (listed and synced as a link, never followed)
+    is_symlink: bool,
+
+    /// Size in bytes (0 for directories)
+    size_in_bytes: u64,
+
+    /// Last modified time (UNIX_EPOCH if unreadable)
+    modified_time: SystemTime,
+}
+
+/// How the two sides of one relative path compare
+///
+/// Marks, as shown in the left column of the compare view:
+/// - ` ` Same: same type, size, content and modified time
+/// - `L` only on the left, `R` only on the right
+/// - `S` sizes differ
+/// - `H` same size, but the content hash differs
+/// - `T` same content, only the modified time differs
+/// - `!` a file on one side, a directory on the other
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CompareStatus {
+    Same,
+    LeftOnly,
+    RightOnly,
+    SizeDiffers,
+    ContentDiffers,
+    TimeDiffers,
+    TypeDiffers,
+}
+
+impl CompareStatus {
+    /// One-character mark for the compare view
+    fn mark(self) -> char {
+        match self {
+            CompareStatus::Same => ' ',
+            CompareStatus::LeftOnly => 'L',
+            CompareStatus::RightOnly => 'R',
+            CompareStatus::SizeDiffers => 'S',
+            CompareStatus::ContentDiffers => 'H',
+            CompareStatus::TimeDiffers => 'T',
+            CompareStatus::TypeDiffers => '!',
+        }
+    }
+}
+
+/// One relative path in a directory compare, with both sides
+#[derive(Debug, Clone)]
+struct CompareRow {
+    /// Path relative to both compare roots, e.g. "src/main.rs"
+    relative_path: PathBuf,
+
+    /// The left side's entry, if it exists there
+    left: Option<CompareSideInfo>,
+
+    /// The right side's entry, if it exists there
+    right: Option<CompareSideInfo>,
+
+    /// How the two sides compare
+    status: CompareStatus,
+}
+
+/// Reads every entry under `root` into a map keyed by relative path
+///
+/// # Arguments
+/// * `root` - Directory to read (one side of the compare)
+///
+/// # Returns
+/// * `Result<BTreeMap<PathBuf, CompareSideInfo>>` - Sorted by path, so a
+///   directory comes right before its contents
+///
+/// # Behavior
+/// - Symlinks are listed but not followed (no loops, no leaving the tree)
+/// - "archive" directories holding only timestamped items are skipped:
+///   sync moves replaced and removed items there, so they are backups, not
+///   content (a user's own `archive/` folder is compared as usual)
+/// - Unreadable subdirectories are skipped with a warning
+/// - More than `MAX_COMPARE_ENTRIES` entries is an error
+fn collect_compare_tree(
+    root: &Path,
+) -> Result<std::collections::BTreeMap<PathBuf, CompareSideInfo>> {
+    let mut tree_entries = std::collections::BTreeMap::new();
+    let mut pending_directories = vec![PathBuf::new()];
+
+    while let Some(relative_directory) = pending_directories.pop() {
+        let directory_entries = match fs::read_dir(root.join(&relative_directory)) {
+            Ok(directory_entries) => directory_entries,
+            // The root itself must be readable; below it, skip and go on
+            Err(e) if relative_directory.as_os_str().is_empty() => return Err(e.into()),
+            Err(e) => {
+                eprintln!(
+                    "Warning: Skipping unreadable directory {}: {}",
+                    escape_path_for_display(&root.join(&relative_directory)),
+                    e
+                );
+                continue;
+            }
+        };
+
+        for directory_entry in directory_entries.flatten() {
+            let entry_name = directory_entry.file_name();
+            let link_metadata = match directory_entry.metadata() {
+                Ok(link_metadata) => link_metadata,
+                Err(_) => continue,
+            };
+            let is_directory = link_metadata.is_dir();
+            if is_directory && is_sync_archive_directory(&directory_entry.path()) {
+                continue;
+            }
+
+            let relative_path = relative_directory.join(&entry_name);
+            if is_directory {
+                pending_directories.push(relative_path.clone());
+            }
+            tree_entries.insert(
+                relative_path,
+                CompareSideInfo {
+                    is_directory,
+                    is_symlink: link_metadata.file_type().is_symlink(),
+                    size_in_bytes: if is_directory { 0 } else { link_metadata.len() },
+                    modified_time: link_metadata.modified().unwrap_or(UNIX_EPOCH),
+                },
+            );
+            if tree_entries.len() > MAX_COMPARE_ENTRIES {
+                return Err(FileFantasticError::TooManyEntries {
+                    path: root.to_path_buf(),
+                    limit: MAX_COMPARE_ENTRIES,
+                });
+            }
+        }
+    }
+    Ok(tree_entries)
+}
+
+/// Hashes a file's content (streamed, so large files use little memory)
+///
+/// # Returns
+/// * `io::Result<u64>` - The hash; equal content gives equal hashes in one run
+fn hash_file_contents(file_path: &Path) -> io::Result<u64> {
+    use std::hash::Hasher;
+    use std::io::Read;
+
+    let mut file = fs::File::open(file_path)?;
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    let mut buffer = [0u8; 64 * 1024];
+    loop {
+        let bytes_read = file.read(&mut buffer)?;
+        if bytes_read == 0 {
+            return Ok(hasher.finish());
+        }
+        hasher.write(&buffer[..bytes_read]);
+    }
+}
+
+/// Compares two directory trees, aligned by relative path
+///
+/// # Arguments
+/// * `left_root` - Left directory
+/// * `right_root` - Right directory
+///
+/// # Returns
+/// * `Result<Vec<CompareRow>>` - One row per relative path found on either
+///   side, in path order (each directory right before its contents)
+///
+/// # Content Check
+/// Files of the same size are hashed; only those can differ silently.
+/// A file that cannot be read counts as `ContentDiffers`.
+fn compare_directory_trees(left_root: &Path, right_root: &Path) -> Result<Vec<CompareRow>> {
+    let mut left_entries = collect_compare_tree(left_root)?;
+    let right_entries = collect_compare_tree(right_root)?;
+
+    let mut compare_rows = Vec::with_capacity(left_entries.len().max(right_entries.len()));
+    let mut right_iterator = right_entries.into_iter().peekable();
+
+    // Merge the two sorted maps: left entries in order, right-only ones in between
+    let left_paths: Vec<PathBuf> = left_entries.keys().cloned().collect();
+    for left_path in left_paths {
+        while let Some((right_path, _)) = right_iterator.peek() {
+            if *right_path >= left_path {
+                break;
+            }
+            if let Some((right_path, right_info)) = right_iterator.next() {
+                compare_rows.push(CompareRow {
+                    relative_path: right_path,
+                    left: None,
+                    right: Some(right_info),
+                    status: CompareStatus::RightOnly,
+                });
+            }
+        }
+
+        let left_info = left_entries.remove(&left_path);
+        let right_info = match right_iterator.peek() {
+            Some((right_path, _)) if *right_path == left_path => {
+                right_iterator.next().map(|(_, right_info)| right_info)
+            }
+            _ => None,
+        };
+
+        let status = match (&left_info, &right_info) {
+            (Some(left), Some(right)) => compare_side_pair(
+                &left_root.join(&left_path),
+                &right_root.join(&left_path),
+                left,
+                right,
+            ),
+            _ => CompareStatus::LeftOnly,
+        };
+        compare_rows.push(CompareRow {
+            relative_path: left_path,
+            left: left_info,
+            right: right_info,
+            status,
+        });
+    }
+
+    // Whatever is left on the right comes after the last left path
+    for (right_path, right_info) in right_iterator {
+        compare_rows.push(CompareRow {
+            relative_path: right_path,
+            left: None,
+            right: Some(right_info),
+            status: CompareStatus::RightOnly,
+        });
+    }
+    Ok(compare_rows)
+}
+
+/// Compares one path that exists on both sides
+fn compare_side_pair(
+    left_path: &Path,
+    right_path: &Path,
+    left: &CompareSideInfo,
+    right: &CompareSideInfo,
+) -> CompareStatus {
+    if left.is_directory != right.is_directory {
+        return CompareStatus::TypeDiffers;
+    }
+    if left.is_directory {
+        return CompareStatus::Same;
+    }
+    // Links are compared by where they point, not by what they point at
+    if left.is_symlink || right.is_symlink {
+        return match (fs::read_link(left_path), fs::read_link(right_path)) {
+            (Ok(left_target), Ok(right_target)) if left_target == right_target => {
+                CompareStatus::Same
+            }
+            _ => CompareStatus::ContentDiffers,
+        };
+    }
+    if left.size_in_bytes != right.size_in_bytes {
+        return CompareStatus::SizeDiffers;
+    }
+    match (
+        hash_file_contents(left_path),
+        hash_file_contents(right_path),
+    ) {
+        (Ok(left_hash), Ok(right_hash)) if left_hash == right_hash => {
+            if left.modified_time == right.modified_time {
+                CompareStatus::Same
+            } else {
+                CompareStatus::TimeDiffers
+            }
+        }
+        _ => CompareStatus::ContentDiffers,
+    }
+}
+
+/// The steps of one sync, worked out before anything is changed
+#[derive(Debug, Default, PartialEq)]
+struct SyncPlan {
+    /// Relative paths to copy from the source side (directories recursively)
+    copy_paths: Vec<PathBuf>,
+
+    /// Relative paths on the target side to move into its archive directory
+    /// (mirror only: items the source does not have, or of the wrong type)
+    archive_paths: Vec<PathBuf>,
+
+    /// Relative paths left alone: a file/directory clash without mirror
+    type_conflict_paths: Vec<PathBuf>,
+}
+
+/// Works out a sync from compare rows
+///
+/// # Arguments
+/// * `compare_rows` - Rows from `compare_directory_trees` (all, or some)
+/// * `left_to_right` - true copies left → right, false right → left
+/// * `mirror` - Also remove target items the source lacks (moved to archive)
+///
+/// # Returns
+/// * `SyncPlan` - What to copy and archive; a directory that is copied or
+///   archived as a whole hides the rows inside it
+///
+/// # Rules (source = the side copied from)
+/// - Only on source, size or content differs: copy
+/// - Same content (even with another modified time): nothing
+/// - Only on target: archive if mirroring, else nothing
+/// - File on one side, directory on the other: mirror archives the target
+///   then copies; otherwise it is listed as a conflict and left alone
+fn plan_directory_sync(compare_rows: &[CompareRow], left_to_right: bool, mirror: bool) -> SyncPlan {
+    let mut sync_plan = SyncPlan::default();
+    // Directories handled as a whole: skip every row inside them
+    let mut handled_directory: Option<PathBuf> = None;
+
+    for compare_row in compare_rows {
+        if let Some(directory) = &handled_directory {
+            if compare_row.relative_path.starts_with(directory) {
+                continue;
+            }
+            handled_directory = None;
+        }
+
+        let (source, target) = if left_to_right {
+            (compare_row.left, compare_row.right)
+        } else {
+            (compare_row.right, compare_row.left)
+        };
+        let relative_path = compare_row.relative_path.clone();
+
+        match (source, target) {
+            (Some(source_info), None) => {
+                if source_info.is_directory {
+                    handled_directory = Some(relative_path.clone());
+                }
+                sync_plan.copy_paths.push(relative_path);
+            }
+            (None, Some(target_info)) if mirror => {
+                if target_info.is_directory {
+                    handled_directory = Some(relative_path.clone());
+                }
+                sync_plan.archive_paths.push(relative_path);
+            }
+            (Some(source_info), Some(target_info)) => match compare_row.status {
+                CompareStatus::SizeDiffers | CompareStatus::ContentDiffers => {
+                    sync_plan.copy_paths.push(relative_path);
+                }
+                CompareStatus::TypeDiffers if mirror => {
+                    // Whichever side is the directory, its contents go with it
+                    // (archived from the target, or copied from the source)
+                    if source_info.is_directory || target_info.is_directory {
+                        handled_directory = Some(relative_path.clone());
+                    }
+                    sync_plan.archive_paths.push(relative_path.clone());
+                    sync_plan.copy_paths.push(relative_path);
+                }
+                CompareStatus::TypeDiffers => sync_plan.type_conflict_paths.push(relative_path),
+                _ => {}
+            },
+            _ => {}
+        }
+    }
+    sync_plan
+}
+
+/// Carries out a sync plan
+///
+/// # Arguments
+/// * `sync_plan` - From `plan_directory_sync`
+/// * `source_root` - Side copied from
+/// * `target_root` - Side copied to
+///
+/// # Returns
+/// * `(usize, usize, usize)` - Items copied, archived, and failed
+///
+/// # Safety
+/// Nothing is deleted. Archived items are moved into an `archive`
+/// directory beside them with a timestamped name, and a file that is
+/// replaced goes through `copy_file_with_archive_handling`, which archives
+/// the old version first. Failures are reported and the rest goes on.
+fn apply_sync_plan(
+    sync_plan: &SyncPlan,
+    source_root: &Path,
+    target_root: &Path,
+) -> (usize, usize, usize) {
+    let (mut copied_count, mut archived_count, mut failed_count) = (0, 0, 0);
+    let timestamp = generate_archive_timestamp();
+
+    // Step 1: Archive first, so a file/directory clash frees the name
+    for relative_path in &sync_plan.archive_paths {
+        match archive_sync_target(&target_root.join(relative_path), &timestamp) {
+            Ok(archive_path) => {
+                println!("Archived: {}", escape_path_for_display(&archive_path));
+                archived_count += 1;
+            }
+            Err(e) => {
+                eprintln!(
+                    "✗ Could not archive {}: {}",
+                    escape_path_for_display(relative_path),
+                    e
+                );
+                failed_count += 1;
+            }
+        }
+    }
+
+    // Step 2: Copy (directories recursively, files with archive-before-overwrite)
+    for relative_path in &sync_plan.copy_paths {
+        let source_path = source_root.join(relative_path);
+        let target_path = target_root.join(relative_path);
+        match copy_sync_item(&source_path, &target_path, &timestamp) {
+            Ok(failed_inside_count) => {
+                copied_count += 1;
+                failed_count += failed_inside_count;
+            }
+            Err(e) => {
+                eprintln!(
+                    "✗ Could not copy {}: {}",
+                    escape_path_for_display(relative_path),
+                    e
+                );
+                failed_count += 1;
+            }
+        }
+    }
+    (copied_count, archived_count, failed_count)
+}
+
+/// Copies one item for a sync, never following symbolic links
+///
+/// # Purpose
+/// The compare lists links without following them, so sync copies them as
+/// links too: following one could copy a whole tree from outside both
+/// roots, and the copy (a file) would never match the link on the next
+/// compare.
+///
+/// # Arguments
+/// * `source_path` - File, directory or link to copy
+/// * `target_path` - Where it goes (same relative path on the other side)
+/// * `timestamp` - Archive timestamp for a target link that is replaced
+///
+/// # Returns
+/// * `Result<usize>` - Items inside a directory that could not be copied
+///   (each reported as a warning); Err if the item itself failed
+///
+/// # Behavior
+/// - Link: recreated as a link with the same target (Unix only; elsewhere
+///   an error), after archiving whatever is at the target path
+/// - Directory: created, then its entries copied the same way
+/// - File: `copy_file_with_archive_handling`, keeping the modified time;
+///   a link at the target path is archived first, not written through
+fn copy_sync_item(source_path: &Path, target_path: &Path, timestamp: &str) -> Result<usize> {
+    let source_metadata = fs::symlink_metadata(source_path)?;
+    let target_is_symlink = fs::symlink_metadata(target_path)
+        .map(|target_metadata| target_metadata.file_type().is_symlink())
+        .unwrap_or(false);
+
+    // Step 1: Links are recreated, pointing where the source link points
+    if source_metadata.file_type().is_symlink() {
+        #[cfg(unix)]
+        {
+            let link_target = fs::read_link(source_path)?;
+            if fs::symlink_metadata(target_path).is_ok() {
+                archive_sync_target(target_path, timestamp)?;
+            } else if let Some(target_directory) = target_path.parent() {
+                fs::create_dir_all(target_directory)?;
+            }
+            std::os::unix::fs::symlink(link_target, target_path)?;
+            return Ok(0);
+        }
+        #[cfg(not(unix))]
+        {
+            return Err(FileFantasticError::UnsupportedPlatform);
+        }
+    }
+
+    // Step 2: Directories: every entry, warning about (not stopping at) failures
+    if source_metadata.is_dir() {
+        if target_is_symlink {
+            archive_sync_target(target_path, timestamp)?;
+        }
+        fs::create_dir_all(target_path)?;
+        let mut failed_inside_count = 0;
+        for directory_entry in fs::read_dir(source_path)? {
+            let copy_result = directory_entry
+                .map_err(FileFantasticError::Io)
+                .and_then(|entry| {
+                    copy_sync_item(
+                        &entry.path(),
+                        &target_path.join(entry.file_name()),
+                        timestamp,
+                    )
+                });
+            match copy_result {
+                Ok(failed_count) => failed_inside_count += failed_count,
+                Err(e) => {
+                    eprintln!(
+                        "Warning: Could not copy an item of {}: {}",
+                        escape_path_for_display(source_path),
+                        e
+                    );
+                    failed_inside_count += 1;
+                }
+            }
+        }
+        return Ok(failed_inside_count);
+    }
+
+    // Step 3: Files (an existing file at the target is archived by the copy)
+    if target_is_symlink {
+        archive_sync_target(target_path, timestamp)?;
+    }
+    let target_directory = target_path
+        .parent()
+        .ok_or_else(|| FileFantasticError::NotFound(target_path.to_path_buf()))?
+        .to_path_buf();
+    fs::create_dir_all(&target_directory)?;
+    let copied_path =
+        copy_file_with_archive_handling(&source_path.to_path_buf(), &target_directory)?;
+    keep_source_modified_time(source_path, &copied_path);
+    Ok(0)
+}
+
+/// Gives a synced copy its source's modified time
+///
+/// # Purpose
+/// Without this every synced file would show as `T` (time differs) on the
+/// next compare. Best effort: a failure only leaves the copy's new time.
+fn keep_source_modified_time(source_path: &Path, copied_path: &Path) {
+    if let Ok(source_modified_time) = fs::metadata(source_path).and_then(|m| m.modified())
+        && let Ok(copied_file) = fs::File::options().write(true).open(copied_path)
+    {
+        let _ = copied_file.set_modified(source_modified_time);
+    }
+}
+
+/// Moves a target item into the `archive` directory beside it (timestamped)
+///
+/// # Returns
+/// * `Result<PathBuf>` - Where the item now is
+fn archive_sync_target(target_path: &Path, timestamp: &str) -> Result<PathBuf> {
+    let parent_directory = target_path
+        .parent()
+        .ok_or_else(|| FileFantasticError::NotFound(target_path.to_path_buf()))?
+        .to_path_buf();
+    let item_name = target_path
+        .file_name()
+        .ok_or_else(|| FileFantasticError::NotFound(target_path.to_path_buf()))?;
+    let archive_directory = ensure_archive_directory_exists(&parent_directory)?;
+    let archive_path = archive_directory.join(generate_archive_filename(item_name, timestamp));
+    if archive_path.exists() {
+        return Err(FileFantasticError::AlreadyExists(archive_path));
+    }
+    fs::rename(target_path, &archive_path)?;
+    Ok(archive_path)
+}
+
+/// Formats one side of a compare row: indented name and size
+fn format_compare_side(
+    compare_row: &CompareRow,
+    side_info: Option<CompareSideInfo>,
+    side_width: usize,
+) -> String {
+    let side_info = match side_info {
+        Some(side_info) => side_info,
+        None => return " ".repeat(side_width),
+    };
+    let depth = compare_row
+        .relative_path
+        .components()
+        .count()
+        .saturating_sub(1);
+    let mut name = format!(
+        "{}{}",
+        "  ".repeat(depth),
+        escape_name_for_display(compare_row.relative_path.file_name().unwrap_or_default())
+    );
+    if side_info.is_directory {
+        name.push('/');
+    } else if side_info.is_symlink {
+        name.push_str(SYMLINK_NAME_MARKER);
+    }
+    let size_display = if side_info.is_directory {
+        String::from("-")
+    } else {
+        format_file_size(side_info.size_in_bytes)
+    };
+    // Name, then the size right-aligned in the last 7 columns
+    let name_width = side_width.saturating_sub(8);
+    format!(
+        "{} {:>7}",
+        pad_to_display_width(&truncate_filename_for_display(name, name_width), name_width),
+        size_display
+    )
+}
+
+/// Resolves both compare roots and checks that they are apart
+///
+/// # Purpose
+/// Syncing a directory into itself or into one of its own subdirectories
+/// would copy its output again and again (`cmp backup` from the backup's
+/// parent: `backup/` is "left only" and would be copied into itself).
+///
+/// # Arguments
+/// * `left_root` - Left directory
+/// * `right_root` - Right directory
+///
+/// # Returns
+/// * `Result<(PathBuf, PathBuf)>` - Both roots canonicalized; Err if one
+///   cannot be resolved, or they are the same directory or one is inside
+///   the other
+fn canonical_compare_roots(left_root: &Path, right_root: &Path) -> Result<(PathBuf, PathBuf)> {
+    let left_root = fs::canonicalize(left_root)?;
+    let right_root = fs::canonicalize(right_root)?;
+    if left_root.starts_with(&right_root) || right_root.starts_with(&left_root) {
+        return Err(FileFantasticError::Io(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!(
+                "cannot compare {} with {}: one is the other or inside it",
+                escape_path_for_display(&left_root),
+                escape_path_for_display(&right_root)
+            ),
+        )));
+    }
+    Ok((left_root, right_root))
+}
+
+/// Two-pane compare and sync mode ("cmp")
+///
+/// # Purpose
+/// Lists two directory trees side by side, aligned by relative path, marks
+/// what differs (see `CompareStatus`), and syncs one side to the other.
+///
+/// # Commands
+/// - `lr` / `rl` - Copy differences left → right / right → left
+/// - `lr N` / `rl N` - Copy just row N
+/// - `mirror lr` / `mirror rl` - Copy, and move target-only items to archive
+///   (previewed, then confirmed by typing "yes")
+/// - `d` - Toggle showing only differences
+/// - Page keys as in the listing (j/k, w/x, arrows...); Enter or `q` returns
+///
+/// # Arguments
+/// * `left_root` - Left directory (usually the current one)
+/// * `right_root` - Right directory
+/// * `nav_state` - For the page height (tall+N) and window size
+///
+/// # Returns
+/// * `Result<()>` - Ok when the user leaves; Err for unreadable or nested
+///   roots, or I/O
+fn run_directory_compare_mode(
+    left_root: &Path,
+    right_root: &Path,
+    nav_state: &NavigationState,
+) -> Result<()> {
+    let (left_root, right_root) = canonical_compare_roots(left_root, right_root)?;
+    let (left_root, right_root) = (left_root.as_path(), right_root.as_path());
+    let mut differences_only = false;
+    let mut page_index = 0;
+    // Hashing both trees is slow: compare once, and again only after a sync
+    let mut all_rows = compare_directory_trees(left_root, right_root)?;
+
+    loop {
+        // Step 1: Rows for this view
+        let shown_rows: Vec<&CompareRow> = all_rows
+            .iter()
+            .filter(|compare_row| !differences_only || compare_row.status != CompareStatus::Same)
+            .collect();
+
+        // Step 2: Layout: "NNNN M left │ right"
+        let window_columns = query_terminal_window_size()
+            .map(|window_size| window_size.cols as usize)
+            .unwrap_or(MAX_TUI_CHAR_LENGTH_DEFAULT);
+        let side_width = (window_columns.saturating_sub(12) / 2).max(20);
+        let rows_per_page = (calculate_items_per_page_from_state(nav_state) as usize).max(1);
+        let page_count = shown_rows.len().div_ceil(rows_per_page).max(1);
+        page_index = page_index.min(page_count - 1);
+        let difference_count = all_rows
+            .iter()
+            .filter(|compare_row| compare_row.status != CompareStatus::Same)
+            .count();
+
+        clear_terminal_screen()?;
+        println!(
+            "{}",
+            fit_menu_line(format!(
+                "Compare  L: {}   R: {}",
+                escape_path_for_display(left_root),
+                escape_path_for_display(right_root)
+            ))
+        );
+        println!(
+            "{}L{}/{}R{} only one side  {}S{}ize  {}H{}ash/content  {}T{}ime only  {}!{} file vs dir   {} of {} differ",
+            RED,
+            RESET,
+            RED,
+            RESET,
+            RED,
+            RESET,
+            RED,
+            RESET,
+            RED,
+            RESET,
+            RED,
+            RESET,
+            difference_count,
+            all_rows.len()
+        );
+        for (row_offset, compare_row) in shown_rows
+            .iter()
+            .skip(page_index * rows_per_page)
+            .take(rows_per_page)
+            .enumerate()
+        {
+            println!(
+                "{:>4} {}{}{} {} │ {}",
+                page_index * rows_per_page + row_offset + 1,
+                YELLOW,
+                compare_row.status.mark(),
+                RESET,
+                format_compare_side(compare_row, compare_row.left, side_width),
+                format_compare_side(compare_row, compare_row.right, side_width)
+            );
+        }
+        println!(
+            "{}{}--- Page {} of {} --- lr / rl [N]: copy   mirror lr|rl   d: diffs only   Enter: back ---{}",
+            BOLD,
+            YELLOW,
+            page_index + 1,
+            page_count,
+            RESET
+        );
+        print!("cmp> ");
+        io::stdout().flush().map_err(FileFantasticError::Io)?;
+
+        let mut input = String::new();
+        io::stdin()
+            .read_line(&mut input)
+            .map_err(FileFantasticError::Io)?;
+        let command = input.trim().to_lowercase();
+
+        // Step 3: Commands
+        if command.is_empty() || command == "q" || command == "b" {
+            return Ok(());
+        }
+        if is_pagination_up_command(&command) {
+            page_index = page_index.saturating_sub(1);
+            continue;
+        }
+        if is_pagination_down_command(&command) {
+            page_index = (page_index + 1).min(page_count - 1);
+            continue;
+        }
+        if command == "d" {
+            differences_only = !differences_only;
+            page_index = 0;
+            continue;
+        }
+
+        // Sync: "[mirror] lr|rl [N]"
+        let mut command_words: Vec<&str> = command.split_whitespace().collect();
+        let mirror = command_words.first() == Some(&"mirror");
+        if mirror {
+            command_words.remove(0);
+        }
+        let left_to_right = match command_words.first() {
+            Some(&"lr") => true,
+            Some(&"rl") => false,
+            _ => {
+                println!("Unknown command '{}'. Press Enter to continue...", command);
+                let _ = io::stdin().read_line(&mut String::new());
+                continue;
+            }
+        };
+        let selected_rows: Vec<CompareRow> = match command_words.get(1) {
+            None => all_rows.clone(),
+            Some(row_number) => match row_number
+                .parse::<usize>()
+                .ok()
+                .and_then(|number| number.checked_sub(1))
+                .and_then(|index| shown_rows.get(index))
+            {
+                Some(compare_row) => vec![(*compare_row).clone()],
+                None => {
+                    println!("No row {}. Press Enter to continue...", row_number);
+                    let _ = io::stdin().read_line(&mut String::new());
+                    continue;
+                }
+            },
+        };
+
+        let sync_plan = plan_directory_sync(&selected_rows, left_to_right, mirror);
+        let (source_root, target_root) = if left_to_right {
+            (left_root, right_root)
+        } else {
+            (right_root, left_root)
+        };
+        if confirm_sync_plan(&sync_plan, target_root)? {
+            let (copied_count, archived_count, failed_count) =
+                apply_sync_plan(&sync_plan, source_root, target_root);
+            // Recompare, so the view shows the disk after the sync
+            all_rows = compare_directory_trees(left_root, right_root)?;
+            println!(
+                "Sync done: {} copied, {} moved to archive, {} failed. Press Enter to continue...",
+                copied_count, archived_count, failed_count
+            );
+            let _ = io::stdin().read_line(&mut String::new());
+        }
+    }
+}
+
+/// Shows what a sync will do and asks to go ahead
+///
+/// # Returns
+/// * `Result<bool>` - true to proceed; a plan that archives (mirror
+///   "deletions") needs "yes" typed out, a copy-only plan "y"
+fn confirm_sync_plan(sync_plan: &SyncPlan, target_root: &Path) -> Result<bool> {
+    for conflict_path in &sync_plan.type_conflict_paths {
+        println!(
+            "Skipped (file on one side, directory on the other; use mirror): {}",
+            escape_path_for_display(conflict_path)
+        );
+    }
+    if sync_plan.copy_paths.is_empty() && sync_plan.archive_paths.is_empty() {
+        println!("Nothing to sync. Press Enter to continue...");
+        let _ = io::stdin().read_line(&mut String::new());
+        return Ok(false);
+    }
+
+    // Removals are previewed in full (up to a screenful) before anything happens
+    if !sync_plan.archive_paths.is_empty() {
+        println!(
+            "\nMirror will REMOVE {} item(s) from {} (moved to archive/, not deleted):",
+            sync_plan.archive_paths.len(),
+            escape_path_for_display(target_root)
+        );
+        for archive_path in sync_plan.archive_paths.iter().take(MIRROR_PREVIEW_ROWS) {
+            println!(
+                "{}",
+                fit_menu_line(format!("  - {}", escape_path_for_display(archive_path)))
+            );
+        }
+        if sync_plan.archive_paths.len() > MIRROR_PREVIEW_ROWS {
+            println!(
+                "  ... and {} more",
+                sync_plan.archive_paths.len() - MIRROR_PREVIEW_ROWS
+            );
+        }
+    }
+    println!(
+        "{} item(s) will be copied to {} (replaced files are archived first).",
+        sync_plan.copy_paths.len(),
+        escape_path_for_display(target_root)
+    );
+
+    let needs_full_yes = !sync_plan.archive_paths.is_empty();
+    if needs_full_yes {
+        print!("Type 'yes' to mirror, anything else cancels: ");
+    } else {
+        print!("Proceed? (y/N): ");
+    }
+    io::stdout().flush().map_err(FileFantasticError::Io)?;
+    let mut input = String::new();
+    io::stdin()
+        .read_line(&mut input)
+        .map_err(FileFantasticError::Io)?;
+    let answer = input.trim().to_lowercase();
+    Ok(if needs_full_yes {
+        answer == "yes"
+    } else {
+        answer == "y" || answer == "yes"
+    })
+}
+
+#[cfg(test)]
+mod directory_compare_tests {
+    use super::*;
+
+    /// Writes `content` to `root/relative_path`, creating parent directories
+    fn write_test_file(root: &Path, relative_path: &str, content: &str) {
+        let file_path = root.join(relative_path);
+        if let Some(parent) = file_path.parent() {
+            fs::create_dir_all(parent).expect("Failed to create test directory");
+        }
+        fs::write(&file_path, content).expect("Failed to write test file");
+    }
+
+    /// Left and right trees covering every compare status
+    fn make_compare_trees(test_name: &str) -> (PathBuf, PathBuf, PathBuf) {
+        let test_root = std::env::temp_dir().join(format!(
+            "ff_compare_test_{}_{}",
+            test_name,
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&test_root);
+        let (left, right) = (test_root.join("left"), test_root.join("right"));
+        for root in [&left, &right] {
+            write_test_file(root, "same.txt", "same");
+            write_test_file(root, "sub/nested_same.txt", "nested");
+        }
+        write_test_file(&left, "left_only.txt", "only left");
+        write_test_file(&left, "left_dir/inside.txt", "inside");
+        write_test_file(&right, "right_only.txt", "only right");
+        write_test_file(&left, "size.txt", "short");
+        write_test_file(&right, "size.txt", "much longer");
+        write_test_file(&left, "content.txt", "aaaa");
+        write_test_file(&right, "content.txt", "bbbb");
+        write_test_file(&left, "clash", "a file");
+        write_test_file(&right, "clash/inside_clash.txt", "in the directory");
+        write_test_file(&left, "time.txt", "same bytes");
+        write_test_file(&right, "time.txt", "same bytes");
+        // Sync's own backups are not compared, a user's archive/ folder is
+        write_test_file(&right, "archive/old_2024_01_02_03_04_05.txt", "backup");
+        write_test_file(&left, "docs/archive/report.txt", "kept by the user");
+
+        // Same content everywhere else: give those files equal times
+        let fixed_time = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        for relative_path in ["same.txt", "sub/nested_same.txt", "time.txt"] {
+            for root in [&left, &right] {
+                let file = fs::File::options()
+                    .write(true)
+                    .open(root.join(relative_path))
+                    .expect("Failed to open test file");
+                file.set_modified(fixed_time).expect("Failed to set time");
+            }
+        }
+        let file = fs::File::options()
+            .write(true)
+            .open(right.join("time.txt"))
+            .expect("Failed to open test file");
+        file.set_modified(fixed_time + Duration::from_secs(60))
+            .expect("Failed to set time");
+        (test_root, left, right)
+    }
+
+    fn status_of(compare_rows: &[CompareRow], relative_path: &str) -> Option<CompareStatus> {
+        compare_rows
+            .iter()
+            .find(|compare_row| compare_row.relative_path == Path::new(relative_path))
+            .map(|compare_row| compare_row.status)
+    }
+
+    #[test]
+    fn test_compare_marks_every_kind_of_difference() {
+        let (test_root, left, right) = make_compare_trees("marks");
+        let compare_rows = compare_directory_trees(&left, &right).expect("Compare should work");
+
+        assert_eq!(
+            status_of(&compare_rows, "same.txt"),
+            Some(CompareStatus::Same)
+        );
+        assert_eq!(status_of(&compare_rows, "sub"), Some(CompareStatus::Same));
+        assert_eq!(
+            status_of(&compare_rows, "sub/nested_same.txt"),
+            Some(CompareStatus::Same)
+        );
+        assert_eq!(
+            status_of(&compare_rows, "left_only.txt"),
+            Some(CompareStatus::LeftOnly)
+        );
+        assert_eq!(
+            status_of(&compare_rows, "left_dir/inside.txt"),
+            Some(CompareStatus::LeftOnly)
+        );
+        assert_eq!(
+            status_of(&compare_rows, "right_only.txt"),
+            Some(CompareStatus::RightOnly)
+        );
+        assert_eq!(
+            status_of(&compare_rows, "size.txt"),
+            Some(CompareStatus::SizeDiffers)
+        );
+        assert_eq!(
+            status_of(&compare_rows, "content.txt"),
+            Some(CompareStatus::ContentDiffers)
+        );
+        assert_eq!(
+            status_of(&compare_rows, "time.txt"),
+            Some(CompareStatus::TimeDiffers)
+        );
+        assert_eq!(
+            status_of(&compare_rows, "clash"),
+            Some(CompareStatus::TypeDiffers)
+        );
+        assert_eq!(status_of(&compare_rows, "archive"), None);
+        assert_eq!(
+            status_of(&compare_rows, "archive/old_2024_01_02_03_04_05.txt"),
+            None
+        );
+        assert_eq!(
+            status_of(&compare_rows, "docs/archive/report.txt"),
+            Some(CompareStatus::LeftOnly)
+        );
+
+        // Aligned by path: a directory comes right before its contents
+        let left_dir_index = compare_rows
+            .iter()
+            .position(|compare_row| compare_row.relative_path == Path::new("left_dir"))
+            .expect("left_dir row");
+        assert_eq!(
+            compare_rows[left_dir_index + 1].relative_path,
+            Path::new("left_dir/inside.txt")
+        );
+
+        let _ = fs::remove_dir_all(&test_root);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_sync_copies_links_as_links() {
+        let (test_root, left, right) = make_compare_trees("links");
+        // Links on the left point outside both trees, to a file and a directory
+        let outside = test_root.join("outside");
+        write_test_file(&outside, "big/inner.txt", "not part of either tree");
+        std::os::unix::fs::symlink(outside.join("big"), left.join("dir_link"))
+            .expect("Failed to create link");
+        std::os::unix::fs::symlink(
+            outside.join("big/inner.txt"),
+            left.join("left_dir/file_link"),
+        )
+        .expect("Failed to create link");
+
+        let compare_rows = compare_directory_trees(&left, &right).expect("Compare should work");
+        assert_eq!(status_of(&compare_rows, "dir_link/inner.txt"), None);
+        let copy_plan = plan_directory_sync(&compare_rows, true, false);
+        let (_, _, failed_count) = apply_sync_plan(&copy_plan, &left, &right);
+        assert_eq!(failed_count, 0);
+
+        // Recreated as links with the same targets, nothing copied from outside
+        for link_name in ["dir_link", "left_dir/file_link"] {
+            let link_path = right.join(link_name);
+            let link_metadata = fs::symlink_metadata(&link_path).unwrap();
+            assert!(link_metadata.file_type().is_symlink());
+            assert_eq!(
+                fs::read_link(&link_path).unwrap(),
+                fs::read_link(left.join(link_name)).unwrap()
+            );
+        }
+        // ...and the next compare sees them as the same
+        let after_rows = compare_directory_trees(&left, &right).expect("Compare should work");
+        for link_name in ["dir_link", "left_dir/file_link"] {
+            assert_eq!(status_of(&after_rows, link_name), Some(CompareStatus::Same));
+        }
+
+        let _ = fs::remove_dir_all(&test_root);
+    }
+
+    #[test]
+    fn test_compare_roots_must_be_apart() {
+        let (test_root, left, right) = make_compare_trees("roots");
+
+        let (canonical_left, canonical_right) =
+            canonical_compare_roots(&left, &right).expect("Siblings can be compared");
+        assert_eq!(canonical_left, fs::canonicalize(&left).unwrap());
+        assert_eq!(canonical_right, fs::canonicalize(&right).unwrap());
+
+        // The same directory (also by another spelling), or one inside the other
+        assert!(canonical_compare_roots(&left, &left.join("sub/..")).is_err());
+        assert!(canonical_compare_roots(&left, &left.join("sub")).is_err());
+        assert!(canonical_compare_roots(&left.join("sub"), &left).is_err());
+        assert!(canonical_compare_roots(&left, &test_root.join("missing")).is_err());
+
+        let _ = fs::remove_dir_all(&test_root);
+    }
+
+    #[test]
+    fn test_sync_plans_and_mirror() {
+        let (test_root, left, right) = make_compare_trees("sync");
+        let compare_rows = compare_directory_trees(&left, &right).expect("Compare should work");
+
+        // Copy left → right: the whole left_dir once, no archiving, clash skipped
+        let copy_plan = plan_directory_sync(&compare_rows, true, false);
+        assert!(copy_plan.copy_paths.contains(&PathBuf::from("left_dir")));
+        assert!(
+            !copy_plan
+                .copy_paths
+                .contains(&PathBuf::from("left_dir/inside.txt"))
+        );
+        assert!(copy_plan.copy_paths.contains(&PathBuf::from("content.txt")));
+        assert!(!copy_plan.copy_paths.contains(&PathBuf::from("time.txt")));
+        assert!(copy_plan.archive_paths.is_empty());
+        assert_eq!(copy_plan.type_conflict_paths, vec![PathBuf::from("clash")]);
+
+        // Mirror left → right: right-only items and the clash are archived;
+        // the clash directory goes whole, not also row by row
+        let mirror_plan = plan_directory_sync(&compare_rows, true, true);
+        assert_eq!(
+            mirror_plan.archive_paths,
+            vec![PathBuf::from("clash"), PathBuf::from("right_only.txt")]
+        );
+        let (_, archived_count, failed_count) = apply_sync_plan(&mirror_plan, &left, &right);
+        assert_eq!((archived_count, failed_count), (2, 0));
+
+        // Afterwards only the untouched time.txt differs, and nothing was lost
+        let after_rows = compare_directory_trees(&left, &right).expect("Compare should work");
+        assert!(after_rows.iter().all(|compare_row| {
+            compare_row.status == CompareStatus::Same
+                || (compare_row.relative_path == Path::new("time.txt")
+                    && compare_row.status == CompareStatus::TimeDiffers)
+        }));
+        assert_eq!(
+            fs::read_to_string(right.join("content.txt")).expect("Synced file"),
+            "aaaa"
+        );
+        let archived_names: Vec<String> = fs::read_dir(right.join("archive"))
+            .expect("Archive directory")
+            .flatten()
+            .map(|entry| entry.file_name().to_string_lossy().into_owned())
+            .collect();
+        for original_name in ["right_only", "clash", "content", "size"] {
+            assert!(
+                archived_names
+                    .iter()
+                    .any(|name| name.starts_with(original_name)),
+                "{} should be in archive/: {:?}",
+                original_name,
+                archived_names
+            );
+        }
+
+        let _ = fs::remove_dir_all(&test_root);
+    }
+}
+
+/// Deepest level the tree listing shows (the current directory's items are level 1)
+const TREE_VIEW_MAX_DEPTH: usize = 6;
+
+/// Most rows in the tree listing (stops a huge expansion from filling memory)
+const TREE_VIEW_MAX_ROWS: usize = 10_000;
+
+/// Builds the tree listing: each expanded directory followed by its contents
+///
+/// # Arguments
+/// * `top_level_entries` - The current directory's entries, already sorted
+///   and filtered
+/// * `nav_state` - Expanded directories, sort method and filter
+///
+/// # Returns
+/// * `(Vec<FileSystemEntry>, Vec<String>)` - Rows in display order, and
+///   each row's guide lines ("" for top level, "│  ├─ " two levels down)
+///
+/// # Rules
+/// - Every level uses the active sort and filter, like the flat listing
+/// - A directory is opened only if it is in `tree_expanded_directories` and
+///   above `TREE_VIEW_MAX_DEPTH` (also what stops symlink loops)
+/// - Unreadable directories show no contents
+/// - At most `TREE_VIEW_MAX_ROWS` rows
+fn build_tree_view_rows(
+    top_level_entries: Vec<FileSystemEntry>,
+    nav_state: &NavigationState,
+) -> (Vec<FileSystemEntry>, Vec<String>) {
+    let mut tree_entries = Vec::new();
+    let mut tree_guides = Vec::new();
+    append_tree_level(
+        top_level_entries,
+        "",
+        1,
+        nav_state,
+        &mut tree_entries,
+        &mut tree_guides,
+    );
+    (tree_entries, tree_guides)
+}
+
+/// Appends one level of the tree (and any expanded directories inside it)
+///
+/// # Arguments
+/// * `level_entries` - Sorted, filtered entries of one directory
+/// * `parent_guide` - Guide lines of the levels above ("│  " or "   " each)
+/// * `level` - 1 for the current directory's items
+/// * `nav_state` - Expanded directories, sort method and filter
+/// * `tree_entries` / `tree_guides` - Rows so far
+fn append_tree_level(
+    level_entries: Vec<FileSystemEntry>,
+    parent_guide: &str,
+    level: usize,
+    nav_state: &NavigationState,
+    tree_entries: &mut Vec<FileSystemEntry>,
+    tree_guides: &mut Vec<String>,
+) {
+    let last_index = level_entries.len().saturating_sub(1);
+    for (entry_index, entry) in level_entries.into_iter().enumerate() {
+        if tree_entries.len() >= TREE_VIEW_MAX_ROWS {
+            return;
+        }
+
+        // Top level has no guides; below it, a branch plus the line to carry on
+        let is_last = entry_index == last_index;
+        let (row_guide, child_guide) = if level == 1 {
+            (String::new(), String::new())
+        } else if is_last {
+            (
+                format!("{}└─ ", parent_guide),
+                format!("{}   ", parent_guide),
+            )
+        } else {
+            (
+                format!("{}├─ ", parent_guide),
+                format!("{}│  ", parent_guide),
+            )
+        };
+
+        let expand = entry.is_directory
+            && level < TREE_VIEW_MAX_DEPTH
+            && nav_state
+                .tree_expanded_directories
+                .contains(&entry.file_system_item_path);
+        let directory_path = entry.file_system_item_path.clone();
+        tree_entries.push(entry);
+        tree_guides.push(row_guide);
+
+        if expand {
+            let mut child_entries = read_directory_contents(&directory_path).unwrap_or_default();
+            sort_directory_entries(&mut child_entries, nav_state.current_sort_method);
+            let child_entries: Vec<FileSystemEntry> = nav_state
+                .apply_filter(&child_entries)
+                .into_iter()
+                .cloned()
+                .collect();
+            append_tree_level(
+                child_entries,
+                &child_guide,
+                level + 1,
+                nav_state,
+                tree_entries,
+                tree_guides,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tree_view_tests {
+    use super::*;
+
+    /// Directory with `alpha/{inner/deep.txt, a.txt}`, `beta/b.txt`, `top.txt`
+    fn make_tree_test_directory(test_name: &str) -> PathBuf {
+        let test_root = std::env::temp_dir().join(format!(
+            "ff_tree_view_test_{}_{}",
+            test_name,
+            std::process::id()
+        ));
+        let _ = fs::remove_dir_all(&test_root);
+        fs::create_dir_all(test_root.join("alpha/inner")).expect("Failed to create test dirs");
+        fs::create_dir_all(test_root.join("beta")).expect("Failed to create test dirs");
+        for file_path in [
+            "alpha/inner/deep.txt",
+            "alpha/a.txt",
+            "beta/b.txt",
+            "top.txt",
+        ] {
+            fs::write(test_root.join(file_path), "x").expect("Failed to write test file");
+        }
+        test_root
+    }
+
+    /// Builds the tree the way the main loop does
+    fn tree_rows(test_root: &Path, nav_state: &NavigationState) -> Vec<(String, String)> {
+        let mut top_level_entries =
+            read_directory_contents(&test_root.to_path_buf()).expect("Readable");
+        sort_directory_entries(&mut top_level_entries, nav_state.current_sort_method);
+        let top_level_entries: Vec<FileSystemEntry> = nav_state
+            .apply_filter(&top_level_entries)
+            .into_iter()
+            .cloned()
+            .collect();
+        let (tree_entries, tree_guides) = build_tree_view_rows(top_level_entries, nav_state);
+        tree_entries
+            .iter()
+            .zip(tree_guides)
+            .map(|(entry, guide)| {
+                (
+                    guide,
+                    entry.file_system_item_name.to_string_lossy().into_owned(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_tree_rows_follow_expanded_directories_with_guides() {
+        let test_root = make_tree_test_directory("guides");
+        let mut nav_state = NavigationState::new();
+
+        // Nothing expanded: same rows as the flat listing
+        let flat_rows = tree_rows(&test_root, &nav_state);
+        assert!(flat_rows.iter().all(|(guide, _)| guide.is_empty()));
+        assert_eq!(flat_rows.len(), 3);
+
+        // Directories come first (name sort), contents under their parent
+        nav_state
+            .tree_expanded_directories
+            .insert(test_root.join("alpha"));
+        nav_state
+            .tree_expanded_directories
+            .insert(test_root.join("alpha/inner"));
+        let expanded_rows = tree_rows(&test_root, &nav_state);
+        let expected: Vec<(String, String)> = [
+            ("", "alpha"),
+            ("├─ ", "inner"),
+            ("│  └─ ", "deep.txt"),
+            ("└─ ", "a.txt"),
+            ("", "beta"),
+            ("", "top.txt"),
+        ]
+        .iter()
+        .map(|(guide, name)| (guide.to_string(), name.to_string()))
+        .collect();
+        assert_eq!(expanded_rows, expected);
+
+        // The filter applies at every level
+        nav_state.set_filter('d');
+        let directory_rows = tree_rows(&test_root, &nav_state);
+        let directory_names: Vec<&str> = directory_rows
+            .iter()
+            .map(|(_, name)| name.as_str())
+            .collect();
+        assert_eq!(directory_names, vec!["alpha", "inner", "beta"]);
+
+        let _ = fs::remove_dir_all(&test_root);
+    }
+
+    #[test]
+    fn test_tree_rows_stop_at_depth_limit() {
+        let test_root =
+            std::env::temp_dir().join(format!("ff_tree_view_test_depth_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&test_root);
+
+        // One directory per level, twice as deep as the limit, all expanded
+        let mut nav_state = NavigationState::new();
+        let mut level_path = test_root.clone();
+        for _ in 0..TREE_VIEW_MAX_DEPTH * 2 {
+            level_path = level_path.join("d");
+            nav_state
+                .tree_expanded_directories
+                .insert(level_path.clone());
+        }
+        fs::create_dir_all(&level_path).expect("Failed to create test dirs");
+
+        assert_eq!(tree_rows(&test_root, &nav_state).len(), TREE_VIEW_MAX_DEPTH);
+
+        let _ = fs::remove_dir_all(&test_root);
+    }
+}
+
+/// Bytes read from the start of a file to tell text from binary
+const PREVIEW_BINARY_SNIFF_BYTES: usize = 8192;
+
+/// Bytes per row in the binary preview's hex dump
+const PREVIEW_HEX_BYTES_PER_ROW: usize = 16;
+
+/// Spaces a tab becomes in the text preview
+const PREVIEW_TAB_WIDTH: usize = 4;
+
+/// What a key pressed in the preview asks for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PreviewKey {
+    NextPage,
+    PreviousPage,
+    Close,
+}
+
+/// Checks if the start of a file looks binary (not text to print)
+///
+/// # Arguments
+/// * `sample` - The first bytes of the file
+///
+/// # Returns
+/// * `bool` - true if there is a NUL byte or the bytes are not UTF-8
+///   (a character cut off at the end of the sample still counts as text)
+fn looks_like_binary(sample: &[u8]) -> bool {
+    if sample.contains(&0) {
+        return true;
+    }
+    match std::str::from_utf8(sample) {
+        Ok(_) => false,
+        // error_len None: the sample just ends mid-character
+        Err(utf8_error) => utf8_error.error_len().is_some(),
+    }
+}
+
+/// Turns one raw line into printable text for the preview
+///
+/// # Arguments
+/// * `raw_line` - Bytes of one line, with or without its line ending
+///
+/// # Returns
+/// * `String` - Line ending removed, tabs expanded, invalid UTF-8 shown as
+///   '�', other control characters shown as `byte_to_display_char` glyphs
+///   (so nothing in a file can move the cursor or change colors)
+fn sanitize_preview_line(raw_line: &[u8]) -> String {
+    let line_text = String::from_utf8_lossy(raw_line);
+    let mut printable_line = String::with_capacity(line_text.len());
+    for character in line_text.trim_end_matches(['\n', '\r']).chars() {
+        match character {
+            '\t' => printable_line.push_str(&" ".repeat(PREVIEW_TAB_WIDTH)),
+            control if control.is_control() && control.is_ascii() => {
+                printable_line.push(byte_to_display_char(control as u8))
+            }
+            // C1 controls (U+0080..U+009F) are not ASCII but still act on terminals
+            control if control.is_control() => printable_line.push('▚'),
+            other => printable_line.push(other),
+        }
+    }
+    printable_line
+}
+
+/// Colors a preview line the way Lines does (symbols magenta, keywords yellow)
+///
+/// # Arguments
+/// * `line` - Sanitized text of one line (see `sanitize_preview_line`)
+///
+/// # Returns
+/// * `String` - The line with color escapes around highlighted parts
+fn highlight_preview_line(line: &str) -> String {
+    let mut highlighted_line = String::with_capacity(line.len() * 2);
+    let mut byte_position = 0;
+    while let Some(character) = line[byte_position..].chars().next() {
+        match buffy_get_syntax_highlight(byte_position, line) {
+            SyntaxHighlight::DefinitionWord { keyword_byte_len } => {
+                let keyword_end = (byte_position + keyword_byte_len).min(line.len());
+                highlighted_line.push_str(&format!(
+                    "{}{}{}",
+                    YELLOW,
+                    &line[byte_position..keyword_end],
+                    RESET
+                ));
+                byte_position = keyword_end;
+            }
+            SyntaxHighlight::SyntaxSymbol => {
+                highlighted_line.push_str(&format!("{}{}{}", MAGENTA, character, RESET));
+                byte_position += character.len_utf8();
+            }
+            SyntaxHighlight::None => {
+                highlighted_line.push(character);
+                byte_position += character.len_utf8();
+            }
+        }
+    }
+    highlighted_line
+}
+
+/// Formats one hex-dump row: offset, hex bytes, then the bytes as glyphs
+///
+/// # Example
+/// `00000010  89 50 4e 47 0d 0a                                 ▚PNG␍␊`
+fn format_hex_dump_row(row_offset: u64, row_bytes: &[u8]) -> String {
+    let hex_cells: Vec<String> = row_bytes
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect();
+    let glyphs: String = row_bytes
+        .iter()
+        .map(|&byte| byte_to_display_char(byte))
+        .collect();
+    format!(
+        "{:08x}  {:<width$}  {}",
+        row_offset,
+        hex_cells.join(" "),
+        glyphs,
+        width = PREVIEW_HEX_BYTES_PER_ROW * 3 - 1
+    )
+}
+
+/// Waits for one key in the preview
+///
+/// # Returns
+/// * `PreviewKey` - Space, j, Down or Page Down: next page; k, Up or
+///   Page Up: previous page; anything else closes. Without a terminal for
+///   single keys, a typed line is used (page commands as in the listing)
+fn read_preview_key() -> PreviewKey {
+    match read_raw_key(false) {
+        Ok(RawKey::Char(' ')) | Ok(RawKey::Down) | Ok(RawKey::PageDown) => PreviewKey::NextPage,
+        Ok(RawKey::Up) | Ok(RawKey::PageUp) => PreviewKey::PreviousPage,
+        Ok(RawKey::Char(character)) if is_pagination_down_command(&character.to_string()) => {
+            PreviewKey::NextPage
+        }
+        Ok(RawKey::Char(character)) if is_pagination_up_command(&character.to_string()) => {
+            PreviewKey::PreviousPage
+        }
+        Ok(_) => PreviewKey::Close,
+        Err(_) => {
+            let mut input = String::new();
+            if io::stdin().read_line(&mut input).is_err() {
+                return PreviewKey::Close;
+            }
+            let command = input.trim().to_lowercase();
+            if is_pagination_down_command(&command) {
+                PreviewKey::NextPage
+            } else if is_pagination_up_command(&command) {
+                PreviewKey::PreviousPage
+            } else {
+                PreviewKey::Close
+            }
+        }
+    }
+}
+
+/// Names the kind of an item that is neither a regular file nor a directory
+///
+/// # Arguments
+/// * `file_type` - From `fs::metadata` (links already followed)
+///
+/// # Returns
+/// * `&'static str` - "named pipe (FIFO)", "socket", "character device",
+///   "block device", or a generic description elsewhere
+fn describe_special_file_kind(file_type: &fs::FileType) -> &'static str {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::FileTypeExt;
+        if file_type.is_fifo() {
+            return "named pipe (FIFO)";
+        } else if file_type.is_socket() {
+            return "socket";
+        } else if file_type.is_char_device() {
+            return "character device";
+        } else if file_type.is_block_device() {
+            return "block device";
+        }
+    }
+    if file_type.is_symlink() {
+        "symbolic link (broken)"
+    } else {
+        "special file (device, pipe or socket)"
+    }
+}
+
+/// Quick look at a file or directory without leaving ff ("N -p")
+///
+/// # Purpose
+/// Checking what is in a file should not mean opening an editor and
+/// quitting it again. The preview fills one screen and returns on any key.
+///
+/// # Arguments
+/// * `item_path` - File or directory to preview
+///
+/// # Returns
+/// * `Result<()>` - Ok when the user leaves the preview; Err if the file
+///   cannot be opened or read
+///
+/// # What It Shows
+/// - Text: numbered lines, highlighted as in Lines (not .txt/.log files)
+/// - Binary (NUL bytes or not UTF-8): a hex dump with glyphs, and the first
+///   byte spelled out by `format_byte_info` (often tells the file type)
+/// - Directory: its file, directory and total item counts
+///
+/// # Paging
+/// Space/j/Down shows the next screen, k/Up the previous one; any other
+/// key returns to the listing. Lines are read page by page, so a large
+/// file is never loaded whole.
+fn run_file_preview(item_path: &Path) -> Result<()> {
+    use std::io::{Read, Seek, SeekFrom};
+
+    // Step 1: Screen size (header and footer take one row each)
+    let (window_rows, window_columns) = query_terminal_window_size()
+        .map(|window_size| (window_size.rows as usize, window_size.cols as usize))
+        .unwrap_or((TUI_ROWS_DEFAULT, MAX_TUI_CHAR_LENGTH_DEFAULT));
+    let rows_per_page = window_rows.saturating_sub(3).max(1);
+    let item_display = escape_path_for_display(item_path);
+
+    // Step 2: Directories show their counts
+    let metadata = fs::metadata(item_path)?;
+    if metadata.is_dir() {
+        let (all_count, file_count, directory_count) =
+            count_directoryitems_ribbon(&item_path.to_path_buf());
+        clear_terminal_screen()?;
+        println!("{}", fit_menu_line(format!("Preview: {}", item_display)));
+        println!(
+            "\nDirectory: {} files, {} directories, {} items in all",
+            file_count, directory_count, all_count
+        );
+        print!("\n{}{}--- any key: back ---{}", BOLD, YELLOW, RESET);
+        io::stdout().flush().map_err(FileFantasticError::Io)?;
+        read_preview_key();
+        return Ok(());
+    }
+
+    // Step 3: Special files are described, not opened (opening a FIFO
+    // blocks until a writer appears; a device may never reach its end)
+    if !metadata.is_file() {
+        clear_terminal_screen()?;
+        println!("{}", fit_menu_line(format!("Preview: {}", item_display)));
+        println!(
+            "
+{}: not previewed",
+            describe_special_file_kind(&metadata.file_type())
+        );
+        print!("\n{}{}--- any key: back ---{}", BOLD, YELLOW, RESET);
+        io::stdout().flush().map_err(FileFantasticError::Io)?;
+        read_preview_key();
+        return Ok(());
+    }
+
+    // Step 4: Text or binary? (from the file's first bytes)
+    let mut file = fs::File::open(item_path)?;
+    let file_size = metadata.len();
+    let mut sample = Vec::with_capacity(PREVIEW_BINARY_SNIFF_BYTES);
+    (&mut file)
+        .take(PREVIEW_BINARY_SNIFF_BYTES as u64)
+        .read_to_end(&mut sample)?;
+    let is_binary = looks_like_binary(&sample);
+    let highlight = !is_binary && !buffy_is_plain_text_extension(Some(item_path));
+
+    let description = if is_binary {
+        let mut byte_info_buffer = [0u8; 64];
+        let first_byte_info = match sample.first() {
+            Some(&first_byte) => {
+                let info_length = format_byte_info(&mut byte_info_buffer, first_byte);
+                String::from_utf8_lossy(&byte_info_buffer[..info_length])
+                    .trim_end()
+                    .to_string()
+            }
+            None => String::from("empty"),
+        };
+        format!("binary, first {}", first_byte_info)
+    } else {
+        String::from("text")
+    };
+
+    // Step 5: Page by page; each page remembers where it starts (for going back)
+    let mut reader = io::BufReader::new(file);
+    // (byte offset, number of the first line) of every page shown so far
+    let mut page_starts: Vec<(u64, usize)> = vec![(0, 1)];
+    loop {
+        let (page_offset, first_line_number) = page_starts[page_starts.len() - 1];
+        reader.seek(SeekFrom::Start(page_offset))?;
+
+        clear_terminal_screen()?;
+        println!(
+            "{}",
+            fit_menu_line(format!(
+                "Preview: {}  ({}, {})",
+                item_display,
+                format_file_size(file_size),
+                description
+            ))
+        );
+
+        let mut next_offset = page_offset;
+        let mut next_line_number = first_line_number;
+        if is_binary {
+            let mut row_bytes = [0u8; PREVIEW_HEX_BYTES_PER_ROW];
+            for _ in 0..rows_per_page {
+                let bytes_read = read_full_chunk(&mut reader, &mut row_bytes)?;
+                if bytes_read == 0 {
+                    break;
+                }
+                println!(
+                    "{}",
+                    format_hex_dump_row(next_offset, &row_bytes[..bytes_read])
+                );
+                next_offset += bytes_read as u64;
+            }
+        } else {
+            // "NNNNN " before each line
+            let text_width = window_columns.saturating_sub(7).max(10);
+            let mut raw_line = Vec::new();
+            for _ in 0..rows_per_page {
+                // At most 4 bytes per shown column are kept (a minified file
+                // can be one huge line); the rest of the line is skipped
+                let bytes_read = read_preview_line(&mut reader, &mut raw_line, text_width * 4)?;
+                if bytes_read == 0 {
+                    break;
+                }
+                let line_text =
+                    take_display_width_prefix(&sanitize_preview_line(&raw_line), text_width);
+                println!(
+                    "{}{:>5}{} {}",
+                    YELLOW,
+                    next_line_number,
+                    RESET,
+                    if highlight {
+                        highlight_preview_line(&line_text)
+                    } else {
+                        line_text
+                    }
+                );
+                next_offset += bytes_read;
+                next_line_number += 1;
+            }
+        }
+
+        let at_end = next_offset >= file_size;
+        print!(
+            "{}{}--- {}{}   Space/j: next  k: back  any other key: return ---{}",
+            BOLD,
+            YELLOW,
+            if is_binary {
+                format!("bytes {}-{} of {}", page_offset, next_offset, file_size)
+            } else {
+                format!(
+                    "lines {}-{}",
+                    first_line_number,
+                    next_line_number.saturating_sub(1)
+                )
+            },
+            if at_end { " (end)" } else { "" },
+            RESET
+        );
+        io::stdout().flush().map_err(FileFantasticError::Io)?;
+
+        match read_preview_key() {
+            PreviewKey::NextPage if !at_end => page_starts.push((next_offset, next_line_number)),
+            PreviewKey::NextPage => {}
+            PreviewKey::PreviousPage => {
+                if page_starts.len() > 1 {
+                    page_starts.pop();
+                }
+            }
+            PreviewKey::Close => return Ok(()),
+        }
+    }
+}
+
+/// Reads one line for the preview without holding all of a long line
+///
+/// # Arguments
+/// * `reader` - Positioned at the start of a line
+/// * `raw_line` - Cleared, then given the line's first bytes
+/// * `max_kept_bytes` - Most bytes kept in `raw_line`
+///
+/// # Returns
+/// * `io::Result<u64>` - Bytes the whole line takes in the file (its
+///   newline included), so the next line starts that far on; 0 at the end
+fn read_preview_line(
+    reader: &mut impl io::BufRead,
+    raw_line: &mut Vec<u8>,
+    max_kept_bytes: usize,
+) -> io::Result<u64> {
+    use std::io::{BufRead, Read};
+
+    // Step 1: The part of the line that is shown
+    raw_line.clear();
+    let mut line_bytes = (&mut *reader)
+        .take(max_kept_bytes as u64)
+        .read_until(b'\n', raw_line)? as u64;
+    if line_bytes == 0 || raw_line.last() == Some(&b'\n') {
+        return Ok(line_bytes);
+    }
+
+    // Step 2: Skip the rest, up to and including the next newline
+    loop {
+        let buffered = match reader.fill_buf() {
+            Ok(buffered) => buffered,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+        if buffered.is_empty() {
+            return Ok(line_bytes);
+        }
+        let (skipped, found_newline) = match buffered.iter().position(|&byte| byte == b'\n') {
+            Some(newline_index) => (newline_index + 1, true),
+            None => (buffered.len(), false),
+        };
+        reader.consume(skipped);
+        line_bytes += skipped as u64;
+        if found_newline {
+            return Ok(line_bytes);
+        }
+    }
+}
+
+/// Reads until `buffer` is full or the file ends
+///
+/// # Returns
+/// * `io::Result<usize>` - Bytes read (less than the buffer only at the end)
+fn read_full_chunk(reader: &mut impl io::Read, buffer: &mut [u8]) -> io::Result<usize> {
+    let mut filled = 0;
+    while filled < buffer.len() {
+        match reader.read(&mut buffer[filled..]) {
+            Ok(0) => break,
+            Ok(bytes_read) => filled += bytes_read,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(filled)
+}
+
+#[cfg(test)]
+mod file_preview_tests {
+    use super::*;
+
+    #[test]
+    fn test_binary_detection() {
+        assert!(!looks_like_binary(b"fn main() {}\n"));
+        assert!(!looks_like_binary("caf\u{e9} \u{1f600}\n".as_bytes()));
+        assert!(!looks_like_binary(b""));
+        // A multi-byte character cut off by the sample is still text
+        assert!(!looks_like_binary(&"\u{e9}".as_bytes()[..1]));
+        assert!(looks_like_binary(b"\x89PNG\r\n\x1a\n\x00\x00"));
+        assert!(looks_like_binary(b"abc\xffdef"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_special_files_are_named_by_kind() {
+        let device_metadata = fs::metadata("/dev/null").expect("/dev/null should exist");
+        assert!(!device_metadata.is_file());
+        assert_eq!(
+            describe_special_file_kind(&device_metadata.file_type()),
+            "character device"
+        );
+    }
+
+    #[test]
+    fn test_preview_lines_are_sanitized_and_highlighted() {
+        // Tabs expand, line endings go, escape codes in a file are defused
+        assert_eq!(sanitize_preview_line(b"\tlet x = 1;\r\n"), "    let x = 1;");
+        assert_eq!(sanitize_preview_line(b"a\x1b[31mb"), "a\u{259a}[31mb");
+
+        let highlighted = highlight_preview_line("fn main()");
+        assert!(highlighted.starts_with(&format!("{}fn {}", YELLOW, RESET)));
+        assert!(highlighted.contains(&format!("{}({}", MAGENTA, RESET)));
+        assert_eq!(strip_ansi_codes(&highlighted), "fn main()");
+    }
+
+    #[test]
+    fn test_long_preview_lines_are_cut_and_skipped() {
+        let text = format!("{}\nshort\nend", "x".repeat(10_000));
+        let mut reader = io::BufReader::with_capacity(64, io::Cursor::new(text.as_bytes()));
+        let mut raw_line = Vec::new();
+        let mut read_line =
+            |raw_line: &mut Vec<u8>| read_preview_line(&mut reader, raw_line, 40).unwrap();
+
+        // Only 40 bytes kept, but the offset moves past the whole line
+        assert_eq!(read_line(&mut raw_line), 10_001);
+        assert_eq!(raw_line, vec![b'x'; 40]);
+        assert_eq!(read_line(&mut raw_line), 6);
+        assert_eq!(raw_line, b"short\n");
+        // A last line without a newline, then the end
+        assert_eq!(read_line(&mut raw_line), 3);
+        assert_eq!(raw_line, b"end");
+        assert_eq!(read_line(&mut raw_line), 0);
+    }
+
+    #[test]
+    fn test_hex_dump_rows_align() {
+        // "00000010" + 2 spaces + 16 hex cells of "xx " (minus the last space) + 2 spaces
+        let glyph_column = 8 + 2 + PREVIEW_HEX_BYTES_PER_ROW * 3 - 1 + 2;
+
+        let full_row = format_hex_dump_row(0x10, b"0123456789abcdef");
+        assert!(full_row.starts_with("00000010  30 31 32"));
+        assert_eq!(&full_row[glyph_column..], "0123456789abcdef");
+
+        // A short last row keeps the glyph column where full rows have it
+        let short_row = format_hex_dump_row(0x20, b"\x89P\n");
+        assert!(short_row.starts_with("00000020  89 50 0a "));
+        assert_eq!(&short_row[glyph_column..], "\u{259a}P\u{240a}");
+    }
+}
+
+/// Largest file the info panel counts lines in (the count reads every byte)
+const INFO_LINE_COUNT_MAX_BYTES: u64 = 16 * 1024 * 1024;
+
+/// Bytes read from the start of a file for content-type sniffing
+const INFO_SNIFF_BYTES: usize = 512;
+
+/// A file's content type, told from its first bytes (not its extension)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SniffedContentType {
+    Png,
+    Jpeg,
+    Pdf,
+    Zip,
+    Elf,
+    Gzip,
+    Sqlite,
+    Utf8TextWithBom,
+    Utf16LittleEndianText,
+    Utf16BigEndianText,
+    AsciiText,
+    Utf8Text,
+    Empty,
+    UnknownBinary,
+}
+
+impl SniffedContentType {
+    /// Description for the info panel
+    fn description(self) -> &'static str {
+        match self {
+            SniffedContentType::Png => "PNG image",
+            SniffedContentType::Jpeg => "JPEG image",
+            SniffedContentType::Pdf => "PDF document",
+            SniffedContentType::Zip => "ZIP archive (also .docx .xlsx .odt .jar ...)",
+            SniffedContentType::Elf => "ELF executable or library",
+            SniffedContentType::Gzip => "gzip compressed data",
+            SniffedContentType::Sqlite => "SQLite 3 database",
+            SniffedContentType::Utf8TextWithBom => "UTF-8 text (with byte order mark)",
+            SniffedContentType::Utf16LittleEndianText => "UTF-16 little-endian text (BOM)",
+            SniffedContentType::Utf16BigEndianText => "UTF-16 big-endian text (BOM)",
+            SniffedContentType::AsciiText => "ASCII text",
+            SniffedContentType::Utf8Text => "UTF-8 text",
+            SniffedContentType::Empty => "empty",
+            SniffedContentType::UnknownBinary => "binary data (unknown type)",
+        }
+    }
+
+    /// Text worth counting lines in
+    fn is_text(self) -> bool {
+        matches!(
+            self,
+            SniffedContentType::Utf8TextWithBom
+                | SniffedContentType::Utf16LittleEndianText
+                | SniffedContentType::Utf16BigEndianText
+                | SniffedContentType::AsciiText
+                | SniffedContentType::Utf8Text
+        )
+    }
+}
+
+/// Tells a file's content type from its first bytes ("magic bytes")
+///
+/// # Arguments
+/// * `header` - The start of the file (`INFO_SNIFF_BYTES` is plenty)
+///
+/// # Returns
+/// * `SniffedContentType` - Known signatures first, then byte order marks,
+///   then text (no NUL bytes, valid UTF-8) or unknown binary
+///
+/// # Why Not Extensions
+/// A name can say `.txt` and hold a PNG, or have no extension at all;
+/// the bytes say what the file really is.
+fn sniff_content_type(header: &[u8]) -> SniffedContentType {
+    const MAGIC_SIGNATURES: &[(&[u8], SniffedContentType)] = &[
+        (b"\x89PNG\r\n\x1a\n", SniffedContentType::Png),
+        (b"\xff\xd8\xff", SniffedContentType::Jpeg),
+        (b"%PDF-", SniffedContentType::Pdf),
+        (b"PK\x03\x04", SniffedContentType::Zip),
+        (b"PK\x05\x06", SniffedContentType::Zip), // empty archive
+        (b"PK\x07\x08", SniffedContentType::Zip), // spanned archive
+        (b"\x7fELF", SniffedContentType::Elf),
+        (b"\x1f\x8b", SniffedContentType::Gzip),
+        (b"SQLite format 3\x00", SniffedContentType::Sqlite),
+        (b"\xef\xbb\xbf", SniffedContentType::Utf8TextWithBom),
+        (b"\xff\xfe", SniffedContentType::Utf16LittleEndianText),
+        (b"\xfe\xff", SniffedContentType::Utf16BigEndianText),
+    ];
+
+    if header.is_empty() {
+        return SniffedContentType::Empty;
+    }
+    for (signature, content_type) in MAGIC_SIGNATURES {
+        if header.starts_with(signature) {
+            return *content_type;
+        }
+    }
+    if looks_like_binary(header) {
+        SniffedContentType::UnknownBinary
+    } else if header.is_ascii() {
+        SniffedContentType::AsciiText
+    } else {
+        SniffedContentType::Utf8Text
+    }
+}
+
+/// Inode-level details for the info panel (Unix only)
+struct InodeDetails {
+    /// Inode number
+    inode_number: u64,
+
+    /// Number of hard links to the inode
+    hard_link_count: u64,
+
+    /// Device the inode is on
+    device_number: u64,
+
+    /// Last status change (ctime: permissions, owner, links, content)
+    status_changed_time: SystemTime,
+}
+
+/// Reads inode number, link count, device and status-change time
+///
+/// # Returns
+/// * `Some(InodeDetails)` - From the metadata
+#[cfg(unix)]
+fn read_inode_details(metadata: &fs::Metadata) -> Option<InodeDetails> {
+    use std::os::unix::fs::MetadataExt;
+    let status_changed_time = if metadata.ctime() >= 0 {
+        UNIX_EPOCH + Duration::new(metadata.ctime() as u64, metadata.ctime_nsec() as u32)
+    } else {
+        UNIX_EPOCH
+    };
+    Some(InodeDetails {
+        inode_number: metadata.ino(),
+        hard_link_count: metadata.nlink(),
+        device_number: metadata.dev(),
+        status_changed_time,
+    })
+}
+
+/// Reads inode number, link count, device and status-change time
+///
+/// # Returns
+/// * `None` - This platform has no inodes
+#[cfg(not(unix))]
+fn read_inode_details(_metadata: &fs::Metadata) -> Option<InodeDetails> {
+    None
+}
+
+/// Gathers everything the info panel shows about one item
+///
+/// # Arguments
+/// * `item_path` - File, directory or symbolic link
+///
+/// # Returns
+/// * `Result<Vec<(&'static str, String)>>` - Label and value rows in
+///   display order; Err if the item cannot be read at all
+///
+/// # Rows
+/// Path, kind (and link target), size in bytes, content type (sniffed from
+/// magic bytes), line count (text up to `INFO_LINE_COUNT_MAX_BYTES`) or
+/// item counts (directory), modified/accessed/created/status-changed times
+/// (UTC), mode, owner, inode, hard links and device. A link is described
+/// by its target; a broken link by the link itself.
+fn collect_item_info(item_path: &Path) -> Result<Vec<(&'static str, String)>> {
+    let link_metadata = fs::symlink_metadata(item_path)?;
+    let is_symlink = link_metadata.file_type().is_symlink();
+    let metadata = fs::metadata(item_path).unwrap_or_else(|_| link_metadata.clone());
+    let mut info_rows: Vec<(&'static str, String)> = Vec::new();
+
+    // Step 1: What it is
+    info_rows.push(("Path", escape_path_for_display(item_path)));
+    let kind = if metadata.is_dir() {
+        "directory"
+    } else if metadata.is_file() {
+        "regular file"
+    } else {
+        describe_special_file_kind(&metadata.file_type())
+    };
+    if is_symlink && !metadata.file_type().is_symlink() {
+        info_rows.push(("Kind", format!("symbolic link to a {}", kind)));
+    } else {
+        info_rows.push(("Kind", kind.to_string()));
+    }
+    if is_symlink {
+        let link_target = fs::read_link(item_path)
+            .map(|target| escape_path_for_display(&target))
+            .unwrap_or_else(|e| format!("unreadable ({})", e));
+        info_rows.push(("Link to", link_target));
+    }
+    info_rows.push((
+        "Size",
+        format!(
+            "{} bytes ({})",
+            metadata.len(),
+            format_file_size(metadata.len())
+        ),
+    ));
+
+    // Step 2: Content (sniffed, not guessed from the extension)
+    if metadata.is_dir() {
+        let (all_count, file_count, directory_count) =
+            count_directoryitems_ribbon(&item_path.to_path_buf());
+        info_rows.push((
+            "Contains",
+            format!(
+                "{} files, {} directories, {} items in all",
+                file_count, directory_count, all_count
+            ),
+        ));
+    } else if metadata.is_file() {
+        use std::io::Read;
+        let mut header = Vec::with_capacity(INFO_SNIFF_BYTES);
+        let content_type = match fs::File::open(item_path)
+            .and_then(|file| file.take(INFO_SNIFF_BYTES as u64).read_to_end(&mut header))
+        {
+            Ok(_) => sniff_content_type(&header),
+            Err(e) => {
+                info_rows.push(("Content", format!("unreadable ({})", e)));
+                return Ok(finish_item_info(info_rows, &metadata));
+            }
+        };
+        info_rows.push(("Content", content_type.description().to_string()));
+
+        if content_type.is_text() {
+            let line_count = if matches!(
+                content_type,
+                SniffedContentType::Utf16LittleEndianText | SniffedContentType::Utf16BigEndianText
+            ) {
+                // Newlines are two bytes there: a byte count would be wrong
+                String::from("not counted (UTF-16)")
+            } else if metadata.len() > INFO_LINE_COUNT_MAX_BYTES {
+                format!(
+                    "not counted (over {})",
+                    format_file_size(INFO_LINE_COUNT_MAX_BYTES)
+                )
+            } else {
+                match count_lines_in_file(item_path) {
+                    Ok((total_lines, _)) => total_lines.to_string(),
+                    Err(e) => format!("not counted ({})", e),
+                }
+            };
+            info_rows.push(("Lines", line_count));
+        }
     }
 
-    #[test]
-    fn test_collect_entries_iterative_combined_limits() {
-        // Test multiple limits at once
-        let nav_state = create_test_navigation_state();
-        let temp_dir = std::env::temp_dir();
-
-        let result = nav_state.collect_entries_iterative(
-            &temp_dir,
-            Some(1),  // Depth limit
-            Some(10), // Entry limit
-            Some(5),  // Memory limit (MB)
-        );
+    Ok(finish_item_info(info_rows, &metadata))
+}
 
-        assert!(result.is_ok(), "Should succeed with combined limits");
-        let entries = result.expect("Failed to get entries");
+/// Appends the times, mode, owner and inode rows to the info panel
+fn finish_item_info(
+    mut info_rows: Vec<(&'static str, String)>,
+    metadata: &fs::Metadata,
+) -> Vec<(&'static str, String)> {
+    let format_time = |time: io::Result<SystemTime>| match time {
+        Ok(time) => format!("{} UTC", format_iso_timestamp(time)),
+        Err(_) => String::from("not available"),
+    };
+    info_rows.push(("Modified", format_time(metadata.modified())));
+    info_rows.push(("Accessed", format_time(metadata.accessed())));
+    info_rows.push(("Created", format_time(metadata.created())));
+
+    let (mode_bits, owner_user_id, owner_group_id) = read_unix_mode_and_owner(metadata);
+    if let Some(mode) = mode_bits {
+        info_rows.push((
+            "Mode",
+            format!("{} ({:04o})", format_unix_mode_bits(mode), mode & 0o7777),
+        ));
+    }
+    if let (Some(user_id), Some(group_id)) = (owner_user_id, owner_group_id) {
+        info_rows.push((
+            "Owner",
+            format!(
+                "{} ({}) / group {} ({})",
+                owner_name_for_id(owner_user_id, user_names_by_id()),
+                user_id,
+                owner_name_for_id(owner_group_id, group_names_by_id()),
+                group_id
+            ),
+        ));
+    }
+    if let Some(inode_details) = read_inode_details(metadata) {
+        info_rows.push((
+            "Changed",
+            format_time(Ok(inode_details.status_changed_time)),
+        ));
+        info_rows.push(("Inode", inode_details.inode_number.to_string()));
+        info_rows.push(("Hard links", inode_details.hard_link_count.to_string()));
+        info_rows.push(("Device", inode_details.device_number.to_string()));
+    }
+    info_rows
+}
 
-        // Should respect the entry limit
-        assert!(
-            entries.len() <= 10,
-            "Should not exceed 10 entries when limit is set, got {}",
-            entries.len()
+/// Shows the info panel for one item ("N -i") until a key is pressed
+///
+/// # Arguments
+/// * `item_path` - File, directory or symbolic link to describe
+///
+/// # Returns
+/// * `Result<()>` - Ok when the user leaves; Err if the item cannot be read
+fn run_item_info_panel(item_path: &Path) -> Result<()> {
+    let info_rows = collect_item_info(item_path)?;
+    clear_terminal_screen()?;
+    println!("{}{}Info{}", BOLD, YELLOW, RESET);
+    for (label, value) in info_rows {
+        println!(
+            "{}",
+            fit_menu_line(format!("  {}{:<11}{} {}", YELLOW, label, RESET, value))
         );
     }
+    print!("\n{}{}--- any key: back ---{}", BOLD, YELLOW, RESET);
+    io::stdout().flush().map_err(FileFantasticError::Io)?;
+    read_preview_key();
+    Ok(())
+}
+
+#[cfg(test)]
+mod item_info_tests {
+    use super::*;
 
     #[test]
-    fn test_collect_entries_iterative_file_attributes() {
-        // Test that collected entries have proper attributes
-        let nav_state = create_test_navigation_state();
-        let temp_dir = std::env::temp_dir();
+    fn test_sniff_content_type_from_magic_bytes() {
+        let cases: &[(&[u8], SniffedContentType)] = &[
+            (b"\x89PNG\r\n\x1a\n\x00\x00", SniffedContentType::Png),
+            (b"\xff\xd8\xff\xe0\x00\x10JFIF", SniffedContentType::Jpeg),
+            (b"%PDF-1.7\n", SniffedContentType::Pdf),
+            (b"PK\x03\x04\x14\x00", SniffedContentType::Zip),
+            (b"\x7fELF\x02\x01\x01", SniffedContentType::Elf),
+            (b"\x1f\x8b\x08\x00", SniffedContentType::Gzip),
+            (b"SQLite format 3\x00\x10\x00", SniffedContentType::Sqlite),
+            (b"\xef\xbb\xbfhello", SniffedContentType::Utf8TextWithBom),
+            (
+                b"\xff\xfeh\x00i\x00",
+                SniffedContentType::Utf16LittleEndianText,
+            ),
+            (
+                b"\xfe\xff\x00h\x00i",
+                SniffedContentType::Utf16BigEndianText,
+            ),
+            (b"plain words\n", SniffedContentType::AsciiText),
+            ("caf\u{e9}\n".as_bytes(), SniffedContentType::Utf8Text),
+            (b"", SniffedContentType::Empty),
+            (b"\x00\x01\x02\x03", SniffedContentType::UnknownBinary),
+        ];
+        for (header, expected) in cases {
+            assert_eq!(sniff_content_type(header), *expected, "header {:?}", header);
+        }
+    }
 
-        let result = nav_state.collect_entries_iterative(
-            &temp_dir,
-            Some(0), // Only immediate children
-            Some(5), // Limit to a few entries
-            None,
-        );
+    #[test]
+    fn test_line_count_includes_unterminated_last_line() {
+        let test_root =
+            std::env::temp_dir().join(format!("ff_line_count_test_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&test_root);
+        fs::create_dir_all(&test_root).expect("Failed to create test directory");
+
+        // Across buffer refills too
+        let long_text = "line\n".repeat(30_000) + "last";
+        let cases: &[(&str, usize)] = &[
+            ("", 0),
+            ("abc", 1),
+            ("one\ntwo", 2),
+            ("one\ntwo\n", 2),
+            ("\n\n", 2),
+            (&long_text, 30_001),
+        ];
+        for (case_index, (text, expected)) in cases.iter().enumerate() {
+            let text_path = test_root.join(format!("case_{}.txt", case_index));
+            fs::write(&text_path, text).expect("Failed to write test file");
+            let (line_count, _) = count_lines_in_file(&text_path).expect("Count should work");
+            assert_eq!(line_count, *expected, "case {}", case_index);
+        }
 
-        assert!(result.is_ok(), "Should collect entries successfully");
-        let entries = result.expect("Failed to get entries");
+        let _ = fs::remove_dir_all(&test_root);
+    }
 
-        // Verify each entry has valid attributes
-        for entry in entries.iter() {
-            // Name should not be empty
-            assert!(
-                !entry.file_system_item_name.is_empty(),
-                "File name should not be empty"
-            );
+    #[test]
+    fn test_item_info_rows_for_text_file_and_directory() {
+        let test_root =
+            std::env::temp_dir().join(format!("ff_item_info_test_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&test_root);
+        fs::create_dir_all(&test_root).expect("Failed to create test directory");
+        // A .png name with text inside: the bytes decide, not the extension
+        let text_path = test_root.join("notes.png");
+        fs::write(&text_path, "one\ntwo\nthree\n").expect("Failed to write test file");
+
+        let info_rows = collect_item_info(&text_path).expect("Info should work");
+        let value_of = |label: &str| {
+            info_rows
+                .iter()
+                .find(|(row_label, _)| *row_label == label)
+                .map(|(_, value)| value.clone())
+        };
+        assert_eq!(value_of("Kind").as_deref(), Some("regular file"));
+        assert!(value_of("Size").unwrap_or_default().starts_with("14 bytes"));
+        assert_eq!(value_of("Content").as_deref(), Some("ASCII text"));
+        assert_eq!(value_of("Lines").as_deref(), Some("3"));
+        assert!(value_of("Modified").unwrap_or_default().ends_with("UTC"));
+        #[cfg(unix)]
+        {
+            assert_eq!(value_of("Hard links").as_deref(), Some("1"));
+            assert!(value_of("Mode").unwrap_or_default().starts_with("-rw"));
+        }
 
-            // Path should exist as a string representation
-            assert!(
-                entry.file_system_item_path.to_str().is_some(),
-                "Path should be valid UTF-8"
-            );
+        // UTF-16 newlines are two bytes: no byte-based count is shown
+        let utf16_path = test_root.join("wide.txt");
+        fs::write(&utf16_path, b"\xff\xfea\x00\n\x00b\x00\n\x00")
+            .expect("Failed to write test file");
+        let utf16_rows = collect_item_info(&utf16_path).expect("Info should work");
+        assert!(
+            utf16_rows
+                .iter()
+                .any(|(label, value)| *label == "Lines" && value == "not counted (UTF-16)")
+        );
 
-            // Last modified time should not be UNIX_EPOCH for real files
-            // (though we handle UNIX_EPOCH as fallback)
-            // Just verify it doesn't panic when accessed
-            let _ = entry.file_system_item_last_modified_time;
+        let directory_rows = collect_item_info(&test_root).expect("Info should work");
+        assert!(
+            directory_rows
+                .iter()
+                .any(|(label, value)| *label == "Contains"
+                    && value.starts_with("2 files, 0 directories"))
+        );
 
-            // is_directory should be properly set
-            let _ = entry.is_directory;
-        }
+        let _ = fs::remove_dir_all(&test_root);
     }
 }
 
@@ -8508,6 +13511,29 @@ pub struct NavigationState {
 
     /// for default file editor
     lines_editor_session_path: PathBuf,
+
+    /// Single-keypress navigation: keys act without Enter (toggle with "keys")
+    raw_key_mode: bool,
+
+    /// Highlighted row (0-based, on the current page) in raw-key mode
+    raw_key_cursor_row: usize,
+
+    /// Screen row (1-based) of item 1 in the last listing drawn, for mouse
+    /// clicks; None before the first listing or when rows were not at fixed
+    /// positions (frame taller or wider than the terminal)
+    listing_first_item_screen_row: Option<usize>,
+
+    /// Optional listing columns (perm, owner, iso, ext, link) chosen with "cols"
+    listing_columns: ListingColumns,
+
+    /// Directories visited this session, for "back" and "fwd"
+    directory_history: DirectoryHistory,
+
+    /// Tree listing: expanded subdirectories shown under their parent ("tree")
+    tree_view_mode: bool,
+
+    /// Directories expanded in the tree listing ("tree N"), as full paths
+    tree_expanded_directories: HashSet<PathBuf>,
 }
 
 fn detect_android() -> bool {
@@ -8520,7 +13546,8 @@ impl NavigationState {
     /// # Platform-Specific Defaults
     /// - **Android devices**: Automatically applies a width reduction of 23 characters
     ///   (tui_wide_adjustment = 23, tui_wide_direction_sign = false) to accommodate
-    ///   typical Android terminal constraints
+    ///   typical Android terminal constraints, but only if the real window size
+    ///   cannot be queried (otherwise the detected width already accounts for it)
     /// - **Non-Android platforms**: No width adjustment (tui_wide_adjustment = 0)
     ///
     /// Android detection is performed by checking for the ANDROID_ROOT environment variable.
@@ -8557,7 +13584,7 @@ impl NavigationState {
 
         // Set width adjustment based on platform
         // Android terminals typically need reduced width
-        let (width_adjustment, width_direction) = if is_android {
+        let (width_adjustment, width_direction) = if is_android && query_terminal_window_size().is_err() {
             // Android: reduce width by 24 characters
             (24, false) // false represents negative direction
         } else {
@@ -8579,9 +13606,38 @@ impl NavigationState {
             tui_wide_direction_sign: width_direction, // Positive direction by default
             current_page_index: 0,                    // Always start at page 0
             lines_editor_session_path: PathBuf::new(),
+            raw_key_mode: false, // Typed commands + Enter by default
+            raw_key_cursor_row: 0,
+            listing_first_item_screen_row: None,
+            listing_columns: ListingColumns::default(), // Name, size, modified only
+            directory_history: DirectoryHistory::new(), // Filled in as directories are shown
+            tree_view_mode: false,                      // Flat listing by default
+            tree_expanded_directories: HashSet::new(),
         }
     }
 
+    /// Creates the state for a new tab
+    ///
+    /// # Purpose
+    /// A tab starts with its own clean sort, filter, page, search and
+    /// history, but keeps the session's settings: the Lines editor session
+    /// directory, TUI size adjustments, listing columns and one-key mode.
+    ///
+    /// # Returns
+    /// * `NavigationState` - The new tab's state
+    fn new_tab_state(&self) -> NavigationState {
+        let mut tab_state = NavigationState::new();
+        tab_state.lines_editor_session_path = self.lines_editor_session_path.clone();
+        tab_state.tui_tall_adjustment = self.tui_tall_adjustment;
+        tab_state.tui_tall_direction_sign = self.tui_tall_direction_sign;
+        tab_state.tui_wide_adjustment = self.tui_wide_adjustment;
+        tab_state.tui_wide_direction_sign = self.tui_wide_direction_sign;
+        tab_state.raw_key_mode = self.raw_key_mode;
+        tab_state.listing_columns = self.listing_columns;
+        tab_state.tree_view_mode = self.tree_view_mode;
+        tab_state
+    }
+
     /// Resets navigation state to clean defaults while preserving location and sort
     ///
     /// # Purpose
@@ -8625,6 +13681,10 @@ impl NavigationState {
 
         // Defaults to Name "Ascending" (alphabetal order)
         self.current_sort_method = DirectorySortingMethodEnum::Name(true);
+
+        // Back to the flat listing, everything collapsed
+        self.tree_view_mode = false;
+        self.tree_expanded_directories.clear();
     }
 
     /// Master wrapper function that handles all search routing and renumbers results
@@ -8944,6 +14004,11 @@ impl NavigationState {
     /// - Breadth-first traversal provides better cache locality for file system metadata
     /// - VecDeque provides amortized O(1) operations for queue management
     /// - Memory estimation helps prevent out-of-memory conditions on large directory trees
+    ///
+    /// # Symbolic Links
+    /// Links to directories are followed, but each directory (by device and
+    /// inode, see `directory_identity`) is entered only once, so a link to a
+    /// parent directory cannot make the traversal loop.
     pub fn collect_entries_iterative(
         &self,
         start_directory: &Path,
@@ -8961,6 +14026,12 @@ impl NavigationState {
         // Begin traversal from the start directory at depth 0
         directories_queue.push_back((start_directory.to_path_buf(), 0));
 
+        // (device, inode) of every directory queued, so symlink loops are entered once
+        let mut visited_directory_identities: HashSet<(u64, u64)> = HashSet::new();
+        if let Some(start_identity) = directory_identity(start_directory) {
+            visited_directory_identities.insert(start_identity);
+        }
+
         // Memory usage tracking
         // These are rough estimates based on typical file system entry sizes
         const ENTRY_SIZE_ESTIMATE_BYTES: usize = 256; // Approximate size of one FileSystemEntry in memory
@@ -9055,28 +14126,17 @@ impl NavigationState {
                     }
                 };
 
-                // Convert OsString to String, skipping entries with invalid UTF-8
-                let file_name = match entry.file_name().into_string() {
-                    Ok(name) => name,
-                    Err(_) => {
-                        // Skip files with non-UTF-8 names
-                        continue;
-                    }
-                };
+                // Keep the raw OsString: names that are not valid UTF-8 are
+                // listed (escaped for display), not skipped
+                let file_name = entry.file_name();
 
                 let path = entry.path();
-                let is_directory = metadata.is_dir();
-
-                // Create the file system entry structure
-                all_entries.push(FileSystemEntry {
-                    file_system_item_name: file_name,
-                    file_system_item_path: path.clone(),
-                    file_system_item_size_in_bytes: metadata.len(),
-                    file_system_item_last_modified_time: metadata
-                        .modified()
-                        .unwrap_or(SystemTime::UNIX_EPOCH),
-                    is_directory,
-                });
+
+                // Create the file system entry structure (links resolved to their targets)
+                let file_system_entry = build_file_system_entry(file_name, path.clone(), &metadata);
+                let is_directory = file_system_entry.is_directory;
+                let is_symlink = file_system_entry.is_symlink;
+                all_entries.push(file_system_entry);
 
                 // Update memory estimate after adding entry
                 estimated_memory_bytes =
@@ -9114,7 +14174,18 @@ impl NavigationState {
                             None => true,
                         };
 
-                        within_entry_limit && within_depth_limit && within_memory_limit
+                        // Loop safety: enter each (device, inode) once, so a link
+                        // back to a parent is listed but not followed around again
+                        let is_first_visit = match directory_identity(&path) {
+                            Some(identity) => visited_directory_identities.insert(identity),
+                            // No identity: follow real directories only, never links
+                            None => !is_symlink,
+                        };
+
+                        within_entry_limit
+                            && within_depth_limit
+                            && within_memory_limit
+                            && is_first_visit
                     };
 
                     if should_queue_subdirectory {
@@ -9311,6 +14382,8 @@ impl NavigationState {
     /// - No matches: Returns empty vector
     /// - Files without extensions: Treated as having no extension
     /// - Unicode handling: Correctly counts UTF-8 characters, not bytes
+    /// - Names that are not valid UTF-8: compared by raw bytes (see
+    ///   `NameUnit`); typing `\xNN` as shown in the listing matches byte NN
     ///
     /// # Example
     /// ```rust
@@ -9333,45 +14406,53 @@ impl NavigationState {
         let mut results = Vec::new();
 
         // Prepare search term for comparison (lowercase for case-insensitive matching)
-        let search_term = config.search_term.to_lowercase();
-
-        // Count characters (not bytes) for proper UTF-8 handling
-        // This ensures "café" has length 4, not 5
-        let search_len = search_term.chars().count();
+        // Units are characters (not bytes) for proper UTF-8 handling, so
+        // "café" has length 4, not 5; "\xNN" escapes become raw bytes
+        let search_term = config.search_term.as_str();
+        let search_units = search_term_units_for_matching(search_term);
+        let search_len = search_units.len();
 
         // Iterate through all provided entries to find matches
         // Note: We don't use enumerate's index here since we'll assign
         // display indices after sorting for uniform output format
         for entry in entries.iter() {
-            // Step 1: Create name without extension for flexible matching
+            // Step 1: Split the raw name into units (invalid UTF-8 stays raw bytes)
+            let name_units = name_units_for_matching(&entry.file_system_item_name);
+
+            // Create name without extension for flexible matching
             // This allows "doc" to match both "document" and "document.txt"
-            let name_without_ext = match entry.file_system_item_name.rsplit_once('.') {
-                Some((name, _ext)) => name.to_string(),
-                None => entry.file_system_item_name.clone(), // No extension found
+            let name_without_ext_units = match name_units
+                .iter()
+                .rposition(|unit| *unit == NameUnit::Character('.'))
+            {
+                Some(dot_position) => &name_units[..dot_position],
+                None => &name_units[..], // No extension found
             };
 
             // Step 2: Create truncated versions for fair comparison
             // We truncate to search term length to avoid penalizing longer names
             // Example: searching "doc" against "document" compares "doc" vs "doc"
-            let full_name_truncated: String = entry
-                .file_system_item_name
-                .to_lowercase()
-                .chars() // Use chars() for proper UTF-8 handling
-                .take(search_len) // Take only as many characters as search term
-                .collect();
-
-            let no_ext_truncated: String = name_without_ext
-                .to_lowercase()
-                .chars()
-                .take(search_len)
-                .collect();
+            let full_name_truncated = &name_units[..search_len.min(name_units.len())];
+            let no_ext_truncated =
+                &name_without_ext_units[..search_len.min(name_without_ext_units.len())];
 
             // Step 3: Calculate Levenshtein distances for both versions
             // This measures how many single-character edits are needed
             // Handle potential errors gracefully by skipping problematic entries
+            let display_name = escape_name_for_display(&entry.file_system_item_name);
             let (distance_with_ext, distance_without_ext) = match (
-                levenshtein_distance(&full_name_truncated, &search_term),
-                levenshtein_distance(&no_ext_truncated, &search_term),
+                levenshtein_distance_of_units(
+                    full_name_truncated,
+                    &search_units,
+                    &display_name,
+                    search_term,
+                ),
+                levenshtein_distance_of_units(
+                    no_ext_truncated,
+                    &search_units,
+                    &display_name,
+                    search_term,
+                ),
             ) {
                 (Ok(dist_with), Ok(dist_without)) => (dist_with, dist_without),
                 (Err(e), _) | (_, Err(e)) => {
@@ -9591,7 +14672,7 @@ impl NavigationState {
 
                     // Create search result for this match
                     results.push(GrepSearchResult {
-                        file_name: entry.file_system_item_name.clone(),
+                        file_name: escape_name_for_display(&entry.file_system_item_name),
                         file_path: entry.file_system_item_path.clone(),
                         line_number: line_number, // Direct field, not Option
                         line_content: context,    // Direct field, not Option
@@ -9806,13 +14887,13 @@ fn read_directory_contents(directory_path_to_read: &PathBuf) -> Result<Vec<FileS
             }
         };
 
-        directory_entries_list.push(FileSystemEntry {
-            file_system_item_name: directory_item.file_name().to_string_lossy().to_string(),
-            file_system_item_path: item_path,
-            file_system_item_size_in_bytes: item_metadata.len(),
-            file_system_item_last_modified_time: modified_time,
-            is_directory: item_metadata.is_dir(),
-        });
+        // Links are resolved to their targets (broken links are kept and marked)
+        let mut file_system_entry =
+            build_file_system_entry(directory_item.file_name(), item_path, &item_metadata);
+        if !file_system_entry.is_symlink {
+            file_system_entry.file_system_item_last_modified_time = modified_time;
+        }
+        directory_entries_list.push(file_system_entry);
     }
 
     Ok(directory_entries_list)
@@ -9909,6 +14990,34 @@ mod tests_formatting {
         let timestamp = create_archive_timestamp(y2k_leap);
         assert_eq!(timestamp, "00_02_29_00_00_00");
     }
+
+    #[test]
+    fn test_truncation_and_padding_use_display_width() {
+        // CJK name: 28 columns of name + ".txt", cut to 20 columns
+        let truncated =
+            truncate_filename_for_display("日本語のとても長いファイル名.txt".to_string(), 20);
+        assert_eq!(truncated, "日本語のとて....txt");
+        assert!(str_display_width(&truncated) <= 20);
+
+        // Combining accent: 13 columns (14 chars) fits in 13, unchanged
+        let decomposed = "cafe\u{0301}_menu.txt".to_string();
+        assert_eq!(
+            truncate_filename_for_display(decomposed.clone(), 13),
+            decomposed
+        );
+
+        // Never leave a combining mark without its base character
+        assert_eq!(take_display_width_suffix("中\u{0301}", 1), "");
+        assert_eq!(take_display_width_suffix("ae\u{0301}", 1), "e\u{0301}");
+
+        // Wide characters are not split across the limit
+        assert_eq!(truncate_with_ellipsis("😀😀😀😀", 6), "😀...");
+        assert_eq!(truncate_column_cell("ユーザー名", 8), "ユーザ~");
+
+        // Padding fills columns, not chars
+        assert_eq!(pad_to_display_width("中文", 6), "中文  ");
+        assert_eq!(pad_to_display_width("toolong", 3), "toolong");
+    }
 }
 
 /// Truncates a file name for display in CLI based on current TUI width settings
@@ -9933,7 +15042,9 @@ mod tests_formatting {
 ///
 /// # Edge Cases
 /// - If max_name_width is very small (≤ 8), still ensures minimum display
-/// - Handles Unicode characters correctly by counting chars, not bytes
+/// - Widths are terminal columns, not chars or bytes: CJK and emoji count
+///   2, combining marks 0 (see `str_display_width`), and a wide character
+///   is never split across the cut
 ///
 /// # Examples
 /// ```rust
@@ -9955,34 +15066,121 @@ fn truncate_filename_for_display(formatted_name: String, max_name_width: usize)
     let ellipsis = "...";
 
     // Check if truncation is needed
-    if formatted_name.chars().count() <= max_name_width {
+    if str_display_width(&formatted_name) <= max_name_width {
         return formatted_name;
     }
 
-    // Calculate how many characters we can take from the start
+    // Calculate how many columns we can take from the start
     // Ensure we don't underflow even with very small max_name_width
-    let prefix_length = max_name_width
+    let prefix_width = max_name_width
         .saturating_sub(FILENAME_SUFFIX_LENGTH)
         .saturating_sub(ellipsis.len());
 
     // Get prefix (start of the filename)
-    let prefix: String = formatted_name.chars().take(prefix_length).collect();
+    let prefix = take_display_width_prefix(&formatted_name, prefix_width);
 
     // Get suffix (end of the filename, including extension)
-    let suffix: String = formatted_name
-        .chars()
-        .skip(
-            formatted_name
-                .chars()
-                .count()
-                .saturating_sub(FILENAME_SUFFIX_LENGTH),
-        )
-        .collect();
+    let suffix = take_display_width_suffix(&formatted_name, FILENAME_SUFFIX_LENGTH);
 
     // Combine prefix, ellipsis, and suffix
     format!("{}{}{}", prefix, ellipsis, suffix)
 }
 
+/// Takes the start of a string, up to a number of terminal columns
+///
+/// # Arguments
+/// * `text` - Text to cut
+/// * `max_width` - Columns available
+///
+/// # Returns
+/// * `String` - Longest prefix whose display width fits; combining marks
+///   stay with their base character, and a wide character that would
+///   straddle the limit is left out
+fn take_display_width_prefix(text: &str, max_width: usize) -> String {
+    let mut prefix = String::new();
+    let mut used_width = 0;
+    for character in text.chars() {
+        let character_width = char_display_width(character);
+        if used_width + character_width > max_width {
+            break;
+        }
+        used_width += character_width;
+        prefix.push(character);
+    }
+    prefix
+}
+
+/// Takes the end of a string, up to a number of terminal columns
+///
+/// # Arguments
+/// * `text` - Text to cut
+/// * `max_width` - Columns available
+///
+/// # Returns
+/// * `String` - Longest suffix whose display width fits, never starting
+///   with a combining mark cut off from its base character
+fn take_display_width_suffix(text: &str, max_width: usize) -> String {
+    let mut reversed_suffix: Vec<char> = Vec::new();
+    let mut used_width = 0;
+    for character in text.chars().rev() {
+        let character_width = char_display_width(character);
+        if used_width + character_width > max_width {
+            break;
+        }
+        used_width += character_width;
+        reversed_suffix.push(character);
+    }
+
+    // Drop marks whose base character did not fit
+    while reversed_suffix
+        .last()
+        .is_some_and(|&character| char_display_width(character) == 0)
+    {
+        reversed_suffix.pop();
+    }
+
+    reversed_suffix.iter().rev().collect()
+}
+
+/// Left-aligns text in a field of terminal columns
+///
+/// # Purpose
+/// `format!("{:<width$}")` pads by char count, which misaligns the columns
+/// after a CJK or emoji name; this pads by display width instead.
+///
+/// # Arguments
+/// * `text` - Cell text (already truncated to fit)
+/// * `width` - Field width in terminal columns
+///
+/// # Returns
+/// * `String` - `text` followed by enough spaces to fill `width`
+///   (unchanged if it is already that wide)
+fn pad_to_display_width(text: &str, width: usize) -> String {
+    let padding = width.saturating_sub(str_display_width(text));
+    format!("{}{}", text, " ".repeat(padding))
+}
+
+/// Fits one line of a Get-Send menu (stacks, pocket dimensions) to the terminal
+///
+/// # Purpose
+/// Long paths would wrap and break up the numbered list; a line wider than
+/// the window is shortened the same way as listing names (start, "...",
+/// end), measured in terminal columns.
+///
+/// # Arguments
+/// * `menu_line` - Full line, e.g. "2. /home/user/projects/notes.txt"
+///
+/// # Returns
+/// * `String` - The line, shortened to the window width if needed
+///   (MAX_TUI_CHAR_LENGTH_DEFAULT when the size cannot be read)
+fn fit_menu_line(menu_line: String) -> String {
+    let window_columns = query_terminal_window_size()
+        .map(|window_size| window_size.cols as usize)
+        .unwrap_or(MAX_TUI_CHAR_LENGTH_DEFAULT);
+    // One column spare so the cursor never wraps onto the next line
+    truncate_filename_for_display(menu_line, window_columns.saturating_sub(1))
+}
+
 /// Formats the navigation legend with color-coded keyboard shortcuts
 ///
 /// # Purpose
@@ -10291,8 +15489,18 @@ fn count_directoryitems_ribbon(path: &PathBuf) -> (String, String, String) {
 /// * `directory_entries` - Vector of FileSystemEntry items to display
 /// * `current_directory_path` - PathBuf of the directory being displayed
 /// * `page_info` - Optional pagination info (current_page, total_pages)
-/// * `filter` - Current filter setting (Some('d'), Some('f'), or None)
-/// * `nav_state` - Navigation state containing TUI size adjustments
+/// * `tab_bar` - Tab bar line shown above the path (empty with a single tab)
+/// * `tree_guides` - Tree guide lines before each row's name, e.g. "├─ "
+///   (empty slice: flat listing)
+/// * `nav_state` - Navigation state: TUI size adjustments, the current
+///   filter (Some('d'), Some('f'), or None) and listing columns
+/// * `listing_frame` - The previous screen; only rows that differ from it
+///   are redrawn (pass a fresh `BuffyFrame::new()` for a full repaint)
+///
+/// # Returns
+/// * `io::Result<Option<usize>>` - Screen row (1-based) of item 1, counted
+///   from the header rows actually drawn; None when the frame is taller or
+///   wider than the terminal, so rows are not at fixed screen positions
 ///
 /// # Display Adjustments
 /// - Name column width adjusts based on nav_state TUI settings
@@ -10317,20 +15525,28 @@ fn display_directory_contents(
     directory_entries: &[FileSystemEntry],
     current_directory_path: &PathBuf,
     page_info: Option<(usize, usize)>,
-    filter: Option<char>,
+    tab_bar: &str,
+    tree_guides: &[String],
     nav_state: &NavigationState, // Add nav_state parameter
-) -> io::Result<()> {
-    // Clear screen
-    print!("\x1B[2J\x1B[1;1H");
+    listing_frame: &mut BuffyFrame,
+) -> io::Result<Option<usize>> {
+    // Build the screen into the frame (no clear: `present` redraws only
+    // the rows that changed since the previous listing)
+    listing_frame.begin_frame();
 
     // Calculate the actual name column width based on TUI settings
     let name_column_width = calculate_name_width_from_state(nav_state) as usize;
 
-    let filter_status = match filter {
+    let filter_status = match nav_state.current_filter {
         Some('d') => "[Directories only] ",
         Some('f') => "[Files only] ",
         _ => "",
     };
+    let tree_status = if tree_guides.is_empty() {
+        ""
+    } else {
+        "[Tree] "
+    };
 
     // Format the navigation legend with error handling
     let legend = match format_navigation_legend() {
@@ -10347,6 +15563,22 @@ fn display_directory_contents(
     // Returns ("?", "?", "?") if counting fails for any reason
     let (all_count, file_count, dir_count) = count_directoryitems_ribbon(current_directory_path);
 
+    // Shorten the path so the status line fits the window (one column
+    // spare, as for menu lines); the colored count letters stay intact
+    let status_width = str_display_width(&format!(
+        "{}{}f{} d{} a{} ",
+        filter_status, tree_status, file_count, dir_count, all_count
+    ));
+    let window_columns = query_terminal_window_size()
+        .map(|window_size| window_size.cols as usize)
+        .unwrap_or(MAX_TUI_CHAR_LENGTH_DEFAULT);
+    let path_text = truncate_filename_for_display(
+        escape_path_for_display(current_directory_path),
+        window_columns
+            .saturating_sub(1)
+            .saturating_sub(status_width),
+    );
+
     // Format path display with summary counts
     // Format: a{all} f{files} d{dirs} {path}
     // Letters are colored YELLOW for visibility
@@ -10361,32 +15593,61 @@ fn display_directory_contents(
         YELLOW,
         RESET,
         all_count, // all items of any kind all_count
-        current_directory_path.display()
+        path_text
     );
 
-    // Display legend and path with counts
-    println!("{}\n{}{}", legend, filter_status, path_display);
+    // Display legend, the tab bar on its own line (if several tabs), and
+    // the path with counts
+    writeln!(listing_frame, "{}", legend)?;
+    if !tab_bar.is_empty() {
+        writeln!(listing_frame, "{}", tab_bar)?;
+    }
+    writeln!(
+        listing_frame,
+        "{}{}{}",
+        filter_status, tree_status, path_display
+    )?;
+
+    // Optional columns chosen with "cols"; an ISO timestamp widens "Modified"
+    let listing_columns = nav_state.listing_columns;
+    let modified_width = if listing_columns.iso_timestamp {
+        ISO_TIMESTAMP_COLUMN_WIDTH
+    } else {
+        MODIFIED_COLUMN_WIDTH
+    };
 
     // Column headers with dynamic name width
-    println!(
-        "{:>4}  {:<width$} {:>5} {:>11}",
+    writeln!(
+        listing_frame,
+        "{:>4}  {:<width$} {:>5} {:>modified_width$}{}",
         " # ",
         "Name",
         "Size",
         "Modified",
-        width = name_column_width
-    );
+        format_optional_column_headers(&listing_columns),
+        width = name_column_width,
+        modified_width = modified_width
+    )?;
 
     // Separator line adjusted for dynamic width
-    let separator_length = 4 + name_column_width + 7 + 1 + 11;
-    println!(" {} ", "-".repeat(separator_length));
+    let separator_length =
+        4 + name_column_width + 7 + 1 + MODIFIED_COLUMN_WIDTH + listing_columns.extra_width();
+    writeln!(listing_frame, " {} ", "-".repeat(separator_length))?;
+
+    // Items start on the row after the header, however many rows it took
+    let first_item_screen_row = listing_frame.row_count();
 
     // Display entries with dynamic width
     for (entry_index, directory_entry) in directory_entries.iter().enumerate() {
-        let formatted_name = if directory_entry.is_directory {
-            format!("{}/", directory_entry.file_system_item_name)
-        } else {
-            directory_entry.file_system_item_name.clone()
+        let formatted_name = match tree_guides.get(entry_index) {
+            Some(tree_guide) => {
+                format!(
+                    "{}{}",
+                    tree_guide,
+                    format_entry_display_name(directory_entry)
+                )
+            }
+            None => format_entry_display_name(directory_entry),
         };
 
         // Use dynamic width for truncation
@@ -10398,16 +15659,29 @@ fn display_directory_contents(
             format_file_size(directory_entry.file_system_item_size_in_bytes)
         };
 
-        let time_display = format_timestamp(directory_entry.file_system_item_last_modified_time);
+        let time_display = if listing_columns.iso_timestamp {
+            format_iso_timestamp(directory_entry.file_system_item_last_modified_time)
+        } else {
+            format_timestamp(directory_entry.file_system_item_last_modified_time)
+        };
 
-        println!(
-            "{:>3}. {:<width$} {:>6} {:>11}",
+        // Pad by display width so CJK/emoji names keep the columns aligned
+        let row_text = format!(
+            "{:>3}. {} {:>6} {:>modified_width$}{}",
             entry_index + 1,
-            display_name,
+            pad_to_display_width(&display_name, name_column_width),
             size_display,
             time_display,
-            width = name_column_width
+            format_optional_column_cells(directory_entry, &listing_columns),
+            modified_width = modified_width
         );
+
+        // Raw-key mode: highlight the cursor row in reverse video
+        if nav_state.raw_key_mode && entry_index == nav_state.raw_key_cursor_row {
+            writeln!(listing_frame, "{}{}{}", REVERSE, row_text, RESET)?;
+        } else {
+            writeln!(listing_frame, "{}", row_text)?;
+        }
     }
 
     // Add pagination footer with TUI size info
@@ -10421,21 +15695,29 @@ fn display_directory_contents(
         );
 
         if total_pages > 1 {
-            println!(
-                "\x1b[1m{}--- Page {} of {}: up/down, j/k, </>, w/x, arrows, etc. Size: {} {} ---{}",
-                YELLOW, current_page, total_pages, tall_display, wide_display, RESET
-            );
+            writeln!(
+                listing_frame,
+                "{}{}--- Page {} of {}: up/down, j/k, </>, w/x, arrows, etc. Size: {} {} ---{}",
+                BOLD, YELLOW, current_page, total_pages, tall_display, wide_display, RESET
+            )?;
         } else {
             // Show size info even when only one page
-            println!(
-                "\x1b[1m{}--- (Re)Size: {} {} ---   See files' row counts using:  --row-counts{}",
-                YELLOW, tall_display, wide_display, RESET
-            );
+            writeln!(
+                listing_frame,
+                "{}{}--- (Re)Size: {} {} ---   See files' row counts using:  --row-counts{}",
+                BOLD, YELLOW, tall_display, wide_display, RESET
+            )?;
         }
     }
 
-    io::stdout().flush()?;
-    Ok(())
+    // One write for the whole screen; a resize since the last listing
+    // (or an invalidated frame) makes this a full repaint
+    let terminal_size = query_terminal_window_size()
+        .ok()
+        .map(|window_size| (window_size.rows, window_size.cols));
+    let rows_at_fixed_positions = listing_frame.fits_screen(terminal_size);
+    listing_frame.present(&mut io::stdout(), terminal_size)?;
+    Ok(rows_at_fixed_positions.then_some(first_item_screen_row))
 }
 
 #[cfg(test)]
@@ -10527,7 +15809,7 @@ mod displaytests {
 }
 
 /// Name of the data directory that contains File Fantastic configuration files
-const FF_DATA_DIRECTORY_NAME: &str = "ff_data";
+pub const FF_DATA_DIRECTORY_NAME: &str = "ff_data";
 
 /// Name of the configuration file that contains partner program paths
 const PARTNER_PROGRAMS_CONFIG_FILENAME: &str =
@@ -11200,12 +16482,14 @@ fn open_in_tmux_split(editor: &str, file_path: &PathBuf, split_type: &str) -> Re
         }
     };
 
-    // Build the command to run in the new split
-    let editor_command = format!("{} {}", editor, file_path.to_string_lossy());
+    // Build the command to run in the new split (raw path bytes kept)
+    let mut editor_command = OsString::from(format!("{} ", editor));
+    editor_command.push(file_path);
 
     // Create the tmux split with the editor
     let output = std::process::Command::new("tmux")
-        .args(["split-window", tmux_split_arg, &editor_command])
+        .args(["split-window", tmux_split_arg])
+        .arg(&editor_command)
         .output()
         .map_err(|e| {
             eprintln!("Failed to create tmux {} split: {}", split_direction, e);
@@ -11243,6 +16527,8 @@ fn open_in_tmux_split(editor: &str, file_path: &PathBuf, split_type: &str) -> Re
 /// * `-vsplit` or `--vertical-split-tmux` - Open in vertical tmux split (normalized to `--vertical-split-tmux`)
 /// * `-hsplit` or `--horizontal-split-tmux` - Open in horizontal tmux split (normalized to `--horizontal-split-tmux`)
 /// * `-rc` or `--rows-and-columns` - Analyze CSV file before opening (normalized to `--rows-and-columns`)
+/// * `-validate` or `--validate-csv` - Check CSV against its saved metadata (normalized to `--validate-csv`)
+/// * `-convert` or `--convert-csv` - Convert CSV to JSON/JSONL/Markdown/CSV (normalized to `--convert-csv`)
 ///
 /// # Examples
 /// * "vim -h" -> Some(("vim", "--headless"))
@@ -11264,6 +16550,10 @@ fn parse_special_flags(input: &str) -> Option<(String, String)> {
         ("--horizontal-split-tmux", "--horizontal-split-tmux"),
         ("-rc", "--rows-and-columns"),
         ("--rows-and-columns", "--rows-and-columns"),
+        ("-validate", "--validate-csv"),
+        ("--validate-csv", "--validate-csv"),
+        ("-convert", "--convert-csv"),
+        ("--convert-csv", "--convert-csv"),
     ];
 
     // Create a set of all valid flags (both terse and verbose) for quick lookup
@@ -11334,6 +16624,16 @@ mod flags_tests {
             parse_special_flags("code -hsplit"),
             Some(("code".to_string(), "--horizontal-split-tmux".to_string()))
         );
+
+        assert_eq!(
+            parse_special_flags("vi -validate"),
+            Some(("vi".to_string(), "--validate-csv".to_string()))
+        );
+
+        assert_eq!(
+            parse_special_flags("hx -convert"),
+            Some(("hx".to_string(), "--convert-csv".to_string()))
+        );
     }
 
     #[test]
@@ -11437,6 +16737,58 @@ fn is_csv_file(file_path: &PathBuf) -> bool {
     }
 }
 
+/// Checks if a file path points to a spreadsheet that -rc can import
+///
+/// # Arguments
+/// * `file_path` - The path to check
+///
+/// # Returns
+/// * `bool` - true if the file has an .xlsx, .xlsm or .ods extension (case-insensitive)
+fn is_spreadsheet_file(file_path: &Path) -> bool {
+    matches!(
+        file_path
+            .extension()
+            .map(|extension| extension.to_ascii_lowercase())
+            .as_ref()
+            .and_then(|extension| extension.to_str()),
+        Some("xlsx") | Some("xlsm") | Some("ods")
+    )
+}
+
+/// Handles -rc on an .xlsx or .ods file: import a sheet to CSV, then analyze it
+///
+/// # Arguments
+/// * `spreadsheet_path` - Path to the spreadsheet
+///
+/// # Returns
+/// * `Result<PathBuf>` - Path to the analysis results file, or error
+///
+/// # Workflow
+/// 1. Calls rc_import_spreadsheet_interactive (asks for a sheet if there are several)
+/// 2. Analyzes the new CSV with handle_csv_analysis
+///
+/// # Error Handling
+/// Returns error if import or analysis fails, allowing caller to re-prompt user
+fn handle_spreadsheet_analysis(spreadsheet_path: &Path) -> Result<PathBuf> {
+    let spreadsheet_path_str = spreadsheet_path.to_str().ok_or_else(|| {
+        FileFantasticError::Io(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            "Invalid file path - contains non-UTF8 characters",
+        ))
+    })?;
+
+    println!("{}Importing spreadsheet...{}", YELLOW, RESET);
+
+    match rc_import_spreadsheet_interactive(spreadsheet_path_str) {
+        Ok(csv_path) => handle_csv_analysis(&csv_path),
+        Err(e) => {
+            let error_msg = format!("Spreadsheet import failed: {}", e);
+            println!("{}Error: {}{}", RED, error_msg, RESET);
+            Err(FileFantasticError::Io(std::io::Error::other(error_msg)))
+        }
+    }
+}
+
 /// Handles CSV analysis when -rc flag is used
 ///
 /// # Arguments
@@ -11447,6 +16799,7 @@ fn is_csv_file(file_path: &PathBuf) -> bool {
 ///
 /// # Workflow
 /// 1. Calls rc_analyze_datafile_save_results_to_resultsfile
+///    (saves the report, then shows histograms / top-value bar charts)
 /// 2. Shows user the analysis will be opened
 /// 3. Waits for user confirmation
 /// 4. Returns the analysis file path
@@ -11465,7 +16818,7 @@ fn handle_csv_analysis(csv_path: &PathBuf) -> Result<PathBuf> {
     println!("{}Analyzing CSV file...{}", YELLOW, RESET);
 
     // Call the analysis function
-    match rc_analyze_datafile_save_results_to_resultsfile(csv_path_str) {
+    match rc_analyze_datafile_save_results_to_resultsfile(csv_path_str, true) {
         Ok(analysis_path) => {
             // Display the analysis file path
             println!("{}Analysis complete! Results saved to:{}", GREEN, RESET);
@@ -11497,6 +16850,112 @@ fn handle_csv_analysis(csv_path: &PathBuf) -> Result<PathBuf> {
     }
 }
 
+/// Handles CSV schema validation when -validate flag is used
+///
+/// # Arguments
+/// * `csv_path` - Path to the CSV file to validate against its saved metadata
+///
+/// # Returns
+/// * `Result<PathBuf>` - Path to the validation report file, or error
+///
+/// # Workflow
+/// 1. Calls rc_validate_datafile_against_metadata with the default metadata path
+/// 2. Shows user the validation report will be opened
+/// 3. Waits for user confirmation
+/// 4. Returns the validation report path
+///
+/// # Error Handling
+/// Returns error if validation cannot run (e.g. no saved metadata yet),
+/// allowing caller to re-prompt user
+fn handle_csv_validation(csv_path: &Path) -> Result<PathBuf> {
+    // Convert PathBuf to string for the validation function
+    let csv_path_str = csv_path.to_str().ok_or_else(|| {
+        FileFantasticError::Io(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            "Invalid file path - contains non-UTF8 characters",
+        ))
+    })?;
+
+    println!("{}Validating CSV against saved metadata...{}", YELLOW, RESET);
+
+    match rc_validate_datafile_against_metadata(csv_path_str, None) {
+        Ok(report_path) => {
+            println!("{}Validation complete! Results saved to:{}", GREEN, RESET);
+            println!("  {}", report_path.display());
+            println!();
+
+            // Prompt user before opening
+            println!(
+                "{}You will open the validation file. Press Enter to continue...{}",
+                YELLOW, RESET
+            );
+            let mut buf = String::new();
+            io::stdin().read_line(&mut buf).map_err(|e| {
+                eprintln!("Failed to read input: {}", e);
+                FileFantasticError::Io(e)
+            })?;
+
+            Ok(report_path)
+        }
+        Err(e) => {
+            let error_msg = format!("CSV validation failed: {}", e);
+            println!("{}Error: {}{}", RED, error_msg, RESET);
+            Err(FileFantasticError::Io(std::io::Error::other(error_msg)))
+        }
+    }
+}
+
+/// Handles CSV conversion when -convert flag is used
+///
+/// # Arguments
+/// * `csv_path` - Path to the CSV file to convert
+///
+/// # Returns
+/// * `Result<PathBuf>` - Path to the converted file, or error
+///
+/// # Workflow
+/// 1. Calls rc_convert_datafile_interactive, which asks for format, columns and filters
+/// 2. Shows user the converted file will be opened
+/// 3. Waits for user confirmation
+/// 4. Returns the converted file path
+///
+/// # Error Handling
+/// Returns error if conversion fails or is cancelled,
+/// allowing caller to re-prompt user
+fn handle_csv_conversion(csv_path: &Path) -> Result<PathBuf> {
+    // Convert PathBuf to string for the conversion function
+    let csv_path_str = csv_path.to_str().ok_or_else(|| {
+        FileFantasticError::Io(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            "Invalid file path - contains non-UTF8 characters",
+        ))
+    })?;
+
+    println!("{}Converting CSV...{}", YELLOW, RESET);
+
+    match rc_convert_datafile_interactive(csv_path_str) {
+        Ok(converted_path) => {
+            println!();
+            println!(
+                "{}You will open the converted file. Press Enter to continue...{}",
+                YELLOW, RESET
+            );
+            let mut buf = String::new();
+            io::stdin().read_line(&mut buf).map_err(|e| {
+                eprintln!("Failed to read input: {}", e);
+                FileFantasticError::Io(e)
+            })?;
+
+            Ok(converted_path)
+        }
+        Err(e) => {
+            let error_msg = format!("CSV conversion failed: {}", e);
+            println!("{}Error: {}{}", RED, error_msg, RESET);
+            Err(FileFantasticError::Io(std::io::Error::other(error_msg)))
+        }
+    }
+}
+
 /// Opens a file with user-selected editor, partner program, or system default
 ///
 /// # Purpose
@@ -11690,16 +17149,16 @@ fn open_file(file_path: &PathBuf, lines_editor_session_path: &PathBuf) -> Result
         let exe_path = std::env::current_exe().map_err(|e| FileFantasticError::Io(e))?;
 
         // Build the command as a single string with full binary path
-        let editor_command = format!(
-            "{} {} --session {}",
-            exe_path.to_string_lossy(),
-            file_path.to_string_lossy(),
-            lines_editor_session_path.to_string_lossy()
-        );
+        // (OsString, so a file name that is not valid UTF-8 reaches the editor intact)
+        let mut editor_command = join_os_name("", exe_path.as_os_str(), " ");
+        editor_command.push(file_path);
+        editor_command.push(" --session ");
+        editor_command.push(lines_editor_session_path);
 
         // Create vertical split (tmux -v = vertical split = horizontal panes)
         let output = std::process::Command::new("tmux")
-            .args(["split-window", "-v", &editor_command])
+            .args(["split-window", "-v"])
+            .arg(&editor_command)
             .output()
             .map_err(|e| FileFantasticError::Io(e))?;
 
@@ -11737,16 +17196,16 @@ fn open_file(file_path: &PathBuf, lines_editor_session_path: &PathBuf) -> Result
         let exe_path = std::env::current_exe().map_err(|e| FileFantasticError::Io(e))?;
 
         // Build the command as a single string with full binary path
-        let editor_command = format!(
-            "{} {} --session {}",
-            exe_path.to_string_lossy(),
-            file_path.to_string_lossy(),
-            lines_editor_session_path.to_string_lossy()
-        );
+        // (OsString, so a file name that is not valid UTF-8 reaches the editor intact)
+        let mut editor_command = join_os_name("", exe_path.as_os_str(), " ");
+        editor_command.push(file_path);
+        editor_command.push(" --session ");
+        editor_command.push(lines_editor_session_path);
 
         // Create horizontal split (tmux -h = horizontal split = vertical panes)
         let output = std::process::Command::new("tmux")
-            .args(["split-window", "-h", &editor_command])
+            .args(["split-window", "-h"])
+            .arg(&editor_command)
             .output()
             .map_err(|e| FileFantasticError::Io(e))?;
 
@@ -11883,12 +17342,73 @@ fn open_file(file_path: &PathBuf, lines_editor_session_path: &PathBuf) -> Result
             return open_file(file_path, lines_editor_session_path); // Re-prompt
         }
 
-        // Check if -rc flag is present and file is CSV
+        // Check if -convert, -validate or -rc flag is present and file is CSV
+        // Validation runs instead of -rc when both are given, since -rc
+        // would first overwrite the saved metadata being validated against
         let mut file_to_open = file_path.clone();
-        if flags.contains("-rc") || flags.contains("--rows-and-columns") {
+        if flags.contains("--convert-csv") {
+            if is_csv_file(file_path) {
+                match handle_csv_conversion(file_path) {
+                    Ok(converted_path) => {
+                        // Open the converted file instead of the original CSV
+                        file_to_open = converted_path;
+                    }
+                    Err(e) => {
+                        // Conversion failed or was cancelled, let user try again
+                        println!(
+                            "{}Conversion failed: {}. Press Enter to try again...{}",
+                            RED, e, RESET
+                        );
+                        let mut buf = String::new();
+                        io::stdin().read_line(&mut buf).map_err(|e| {
+                            eprintln!("Failed to read input: {}", e);
+                            FileFantasticError::Io(e)
+                        })?;
+                        return open_file(file_path, lines_editor_session_path); // Re-prompt
+                    }
+                }
+            } else {
+                println!(
+                    "{}Note: -convert flag ignored (not a CSV file){}",
+                    YELLOW, RESET
+                );
+            }
+        } else if flags.contains("--validate-csv") {
             if is_csv_file(file_path) {
-                // Perform CSV analysis
-                match handle_csv_analysis(file_path) {
+                match handle_csv_validation(file_path) {
+                    Ok(report_path) => {
+                        // Use the validation report instead of the original CSV
+                        file_to_open = report_path;
+                    }
+                    Err(e) => {
+                        // Validation failed to run, let user try again
+                        println!(
+                            "{}Validation failed: {}. Press Enter to try again...{}",
+                            RED, e, RESET
+                        );
+                        let mut buf = String::new();
+                        io::stdin().read_line(&mut buf).map_err(|e| {
+                            eprintln!("Failed to read input: {}", e);
+                            FileFantasticError::Io(e)
+                        })?;
+                        return open_file(file_path, lines_editor_session_path); // Re-prompt
+                    }
+                }
+            } else {
+                println!(
+                    "{}Note: -validate flag ignored (not a CSV file){}",
+                    YELLOW, RESET
+                );
+            }
+        } else if flags.contains("-rc") || flags.contains("--rows-and-columns") {
+            if is_csv_file(file_path) || is_spreadsheet_file(file_path) {
+                // Perform CSV analysis (spreadsheets are imported to CSV first)
+                let analysis_result = if is_spreadsheet_file(file_path) {
+                    handle_spreadsheet_analysis(file_path)
+                } else {
+                    handle_csv_analysis(file_path)
+                };
+                match analysis_result {
                     Ok(analysis_path) => {
                         // Use the analysis file instead of the original CSV
                         file_to_open = analysis_path;
@@ -11909,7 +17429,7 @@ fn open_file(file_path: &PathBuf, lines_editor_session_path: &PathBuf) -> Result
                 }
             } else {
                 // Not a CSV file, silently ignore -rc flag
-                println!("{}Note: -rc flag ignored (not a CSV file){}", YELLOW, RESET);
+                println!("{}Note: -rc flag ignored (not a CSV or spreadsheet file){}", YELLOW, RESET);
             }
         }
 
@@ -12741,6 +18261,7 @@ Distance between 'test' and '' is 4
 /// # Usage Context
 /// Used in the `fuzzy_search` method to find files matching a partial query,
 /// allowing for approximate/inexact matches when users don't know the exact filename.
+/// The `z` jump uses it to match a fragment against visited directory names.
 ///
 /// # Examples
 /// ```
@@ -12749,29 +18270,54 @@ Distance between 'test' and '' is 4
 /// assert_eq!(levenshtein_distance("", "test")?, 4);
 /// ```
 fn levenshtein_distance(s: &str, t: &str) -> Result<usize> {
-    // Define maximum allowed string length for safety
-    const MAX_STRING_LENGTH: usize = 1000;
-
     // Convert strings to vectors of chars for easier indexing
     // Do this FIRST to get correct character counts
     let s_chars: Vec<char> = s.chars().collect();
     let t_chars: Vec<char> = t.chars().collect();
 
-    // Get the CHARACTER lengths, not byte lengths
-    let m = s_chars.len();
-    let n = t_chars.len();
+    levenshtein_distance_of_units(&s_chars, &t_chars, s, t)
+}
+
+/// Calculates the Levenshtein distance between two sequences of units
+///
+/// # Purpose
+/// The algorithm behind `levenshtein_distance`, over any comparable unit.
+/// Fuzzy search uses it with `NameUnit`s so a file name that is not valid
+/// UTF-8 is compared by its raw bytes rather than by '�' placeholders.
+///
+/// # Arguments
+/// * `s_units` - First sequence (e.g. a file name)
+/// * `t_units` - Second sequence (e.g. the search term)
+/// * `s_label` - How to name the first sequence in a length error
+/// * `t_label` - How to name the second sequence in a length error
+///
+/// # Returns
+/// * `Ok(usize)` - Single-unit edits needed to turn one into the other
+/// * `Err(FileFantasticError)` - If either sequence exceeds the length limit
+fn levenshtein_distance_of_units<T: PartialEq>(
+    s_units: &[T],
+    t_units: &[T],
+    s_label: &str,
+    t_label: &str,
+) -> Result<usize> {
+    // Define maximum allowed string length for safety
+    const MAX_STRING_LENGTH: usize = 1000;
+
+    // Lengths in units (characters), not bytes
+    let m = s_units.len();
+    let n = t_units.len();
 
     // Check length constraints before expensive operations
     if m > MAX_STRING_LENGTH {
         return Err(FileFantasticError::LevenshteinError {
-            filename: s.to_string(),
+            filename: s_label.to_string(),
             length: m,
         });
     }
 
     if n > MAX_STRING_LENGTH {
         return Err(FileFantasticError::LevenshteinError {
-            filename: t.to_string(),
+            filename: t_label.to_string(),
             length: n,
         });
     }
@@ -12783,12 +18329,12 @@ fn levenshtein_distance(s: &str, t: &str) -> Result<usize> {
         // Return error for the longer string
         if m >= n {
             return Err(FileFantasticError::LevenshteinError {
-                filename: s.to_string(),
+                filename: s_label.to_string(),
                 length: m,
             });
         } else {
             return Err(FileFantasticError::LevenshteinError {
-                filename: t.to_string(),
+                filename: t_label.to_string(),
                 length: n,
             });
         }
@@ -12808,7 +18354,7 @@ fn levenshtein_distance(s: &str, t: &str) -> Result<usize> {
     let mut v1: Vec<usize> = vec![0; n + 1];
 
     // Iterate through each character of s
-    for i in 0..m {
+    for (i, s_unit) in s_units.iter().enumerate() {
         // First element of v1 is the deletion cost
         v1[0] = i + 1;
 
@@ -12816,7 +18362,7 @@ fn levenshtein_distance(s: &str, t: &str) -> Result<usize> {
         for j in 0..n {
             let deletion_cost = v0[j + 1] + 1;
             let insertion_cost = v1[j] + 1;
-            let substitution_cost = v0[j] + if s_chars[i] == t_chars[j] { 0 } else { 1 };
+            let substitution_cost = v0[j] + if *s_unit == t_units[j] { 0 } else { 1 };
 
             v1[j + 1] = deletion_cost.min(insertion_cost).min(substitution_cost);
         }
@@ -13626,63 +19172,402 @@ fn parse_all_command_line_args() -> Result<CommandLineArgs> {
         starting_line: optional_flags.starting_line,
     };
 
-    #[cfg(debug_assertions)]
-    eprintln!("[DEBUG] === Command-line arguments parsed successfully ===");
+    #[cfg(debug_assertions)]
+    eprintln!("[DEBUG] === Command-line arguments parsed successfully ===");
+
+    Ok(cli_args)
+}
+
+/// Represents the starting location for File Fantastic application
+///
+/// # Project Context
+/// File Fantastic (FF) has dual modes:
+/// 1. File editing - Opens Lines editor for a specific file
+/// 2. Directory navigation - Browses and manages files in a directory
+///
+/// This struct separates these concerns, allowing FF to:
+/// - Open a file in Lines editor first (if user provided a file path)
+/// - Then start FF navigation in the appropriate directory
+///
+/// # Fields
+/// * `file_to_open` - Optional file path to open in Lines editor before starting FF
+///   - `Some(path)` when user provides a file path as argument
+///   - `None` when user provides a directory or no argument
+///
+/// * `directory_to_browse` - Directory where FF will start browsing
+///   - Guaranteed to be a valid, absolute, accessible directory path
+///   - Never None - always falls back to current directory if needed
+///   - If user provides a file, this is the file's parent directory
+///   - If user provides a directory, this is that directory
+///   - If no args or invalid args, this is the current working directory
+///
+/// # Guarantees
+/// - All paths are absolute (never relative)
+/// - `directory_to_browse` always exists and is accessible
+/// - `file_to_open` exists if `Some` (but may not be readable - caller's responsibility)
+struct StartingLocation {
+    /// Optional file path to open in Lines editor before starting FF
+    file_to_open: Option<PathBuf>,
+
+    /// Directory where FF will start browsing (guaranteed valid)
+    directory_to_browse: PathBuf,
+}
+
+/// Checks if input is a "previous page" command
+/// Supports multiple key options: j, <, [
+fn is_pagination_up_command(input: &str) -> bool {
+    matches!(
+        input,
+        "w" | "k" | "<" | "[" | "up" | "prev" | "," | "+" | "\x1b[A"
+    )
+}
+
+/// Checks if input is a "next page" command
+/// Supports multiple key options: k, >, ]
+fn is_pagination_down_command(input: &str) -> bool {
+    matches!(
+        input,
+        "x" | "j" | ">" | "]" | "down" | "next" | "." | "-" | "\x1b[B"
+    )
+}
+
+/// Checks if a command only changes what the listing shows
+///
+/// # Purpose
+/// These commands print nothing of their own and start no other program, so
+/// the screen still matches the last listing frame and the next redraw can
+/// send only the rows that changed. Every other command invalidates the frame.
+///
+/// # Arguments
+/// * `input` - The trimmed user input
+///
+/// # Returns
+/// * `bool` - True for empty input, "keys", and pagination keys
+fn is_listing_only_command(input: &str) -> bool {
+    input.is_empty()
+        || input == "keys"
+        || is_pagination_up_command(input)
+        || is_pagination_down_command(input)
+}
+
+/// What the main loop should do with one keypress in raw-key mode
+#[derive(Debug, PartialEq, Eq)]
+enum RawKeyNavigation {
+    /// Cursor or page moved (or key ignored): just redraw
+    Redraw,
+    /// Run this as if it had been typed and Enter pressed
+    Command(String),
+    /// ':' pressed: read one typed command line
+    TypedCommandLine,
+}
+
+/// Translates one keypress into cursor movement or an existing ff command
+///
+/// # Purpose
+/// In raw-key mode ff reads single keys instead of lines. Movement keys
+/// move the highlighted row (changing page at the edges); everything else
+/// becomes the same command string the typed-line flow already handles,
+/// so no NavigationAction needs a second implementation.
+///
+/// # Arguments
+/// * `key` - The key (or mouse report) read by `read_raw_key(true)`
+/// * `nav_state` - Holds the highlighted row; page index is kept in sync
+/// * `dir_view` - Paginated view of the current directory
+///
+/// # Returns
+/// * `RawKeyNavigation` - Redraw, a command string, or typed-line request
+///
+/// # Key Map
+/// - j/k, Down/Up: move highlight one row (wraps onto next/previous page)
+/// - Left/Right, PageUp/PageDown: previous/next page
+/// - Home/End: first/last row of the page
+/// - Enter: open the highlighted item (its page-relative number)
+/// - Backspace: parent directory ("b"); Ctrl-C: quit ("q")
+/// - ':': type a full command line (numbers, search, tall+N, ...)
+/// - Any other printable key: that single-letter command
+/// - Mouse wheel: previous/next page; left click on a row: open that item
+fn handle_raw_navigation_key(
+    key: RawKey,
+    nav_state: &mut NavigationState,
+    dir_view: &mut DirectoryView,
+) -> RawKeyNavigation {
+    let page_length = dir_view.current_page_entries().len();
+    let has_next_page = dir_view.get_current_page() + 1 < dir_view.total_pages();
+
+    match key {
+        RawKey::Up | RawKey::Char('k') => {
+            if nav_state.raw_key_cursor_row > 0 {
+                nav_state.raw_key_cursor_row -= 1;
+            } else if dir_view.prev_page() {
+                nav_state.current_page_index = dir_view.get_current_page();
+                // Land on the last row of the previous page
+                nav_state.raw_key_cursor_row =
+                    dir_view.current_page_entries().len().saturating_sub(1);
+            }
+            RawKeyNavigation::Redraw
+        }
+        RawKey::Down | RawKey::Char('j') => {
+            if nav_state.raw_key_cursor_row + 1 < page_length {
+                nav_state.raw_key_cursor_row += 1;
+            } else if has_next_page && dir_view.next_page() {
+                nav_state.current_page_index = dir_view.get_current_page();
+                nav_state.raw_key_cursor_row = 0;
+            }
+            RawKeyNavigation::Redraw
+        }
+        RawKey::Left
+        | RawKey::PageUp
+        | RawKey::Mouse(MouseEvent {
+            kind: MouseEventKind::WheelUp,
+            ..
+        }) => {
+            if dir_view.prev_page() {
+                nav_state.current_page_index = dir_view.get_current_page();
+                nav_state.raw_key_cursor_row = 0;
+            }
+            RawKeyNavigation::Redraw
+        }
+        RawKey::Right
+        | RawKey::PageDown
+        | RawKey::Mouse(MouseEvent {
+            kind: MouseEventKind::WheelDown,
+            ..
+        }) => {
+            if has_next_page && dir_view.next_page() {
+                nav_state.current_page_index = dir_view.get_current_page();
+                nav_state.raw_key_cursor_row = 0;
+            }
+            RawKeyNavigation::Redraw
+        }
+        RawKey::Home => {
+            nav_state.raw_key_cursor_row = 0;
+            RawKeyNavigation::Redraw
+        }
+        RawKey::End => {
+            nav_state.raw_key_cursor_row = page_length.saturating_sub(1);
+            RawKeyNavigation::Redraw
+        }
+        RawKey::Enter => {
+            if page_length == 0 {
+                RawKeyNavigation::Redraw
+            } else {
+                // Displayed numbers are 1-based and page-relative
+                RawKeyNavigation::Command((nav_state.raw_key_cursor_row + 1).to_string())
+            }
+        }
+        RawKey::Backspace => RawKeyNavigation::Command(String::from("b")),
+        RawKey::CtrlC => RawKeyNavigation::Command(String::from("q")),
+        RawKey::Char(':') => RawKeyNavigation::TypedCommandLine,
+        RawKey::Char(key_char) => RawKeyNavigation::Command(key_char.to_string()),
+        RawKey::Mouse(MouseEvent {
+            kind: MouseEventKind::LeftPress,
+            row,
+            ..
+        }) => {
+            // Screen row -> displayed number, checked against the lookup table
+            // built for the page on screen
+            let clicked_item = nav_state
+                .listing_first_item_screen_row
+                .and_then(|first_item_row| (row as usize).checked_sub(first_item_row))
+                .map(|row_offset| row_offset + 1)
+                .filter(|display_number| nav_state.lookup_item(*display_number).is_some());
+
+            match clicked_item {
+                Some(display_number) => {
+                    nav_state.raw_key_cursor_row = display_number - 1;
+                    RawKeyNavigation::Command(display_number.to_string())
+                }
+                None => RawKeyNavigation::Redraw,
+            }
+        }
+        RawKey::Mouse(_) | RawKey::Escape | RawKey::Unknown => RawKeyNavigation::Redraw,
+    }
+}
+
+#[cfg(test)]
+mod raw_key_navigation_tests {
+    use super::*;
+
+    /// Builds `count` plain file entries named file_0, file_1, ...
+    fn make_entries(count: usize) -> Vec<FileSystemEntry> {
+        (0..count)
+            .map(|index| FileSystemEntry {
+                file_system_item_name: format!("file_{}", index).into(),
+                file_system_item_path: PathBuf::from(format!("file_{}", index)),
+                file_system_item_size_in_bytes: 0,
+                file_system_item_last_modified_time: SystemTime::UNIX_EPOCH,
+                is_directory: false,
+                unix_mode_bits: None,
+                owner_user_id: None,
+                owner_group_id: None,
+                symlink_target: None,
+                is_symlink: false,
+                is_broken_symlink: false,
+            })
+            .collect()
+    }
+
+    /// Cursor moves within a page, wraps across pages, and Enter opens the highlighted row.
+    #[test]
+    fn test_handle_raw_navigation_key_moves_and_opens() {
+        let entries = make_entries(5);
+        let mut dir_view = DirectoryView::new(&entries, 3);
+        let mut nav_state = NavigationState::new();
+
+        // Down twice stays on page 1, third Down wraps to page 2 row 0
+        for _ in 0..2 {
+            handle_raw_navigation_key(RawKey::Char('j'), &mut nav_state, &mut dir_view);
+        }
+        assert_eq!(nav_state.raw_key_cursor_row, 2);
+        handle_raw_navigation_key(RawKey::Down, &mut nav_state, &mut dir_view);
+        assert_eq!(dir_view.get_current_page(), 1);
+        assert_eq!(nav_state.current_page_index, 1);
+        assert_eq!(nav_state.raw_key_cursor_row, 0);
+
+        // Down at the last row of the last page does nothing
+        handle_raw_navigation_key(RawKey::End, &mut nav_state, &mut dir_view);
+        assert_eq!(nav_state.raw_key_cursor_row, 1);
+        handle_raw_navigation_key(RawKey::Down, &mut nav_state, &mut dir_view);
+        assert_eq!(dir_view.get_current_page(), 1);
+        assert_eq!(nav_state.raw_key_cursor_row, 1);
+
+        // Enter opens the page-relative number of the highlighted row
+        assert_eq!(
+            handle_raw_navigation_key(RawKey::Enter, &mut nav_state, &mut dir_view),
+            RawKeyNavigation::Command(String::from("2"))
+        );
+
+        // Up from row 0 goes to the last row of the previous page
+        handle_raw_navigation_key(RawKey::Home, &mut nav_state, &mut dir_view);
+        handle_raw_navigation_key(RawKey::Char('k'), &mut nav_state, &mut dir_view);
+        assert_eq!(dir_view.get_current_page(), 0);
+        assert_eq!(nav_state.raw_key_cursor_row, 2);
+    }
+
+    /// Non-movement keys map onto the existing typed commands.
+    #[test]
+    fn test_handle_raw_navigation_key_commands() {
+        let entries = make_entries(0);
+        let mut dir_view = DirectoryView::new(&entries, 3);
+        let mut nav_state = NavigationState::new();
+
+        assert_eq!(
+            handle_raw_navigation_key(RawKey::Backspace, &mut nav_state, &mut dir_view),
+            RawKeyNavigation::Command(String::from("b"))
+        );
+        assert_eq!(
+            handle_raw_navigation_key(RawKey::CtrlC, &mut nav_state, &mut dir_view),
+            RawKeyNavigation::Command(String::from("q"))
+        );
+        assert_eq!(
+            handle_raw_navigation_key(RawKey::Char('s'), &mut nav_state, &mut dir_view),
+            RawKeyNavigation::Command(String::from("s"))
+        );
+        assert_eq!(
+            handle_raw_navigation_key(RawKey::Char(':'), &mut nav_state, &mut dir_view),
+            RawKeyNavigation::TypedCommandLine
+        );
+
+        // Empty directory: movement and Enter are harmless redraws
+        assert_eq!(
+            handle_raw_navigation_key(RawKey::Down, &mut nav_state, &mut dir_view),
+            RawKeyNavigation::Redraw
+        );
+        assert_eq!(
+            handle_raw_navigation_key(RawKey::Enter, &mut nav_state, &mut dir_view),
+            RawKeyNavigation::Redraw
+        );
+        assert_eq!(nav_state.raw_key_cursor_row, 0);
+    }
+
+    /// Wheel turns page; a left click opens the clicked row via the lookup table.
+    #[test]
+    fn test_handle_raw_navigation_mouse() {
+        let entries = make_entries(5);
+        let mut dir_view = DirectoryView::new(&entries, 3);
+        let mut nav_state = NavigationState::new();
+        nav_state.update_lookup_table(dir_view.current_page_entries());
+
+        let mouse = |kind, row| {
+            RawKey::Mouse(MouseEvent {
+                kind,
+                row,
+                column: 10,
+            })
+        };
+
+        // No listing at known screen rows yet: clicks are ignored
+        assert_eq!(
+            handle_raw_navigation_key(
+                mouse(MouseEventKind::LeftPress, 6),
+                &mut nav_state,
+                &mut dir_view
+            ),
+            RawKeyNavigation::Redraw
+        );
+        nav_state.listing_first_item_screen_row = Some(5);
+
+        // Row 6 is the second item on screen
+        assert_eq!(
+            handle_raw_navigation_key(
+                mouse(MouseEventKind::LeftPress, 6),
+                &mut nav_state,
+                &mut dir_view
+            ),
+            RawKeyNavigation::Command(String::from("2"))
+        );
+        assert_eq!(nav_state.raw_key_cursor_row, 1);
 
-    Ok(cli_args)
-}
+        // Header rows and rows below the last item are ignored
+        for row in [1, 4, 8] {
+            assert_eq!(
+                handle_raw_navigation_key(
+                    mouse(MouseEventKind::LeftPress, row),
+                    &mut nav_state,
+                    &mut dir_view
+                ),
+                RawKeyNavigation::Redraw
+            );
+        }
 
-/// Represents the starting location for File Fantastic application
-///
-/// # Project Context
-/// File Fantastic (FF) has dual modes:
-/// 1. File editing - Opens Lines editor for a specific file
-/// 2. Directory navigation - Browses and manages files in a directory
-///
-/// This struct separates these concerns, allowing FF to:
-/// - Open a file in Lines editor first (if user provided a file path)
-/// - Then start FF navigation in the appropriate directory
-///
-/// # Fields
-/// * `file_to_open` - Optional file path to open in Lines editor before starting FF
-///   - `Some(path)` when user provides a file path as argument
-///   - `None` when user provides a directory or no argument
-///
-/// * `directory_to_browse` - Directory where FF will start browsing
-///   - Guaranteed to be a valid, absolute, accessible directory path
-///   - Never None - always falls back to current directory if needed
-///   - If user provides a file, this is the file's parent directory
-///   - If user provides a directory, this is that directory
-///   - If no args or invalid args, this is the current working directory
-///
-/// # Guarantees
-/// - All paths are absolute (never relative)
-/// - `directory_to_browse` always exists and is accessible
-/// - `file_to_open` exists if `Some` (but may not be readable - caller's responsibility)
-struct StartingLocation {
-    /// Optional file path to open in Lines editor before starting FF
-    file_to_open: Option<PathBuf>,
+        // Releases and right clicks do nothing
+        assert_eq!(
+            handle_raw_navigation_key(
+                mouse(MouseEventKind::Release, 5),
+                &mut nav_state,
+                &mut dir_view
+            ),
+            RawKeyNavigation::Redraw
+        );
 
-    /// Directory where FF will start browsing (guaranteed valid)
-    directory_to_browse: PathBuf,
-}
+        // Wheel down/up changes page
+        handle_raw_navigation_key(
+            mouse(MouseEventKind::WheelDown, 5),
+            &mut nav_state,
+            &mut dir_view,
+        );
+        assert_eq!(dir_view.get_current_page(), 1);
+        handle_raw_navigation_key(
+            mouse(MouseEventKind::WheelUp, 5),
+            &mut nav_state,
+            &mut dir_view,
+        );
+        assert_eq!(dir_view.get_current_page(), 0);
+    }
 
-/// Checks if input is a "previous page" command
-/// Supports multiple key options: j, <, [
-fn is_pagination_up_command(input: &str) -> bool {
-    matches!(
-        input,
-        "w" | "k" | "<" | "[" | "up" | "prev" | "," | "+" | "\x1b[A"
-    )
-}
+    /// Only commands that just move around the listing keep the diff frame.
+    #[test]
+    fn test_is_listing_only_command() {
+        assert!(is_listing_only_command(""));
+        assert!(is_listing_only_command("keys"));
+        assert!(is_listing_only_command("j"));
+        assert!(is_listing_only_command("k"));
 
-/// Checks if input is a "next page" command
-/// Supports multiple key options: k, >, ]
-fn is_pagination_down_command(input: &str) -> bool {
-    matches!(
-        input,
-        "x" | "j" | ">" | "]" | "down" | "next" | "." | "-" | "\x1b[B"
-    )
+        assert!(!is_listing_only_command("3"));
+        assert!(!is_listing_only_command("b"));
+        assert!(!is_listing_only_command("q"));
+        assert!(!is_listing_only_command("tall+2"));
+    }
 }
 
 #[cfg(test)]
@@ -14137,26 +20022,28 @@ mod archive_tests_2 {
 /// These constants provide color and style formatting for terminal output.
 /// Using ANSI escape sequences for maximum compatibility.
 mod ansi_colors {
+    use super::ThemedSgr;
+
     /// Reset all formatting to default
-    pub const RESET: &str = "\x1b[0m";
+    pub const RESET: ThemedSgr = ThemedSgr("\x1b[0m");
 
     /// Bold text for headers
-    pub const BOLD: &str = "\x1b[1m";
+    pub const BOLD: ThemedSgr = ThemedSgr("\x1b[1m");
 
     /// Cyan color for commands
-    pub const CYAN: &str = "\x1b[36m";
+    pub const CYAN: ThemedSgr = ThemedSgr("\x1b[36m");
 
     /// Green color for examples
-    pub const GREEN: &str = "\x1b[32m";
+    pub const GREEN: ThemedSgr = ThemedSgr("\x1b[32m");
 
     /// Yellow color for warnings or important notes
-    pub const YELLOW: &str = "\x1b[33m";
+    pub const YELLOW: ThemedSgr = ThemedSgr("\x1b[33m");
 
     /// Bright white for emphasis
-    pub const BRIGHT_WHITE: &str = "\x1b[97m";
+    pub const BRIGHT_WHITE: ThemedSgr = ThemedSgr("\x1b[97m");
 
     /// Magenta for section numbers
-    pub const MAGENTA: &str = "\x1b[35m";
+    pub const MAGENTA: ThemedSgr = ThemedSgr("\x1b[35m");
 }
 
 /// Help section identifiers for menu navigation
@@ -14175,6 +20062,7 @@ enum HelpSection {
     GetSendModeBlurb,
     ModularViewModes,
     Configuration,
+    ColorThemes,
 }
 
 /// Main help menu header text
@@ -14247,22 +20135,23 @@ const HELP_SECTION_NAVIGATION: &str = r#"
 
  BASIC NAVIGATION:
    [number]              Enter item number to open file/directory
-   b                     Go back to parent directory
-   q                     Quit File Fantastic
-   [Enter]               Reset view and refresh
-
- PAGINATION:
-   When viewing long lists:
-   - Items are automatically paginated
-   - Navigate pages with standard controls
-       up/down = j/k, </>, w/x, +/- arrows keys, etc
-   - Current page shown in status
-
- POCKET DIMENSIONS: saved locations in Get-Send Mode
-   Often you want to navigate somewhere, but then come back!
-   - Save current location as a "Pocket Dimension"
-   - Jump between any saved 'pocket dimensions.'
-   - Pocket Dimentions should retain all your filters & sorts.
+   b  /  q  /  [Enter]   Parent directory  /  Quit  /  Reset view and refresh
+
+ PAGINATION: Long lists are paginated; the current page is shown in status.
+   Navigate pages: up/down = j/k, </>, w/x, +/- arrows keys, etc
+
+ ONE-KEY MODE:  keys   Toggle: no Enter needed; j/k/arrows move the
+   highlight, Enter opens, Backspace = b, ':' types a full command;
+   mouse: click a row to open it, the wheel turns pages
+
+ HISTORY & JUMPS:  Often you want to navigate somewhere, then come back!
+   back / fwd            Previous / next directory (like a web browser)
+   z [part of a name]    Jump to the best match among often- and recently-
+                         visited directories (z alone: pick from a list)
+   Pocket dimensions: saved spots with filters & sorts, in Get-Send Mode
+ TABS: tab (new, here)  tab N (switch)  tabn/tabp (next/prev)  tabc (close)
+   Each tab has its own sort, filter, page & history; Get-Send 9 copies to a tab
+   cmp [N|path]  Compare side by side & sync with the next tab, tab N or a path
 
  Press Enter to return to help menu..."#;
 
@@ -14285,6 +20174,8 @@ sort is easy: toggle! (And use --count-rows to view/sort data-lines!)
    d                     Show only directories
    f                     Show only files
    [Enter]               Reset filter (show all items)
+ TREE VIEW:  tree (toggle)   tree N (open/close directory N, 6 levels deep)
+   Sort and filter apply at every level; numbers select items as usual
 
  SORT ORDER (reverse the order):
    - First press: ascending order (A-Z, smallest-largest, oldest-newest)
@@ -14324,16 +20215,16 @@ const HELP_SECTION_FILE_OPERATIONS: &str = r#"
  FILE OPENING:           (after entering the file number)
    Empty Enter	         Open file with default editor
    {editor}              Open file with chosen editor
-   {editor} -h           Headless! Open in current terminal
-                           Alternative: -- headless
+   {editor} -h           Headless! Open in current terminal (--headless)
    {editor} -vsplit      Open in tmux vertical split
                            Alternative: --vertical-split-tmux
    {editor} -hsplit      Open in tmux horizontal split
                            Alternative: --horizontal-split-tmux
+ PEEK: [number] -p  preview text/hex/dir    [number] -i  info: stat, type, lines
  CSV ANALYSIS:
-    [number] -rc         Analyzes a CSV file (rows, columns, stats)
-                         Opens analysis in temporary file
-                           Alternative: --rows-and-columns
+    [number] -rc         CSV/xlsx/ods stats, plots (--rows-and-columns)
+    [number] -validate   Checks CSV vs. saved -rc metadata (--validate-csv)
+    [number] -convert    CSV to JSON/JSONL/Markdown/CSV (--convert-csv)
  EXAMPLES:
   hx                    Open file with Helix editor, in a new window
   vi -h                 Headless: Open with vi editor in same terminal
@@ -14362,8 +20253,9 @@ const HELP_SECTION_GET_SEND_MODE: &str = r#"
    3. Save current location as pocket dimension
    4. Go to pocket dimension
    5. View stacks & pocket dimensions
-   6. Archive file/directory 'a': zip/timestamp
-   7. Clear all stacks"#;
+   6. Archive file/directory 'a': zip/timestamp      7. Clear all stacks
+   8. Link: symlink here TO a stack item (shown as name@, broken: name@!)
+   9. Send: copy an item here TO another tab's directory (see: tab)"#;
 
 /// Get-Send Mode
 const HELP_SECTION_VIEW_MODES: &str = r#"
@@ -14372,10 +20264,13 @@ const HELP_SECTION_VIEW_MODES: &str = r#"
   COUNT ROWS / LINES: See how many rows the files in a directory have.
   Enter mode with '--row-counts' or '--line-counts'
 
-  Commands: (n)ame sort, (c)ount sort, (h)eader, (Enter) reset, (b/q)uit
-  Sort by name or count (toggle to reverse sort), and
+  Columns: Code, Comment, Blank, Lines; per-language totals at the bottom
+  Sort: (n)ame, (c)ount, c(o)de, co(m)ment, b(l)ank (toggle to reverse)
   'h' removes the header from the row count: Count only data rows
-
+  'r' recursive: whole project (skips target/ .git/ node_modules/)
+  'e' / 'j' export the table as CSV (open it with -rc) or JSON
+  's' saves a snapshot to ff_data; 'd' shows growth since a snapshot
+  Enter a file's number to open it in Lines at line 1
 
   MAKE YOUR OWN DIRECTORY-VIEW MODULES:
     Make your own custom views and add them to FF.
@@ -14404,11 +20299,11 @@ const HELP_SECTION_TERMINAL: &str = r#"
    vsplit                Create vertical tmux split (current directory)
    hsplit                Create horizontal tmux split (current directory)
 
- DISPLAY RESIZING:       'N' here is whatever number you enter.
-   tall+N                Increase display height by N rows
-   tall-N                Decrease display height by N rows
+ DISPLAY RESIZING:  ff fits the window (re-fits on resize); adjust on top:
+   tall+N / tall-N       Increase / decrease display height by N rows
    wide+N                Increase display width by N chars
    wide-N                Decrease display width by N chars
+   cols perm owner iso ext link   Extra columns (cols alone: default)
 
  When ff exits, it will tell you where you last were,
  and the ~bash line to run to go back there in a terminal.
@@ -14440,6 +20335,32 @@ const HELP_SECTION_CONFIGURATION: &str = r#"
 
  Press Enter to return to help menu... "#;
 
+/// Color themes help section content
+const HELP_SECTION_COLOR_THEMES: &str = r#"
+ ═══ COLOR THEMES, NO_COLOR & MONOCHROME ═══
+
+ ff, Lines and the pasty clipboard view share one color theme,
+ chosen at start-up (first match wins):
+
+   TERM=dumb, or output piped     -> plain (no escape codes at all)
+   NO_COLOR=1 (any non-empty)     -> mono  (bold and reverse video only)
+   FF_THEME=light ff              -> the named theme
+   ff_data/color_theme.txt        -> first word in the file, e.g. light
+   otherwise                      -> default
+
+ BUILT-IN THEMES:   default   light   mono   plain
+ YOUR OWN THEME:  ff_data/themes/NAME.txt, then use NAME as above.
+   One 'slot = SGR parameters' per line, over the default palette:
+     # warmer text for a cream background
+     yellow = 38;5;130
+     green  = 32;1
+     bg_white = 43
+   Slots: reset bold dim italic underline blink reverse hidden
+     black red green yellow blue magenta cyan white default_fg gray
+     bright_white, and bg_ versions of the 8 colors, default_bg
+
+ Press Enter to return to help menu... "#;
+
 /// Display the main help menu and handle section selection
 ///
 /// This function presents the user with a numbered menu of help sections
@@ -14529,13 +20450,18 @@ pub fn display_help_menu_system() -> Result<()> {
             ansi_colors::RESET
         );
         println!(
-            "  {}11.{} View help menu doc in editor (vi/nano)",
+            "  {}11.{} Color Themes, NO_COLOR & Monochrome",
+            ansi_colors::MAGENTA,
+            ansi_colors::RESET
+        );
+        println!(
+            "  {}12.{} View help menu doc in editor (vi/nano)",
             ansi_colors::GREEN,
             ansi_colors::RESET
         );
         println!();
         print!(
-            "{}Enter section number (1-10) or 'q' to quit: {}",
+            "{}Enter section number (1-12) or 'q' to quit: {}",
             ansi_colors::BOLD,
             ansi_colors::RESET
         );
@@ -14562,7 +20488,8 @@ pub fn display_help_menu_system() -> Result<()> {
             "8" => display_help_section_content(HelpSection::ModularViewModes)?,
             "9" => display_help_section_content(HelpSection::TerminalManagement)?,
             "10" => display_help_section_content(HelpSection::Configuration)?,
-            "11" => open_complete_help_in_editor()?,
+            "11" => display_help_section_content(HelpSection::ColorThemes)?,
+            "12" => open_complete_help_in_editor()?,
             "q" | "quit" | "exit" => {
                 println!(
                     "{}Exiting help system...{}",
@@ -14573,7 +20500,7 @@ pub fn display_help_menu_system() -> Result<()> {
             }
             _ => {
                 println!(
-                    "{}Try again...Please enter 1-10 or 'q'.{}",
+                    "{}Try again...Please enter 1-12 or 'q'.{}",
                     ansi_colors::YELLOW,
                     ansi_colors::RESET
                 );
@@ -14608,6 +20535,7 @@ fn display_help_section_content(section: HelpSection) -> Result<()> {
         HelpSection::ModularViewModes => HELP_SECTION_VIEW_MODES,
         HelpSection::TerminalManagement => HELP_SECTION_TERMINAL,
         HelpSection::Configuration => HELP_SECTION_CONFIGURATION,
+        HelpSection::ColorThemes => HELP_SECTION_COLOR_THEMES,
     };
 
     // Display with color formatting
@@ -14682,6 +20610,12 @@ fn open_complete_help_in_editor() -> Result<()> {
     complete_help.push_str("-".repeat(78).as_str());
     complete_help.push_str("\n");
     complete_help.push_str(strip_ansi_codes(HELP_SECTION_CONFIGURATION).as_str());
+    complete_help.push_str("\n\n");
+
+    complete_help.push_str("COLOR THEMES, NO_COLOR & MONOCHROME\n");
+    complete_help.push_str("-".repeat(78).as_str());
+    complete_help.push('\n');
+    complete_help.push_str(strip_ansi_codes(HELP_SECTION_COLOR_THEMES).as_str());
 
     // Create temp file
     let temp_dir = env::temp_dir();
@@ -14840,6 +20774,9 @@ pub fn display_quick_usage_info() -> Result<()> {
     println!("  -h, --help    Show complete help menu.");
     println!("  -v, --version Show build version info.");
     println!("  --source      Get ff source code.");
+    println!("  --rc FILE [--format json|toml|text] [--bins N] [--sheet NAME|N]");
+    println!("                Print CSV analysis to stdout (no TUI).");
+    println!("                Exit: 0 ok, 2 usage, 3 file, 4 CSV, 5 config");
     println!();
     println!("{}EXAMPLES:{}", ansi_colors::GREEN, ansi_colors::RESET);
     println!("  ff            Open in current directory.");
@@ -14920,6 +20857,7 @@ mod helpview_tests {
         assert!(!HELP_SECTION_GET_SEND_MODE.is_empty());
         assert!(!HELP_SECTION_TERMINAL.is_empty());
         assert!(!HELP_SECTION_CONFIGURATION.is_empty());
+        assert!(!HELP_SECTION_COLOR_THEMES.is_empty());
     }
 
     /// Test that help sections fit in 80x24 terminal
@@ -14939,6 +20877,7 @@ mod helpview_tests {
             HELP_SECTION_GET_SEND_MODE,
             HELP_SECTION_TERMINAL,
             HELP_SECTION_CONFIGURATION,
+            HELP_SECTION_COLOR_THEMES,
         ];
 
         for (i, section) in sections.iter().enumerate() {
@@ -14978,10 +20917,18 @@ const FF_SOURCE_FILES: &[SourcedFile] = &[
         "src/csv_processor_module.rs",
         include_str!("csv_processor_module.rs"),
     ),
+    SourcedFile::new(
+        "src/csv_convert_module.rs",
+        include_str!("csv_convert_module.rs"),
+    ),
     SourcedFile::new(
         "src/error_types_module.rs",
         include_str!("error_types_module.rs"),
     ),
+    SourcedFile::new(
+        "src/spreadsheet_import_module.rs",
+        include_str!("spreadsheet_import_module.rs"),
+    ),
     SourcedFile::new(
         "src/buffy_format_write_module.rs",
         include_str!("buffy_format_write_module.rs"),
@@ -15006,6 +20953,10 @@ const FF_SOURCE_FILES: &[SourcedFile] = &[
         "src/row_line_count_tui_module.rs",
         include_str!("row_line_count_tui_module.rs"),
     ),
+    SourcedFile::new(
+        "src/color_theme_module.rs",
+        include_str!("color_theme_module.rs"),
+    ),
     SourcedFile::new("README.md", include_str!("../README.md")),
     SourcedFile::new("LICENSE", include_str!("../LICENSE")),
     SourcedFile::new(
@@ -15362,6 +21313,35 @@ pub fn simple_make_lines_editor_session_directory(
     Ok(session_path)
 }
 
+/// Exits ff after Ctrl-C or SIGTERM, leaving the terminal usable
+///
+/// # Purpose
+/// Called from the main loop when `pending_termination_signal()` is set.
+/// Any raw-key `RawTerminal` has already been dropped (restoring termios)
+/// by the time control is back in the loop; this resets colors/cursor,
+/// prints the usual "continue from here" line, and exits.
+///
+/// # Arguments
+/// * `signal_number` - SIGINT or SIGTERM
+/// * `current_directory_path` - Where the user was, for the cd hint
+///
+/// # Exit Codes
+/// - 130: SIGINT (Ctrl-C)
+/// - 143: SIGTERM
+///
+/// Normal quit exits 0, errors exit 1.
+fn exit_ff_on_termination_signal(signal_number: i32, current_directory_path: &Path) -> ! {
+    reset_terminal_display_state();
+    println!(
+        "ff stopped by {} (no unsaved changes in ff).",
+        termination_signal_name(signal_number)
+    );
+    println!("To continue from this location, run:");
+    println!("cd {}", current_directory_path.display());
+    let _ = io::stdout().flush();
+    std::process::exit(termination_exit_code(signal_number));
+}
+
 /// Public entry point for File Fantastic file manager module
 ///
 /// # Usage as a Module
@@ -15437,6 +21417,11 @@ pub fn file_fantastic() -> Result<PathBuf> {
         return Ok(PathBuf::new());
     }
 
+    // Headless CSV analysis: print results to stdout and exit with a stable code
+    if let Some(rc_flag_position) = args.iter().position(|arg| arg == "--rc") {
+        std::process::exit(rc_run_headless_cli(&args[rc_flag_position + 1..]));
+    }
+
     // Check if help was requested
     if check_for_help_flag_in_args(&args) {
         println!("Help requested!");
@@ -15535,6 +21520,22 @@ pub fn file_fantastic() -> Result<PathBuf> {
     let mut nav_state = NavigationState::new();
     let mut state_manager = NavigationStateManager::new(); // Initialize here at the top
 
+    // Visited directories across sessions, for "z {fragment}"
+    let mut frecency_database = FrecencyDatabase::load_from_ff_data();
+
+    // Tabs: the active tab is current_directory_path + nav_state, the rest wait here
+    let mut browser_tabs = BrowserTabs::new();
+
+    // Redraw when the window is resized (non-fatal: sizes are re-queried each draw anyway)
+    if let Err(e) = install_window_resize_handler() {
+        eprintln!("Warning: Window resize detection unavailable: {}", e);
+    }
+
+    // Ctrl-C / SIGTERM: exit through the main loop so the terminal is restored
+    if let Err(e) = install_termination_handler() {
+        eprintln!("Warning: Clean Ctrl-C handling unavailable: {}", e);
+    }
+
     //  ========================================
     //  Set Up & Build The Path for Lines Editor
     //  ========================================
@@ -15542,6 +21543,15 @@ pub fn file_fantastic() -> Result<PathBuf> {
     nav_state.lines_editor_session_path =
         simple_make_lines_editor_session_directory(session_time_base)?;
 
+    // Directory the raw-key highlight belongs to (highlight resets on directory change)
+    let mut raw_key_cursor_directory = PathBuf::new();
+
+    // Last directory counted in history and frecency (one count per visit, not per redraw)
+    let mut last_visited_directory = PathBuf::new();
+
+    // The listing as last drawn; each redraw only sends the rows that changed
+    let mut listing_frame = BuffyFrame::new();
+
     loop {
         // Read directory contents with proper error handling
         let mut all_entries = match read_directory_contents(&current_directory_path) {
@@ -15626,31 +21636,84 @@ pub fn file_fantastic() -> Result<PathBuf> {
                 file_system_item_size_in_bytes: entry.file_system_item_size_in_bytes,
                 file_system_item_last_modified_time: entry.file_system_item_last_modified_time,
                 is_directory: entry.is_directory,
+                unix_mode_bits: entry.unix_mode_bits,
+                owner_user_id: entry.owner_user_id,
+                owner_group_id: entry.owner_group_id,
+                symlink_target: entry.symlink_target.clone(),
+                is_symlink: entry.is_symlink,
+                is_broken_symlink: entry.is_broken_symlink,
             })
             .collect();
 
+        // Tree listing: expanded directories followed by their contents
+        let (directory_entries, tree_guides) = if nav_state.tree_view_mode {
+            build_tree_view_rows(directory_entries, &nav_state)
+        } else {
+            (directory_entries, Vec::new())
+        };
+
         // Create paginated view starting at current page from navigation state
-        // Calculate items per page based on TUI height settings
-        let items_per_page = calculate_items_per_page_from_state(&nav_state);
+        // Calculate items per page based on TUI height settings, less the
+        // tab bar's own row when more than one tab is open
+        let tab_bar_rows = if browser_tabs.tab_count() > 1 { 1 } else { 0 };
+        let items_per_page = calculate_items_per_page_from_state(&nav_state)
+            .saturating_sub(tab_bar_rows)
+            .max(1);
         let mut dir_view = DirectoryView::new(&directory_entries, items_per_page);
         dir_view.set_current_page(nav_state.current_page_index);
 
+        if raw_key_cursor_directory != current_directory_path {
+            nav_state.raw_key_cursor_row = 0;
+            raw_key_cursor_directory = current_directory_path.clone();
+        }
+
+        // History and frecency: count each directory change, however it happened
+        if last_visited_directory != current_directory_path {
+            nav_state
+                .directory_history
+                .record_visit(&current_directory_path);
+            frecency_database.record_visit(&current_directory_path, unix_seconds_now());
+            if let Err(e) = frecency_database.save() {
+                eprintln!("Warning: Could not save directory frecency database: {}", e);
+                frecency_database.disable_saving();
+            }
+            last_visited_directory = current_directory_path.clone();
+        }
+
         // Inner loop for pagination within the same directory
         loop {
+            // Ctrl-C / SIGTERM arrived (poll and raw reads wake up for it)
+            if let Some(signal_number) = pending_termination_signal() {
+                exit_ff_on_termination_signal(signal_number, &current_directory_path);
+            }
+
             // Get current page entries
             let page_entries = dir_view.current_page_entries();
             nav_state.update_lookup_table(page_entries);
 
+            // Keep the raw-key highlight on a real row (page length can shrink)
+            nav_state.raw_key_cursor_row = nav_state
+                .raw_key_cursor_row
+                .min(page_entries.len().saturating_sub(1));
+
             // Display with pagination info and filter status
             match display_directory_contents(
                 page_entries,
                 &current_directory_path,
                 Some((dir_view.current_page + 1, dir_view.total_pages())),
-                nav_state.current_filter,
+                &browser_tabs.tab_bar(&current_directory_path),
+                tree_guides
+                    .get(dir_view.current_page_range())
+                    .unwrap_or_default(),
                 &nav_state, // Pass nav_state for TUI size calculations
+                &mut listing_frame,
             ) {
-                Ok(_) => {}
+                Ok(first_item_screen_row) => {
+                    nav_state.listing_first_item_screen_row = first_item_screen_row;
+                }
                 Err(e) => {
+                    nav_state.listing_first_item_screen_row = None;
+                    listing_frame.invalidate();
                     eprintln!("Error displaying directory contents: {}", e);
                     eprintln!("Press Enter to try again or 'q' to quit...");
 
@@ -15667,28 +21730,106 @@ pub fn file_fantastic() -> Result<PathBuf> {
                 }
             }
 
-            // print!("\n>> "); // for extra space, maybe easier to see
-            print!(">> "); // saves space
-            match io::stdout().flush() {
-                Ok(_) => {}
-                Err(e) => {
-                    eprintln!("Failed to flush stdout: {}", e);
-                    // Non-critical error, continue
+            let mut user_input = String::new();
+
+            // Catch Ctrl-C / SIGTERM only while waiting for the command line
+            // below (the first SIGINT would be lost in other prompts' reads)
+            let _ = install_termination_handler();
+
+            if nav_state.raw_key_mode {
+                // Single keypress: j/k/arrows move, Enter opens, ':' for a typed line
+                print!("keys> j/k move, Enter open, Bksp back, : type, :keys off ");
+                let _ = io::stdout().flush();
+
+                let key = match read_raw_key_or_resize(true) {
+                    Ok(Some(key)) => key,
+                    // Interrupted by SIGTERM: the loop top exits cleanly
+                    Ok(None) | Err(_) if pending_termination_signal().is_some() => continue,
+                    // Window resized while waiting: re-layout and redraw
+                    Ok(None) => {
+                        take_window_resized();
+                        break;
+                    }
+                    Err(e) => {
+                        // No usable terminal: fall back to typed commands
+                        eprintln!("\nRaw-key mode unavailable ({}), using typed commands", e);
+                        nav_state.raw_key_mode = false;
+                        continue;
+                    }
+                };
+
+                match handle_raw_navigation_key(key, &mut nav_state, &mut dir_view) {
+                    RawKeyNavigation::Redraw => continue,
+                    RawKeyNavigation::Command(command) => {
+                        // Keys are not echoed in raw mode; end the prompt line
+                        println!();
+                        user_input = command;
+                    }
+                    RawKeyNavigation::TypedCommandLine => {
+                        print!("\n: ");
+                        let _ = io::stdout().flush();
+                        // A plain blocking read: let Ctrl-C stop ff at once
+                        let _ = restore_default_termination_handling();
+                        if let Err(e) = io::stdin().read_line(&mut user_input) {
+                            eprintln!("Error reading input: {}", e);
+                            continue;
+                        }
+                    }
+                }
+            } else {
+                // print!("\n>> "); // for extra space, maybe easier to see
+                print!(">> "); // saves space
+                match io::stdout().flush() {
+                    Ok(_) => {}
+                    Err(e) => {
+                        eprintln!("Failed to flush stdout: {}", e);
+                        // Non-critical error, continue
+                    }
                 }
-            }
 
-            let mut user_input = String::new();
-            match io::stdin().read_line(&mut user_input) {
-                Ok(_) => {}
-                Err(e) => {
-                    eprintln!("Error reading input: {}", e);
-                    continue; // Try again
+                // Window resized while waiting: break inner loop to re-layout and redraw
+                if let Ok(true) = wait_for_stdin_or_resize() {
+                    take_window_resized();
+                    break;
+                }
+
+                match io::stdin().read_line(&mut user_input) {
+                    Ok(_) => {}
+                    Err(e) => {
+                        eprintln!("Error reading input: {}", e);
+                        continue; // Try again
+                    }
                 }
             }
 
             // Trim the input for processing
             let trimmed_input = user_input.trim();
 
+            // Signal arrived during a read that is not woken by poll (piped stdin)
+            if let Some(signal_number) = pending_termination_signal() {
+                exit_ff_on_termination_signal(signal_number, &current_directory_path);
+            }
+
+            // Commands run cooked-mode prompts that do not poll the flag
+            // (menus, y/N questions, the line-count and rows-and-columns
+            // TUIs): there Ctrl-C and SIGTERM stop ff as they normally would
+            let _ = restore_default_termination_handling();
+
+            // Re-borrow the page (raw-key handling may have borrowed dir_view mutably)
+            let page_entries = dir_view.current_page_entries();
+
+            // Anything but a listing-only command may print over the screen
+            // (messages, prompts, other programs): repaint it fully next time
+            if !is_listing_only_command(trimmed_input) {
+                listing_frame.invalidate();
+            }
+
+            // Toggle single-keypress navigation
+            if trimmed_input == "keys" {
+                nav_state.raw_key_mode = !nav_state.raw_key_mode;
+                continue;
+            }
+
             // Handle pagination commands with multiple key options
             if is_pagination_up_command(trimmed_input) {
                 if dir_view.prev_page() {
@@ -15717,12 +21858,32 @@ pub fn file_fantastic() -> Result<PathBuf> {
                         // Update selected item in nav_state
                         nav_state.set_selected_item(Some(number));
 
+                        if entry.is_broken_symlink {
+                            // Nothing to open: say where the link points
+                            let target_display = entry
+                                .symlink_target
+                                .as_ref()
+                                .map(|target| escape_path_for_display(target))
+                                .unwrap_or_else(|| String::from("?"));
+                            println!(
+                                "Broken link: {} -> {} (target does not exist)",
+                                escape_name_for_display(&entry.file_system_item_name),
+                                target_display
+                            );
+                            println!("Press Enter to continue...");
+                            let _ = io::stdin().read_line(&mut String::new());
+                            listing_frame.invalidate();
+                            continue; // Stay in inner loop
+                        }
+
                         if entry.is_directory {
                             current_directory_path = entry.file_system_item_path.clone();
                             // IMPORTANT: Clear the selected item when changing directories
                             nav_state.selected_item_index = None;
                             break; // Break inner loop to read new directory
                         } else {
+                            // The editor/viewer takes over the whole screen
+                            listing_frame.invalidate();
                             match handle_file_open(
                                 &entry.file_system_item_path,
                                 &nav_state.lines_editor_session_path,
@@ -15797,6 +21958,21 @@ pub fn file_fantastic() -> Result<PathBuf> {
                             // Break inner loop to refresh display with new settings
                             break;
                         }
+                        NavigationAction::SetListingColumns(listing_columns) => {
+                            nav_state.listing_columns = listing_columns;
+                            println!("Listing columns: {}", listing_columns.describe());
+
+                            // Brief pause so user can see the feedback
+                            std::thread::sleep(std::time::Duration::from_millis(500));
+
+                            // Break inner loop to re-layout with the new columns
+                            break;
+                        }
+                        NavigationAction::InvalidListingColumns(message) => {
+                            println!("{}", message);
+                            println!("Press Enter to continue...");
+                            let _ = io::stdin().read_line(&mut String::new());
+                        }
                         NavigationAction::Filter(filter_char) => {
                             nav_state.set_filter(filter_char);
                             nav_state.current_page_index = 0; // Reset to first page after filter change
@@ -15826,6 +22002,183 @@ pub fn file_fantastic() -> Result<PathBuf> {
                                 }
                             }
                         }
+                        NavigationAction::HistoryBack | NavigationAction::HistoryForward => {
+                            let history_step = if matches!(action, NavigationAction::HistoryBack) {
+                                nav_state.directory_history.go_back()
+                            } else {
+                                nav_state.directory_history.go_forward()
+                            };
+                            match history_step {
+                                Some(history_directory) => {
+                                    current_directory_path = history_directory;
+                                    nav_state.current_page_index = 0; // Reset to first page
+                                    nav_state.selected_item_index = None; // clears fields
+                                    break; // Break inner loop to read new directory
+                                }
+                                None => {
+                                    println!("No directory to go to in that direction.");
+                                    println!("Press Enter to continue...");
+                                    let _ = io::stdin().read_line(&mut String::new());
+                                }
+                            }
+                        }
+                        NavigationAction::OpenTab
+                        | NavigationAction::CloseTab
+                        | NavigationAction::SwitchToTab(_)
+                        | NavigationAction::CycleTab(_) => {
+                            let tab_changed = match action {
+                                NavigationAction::OpenTab => browser_tabs
+                                    .open_tab(&mut current_directory_path, &mut nav_state),
+                                NavigationAction::CloseTab => browser_tabs
+                                    .close_active_tab(&mut current_directory_path, &mut nav_state),
+                                NavigationAction::SwitchToTab(tab_number) => browser_tabs
+                                    .switch_to(
+                                        tab_number.wrapping_sub(1),
+                                        &mut current_directory_path,
+                                        &mut nav_state,
+                                    ),
+                                _ => browser_tabs.cycle(
+                                    matches!(action, NavigationAction::CycleTab(true)),
+                                    &mut current_directory_path,
+                                    &mut nav_state,
+                                ),
+                            };
+                            if tab_changed {
+                                // Showing a tab is not a new visit to its directory
+                                last_visited_directory = current_directory_path.clone();
+                                break; // Break inner loop to show the tab
+                            }
+                            println!(
+                                "Tab unchanged ({} open, at most {}; the last tab stays open). Press Enter...",
+                                browser_tabs.tab_count(),
+                                MAX_BROWSER_TABS
+                            );
+                            let _ = io::stdin().read_line(&mut String::new());
+                        }
+                        NavigationAction::PreviewItem(item_path) => {
+                            if let Err(e) = run_file_preview(&item_path) {
+                                eprintln!("Preview failed: {}", e);
+                                println!("Press Enter to continue...");
+                                let _ = io::stdin().read_line(&mut String::new());
+                            }
+                        }
+                        NavigationAction::ShowItemInfo(item_path) => {
+                            if let Err(e) = run_item_info_panel(&item_path) {
+                                eprintln!("Info failed: {}", e);
+                                println!("Press Enter to continue...");
+                                let _ = io::stdin().read_line(&mut String::new());
+                            }
+                        }
+                        NavigationAction::ToggleTreeView => {
+                            nav_state.tree_view_mode = !nav_state.tree_view_mode;
+                            break; // Break inner loop to rebuild the listing
+                        }
+                        NavigationAction::ToggleTreeExpansion(item_number) => {
+                            let directory_path = match nav_state.lookup_item(item_number) {
+                                Some(item_info)
+                                    if item_info.item_type == FileSystemItemType::Directory =>
+                                {
+                                    item_info.item_path.clone()
+                                }
+                                _ => {
+                                    println!(
+                                        "Item {} is not a directory on this page. Press Enter to continue...",
+                                        item_number
+                                    );
+                                    let _ = io::stdin().read_line(&mut String::new());
+                                    continue;
+                                }
+                            };
+
+                            // Level of the directory below the current one (its items are one deeper)
+                            let directory_level = directory_path
+                                .strip_prefix(&current_directory_path)
+                                .map(|relative_path| relative_path.components().count())
+                                .unwrap_or(usize::MAX);
+                            if nav_state.tree_view_mode
+                                && nav_state.tree_expanded_directories.remove(&directory_path)
+                            {
+                                break; // Collapsed: rebuild the listing
+                            }
+                            if directory_level >= TREE_VIEW_MAX_DEPTH {
+                                println!(
+                                    "The tree shows at most {} levels. Press Enter to continue...",
+                                    TREE_VIEW_MAX_DEPTH
+                                );
+                                let _ = io::stdin().read_line(&mut String::new());
+                                continue;
+                            }
+                            nav_state.tree_view_mode = true;
+                            nav_state.tree_expanded_directories.insert(directory_path);
+                            break; // Break inner loop to rebuild the listing (same page)
+                        }
+                        NavigationAction::CompareDirectories(compare_target) => {
+                            // Step 1: Pick the right side: next tab, tab N, or a path
+                            let tab_directories =
+                                browser_tabs.tab_directories(&current_directory_path);
+                            let right_directory = if compare_target.is_empty() {
+                                if tab_directories.len() > 1 {
+                                    let next_tab_index =
+                                        (browser_tabs.active_tab_index + 1) % tab_directories.len();
+                                    Some(tab_directories[next_tab_index].clone())
+                                } else {
+                                    None
+                                }
+                            } else if let Ok(tab_number) = compare_target.parse::<usize>() {
+                                tab_number
+                                    .checked_sub(1)
+                                    .and_then(|tab_index| tab_directories.get(tab_index))
+                                    .cloned()
+                            } else {
+                                let compare_path = current_directory_path.join(&compare_target);
+                                Some(fs::canonicalize(&compare_path).unwrap_or(compare_path))
+                            };
+
+                            // Step 2: Compare (and sync) until the user comes back
+                            match right_directory {
+                                Some(right_directory) if right_directory.is_dir() => {
+                                    if let Err(e) = run_directory_compare_mode(
+                                        &current_directory_path,
+                                        &right_directory,
+                                        &nav_state,
+                                    ) {
+                                        eprintln!("Compare failed: {}", e);
+                                        println!("Press Enter to continue...");
+                                        let _ = io::stdin().read_line(&mut String::new());
+                                    }
+                                    break; // Break inner loop to re-read (sync may change it)
+                                }
+                                _ => {
+                                    println!(
+                                        "Compare with what? Use 'cmp' with a second tab open, 'cmp N' for tab N, or 'cmp path'."
+                                    );
+                                    println!("Press Enter to continue...");
+                                    let _ = io::stdin().read_line(&mut String::new());
+                                }
+                            }
+                        }
+                        NavigationAction::FrecencyJump(fragment) => {
+                            let jump_target = if fragment.is_empty() {
+                                select_frecent_directory(&frecency_database)?
+                            } else {
+                                let ranked_matches =
+                                    frecency_database.ranked_matches(&fragment, unix_seconds_now());
+                                if ranked_matches.is_empty() {
+                                    println!(
+                                        "No visited directory matches '{}'. Press Enter to continue...",
+                                        fragment
+                                    );
+                                    let _ = io::stdin().read_line(&mut String::new());
+                                }
+                                ranked_matches.into_iter().next()
+                            };
+                            if let Some(target_directory) = jump_target {
+                                current_directory_path = target_directory;
+                                nav_state.current_page_index = 0; // Reset to first page
+                                nav_state.selected_item_index = None; // clears fields
+                                break; // Break inner loop to read new directory
+                            }
+                        }
                         NavigationAction::OpenFile(ref path) => {
                             match handle_file_open(path, &nav_state.lines_editor_session_path) {
                                 Ok(_) => {}
@@ -16185,7 +22538,14 @@ pub fn file_fantastic() -> Result<PathBuf> {
                                             for (i, file) in
                                                 state_manager.file_path_stack.iter().enumerate()
                                             {
-                                                println!("  {}. {}", i + 1, file.display());
+                                                println!(
+                                                    "{}",
+                                                    fit_menu_line(format!(
+                                                        "  {}. {}",
+                                                        i + 1,
+                                                        file.display()
+                                                    ))
+                                                );
                                             }
                                         }
 
@@ -16196,7 +22556,14 @@ pub fn file_fantastic() -> Result<PathBuf> {
                                                 .iter()
                                                 .enumerate()
                                             {
-                                                println!("  {}. {}", i + 1, dir.display());
+                                                println!(
+                                                    "{}",
+                                                    fit_menu_line(format!(
+                                                        "  {}. {}",
+                                                        i + 1,
+                                                        dir.display()
+                                                    ))
+                                                );
                                             }
                                         }
 
@@ -16207,10 +22574,13 @@ pub fn file_fantastic() -> Result<PathBuf> {
                                                 dimensions.iter().enumerate()
                                             {
                                                 println!(
-                                                    "  {}. {} - {}",
-                                                    i + 1,
-                                                    nickname,
-                                                    state.description
+                                                    "{}",
+                                                    fit_menu_line(format!(
+                                                        "  {}. {} - {}",
+                                                        i + 1,
+                                                        nickname,
+                                                        state.description
+                                                    ))
                                                 );
                                             }
                                         }
@@ -16245,6 +22615,42 @@ pub fn file_fantastic() -> Result<PathBuf> {
                                             println!("All stacks and pocket dimensions cleared.");
                                         }
                                     }
+                                    GetSendModeAction::LinkItemFromStack => {
+                                        match state_manager.interactive_get_item_from_stack() {
+                                            Ok(Some(retrieval_result)) => {
+                                                match create_symlink_to_stack_item(
+                                                    &retrieval_result.path,
+                                                    &current_directory_path,
+                                                ) {
+                                                    Ok(link_path) => {
+                                                        println!(
+                                                            "✓ Link created: {} -> {}",
+                                                            link_path.display(),
+                                                            retrieval_result.path.display()
+                                                        );
+                                                    }
+                                                    Err(e) => {
+                                                        eprintln!("✗ Link creation failed: {}", e);
+                                                    }
+                                                }
+                                                println!("\nPress Enter to continue...");
+                                                let _ = io::stdin().read_line(&mut String::new());
+                                            }
+                                            Ok(None) => println!("No item selected."),
+                                            Err(e) => {
+                                                println!("Error getting item from stack: {}", e)
+                                            }
+                                        }
+                                    }
+                                    GetSendModeAction::SendItemToTab => {
+                                        if let Err(e) = send_item_to_tab_interactive(
+                                            &nav_state,
+                                            &browser_tabs.tab_directories(&current_directory_path),
+                                            browser_tabs.active_tab_index,
+                                        ) {
+                                            println!("Error sending item to tab: {}", e);
+                                        }
+                                    }
                                     GetSendModeAction::ReturnToBrowser => break,
                                 }
                             }
@@ -1299,6 +1299,7 @@ fn main() -> Result<(), LinesError> {
 - Everything is "command + Enter"
 */
 
+use std::cell::RefCell;
 use std::env;
 use std::fmt;
 use std::fs::{self, File, OpenOptions};
@@ -1326,8 +1327,8 @@ use super::buttons_reversible_edit_changelog_module::{
 };
 
 use super::buffy_format_write_module::{
-    BuffyFormatArg, BuffyStyles, SyntaxHighlight, buffy_get_syntax_highlight,
-    buffy_is_plain_text_extension, buffy_print, buffy_println,
+    BuffyFormatArg, BuffyFrame, BuffyStyles, SyntaxHighlight, buffy_get_syntax_highlight,
+    buffy_is_plain_text_extension, buffy_print, buffy_println, buffy_write, buffy_writeln,
 };
 
 // ============================================================================
@@ -1353,6 +1354,27 @@ use super::buffy_format_write_module::{
 // ============================================================================
 use crate::raw_terminal_x86_module::RawTerminal;
 
+// Mouse clicks and wheel in keystroke-input mode (SGR mouse reports)
+use crate::raw_terminal_x86_module::{
+    MouseEvent, MouseEventKind, RAW_KEY_MAX_BYTES, RawKey, SGR_MOUSE_REPORT_PREFIX,
+    parse_raw_key_bytes,
+};
+
+// Real window size and resize notifications (TIOCGWINSZ / SIGWINCH)
+use crate::raw_terminal_x86_module::{
+    TerminalWindowSize, install_window_resize_handler, query_terminal_window_size,
+    take_window_resized, wait_for_stdin_or_resize,
+};
+
+// Color themes: escapes print per the active theme (NO_COLOR, TERM=dumb, mono)
+use crate::color_theme_module::{ThemedSgr, themed_bytes};
+
+// Clean exit on Ctrl-C / SIGTERM (flag polled by the main loop)
+use crate::raw_terminal_x86_module::{
+    install_termination_handler, pending_termination_signal, reset_terminal_display_state,
+    termination_exit_code, termination_signal_name,
+};
+
 /// Style for line numbers - green, no bold
 const LINE_NUMBER_STYLE: BuffyStyles = BuffyStyles {
     fg_color: Some("\x1b[32m"), // GREEN
@@ -1433,10 +1455,10 @@ pub const MIN_TUI_VIZ_COLS: usize = 1;
 pub const DEFAULT_ROWS: usize = 24;
 pub const DEFAULT_COLS: usize = 80;
 
-const RESET: &str = "\x1b[0m";
-const RED: &str = "\x1b[31m";
-const YELLOW: &str = "\x1b[33m";
-const GREEN: &str = "\x1b[32m";
+const RESET: ThemedSgr = ThemedSgr("\x1b[0m");
+const RED: ThemedSgr = ThemedSgr("\x1b[31m");
+const YELLOW: ThemedSgr = ThemedSgr("\x1b[33m");
+const GREEN: ThemedSgr = ThemedSgr("\x1b[32m");
 // const BLUE: &str = "\x1b[34m";
 // const BOLD: &str = "\x1b[1m";
 // const ITALIC: &str = "\x1b[3m";
@@ -2549,7 +2571,8 @@ fn apply_format_spec(value: &str, spec: &FormatSpec) -> String {
         None => return value.to_string(), // No width, return as-is
     };
 
-    let value_len = value.len();
+    // Width in terminal columns, so CJK/emoji inserts still line up
+    let value_len = double_width::str_display_width(value);
 
     if value_len >= width {
         // Value already meets or exceeds width
@@ -3361,20 +3384,23 @@ pub fn split_timestamp_no_heap(
 ///
 /// # Purpose
 /// Single-pass linear scan through file to count newlines.
-/// Used to find total line count for GotoFileEnd command.
-/// Does not load entire file into memory.
+/// Used to find total line count for GotoFileEnd command, and by ff's
+/// info panel. Does not load entire file into memory.
+/// Counts `\n` bytes, so it suits ASCII/UTF-8 text: UTF-16 text must be
+/// decoded first (its newline is two bytes).
 ///
 /// # Arguments
 /// * `file_path` - Absolute path to file (must exist and be readable)
 ///
 /// # Returns
 /// * `Ok((line_count, last_newline_byte_pos))` where:
-///   - `line_count` - Total lines (1-indexed, 0 for empty file)
+///   - `line_count` - Newlines, plus one for a last line without a newline:
+///     `abc` is 1 line, `one\ntwo` 2, `one\ntwo\n` 2, empty 0
 ///   - `last_newline_byte_pos` - Byte offset of final \n (0-indexed), or 0 if no newlines
 /// * `Err(LinesError)` - File open, read, or seek failed
 ///
 /// # Memory Safety
-/// - Stack-only: single 1-byte buffer
+/// - Stack-only: one fixed read buffer (`LINE_COUNT_READ_BUFFER_SIZE`)
 /// - No heap allocation during scan
 /// - No file pre-loading
 ///
@@ -3386,9 +3412,9 @@ pub fn split_timestamp_no_heap(
 ///
 /// # Edge Cases
 /// - Empty file (0 bytes): returns `Ok((0, 0))`
-/// - File with no newlines: returns `Ok((0, 0))`
+/// - Non-empty file with no newlines: returns `Ok((1, 0))`
 /// - File ending with newline
-/// - File ending without newline  (last line still exists)
+/// - File ending without newline  (last line still exists, and is counted)
 ///
 /// # Example
 /// ```ignore
@@ -3397,6 +3423,9 @@ pub fn split_timestamp_no_heap(
 /// execute_command(state, Command::GotoLine(total_lines))?;
 /// ```
 pub fn count_lines_in_file(file_path: &Path) -> Result<(usize, u64)> {
+    /// Bytes read per `read()` call (stack buffer)
+    const LINE_COUNT_READ_BUFFER_SIZE: usize = 8192;
+
     // =========================================================================
     // STEP 1: DEFENSIVE INPUT VALIDATION
     // =========================================================================
@@ -3439,13 +3468,14 @@ pub fn count_lines_in_file(file_path: &Path) -> Result<(usize, u64)> {
     // STEP 3: INITIALIZE STATE
     // =========================================================================
 
-    // Pre-allocated 1-byte buffer on stack (no dynamic allocation)
-    let mut byte_buffer: [u8; 1] = [0];
+    // Pre-allocated read buffer on stack (no dynamic allocation)
+    let mut read_buffer = [0u8; LINE_COUNT_READ_BUFFER_SIZE];
 
     // Counters for line tracking
     let mut line_count: usize = 0;
     let mut last_newline_position: u64 = 0;
     let mut current_byte_position: u64 = 0;
+    let mut last_byte: Option<u8> = None;
 
     // Loop iteration counter (NASA Rule #2: upper bound on loops)
     let mut iterations: usize = 0;
@@ -3456,7 +3486,7 @@ pub fn count_lines_in_file(file_path: &Path) -> Result<(usize, u64)> {
     const MAX_ITERATIONS: usize = 10_737_418_240;
 
     // =========================================================================
-    // STEP 4: LINEAR SCAN - READ BYTE BY BYTE
+    // STEP 4: LINEAR SCAN - READ BUFFER BY BUFFER
     // =========================================================================
 
     loop {
@@ -3473,32 +3503,27 @@ pub fn count_lines_in_file(file_path: &Path) -> Result<(usize, u64)> {
 
         iterations += 1;
 
-        // Read one byte
-        match file.read(&mut byte_buffer) {
+        // Read the next chunk
+        match file.read(&mut read_buffer) {
             Ok(0) => {
                 // EOF reached - exit loop normally
                 break;
             }
-            Ok(1) => {
-                // Got one byte - check if it's newline
-                if byte_buffer[0] == b'\n' {
-                    line_count += 1;
-                    last_newline_position = current_byte_position;
+            Ok(bytes_read) => {
+                // Count the newlines in this chunk
+                let chunk = &read_buffer[..bytes_read];
+                for (chunk_offset, &byte) in chunk.iter().enumerate() {
+                    if byte == b'\n' {
+                        line_count += 1;
+                        last_newline_position = current_byte_position + chunk_offset as u64;
+                    }
                 }
-                current_byte_position += 1;
-            }
-            Ok(n) => {
-                // Unexpected: read() should return 0 or 1 for 1-byte buffer
-                let error_msg = stack_format_it(
-                    "read() returned unexpected byte count: {} (expected 0 or 1)",
-                    &[&n.to_string()],
-                    "read() returned unexpected byte count (expected 0 or 1)",
-                );
-                log_error(&error_msg, Some("count_lines_in_file"));
-                return Err(LinesError::Io(io::Error::new(
-                    io::ErrorKind::InvalidData,
-                    error_msg,
-                )));
+                current_byte_position += bytes_read as u64;
+                last_byte = chunk.last().copied();
+            }
+            Err(e) if e.kind() == ErrorKind::Interrupted => {
+                // Signal arrived mid-read: try again
+                continue;
             }
             Err(e) => {
                 // Read error - propagate
@@ -3521,6 +3546,11 @@ pub fn count_lines_in_file(file_path: &Path) -> Result<(usize, u64)> {
         }
     }
 
+    // A last line without a trailing newline still counts
+    if last_byte.is_some_and(|byte| byte != b'\n') {
+        line_count += 1;
+    }
+
     // =========================================================================
     // STEP 5: RETURN RESULTS
     // =========================================================================
@@ -3550,56 +3580,65 @@ pub fn count_lines_in_file(file_path: &Path) -> Result<(usize, u64)> {
 }
 
 // TODO, maybe add to buffy
-/// Writes a single hotkey command with color highlighting directly to terminal
+/// Writes a single hotkey command with color highlighting to a writer
 ///
 /// ## Memory: ZERO HEAP
-/// Writes hotkey (RED) + description (YELLOW) using buffy_print
+/// Writes hotkey (RED) + description (YELLOW) using buffy_write
 ///
 /// ## Parameters
+/// - writer: stdout, or the TUI frame being built
 /// - hotkey: The command character(s) to highlight in RED
 /// - description: The rest of the text in YELLOW
 ///
 /// ## Example
 /// ```rust
-/// write_red_hotkey("q", "uit ")?;  // Outputs: RED"q" + YELLOW"uit "
+/// write_red_hotkey(&mut io::stdout(), "q", "uit ")?;  // Outputs: RED"q" + YELLOW"uit "
 /// ```
-fn write_red_hotkey(hotkey: &str, description: &str) -> io::Result<()> {
-    buffy_print(
+fn write_red_hotkey<W: Write>(writer: &mut W, hotkey: &str, description: &str) -> io::Result<()> {
+    buffy_write(
+        writer,
         "{}{}{}{}",
         &[
-            BuffyFormatArg::Str(RED),
+            BuffyFormatArg::Str(RED.as_str()),
             BuffyFormatArg::Str(hotkey),
-            BuffyFormatArg::Str(YELLOW),
+            BuffyFormatArg::Str(YELLOW.as_str()),
             BuffyFormatArg::Str(description),
         ],
     )
 }
 
 // TODO, maybe add to buffy
-/// Writes a two-part hotkey command with color highlighting directly to terminal
+/// Writes a two-part hotkey command with color highlighting to a writer
 ///
 /// ## Memory: ZERO HEAP
-/// Writes hotkey_1 (RED) + hotkey_2 (GREEN) + description (YELLOW) using buffy_print
+/// Writes hotkey_1 (RED) + hotkey_2 (GREEN) + description (YELLOW) using buffy_write
 ///
 /// ## Parameters
+/// - writer: stdout, or the TUI frame being built
 /// - hotkey_1: First part of command to highlight in RED
 /// - hotkey_2: Second part of command to highlight in GREEN
 /// - description: The rest of the text in YELLOW
 ///
 /// ## Example
 /// ```rust
-/// write_red_green_hotkey("s", "a", "v ")?;  // Outputs: RED"s" + GREEN"a" + YELLOW"v "
-/// write_red_green_hotkey("/", "/", "/cmnt ")?;  // Outputs: RED"/" + GREEN"/" + YELLOW"/cmnt "
+/// write_red_green_hotkey(&mut io::stdout(), "s", "a", "v ")?;  // Outputs: RED"s" + GREEN"a" + YELLOW"v "
+/// write_red_green_hotkey(&mut io::stdout(), "/", "/", "/cmnt ")?;  // Outputs: RED"/" + GREEN"/" + YELLOW"/cmnt "
 /// ```
-fn write_red_green_hotkey(hotkey_1: &str, hotkey_2: &str, description: &str) -> io::Result<()> {
-    buffy_print(
+fn write_red_green_hotkey<W: Write>(
+    writer: &mut W,
+    hotkey_1: &str,
+    hotkey_2: &str,
+    description: &str,
+) -> io::Result<()> {
+    buffy_write(
+        writer,
         "{}{}{}{}{}{}",
         &[
-            BuffyFormatArg::Str(RED),
+            BuffyFormatArg::Str(RED.as_str()),
             BuffyFormatArg::Str(hotkey_1),
-            BuffyFormatArg::Str(GREEN),
+            BuffyFormatArg::Str(GREEN.as_str()),
             BuffyFormatArg::Str(hotkey_2),
-            BuffyFormatArg::Str(YELLOW),
+            BuffyFormatArg::Str(YELLOW.as_str()),
             BuffyFormatArg::Str(description),
         ],
     )
@@ -3648,52 +3687,52 @@ fn write_red_green_hotkey(hotkey_1: &str, hotkey_2: &str, description: &str) ->
 /// ## Example
 /// ```rust
 ///  // In main display loop:
-/// write_formatted_navigation_legend_to_tui()?;
+/// write_formatted_navigation_legend_to_tui(&mut io::stdout())?;
 /// ```
-fn write_formatted_navigation_legend_to_tui() -> Result<()> {
+fn write_formatted_navigation_legend_to_tui<W: Write>(writer: &mut W) -> Result<()> {
     // File operations group
-    write_red_hotkey("q", "uit ")?;
+    write_red_hotkey(writer, "q", "uit ")?;
     // Three Colour
-    write_red_green_hotkey("s", "a", "v ")?;
+    write_red_green_hotkey(writer, "s", "a", "v ")?;
     // Red only
-    write_red_hotkey("re", ",")?;
-    write_red_hotkey("u", "ndo ")?;
+    write_red_hotkey(writer, "re", ",")?;
+    write_red_hotkey(writer, "u", "ndo ")?;
 
     // Mode operations group
-    write_red_hotkey("d", "el|")?;
-    write_red_hotkey("n", "rm ")?;
+    write_red_hotkey(writer, "d", "el|")?;
+    write_red_hotkey(writer, "n", "rm ")?;
     // write_red_hotkey("i", "ns ")?;
-    write_red_green_hotkey("k", "i", "ns ")?;
-    write_red_hotkey("v", "is ")?;
-    write_red_hotkey("hex", "|")?;
+    write_red_green_hotkey(writer, "k", "i", "ns ")?;
+    write_red_hotkey(writer, "v", "is ")?;
+    write_red_hotkey(writer, "hex", "|")?;
 
     // View operations group
     // write_red_hotkey("r", "aw|")?;
-    write_red_hotkey("g", "o ")?;
-    write_red_hotkey("p", "asty ")?;
-    write_red_hotkey("cvy", "|")?;
+    write_red_hotkey(writer, "g", "o ")?;
+    write_red_hotkey(writer, "p", "asty ")?;
+    write_red_hotkey(writer, "cvy", "|")?;
 
     // Navigation group
-    write_red_hotkey("w", "rd,")?;
-    write_red_hotkey("b", ",")?;
-    write_red_hotkey("e", "nd ")?;
+    write_red_hotkey(writer, "w", "rd,")?;
+    write_red_hotkey(writer, "b", ",")?;
+    write_red_hotkey(writer, "e", "nd ")?;
 
     // Comment/indent group
     // Three Colour
-    write_red_green_hotkey("/", "/", "/cmnt ")?;
+    write_red_green_hotkey(writer, "/", "/", "/cmnt ")?;
     // Red only
-    write_red_hotkey("[]", "idnt ")?;
+    write_red_hotkey(writer, "[]", "idnt ")?;
 
     // Movement group
-    write_red_hotkey("hjkl", "")?;
+    write_red_hotkey(writer, "hjkl", "")?;
 
     // Clear formatting: ANSI color codes are stateful
     // Make sure NEXT prints
     // are not also formatted.
-    buffy_print("{}", &[BuffyFormatArg::Str(RESET)])?;
+    buffy_write(writer, "{}", &[BuffyFormatArg::Str(RESET.as_str())])?;
 
     // Complete the line with newline \n
-    buffy_println("", &[])?;
+    buffy_writeln(writer, "", &[])?;
 
     // Done
     Ok(())
@@ -4233,6 +4272,7 @@ fn render_pasty_tui(
 
     // Clear screen and move cursor to top-left
     print!("\x1b[2J\x1b[H");
+    invalidate_utf8txt_tui_frame();
 
     // Draw legend (using existing helper)
     let _ = format_pasty_tui_legend();
@@ -4313,6 +4353,11 @@ pub struct EditorState {
     pub effective_rows: usize,
     pub effective_cols: usize,
 
+    /// User's tall+/tall- and wide+/wide- changes, kept as offsets so they
+    /// survive when the editing area is re-fitted to a resized window
+    pub tui_rows_offset: isize,
+    pub tui_cols_offset: isize,
+
     /// start stop Byte positions for each display row in the file
     ///
     /// # Purpose
@@ -4452,6 +4497,8 @@ impl EditorState {
 
             effective_rows,
             effective_cols,
+            tui_rows_offset: 0,
+            tui_cols_offset: 0,
 
             windowmap_line_byte_start_end_position_pairs: [None; MAX_TUI_ROWS],
             security_mode: false, // default setting, purpose: to force-reset manually clear overwrite buffers
@@ -4494,6 +4541,38 @@ impl EditorState {
         }
     }
 
+    /// Fits the editing area to the terminal window plus the user's offsets
+    ///
+    /// # Purpose
+    /// Called at startup and after SIGWINCH. The window size (minus 3 rows
+    /// for header/footer and 3 cols, as in `new`) is the base; the tall/wide
+    /// offsets are added on top, then clamped to the supported range.
+    ///
+    /// # Arguments
+    /// * `window_size` - Detected size, or None to use DEFAULT_ROWS x DEFAULT_COLS
+    ///
+    /// # Note
+    /// The cursor is pulled back inside the area if the window shrank. The
+    /// caller rebuilds the window map afterwards.
+    pub fn fit_to_terminal_window(&mut self, window_size: Option<TerminalWindowSize>) {
+        let (window_rows, window_cols) = match window_size {
+            Some(size) => (size.rows as usize, size.cols as usize),
+            None => (DEFAULT_ROWS, DEFAULT_COLS),
+        };
+
+        let base_rows = window_rows.saturating_sub(3) as isize;
+        let base_cols = window_cols.saturating_sub(3) as isize;
+
+        self.effective_rows = (base_rows + self.tui_rows_offset)
+            .clamp(MIN_TUI_ROWS as isize, MAX_TUI_ROWS as isize) as usize;
+        self.effective_cols = (base_cols + self.tui_cols_offset)
+            .clamp(MIN_TUI_VIZ_COLS as isize, MAX_TUI_VIZ_COLS as isize) as usize;
+
+        // Keep the cursor on screen
+        self.cursor.tui_row = self.cursor.tui_row.min(self.effective_rows - 1);
+        self.cursor.tui_visual_col = self.cursor.tui_visual_col.min(self.effective_cols - 1);
+    }
+
     /// Stores the byte range for a single display row
     ///
     /// # Purpose
@@ -5997,6 +6076,7 @@ impl EditorState {
 
                     print!("\x1B[2J\x1B[1;1H");
                     io::stdout().flush()?;
+                    invalidate_utf8txt_tui_frame();
 
                     // 2. paste into file-path
                     // Lets users do N multi-line pastes, works like append-mode
@@ -6528,6 +6608,11 @@ impl EditorState {
             command_buffer[i] = 0;
         }
 
+        // Window resized while waiting: return so the main loop re-fits and redraws
+        if wait_for_stdin_or_resize()? {
+            return Ok(true);
+        }
+
         // Read single command (no chunking needed in hex mode)
         let bytes_read = stdin_handle.read(command_buffer)?;
 
@@ -7273,6 +7358,7 @@ impl EditorState {
     ///
     /// ```text
     /// create RawTerminal (on failure: log, set Normal, return Ok(true))
+    /// term.enable_mouse_reporting()         // failure: logged, keyboard still works
     /// loop while self.mode == KeystrokeInputMode:
     ///     term.suspend_raw_mode()             // -> cooked terminal
     ///     render_tui_utf8txt(self)            // renders like every other mode
@@ -7280,12 +7366,15 @@ impl EditorState {
     ///     n = term.read(&mut [0u8; 3])         // VMIN=1: returns 1..=3 bytes
     ///     match n:
     ///         Ok(0)  -> EOF: break, set Normal
-    ///         Ok(k)  -> if classify_arrow_bytes(&buf[0..k]) == Some(dir):
+    ///         Ok(k)  -> if &buf[0..k] == ESC [ <:                    // SGR mouse report
+    ///                       read rest, handle_mouse_event_input_mode(self, event)
+    ///                   else if classify_arrow_bytes(&buf[0..k]) == Some(dir):
     ///                       handle_arrow_key_input_mode(self, dir)   // checked
     ///                   else:
     ///                       for byte in &buf[0..k]:                  // A2, no drop
     ///                           handle_single_byte_keystroke_input_mode(self, byte, &read_copy)  // checked
     ///         Err(_) -> break, set Normal
+    /// term.disable_mouse_reporting()
     /// (RawTerminal drops here -> terminal restored)
     /// return Ok(true)
     /// ```
@@ -7406,6 +7495,18 @@ impl EditorState {
             }
         };
 
+        // Clicks place the cursor and the wheel scrolls. Without mouse
+        // reporting the session still works from the keyboard.
+        if let Err(_e) = term.enable_mouse_reporting() {
+            #[cfg(debug_assertions)]
+            eprintln!("hkis: enable_mouse_reporting failed: {:?}", _e);
+
+            log_error(
+                "ki mouse unavailable",
+                Some("handle_keystroke_input_session:mouse"),
+            );
+        }
+
         // ---------------------------------------------------------------------
         // Step 2: Keystroke read loop (cooked-render / raw-read cycle).
         // ---------------------------------------------------------------------
@@ -7510,8 +7611,37 @@ impl EditorState {
                     let filled_end = bytes_read.min(byte_buffer.len());
                     let filled_buffer = &byte_buffer[0..filled_end];
 
-                    // ---- Arrow path: exact 3-byte escape sequence only. ----
-                    if let Some(arrow_direction) = classify_arrow_bytes(filled_buffer) {
+                    // ---- Mouse path: `ESC [ <` starts an SGR mouse report. ----
+                    // Read the rest of the report (it arrives together), then
+                    // dispatch it; an undecodable report is ignored.
+                    if filled_buffer == SGR_MOUSE_REPORT_PREFIX {
+                        let mut report_bytes = [0u8; RAW_KEY_MAX_BYTES];
+                        report_bytes[..filled_end].copy_from_slice(filled_buffer);
+
+                        let report_length =
+                            match term.read_escape_sequence_rest(&mut report_bytes, filled_end) {
+                                Ok(length) => length,
+                                Err(_e) => {
+                                    #[cfg(debug_assertions)]
+                                    eprintln!("hkis: mouse report read error: {:?}", _e);
+
+                                    log_error(
+                                        "ki mouse read error",
+                                        Some("handle_keystroke_input_session:mouse"),
+                                    );
+                                    self.mode = EditorMode::Normal;
+                                    break;
+                                }
+                            };
+
+                        if let RawKey::Mouse(mouse_event) =
+                            parse_raw_key_bytes(&report_bytes[..report_length])
+                        {
+                            // Clicks and wheel turns never end the session
+                            handle_mouse_event_input_mode(self, mouse_event)?;
+                        }
+                    } else if let Some(arrow_direction) = classify_arrow_bytes(filled_buffer) {
+                        // ---- Arrow path: exact 3-byte escape sequence only. ----
                         // Map the classified direction to a cursor-move command.
                         // The returned bool is the keep-running flag; loop exit is
                         // driven by self.mode, not by this value, but we check it
@@ -7604,6 +7734,10 @@ impl EditorState {
         // ---------------------------------------------------------------------
         // Step 3: Leave the session.
         // ---------------------------------------------------------------------
+        // Mouse reporting off first, so the cooked modes (and the shell) get
+        // no mouse sequences. Drop does this too if we never get here.
+        let _ = term.disable_mouse_reporting();
+
         // `term` (RawTerminal) drops here, restoring the original (cooked)
         // terminal settings. We return Ok(true) so the main editor loop
         // continues; by now self.mode is Normal (set by ESC, EOF, error, or
@@ -8603,6 +8737,11 @@ impl EditorState {
             command_buffer[i] = 0;
         }
 
+        // Window resized while waiting: return so the main loop re-fits and redraws
+        if wait_for_stdin_or_resize()? {
+            return Ok(true);
+        }
+
         // Read single command (no chunking)
         let bytes_read = stdin_handle.read(command_buffer)?;
 
@@ -9087,10 +9226,11 @@ pub fn pasty_paste_mode<R: BufRead>(absolute_path: &Path, stdin_handle: &mut R)
     loop {
         buffy_print("\x1B[2J\x1B[1;1H", &[])?;
         io::stdout().flush()?;
-        write_red_hotkey("", "Paste multiline text here. Type '")?;
-        write_red_hotkey("b", "' to go")?;
-        write_red_hotkey(" back", ". Paste here:")?;
-        buffy_print("{} > ", &[BuffyFormatArg::Str(RESET)])?;
+        invalidate_utf8txt_tui_frame();
+        write_red_hotkey(&mut io::stdout(), "", "Paste multiline text here. Type '")?;
+        write_red_hotkey(&mut io::stdout(), "b", "' to go")?;
+        write_red_hotkey(&mut io::stdout(), " back", ". Paste here:")?;
+        buffy_print("{} > ", &[BuffyFormatArg::Str(RESET.as_str())])?;
         io::stdout().flush()?;
 
         // Defensive: prevent infinite loop
@@ -9640,6 +9780,165 @@ pub mod double_width {
 
         false
     }
+
+    /// Unicode ranges of characters that take no terminal column of their own.
+    /// Each tuple is an inclusive (start, end) range, sorted by start.
+    ///
+    /// # Source
+    /// - Combining marks (general, extended, supplement, for symbols, half marks)
+    /// - Hangul Jungseong/Jongseong (joined to the preceding Choseong)
+    /// - Zero-width space, joiners, direction marks, word joiner, BOM
+    /// - Variation selectors (e.g. emoji vs text presentation)
+    const ZERO_WIDTH_RANGES: [(u32, u32); 12] = [
+        // Combining Diacritical Marks
+        (0x0300, 0x036F),
+        // Hangul Jungseong and Jongseong
+        (0x1160, 0x11FF),
+        // Combining Diacritical Marks Extended
+        (0x1AB0, 0x1AFF),
+        // Combining Diacritical Marks Supplement
+        (0x1DC0, 0x1DFF),
+        // Zero-width space, ZWNJ, ZWJ, LRM, RLM
+        (0x200B, 0x200F),
+        // Word joiner and invisible operators
+        (0x2060, 0x2064),
+        // Combining Diacritical Marks for Symbols
+        (0x20D0, 0x20FF),
+        // Variation Selectors
+        (0xFE00, 0xFE0F),
+        // Combining Half Marks
+        (0xFE20, 0xFE2F),
+        // Zero-width no-break space (BOM)
+        (0xFEFF, 0xFEFF),
+        // Emoji skin tone modifiers (drawn on the preceding emoji)
+        (0x1F3FB, 0x1F3FF),
+        // Variation Selectors Supplement
+        (0xE0100, 0xE01EF),
+    ];
+
+    /// Unicode ranges of emoji that terminals draw two columns wide.
+    /// Each tuple is an inclusive (start, end) range, sorted by start.
+    ///
+    /// # Source
+    /// Blocks whose characters default to emoji presentation
+    /// (East Asian Width "W" for emoji since Unicode 9).
+    const WIDE_EMOJI_RANGES: [(u32, u32); 6] = [
+        // Miscellaneous Symbols and Pictographs
+        (0x1F300, 0x1F5FF),
+        // Emoticons
+        (0x1F600, 0x1F64F),
+        // Transport and Map Symbols
+        (0x1F680, 0x1F6FF),
+        // Geometric Shapes Extended (colored circles and squares)
+        (0x1F7E0, 0x1F7EB),
+        // Supplemental Symbols and Pictographs
+        (0x1F900, 0x1F9FF),
+        // Symbols and Pictographs Extended-A
+        (0x1FA70, 0x1FAFF),
+    ];
+
+    /// Checks a code point against a small sorted table of inclusive ranges.
+    ///
+    /// # Arguments
+    /// * `code_point` - The character as u32
+    /// * `ranges` - Sorted (start, end) pairs
+    ///
+    /// # Returns
+    /// * `true` if any range contains the code point
+    fn in_ranges(code_point: u32, ranges: &[(u32, u32)]) -> bool {
+        // Bounded by the table length (NASA Power of 10 rule #2)
+        for &(range_start, range_end) in ranges {
+            if code_point < range_start {
+                // Sorted: no later range can match
+                return false;
+            }
+            if code_point <= range_end {
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Determines if a character takes no terminal column of its own.
+    ///
+    /// # Arguments
+    /// * `c` - The character to check
+    ///
+    /// # Returns
+    /// * `true` for combining marks, zero-width joiners/spaces, variation
+    ///   selectors, and control characters (which do not print)
+    ///
+    /// # Examples
+    /// ```
+    /// assert_eq!(is_zero_width('\u{0301}'), true); // combining acute accent
+    /// assert_eq!(is_zero_width('\u{200D}'), true); // zero-width joiner
+    /// assert_eq!(is_zero_width('e'), false);
+    /// ```
+    pub fn is_zero_width(c: char) -> bool {
+        let code_point = c as u32;
+
+        // Fast path: printable ASCII
+        if (0x20..0x7F).contains(&code_point) {
+            return false;
+        }
+
+        // C0 and C1 control characters (and DEL) do not advance the cursor
+        if code_point < 0x20 || (0x7F..0xA0).contains(&code_point) {
+            return true;
+        }
+
+        in_ranges(code_point, &ZERO_WIDTH_RANGES)
+    }
+
+    /// Number of terminal columns one character occupies: 0, 1, or 2.
+    ///
+    /// # Purpose
+    /// The one display-width routine shared by Lines and the ff listing.
+    /// Extends `is_double_width` with zero-width characters (see
+    /// `is_zero_width`) and wide emoji.
+    ///
+    /// # Arguments
+    /// * `c` - The character to measure
+    ///
+    /// # Returns
+    /// * `0` - Combining, zero-width, or control character
+    /// * `2` - CJK / fullwidth (see `is_double_width`) or wide emoji
+    /// * `1` - Everything else
+    ///
+    /// # Examples
+    /// ```
+    /// assert_eq!(char_display_width('a'), 1);
+    /// assert_eq!(char_display_width('中'), 2);
+    /// assert_eq!(char_display_width('😀'), 2);
+    /// assert_eq!(char_display_width('\u{0301}'), 0);
+    /// ```
+    pub fn char_display_width(c: char) -> usize {
+        if is_zero_width(c) {
+            return 0;
+        }
+        if is_double_width(c) || in_ranges(c as u32, &WIDE_EMOJI_RANGES) {
+            return 2;
+        }
+        1
+    }
+
+    /// Number of terminal columns a string occupies.
+    ///
+    /// # Arguments
+    /// * `text` - The text to measure (no escape sequences)
+    ///
+    /// # Returns
+    /// * `usize` - Sum of `char_display_width` over the characters
+    ///
+    /// # Examples
+    /// ```
+    /// assert_eq!(str_display_width("Hello"), 5);
+    /// assert_eq!(str_display_width("Hello世界"), 9);
+    /// assert_eq!(str_display_width("e\u{0301}te\u{0301}"), 3); // "été", decomposed
+    /// ```
+    pub fn str_display_width(text: &str) -> usize {
+        text.chars().map(char_display_width).sum()
+    }
 }
 
 /// Seeks to a specific line number in the file and returns the byte position
@@ -11797,6 +12096,56 @@ fn cleanup_session_directory_draft(state: &EditorState) -> io::Result<()> {
 
     Ok(())
 }
+/// Exits Lines after Ctrl-C or SIGTERM
+///
+/// # Purpose
+/// Called from the main editor loop when `pending_termination_signal()` is
+/// set. By then any keystroke-mode `RawTerminal` has been dropped (termios
+/// restored); this resets colors/cursor, handles the session draft, tells
+/// the user about unsaved changes, and exits the process.
+///
+/// # Arguments
+/// * `state` - Editor state (draft path, modified flag, original file)
+/// * `signal_number` - SIGINT or SIGTERM
+///
+/// # Draft Handling
+/// - No unsaved changes: the draft is removed (`cleanup_session_directory_draft`)
+/// - Unsaved changes: the draft is KEPT and its path printed, because it
+///   holds the only copy of those edits (the original file is untouched)
+///
+/// # Exit Codes
+/// - 130: SIGINT (Ctrl-C)
+/// - 143: SIGTERM
+fn exit_lines_on_termination_signal(state: &EditorState, signal_number: i32) -> ! {
+    reset_terminal_display_state();
+    println!(
+        "Lines stopped by {}.",
+        termination_signal_name(signal_number)
+    );
+
+    if state.is_modified {
+        // Keep the edits: the draft is the only place they exist
+        println!("Unsaved changes were NOT written to the original file.");
+        if let Some(draft_path) = &state.read_copy_path {
+            println!(
+                "They are kept in the session draft: {}",
+                draft_path.display()
+            );
+        }
+    } else if let Err(_e) = cleanup_session_directory_draft(state) {
+        #[cfg(debug_assertions)]
+        eprintln!("Warning: Session cleanup failed: {}", _e);
+        log_error(
+            "Session cleanup failed",
+            Some("exit_lines_on_termination_signal"),
+        );
+        // Exit anyway
+    }
+
+    let _ = io::stdout().flush();
+    std::process::exit(termination_exit_code(signal_number));
+}
+
 /// Cleans up session directory and all its contents
 ///
 /// # Purpose
@@ -13025,6 +13374,7 @@ pub fn execute_command(lines_editor_state: &mut EditorState, command: Command) -
             // Check for handle here: must not be > MAX
             if (lines_editor_state.effective_rows + 1) <= MAX_TUI_ROWS {
                 lines_editor_state.effective_rows += 1;
+                lines_editor_state.tui_rows_offset += 1;
                 build_windowmap_nowrap(lines_editor_state, &edit_file_path)?;
             }
             // Else, Nothing to Do
@@ -13035,6 +13385,7 @@ pub fn execute_command(lines_editor_state: &mut EditorState, command: Command) -
             // Check for handle here: must not be < MIN
             if (lines_editor_state.effective_rows - 1) >= MIN_TUI_ROWS {
                 lines_editor_state.effective_rows -= 1;
+                lines_editor_state.tui_rows_offset -= 1;
                 build_windowmap_nowrap(lines_editor_state, &edit_file_path)?;
             }
             // Else, Nothing to Do
@@ -13046,6 +13397,7 @@ pub fn execute_command(lines_editor_state: &mut EditorState, command: Command) -
             // Check for handle here: must not be > MAX
             if (lines_editor_state.effective_cols + 1) <= MAX_TUI_VIZ_COLS {
                 lines_editor_state.effective_cols += 1;
+                lines_editor_state.tui_cols_offset += 1;
                 build_windowmap_nowrap(lines_editor_state, &edit_file_path)?;
             }
             Ok(true)
@@ -13055,6 +13407,7 @@ pub fn execute_command(lines_editor_state: &mut EditorState, command: Command) -
             // Check for handle here: must not be < MIN
             if (lines_editor_state.effective_cols - 1) >= MIN_TUI_VIZ_COLS {
                 lines_editor_state.effective_cols -= 1;
+                lines_editor_state.tui_cols_offset -= 1;
                 build_windowmap_nowrap(lines_editor_state, &edit_file_path)?;
             }
             Ok(true)
@@ -13980,6 +14333,116 @@ fn handle_arrow_key_input_mode(
     }
 }
 
+/// Rows scrolled per mouse-wheel notch in keystroke-input mode
+const MOUSE_WHEEL_SCROLL_ROWS: usize = 3;
+
+/// Screen row (1-based) of TUI row 0: the navigation legend is on row 1
+pub const FIRST_TEXT_ROW_ON_SCREEN: usize = 2;
+
+/// Maps the screen row of a mouse click to the TUI row showing a file line.
+///
+/// # Project Context
+///
+/// The renderer writes the legend on screen row 1 and TUI row N on screen
+/// row N + 2. Only rows with an entry in
+/// `windowmap_line_byte_start_end_position_pairs` show a line; clicks on
+/// empty rows below the end of the file, the legend, or the info bar map
+/// to nothing.
+///
+/// # Arguments
+///
+/// * `lines_editor_state` - editor state (window size and window map)
+/// * `screen_row` - 1-based screen row from the mouse report
+///
+/// # Returns
+///
+/// * `Some(tui_row)` - 0-based TUI row with a file line on it
+/// * `None` - the click was not on a file line
+pub fn tui_row_for_mouse_click(lines_editor_state: &EditorState, screen_row: u16) -> Option<usize> {
+    let tui_row = (screen_row as usize).checked_sub(FIRST_TEXT_ROW_ON_SCREEN)?;
+    if tui_row >= lines_editor_state.effective_rows {
+        return None;
+    }
+
+    lines_editor_state.windowmap_line_byte_start_end_position_pairs[tui_row].map(|_| tui_row)
+}
+
+/// Applies one mouse event in `EditorMode::KeystrokeInputMode`.
+///
+/// # Project Context
+///
+/// The mouse counterpart of `handle_arrow_key_input_mode`: the session loop
+/// decodes the SGR report and calls this. It does not read input or render.
+///
+/// | Event       | Action                                                     |
+/// |-------------|------------------------------------------------------------|
+/// | Left click  | cursor to the clicked cell (line end if past the end)      |
+/// | Wheel up    | `Command::MoveUp(MOUSE_WHEEL_SCROLL_ROWS)`                 |
+/// | Wheel down  | `Command::MoveDown(MOUSE_WHEEL_SCROLL_ROWS)`               |
+/// | other       | ignored                                                    |
+///
+/// A click in the line-number prefix lands on the line's first character.
+/// The clicked cell is checked with `get_row_col_file_position`, the one
+/// (row, col) -> file position conversion, so the cursor never lands on a
+/// cell that has no file position.
+///
+/// # Arguments
+///
+/// * `lines_editor_state` - mutable editor state
+/// * `mouse_event` - the decoded mouse report
+///
+/// # Returns
+///
+/// * `Ok(true)` - keep running (mouse events never stop the editor)
+/// * `Err(LinesError)` - read-copy I/O failure or error from a move command
+fn handle_mouse_event_input_mode(
+    lines_editor_state: &mut EditorState,
+    mouse_event: MouseEvent,
+) -> Result<bool> {
+    match mouse_event.kind {
+        MouseEventKind::WheelUp => {
+            execute_command(lines_editor_state, Command::MoveUp(MOUSE_WHEEL_SCROLL_ROWS))
+        }
+        MouseEventKind::WheelDown => execute_command(
+            lines_editor_state,
+            Command::MoveDown(MOUSE_WHEEL_SCROLL_ROWS),
+        ),
+        MouseEventKind::LeftPress => {
+            // Step 1: Screen row -> TUI row showing a file line
+            let tui_row = match tui_row_for_mouse_click(lines_editor_state, mouse_event.row) {
+                Some(row) => row,
+                None => return Ok(true),
+            };
+
+            // Step 2: Screen column -> visual column, kept out of the prefix
+            let line_num_width = calculate_line_number_width(
+                lines_editor_state.line_count_at_top_of_window,
+                tui_row,
+                lines_editor_state.effective_rows,
+            );
+            let visual_col = (mouse_event.column as usize)
+                .saturating_sub(1)
+                .max(line_num_width);
+
+            // Step 3: A real cell: go there. Past the end: start, then line end.
+            lines_editor_state.cursor.tui_row = tui_row;
+            if lines_editor_state
+                .get_row_col_file_position(tui_row, visual_col)?
+                .is_some()
+            {
+                lines_editor_state.cursor.tui_visual_col = visual_col;
+                Ok(true)
+            } else {
+                lines_editor_state.cursor.tui_visual_col = line_num_width;
+                execute_command(lines_editor_state, Command::GotoLineEnd)
+            }
+        }
+        MouseEventKind::MiddlePress | MouseEventKind::RightPress | MouseEventKind::Release => {
+            Ok(true)
+        }
+    }
+}
+
 /// Dispatches a single keystroke byte to the editor action.
 ///
 /// # Project Context
@@ -20388,32 +20851,36 @@ pub fn read_and_sort_pasty_clipboard(clipboard_dir: &PathBuf) -> io::Result<Vec<
 /// ## Example
 /// ```rust
 ///  // In main display loop:
-/// write_formatted_navigation_legend_to_tui()?;
+/// write_formatted_navigation_legend_to_tui(&mut io::stdout())?;
 /// ```
 fn format_pasty_tui_legend() -> Result<()> {
     // File operations group
-    write_red_hotkey("", "Have a Pasty!! ")?;
+    write_red_hotkey(&mut io::stdout(), "", "Have a Pasty!! ")?;
     // Three Colour
     // write_red_green_hotkey("s", "a", "v ")?;
     // Red only
-    write_red_hotkey("b", "ack paste")?;
-    write_red_hotkey("N", " ")?;
+    write_red_hotkey(&mut io::stdout(), "b", "ack paste")?;
+    write_red_hotkey(&mut io::stdout(), "N", " ")?;
 
     // Mode operations group
-    write_red_hotkey("str", "(any file-path) | ")?;
-    write_red_hotkey("clear", " all | ")?;
-    write_red_green_hotkey("clear", "N", " item ")?;
+    write_red_hotkey(&mut io::stdout(), "str", "(any file-path) | ")?;
+    write_red_hotkey(&mut io::stdout(), "clear", " all | ")?;
+    write_red_green_hotkey(&mut io::stdout(), "clear", "N", " item ")?;
     // newline \n
     buffy_println("", &[])?;
 
-    write_red_hotkey("Empty Enter", " Add Freshest Clipboard Item | ")?;
+    write_red_hotkey(
+        &mut io::stdout(),
+        "Empty Enter",
+        " Add Freshest Clipboard Item | ",
+    )?;
 
-    write_red_hotkey("paste", " multi-line cut and paste")?;
+    write_red_hotkey(&mut io::stdout(), "paste", " multi-line cut and paste")?;
 
     // Clear formatting: ANSI color codes are stateful
     // Make sure NEXT prints
     // are not also formatted.
-    buffy_print("{}", &[BuffyFormatArg::Str(RESET)])?;
+    buffy_print("{}", &[BuffyFormatArg::Str(RESET.as_str())])?;
 
     // newline \n
     buffy_println("", &[])?;
@@ -20449,18 +20916,24 @@ fn display_pasty_info_bar(
     // =========================================================================
     buffy_print(
         "{}{}",
-        &[BuffyFormatArg::Str(RED), BuffyFormatArg::Usize(total_count)],
+        &[
+            BuffyFormatArg::Str(RED.as_str()),
+            BuffyFormatArg::Usize(total_count),
+        ],
     )?;
 
     // =========================================================================
     // SECTION 2: YELLOW " Clipboard Items, "
     // =========================================================================
-    buffy_print("{} Clipboard Items, ", &[BuffyFormatArg::Str(YELLOW)])?;
+    buffy_print(
+        "{} Clipboard Items, ",
+        &[BuffyFormatArg::Str(YELLOW.as_str())],
+    )?;
 
     // =========================================================================
     // SECTION 3: YELLOW "Showing"
     // =========================================================================
-    buffy_print("{}Showing ", &[BuffyFormatArg::Str(YELLOW)])?;
+    buffy_print("{}Showing ", &[BuffyFormatArg::Str(YELLOW.as_str())])?;
 
     // =========================================================================
     // SECTION 4: RED first_count_visible
@@ -20468,7 +20941,7 @@ fn display_pasty_info_bar(
     buffy_print(
         "{}{}",
         &[
-            BuffyFormatArg::Str(RED),
+            BuffyFormatArg::Str(RED.as_str()),
             BuffyFormatArg::Usize(first_count_visible),
         ],
     )?;
@@ -20476,7 +20949,7 @@ fn display_pasty_info_bar(
     // =========================================================================
     // SECTION 5: YELLOW "-"
     // =========================================================================
-    buffy_print("{}-", &[BuffyFormatArg::Str(YELLOW)])?;
+    buffy_print("{}-", &[BuffyFormatArg::Str(YELLOW.as_str())])?;
 
     // =========================================================================
     // SECTION 6: RED last_count_visible
@@ -20484,7 +20957,7 @@ fn display_pasty_info_bar(
     buffy_print(
         "{}{}",
         &[
-            BuffyFormatArg::Str(RED),
+            BuffyFormatArg::Str(RED.as_str()),
             BuffyFormatArg::Usize(last_count_visible),
         ],
     )?;
@@ -20492,7 +20965,10 @@ fn display_pasty_info_bar(
     // =========================================================================
     // SECTION 7: YELLOW " (Page up/down k/j) "
     // =========================================================================
-    buffy_print("{} (Page up/down k/j) ", &[BuffyFormatArg::Str(YELLOW)])?;
+    buffy_print(
+        "{} (Page up/down k/j) ",
+        &[BuffyFormatArg::Str(YELLOW.as_str())],
+    )?;
 
     // =========================================================================
     // SECTION 8: YELLOW info_bar_message (if present)
@@ -20501,7 +20977,7 @@ fn display_pasty_info_bar(
         buffy_print(
             "{}{}",
             &[
-                BuffyFormatArg::Str(YELLOW),
+                BuffyFormatArg::Str(YELLOW.as_str()),
                 BuffyFormatArg::Str(info_bar_message),
             ],
         )?;
@@ -20514,7 +20990,7 @@ fn display_pasty_info_bar(
 
     buffy_print("or file-path to paste file text ", &[])?;
 
-    buffy_print("{}> ", &[BuffyFormatArg::Str(RESET)])?;
+    buffy_print("{}> ", &[BuffyFormatArg::Str(RESET.as_str())])?;
 
     // =========================================================================
     // FINAL: Flush to ensure prompt appears immediately
@@ -21291,6 +21767,7 @@ MODES:
     n               Normal-Mode (navigation)
     i               Insert-Mode (type in text, delete previous)
     ki              Keystroke Insert-Mode (type in text, del previous)
+                    mouse: click places cursor, wheel scrolls
     v               Visual/Select-Mode (select and act on selections
     hex             Hex Editor Mode
     p | pasty       Clipboard / Paste Mode
@@ -21617,6 +22094,7 @@ pub fn display_help_menu_system(stdin_handle: &mut StdinLock) -> Result<()> {
 fn clear_terminal_screen() -> Result<()> {
     // ANSI escape codes: clear screen and move cursor to top-left
     print!("\x1b[2J\x1b[1;1H");
+    invalidate_utf8txt_tui_frame();
     io::stdout().flush().map_err(LinesError::Io)?;
     Ok(())
 }
@@ -21626,26 +22104,28 @@ fn clear_terminal_screen() -> Result<()> {
 /// These constants provide color and style formatting for terminal output.
 /// Using ANSI escape sequences for maximum compatibility.
 mod ansi_colors {
+    use super::ThemedSgr;
+
     /// Reset all formatting to default
-    pub const RESET: &str = "\x1b[0m";
+    pub const RESET: ThemedSgr = ThemedSgr("\x1b[0m");
 
     /// Bold text for headers
-    pub const BOLD: &str = "\x1b[1m";
+    pub const BOLD: ThemedSgr = ThemedSgr("\x1b[1m");
 
     /// Cyan color for commands
-    pub const CYAN: &str = "\x1b[36m";
+    pub const CYAN: ThemedSgr = ThemedSgr("\x1b[36m");
 
     /// Green color for examples
-    pub const GREEN: &str = "\x1b[32m";
+    pub const GREEN: ThemedSgr = ThemedSgr("\x1b[32m");
 
     /// Yellow color for warnings or important notes
-    pub const YELLOW: &str = "\x1b[33m";
+    pub const YELLOW: ThemedSgr = ThemedSgr("\x1b[33m");
 
     /// Bright white for emphasis
-    pub const BRIGHT_WHITE: &str = "\x1b[97m";
+    pub const BRIGHT_WHITE: ThemedSgr = ThemedSgr("\x1b[97m");
 
     /// Magenta for section numbers
-    pub const MAGENTA: &str = "\x1b[35m";
+    pub const MAGENTA: ThemedSgr = ThemedSgr("\x1b[35m");
 }
 
 /// Display a specific help section with proper formatting
@@ -21771,21 +22251,21 @@ fn format_info_bar_cafe_normal_visualselect(lines_editor_state: &EditorState) ->
     let info_bar = stack_format_it(
         "{}{} {}{}{}:{}{}{} {}{} @{}{}{} {}{} > ",
         &[
-            &YELLOW,
+            YELLOW.as_str(),
             &mode_str,
-            &RED,
+            RED.as_str(),
             &line_display.to_string(),
-            &YELLOW,
-            &YELLOW,
-            &RED,
+            YELLOW.as_str(),
+            YELLOW.as_str(),
+            RED.as_str(),
             &in_line_byte_string,
-            &YELLOW,
+            YELLOW.as_str(),
             &filename,
-            &RED,
+            RED.as_str(),
             &file_position_string,
-            &YELLOW,
+            YELLOW.as_str(),
             &message_for_infobar,
-            &RESET,
+            RESET.as_str(),
         ],
         " > ",
     );
@@ -21877,8 +22357,9 @@ impl HexCursor {
 /// Reads only 26 bytes starting at `hex_cursor.byte_offset_linear_file_absolute_position`
 /// Does NOT load entire file into memory
 pub fn render_tui_hex(state: &EditorState) -> Result<()> {
-    // Clear screen
+    // Clear screen (the text view's saved frame no longer matches it)
     print!("\x1B[2J\x1B[H");
+    invalidate_utf8txt_tui_frame();
     io::stdout().flush().map_err(|e| {
         LinesError::DisplayError(stack_format_it(
             "Failed to flush stdout: {}",
@@ -21888,7 +22369,7 @@ pub fn render_tui_hex(state: &EditorState) -> Result<()> {
     })?;
 
     // === TOP LINE: LEGEND (same as UTF-8 mode) ===
-    let _ = write_formatted_navigation_legend_to_tui()?;
+    let _ = write_formatted_navigation_legend_to_tui(&mut io::stdout())?;
 
     // padding
     for _ in 0..5 {
@@ -21967,10 +22448,10 @@ pub fn render_tui_hex(state: &EditorState) -> Result<()> {
 /// - No heap allocation during render
 fn render_hex_row(state: &EditorState) -> Result<String> {
     const BYTES_TO_DISPLAY: usize = 26;
-    const BOLD: &str = "\x1b[1m";
-    const RED: &str = "\x1b[31m";
-    const BG_WHITE: &str = "\x1b[47m";
-    const RESET: &str = "\x1b[0m";
+    const BOLD: ThemedSgr = ThemedSgr("\x1b[1m");
+    const RED: ThemedSgr = ThemedSgr("\x1b[31m");
+    const BG_WHITE: ThemedSgr = ThemedSgr("\x1b[47m");
+    const RESET: ThemedSgr = ThemedSgr("\x1b[0m");
 
     // Pre-allocate display buffers
     // 26 bytes × 3 chars per byte ("48 ") = 78 chars + safety margin
@@ -22036,10 +22517,10 @@ fn render_hex_row(state: &EditorState) -> Result<String> {
                 byte,
                 &mut hex_buf,
                 i == cursor_col, // highlight flag
-                BOLD,
-                RED,
-                BG_WHITE,
-                RESET,
+                BOLD.as_str(),
+                RED.as_str(),
+                BG_WHITE.as_str(),
+                RESET.as_str(),
             ) {
                 hex_line.push_str(formatted);
             } else {
@@ -22343,16 +22824,16 @@ fn format_hex_info_bar(lines_editor_state: &EditorState) -> Result<String> {
     let info_bar = stack_format_it(
         "{}HEX byte {}{}{} of {}{}{} {}, Edit:Enter Hex|Insrt:NN-i|GoTo:gN|d {} {}> ",
         &[
-            &YELLOW,
-            &RED,
+            YELLOW.as_str(),
+            RED.as_str(),
             &string_lines.to_string(),
-            &YELLOW,
-            &RED,
+            YELLOW.as_str(),
+            RED.as_str(),
             &file_size.to_string(),
-            &YELLOW,
+            YELLOW.as_str(),
             &filename,
             &message_for_infobar,
-            &RESET,
+            RESET.as_str(),
         ],
         "Invalid byte range",
     );
@@ -22360,6 +22841,22 @@ fn format_hex_info_bar(lines_editor_state: &EditorState) -> Result<String> {
     Ok(info_bar)
 }
 
+thread_local! {
+    /// Previous UTF8-text screen, so `render_tui_utf8txt` redraws only changed rows.
+    ///
+    /// Kept here rather than in EditorState: it mirrors what the terminal
+    /// shows (screen state, not editor state), and renderers take `&EditorState`.
+    static UTF8TXT_TUI_FRAME: RefCell<BuffyFrame> = RefCell::new(BuffyFrame::new());
+}
+
+/// Makes the next `render_tui_utf8txt` repaint the whole screen.
+///
+/// Call after anything else draws on the terminal (hex view, pasty view,
+/// a prompt, a new editor session).
+pub fn invalidate_utf8txt_tui_frame() {
+    UTF8TXT_TUI_FRAME.with(|frame_cell| frame_cell.borrow_mut().invalidate());
+}
+
 /// Renders the complete UTF8-text TUI to terminal: legend + content + info bar.
 ///
 /// # Purpose (Project Context)
@@ -22442,24 +22939,52 @@ fn format_hex_info_bar(lines_editor_state: &EditorState) -> Result<String> {
 /// - Zero heap allocation in the rendering hot path
 pub fn render_tui_utf8txt(state: &EditorState) -> Result<()> {
     // =========================================================================
-    // CLEAR SCREEN
+    // BUILD INTO THE FRAME (no screen clear)
     // =========================================================================
-    // Move cursor to top-left and clear entire screen.
-    // This is a single write of static bytes — no allocation.
-    print!("\x1B[2J\x1B[H");
-    io::stdout().flush().map_err(|e| {
-        LinesError::DisplayError(stack_format_it(
-            "render_tui: flush clear: {}",
-            &[&e.to_string()],
-            "render_tui: flush clear",
-        ))
-    })?;
+    // The whole screen is written into the reused frame; `present` at the
+    // end sends only the rows that changed since the last render, in one
+    // write. (A resize or `invalidate_utf8txt_tui_frame` repaints fully.)
+    UTF8TXT_TUI_FRAME.with(|frame_cell| {
+        let mut frame = frame_cell.borrow_mut();
+        frame.begin_frame();
+        write_utf8txt_tui_frame(&mut frame, state)?;
+
+        let terminal_size = query_terminal_window_size()
+            .ok()
+            .map(|window_size| (window_size.rows, window_size.cols));
+        frame
+            .present(&mut io::stdout(), terminal_size)
+            .map_err(|e| {
+                LinesError::DisplayError(stack_format_it(
+                    "render_tui: present frame: {}",
+                    &[&e.to_string()],
+                    "render_tui: present frame",
+                ))
+            })?;
+        Ok(())
+    })
+}
 
+/// Writes the complete UTF8-text TUI (legend + content + info bar) into a frame.
+///
+/// # Purpose
+/// The body of `render_tui_utf8txt`: identical drawing, but every byte goes
+/// to `frame` instead of stdout, so the frame can be compared with the
+/// previous one and only changed rows sent to the terminal.
+///
+/// # Arguments
+/// * `frame` - The frame being built (`begin_frame` already called)
+/// * `state` - Current editor state
+///
+/// # Returns
+/// * `Ok(())` - All three sections written to the frame
+/// * `Err(LinesError)` - Row lookup/selection failure or format error
+fn write_utf8txt_tui_frame(frame: &mut BuffyFrame, state: &EditorState) -> Result<()> {
     // =========================================================================
     // TOP LINE: NAVIGATION LEGEND
     // =========================================================================
     // Static hotkey reference line. Written once per refresh.
-    let _ = write_formatted_navigation_legend_to_tui()?;
+    let _ = write_formatted_navigation_legend_to_tui(frame)?;
 
     // =========================================================================
     // SYNTAX HIGHLIGHTING: PLAIN TEXT CHECK (computed once for all rows)
@@ -22524,7 +23049,8 @@ pub fn render_tui_utf8txt(state: &EditorState) -> Result<()> {
                     // WRITE LINE NUMBER PREFIX (green)
                     // ---------------------------------------------------------
                     // Written via buffy_print: zero heap, direct to stdout.
-                    buffy_print(
+                    buffy_write(
+                        frame,
                         "{}",
                         &[BuffyFormatArg::StrStyled(line_num_part, LINE_NUMBER_STYLE)],
                     )?;
@@ -22559,6 +23085,7 @@ pub fn render_tui_utf8txt(state: &EditorState) -> Result<()> {
                     //   3. Syntax highlighting (cyan symbols, yellow keywords)
                     //   4. Plain character (no ANSI codes)
                     render_utf8txt_row_with_cursor(
+                        frame,
                         state,
                         row,
                         content_part,
@@ -22571,14 +23098,14 @@ pub fn render_tui_utf8txt(state: &EditorState) -> Result<()> {
                     // ---------------------------------------------------------
                     // render_utf8txt_row_with_cursor does NOT write a newline.
                     // The caller (here) is responsible for line termination.
-                    // buffy_println with empty template writes just "\n" + flush.
-                    buffy_println("", &[])?;
+                    // buffy_writeln with empty template writes just "\n".
+                    buffy_writeln(frame, "", &[])?;
                 }
                 Err(_) => {
                     // UTF-8 decode failure for this row's display buffer.
                     // Show replacement character and continue rendering
                     // remaining rows. Do not halt for one bad row.
-                    buffy_println("�", &[])?;
+                    buffy_writeln(frame, "�", &[])?;
                 }
             }
         } else {
@@ -22588,9 +23115,13 @@ pub fn render_tui_utf8txt(state: &EditorState) -> Result<()> {
             // If the cursor is on this empty row, show a visible cursor block
             // so the user knows where they are. Otherwise, blank line.
             if row == state.cursor.tui_row {
-                buffy_println("{}", &[BuffyFormatArg::CharStyled('█', CURSOR_BLOCK_STYLE)])?;
+                buffy_writeln(
+                    frame,
+                    "{}",
+                    &[BuffyFormatArg::CharStyled('█', CURSOR_BLOCK_STYLE)],
+                )?;
             } else {
-                buffy_println("", &[])?;
+                buffy_writeln(frame, "", &[])?;
             }
         }
     }
@@ -22602,26 +23133,13 @@ pub fn render_tui_utf8txt(state: &EditorState) -> Result<()> {
     // Written as the final line with no trailing newline (cursor stays on
     // the info bar for command input visibility).
     let info_bar = format_info_bar_cafe_normal_visualselect(state)?;
-    buffy_print(&info_bar, &[])?;
-
-    // =========================================================================
-    // FINAL FLUSH
-    // =========================================================================
-    // Ensure all buffered output reaches the terminal before returning.
-    // Without this flush, the screen may appear partially rendered.
-    io::stdout().flush().map_err(|e| {
-        LinesError::DisplayError(stack_format_it(
-            "render_tui: flush final: {}",
-            &[&e.to_string()],
-            "render_tui: flush final",
-        ))
-    })?;
+    buffy_write(frame, &info_bar, &[])?;
 
     Ok(())
 }
 
-/// Renders one row of display directly to stdout with cursor, selection,
-/// and syntax highlighting — zero heap allocation.
+/// Renders one row of display to a writer (the TUI frame) with cursor,
+/// selection, and syntax highlighting — zero heap allocation.
 ///
 /// # Purpose (Project Context)
 /// Character-by-character renderer for the TUI content area. It writes
@@ -22661,8 +23179,9 @@ pub fn render_tui_utf8txt(state: &EditorState) -> Result<()> {
 /// source and keeps the block in lockstep with the resolved file byte.
 ///
 /// # Direct-Write Pattern (No Heap)
-/// Writes ANSI codes and character bytes via stdout.write_all(). No String
-/// accumulation, no Vec<char>, no format!() macro.
+/// Writes ANSI codes and character bytes via writer.write_all(). No String
+/// accumulation, no Vec<char>, no format!() macro. (The writer is the
+/// reused `BuffyFrame` of `render_tui_utf8txt`.)
 ///
 /// # Coordinate Spaces (see the module "Coordinate Spaces" reference)
 /// - In  `row_index`  : #6 TUI display row
@@ -22671,6 +23190,7 @@ pub fn render_tui_utf8txt(state: &EditorState) -> Result<()> {
 ///                      visual cells and places the cursor where they match.
 ///
 /// # Arguments
+/// * `writer`         - Destination for the row bytes (the TUI frame)
 /// * `state`          - Editor state (mode, cursor position)
 /// * `row_index`      - Display row being rendered (0-indexed within window)
 /// * `row_content`    - Content portion of the row (line-number prefix already
@@ -22680,19 +23200,19 @@ pub fn render_tui_utf8txt(state: &EditorState) -> Result<()> {
 /// * `is_plain_text`  - If true, skip syntax highlighting entirely
 ///
 /// # Returns
-/// * `Ok(())` - Row content written to stdout successfully
-/// * `Err(LinesError)` - On lookup, selection, or stdout write failure
+/// * `Ok(())` - Row content written successfully
+/// * `Err(LinesError)` - On lookup, selection, or write failure
 ///
 /// # Error Handling
 /// All write and lookup failures are propagated; never panics in production.
-fn render_utf8txt_row_with_cursor(
+fn render_utf8txt_row_with_cursor<W: Write>(
+    writer: &mut W,
     state: &EditorState,
     row_index: usize,
     row_content: &str,
     cursor_col: usize,
     is_plain_text: bool,
 ) -> Result<()> {
-    let mut stdout = io::stdout();
     let row_bytes = row_content.as_bytes();
     let row_len = row_bytes.len();
 
@@ -22769,7 +23289,7 @@ fn render_utf8txt_row_with_cursor(
                 char_byte_len,
                 row_len - byte_pos
             );
-            stdout.write_all("�".as_bytes()).map_err(|e| {
+            writer.write_all("�".as_bytes()).map_err(|e| {
                 LinesError::DisplayError(stack_format_it(
                     "rURWC write error: {}",
                     &[&e.to_string()],
@@ -22809,35 +23329,35 @@ fn render_utf8txt_row_with_cursor(
             && effective_cursor_col >= visual_col
             && effective_cursor_col < visual_col + display_width
         {
-            stdout.write_all(BOLD_U8).map_err(|e| {
+            writer.write_all(themed_bytes(BOLD_U8)).map_err(|e| {
                 LinesError::DisplayError(stack_format_it(
                     "rURWC cursor write: {}",
                     &[&e.to_string()],
                     "rURWC cursor write",
                 ))
             })?;
-            stdout.write_all(RED_U8).map_err(|e| {
+            writer.write_all(themed_bytes(RED_U8)).map_err(|e| {
                 LinesError::DisplayError(stack_format_it(
                     "rURWC cursor write: {}",
                     &[&e.to_string()],
                     "rURWC cursor write",
                 ))
             })?;
-            stdout.write_all(BG_WHITE_U8).map_err(|e| {
+            writer.write_all(themed_bytes(BG_WHITE_U8)).map_err(|e| {
                 LinesError::DisplayError(stack_format_it(
                     "rURWC cursor write: {}",
                     &[&e.to_string()],
                     "rURWC cursor write",
                 ))
             })?;
-            stdout.write_all(char_bytes).map_err(|e| {
+            writer.write_all(char_bytes).map_err(|e| {
                 LinesError::DisplayError(stack_format_it(
                     "rURWC cursor write: {}",
                     &[&e.to_string()],
                     "rURWC cursor write",
                 ))
             })?;
-            stdout.write_all(RESET_U8).map_err(|e| {
+            writer.write_all(themed_bytes(RESET_U8)).map_err(|e| {
                 LinesError::DisplayError(stack_format_it(
                     "rURWC cursor write: {}",
                     &[&e.to_string()],
@@ -22872,35 +23392,35 @@ fn render_utf8txt_row_with_cursor(
                 )?;
 
                 if in_selection {
-                    stdout.write_all(BOLD_U8).map_err(|e| {
+                    writer.write_all(themed_bytes(BOLD_U8)).map_err(|e| {
                         LinesError::DisplayError(stack_format_it(
                             "rURWC sel write: {}",
                             &[&e.to_string()],
                             "rURWC sel write",
                         ))
                     })?;
-                    stdout.write_all(YELLOW_U8).map_err(|e| {
+                    writer.write_all(themed_bytes(YELLOW_U8)).map_err(|e| {
                         LinesError::DisplayError(stack_format_it(
                             "rURWC sel write: {}",
                             &[&e.to_string()],
                             "rURWC sel write",
                         ))
                     })?;
-                    stdout.write_all(BG_CYAN_U8).map_err(|e| {
+                    writer.write_all(themed_bytes(BG_CYAN_U8)).map_err(|e| {
                         LinesError::DisplayError(stack_format_it(
                             "rURWC sel write: {}",
                             &[&e.to_string()],
                             "rURWC sel write",
                         ))
                     })?;
-                    stdout.write_all(char_bytes).map_err(|e| {
+                    writer.write_all(char_bytes).map_err(|e| {
                         LinesError::DisplayError(stack_format_it(
                             "rURWC sel write: {}",
                             &[&e.to_string()],
                             "rURWC sel write",
                         ))
                     })?;
-                    stdout.write_all(RESET_U8).map_err(|e| {
+                    writer.write_all(themed_bytes(RESET_U8)).map_err(|e| {
                         LinesError::DisplayError(stack_format_it(
                             "rURWC sel write: {}",
                             &[&e.to_string()],
@@ -22924,21 +23444,21 @@ fn render_utf8txt_row_with_cursor(
             match highlight {
                 SyntaxHighlight::SyntaxSymbol => {
                     // Single symbol character in colour.
-                    stdout.write_all(SYMBOL_COLOUR).map_err(|e| {
+                    writer.write_all(themed_bytes(SYMBOL_COLOUR)).map_err(|e| {
                         LinesError::DisplayError(stack_format_it(
                             "rURWC syn write: {}",
                             &[&e.to_string()],
                             "rURWC syn write",
                         ))
                     })?;
-                    stdout.write_all(char_bytes).map_err(|e| {
+                    writer.write_all(char_bytes).map_err(|e| {
                         LinesError::DisplayError(stack_format_it(
                             "rURWC syn write: {}",
                             &[&e.to_string()],
                             "rURWC syn write",
                         ))
                     })?;
-                    stdout.write_all(RESET_U8).map_err(|e| {
+                    writer.write_all(themed_bytes(RESET_U8)).map_err(|e| {
                         LinesError::DisplayError(stack_format_it(
                             "rURWC syn write: {}",
                             &[&e.to_string()],
@@ -22983,21 +23503,23 @@ fn render_utf8txt_row_with_cursor(
                         // No cursor conflict: write the whole keyword in yellow.
                         let keyword_bytes = &row_bytes[byte_pos..keyword_end_byte];
 
-                        stdout.write_all(DEFINITION_COLOUR).map_err(|e| {
-                            LinesError::DisplayError(stack_format_it(
-                                "rURWC kw write: {}",
-                                &[&e.to_string()],
-                                "rURWC kw write",
-                            ))
-                        })?;
-                        stdout.write_all(keyword_bytes).map_err(|e| {
+                        writer
+                            .write_all(themed_bytes(DEFINITION_COLOUR))
+                            .map_err(|e| {
+                                LinesError::DisplayError(stack_format_it(
+                                    "rURWC kw write: {}",
+                                    &[&e.to_string()],
+                                    "rURWC kw write",
+                                ))
+                            })?;
+                        writer.write_all(keyword_bytes).map_err(|e| {
                             LinesError::DisplayError(stack_format_it(
                                 "rURWC kw write: {}",
                                 &[&e.to_string()],
                                 "rURWC kw write",
                             ))
                         })?;
-                        stdout.write_all(RESET_U8).map_err(|e| {
+                        writer.write_all(themed_bytes(RESET_U8)).map_err(|e| {
                             LinesError::DisplayError(stack_format_it(
                                 "rURWC kw write: {}",
                                 &[&e.to_string()],
@@ -23013,21 +23535,21 @@ fn render_utf8txt_row_with_cursor(
                     // Cursor IS inside the keyword: write only this first
                     // character (in yellow); a later iteration lands the cursor
                     // character on PRIORITY 1.
-                    stdout.write_all(YELLOW_U8).map_err(|e| {
+                    writer.write_all(themed_bytes(YELLOW_U8)).map_err(|e| {
                         LinesError::DisplayError(stack_format_it(
                             "rURWC kw partial: {}",
                             &[&e.to_string()],
                             "rURWC kw partial",
                         ))
                     })?;
-                    stdout.write_all(char_bytes).map_err(|e| {
+                    writer.write_all(char_bytes).map_err(|e| {
                         LinesError::DisplayError(stack_format_it(
                             "rURWC kw partial: {}",
                             &[&e.to_string()],
                             "rURWC kw partial",
                         ))
                     })?;
-                    stdout.write_all(RESET_U8).map_err(|e| {
+                    writer.write_all(themed_bytes(RESET_U8)).map_err(|e| {
                         LinesError::DisplayError(stack_format_it(
                             "rURWC kw partial: {}",
                             &[&e.to_string()],
@@ -23052,21 +23574,21 @@ fn render_utf8txt_row_with_cursor(
         // Rendered as a blue → glyph (TAB_GLYPH), which is one visual cell, so
         // visual_col advances by display_width (== 1 for the single-byte tab).
         if char_bytes == b"\t" {
-            stdout.write_all(TAB_COLOUR).map_err(|e| {
+            writer.write_all(themed_bytes(TAB_COLOUR)).map_err(|e| {
                 LinesError::DisplayError(stack_format_it(
                     "rURWC tab write: {}",
                     &[&e.to_string()],
                     "rURWC tab write",
                 ))
             })?;
-            stdout.write_all(TAB_GLYPH).map_err(|e| {
+            writer.write_all(TAB_GLYPH).map_err(|e| {
                 LinesError::DisplayError(stack_format_it(
                     "rURWC tab write: {}",
                     &[&e.to_string()],
                     "rURWC tab write",
                 ))
             })?;
-            stdout.write_all(RESET_U8).map_err(|e| {
+            writer.write_all(themed_bytes(RESET_U8)).map_err(|e| {
                 LinesError::DisplayError(stack_format_it(
                     "rURWC tab write: {}",
                     &[&e.to_string()],
@@ -23082,21 +23604,23 @@ fn render_utf8txt_row_with_cursor(
         // =====================================================================
         // PRIORITY 5: PLAIN CHARACTER — DEFAULT_TEXT_COLOUR (green)
         // =====================================================================
-        stdout.write_all(DEFAULT_TEXT_COLOUR).map_err(|e| {
-            LinesError::DisplayError(stack_format_it(
-                "rURWC plain write: {}",
-                &[&e.to_string()],
-                "rURWC plain write",
-            ))
-        })?;
-        stdout.write_all(char_bytes).map_err(|e| {
+        writer
+            .write_all(themed_bytes(DEFAULT_TEXT_COLOUR))
+            .map_err(|e| {
+                LinesError::DisplayError(stack_format_it(
+                    "rURWC plain write: {}",
+                    &[&e.to_string()],
+                    "rURWC plain write",
+                ))
+            })?;
+        writer.write_all(char_bytes).map_err(|e| {
             LinesError::DisplayError(stack_format_it(
                 "rURWC plain write: {}",
                 &[&e.to_string()],
                 "rURWC plain write",
             ))
         })?;
-        stdout.write_all(RESET_U8).map_err(|e| {
+        writer.write_all(themed_bytes(RESET_U8)).map_err(|e| {
             LinesError::DisplayError(stack_format_it(
                 "rURWC plain write: {}",
                 &[&e.to_string()],
@@ -23115,35 +23639,35 @@ fn render_utf8txt_row_with_cursor(
     // width, draw the block at the end so the user can append after the last
     // character. Compared in VISUAL cells (matches Option A).
     if cursor_on_this_row && effective_cursor_col >= total_visual_width {
-        stdout.write_all(BOLD_U8).map_err(|e| {
+        writer.write_all(themed_bytes(BOLD_U8)).map_err(|e| {
             LinesError::DisplayError(stack_format_it(
                 "rURWC eol cursor: {}",
                 &[&e.to_string()],
                 "rURWC eol cursor",
             ))
         })?;
-        stdout.write_all(RED_U8).map_err(|e| {
+        writer.write_all(themed_bytes(RED_U8)).map_err(|e| {
             LinesError::DisplayError(stack_format_it(
                 "rURWC eol cursor: {}",
                 &[&e.to_string()],
                 "rURWC eol cursor",
             ))
         })?;
-        stdout.write_all(BG_WHITE_U8).map_err(|e| {
+        writer.write_all(themed_bytes(BG_WHITE_U8)).map_err(|e| {
             LinesError::DisplayError(stack_format_it(
                 "rURWC eol cursor: {}",
                 &[&e.to_string()],
                 "rURWC eol cursor",
             ))
         })?;
-        stdout.write_all("█".as_bytes()).map_err(|e| {
+        writer.write_all("█".as_bytes()).map_err(|e| {
             LinesError::DisplayError(stack_format_it(
                 "rURWC eol cursor: {}",
                 &[&e.to_string()],
                 "rURWC eol cursor",
             ))
         })?;
-        stdout.write_all(RESET_U8).map_err(|e| {
+        writer.write_all(themed_bytes(RESET_U8)).map_err(|e| {
             LinesError::DisplayError(stack_format_it(
                 "rURWC eol cursor: {}",
                 &[&e.to_string()],
@@ -23872,6 +24396,15 @@ pub fn lines_fullfile_editor_core(
     let mut lines_editor_state = EditorState::new();
     lines_editor_state.original_file_path = Some(target_path.clone());
 
+    // Size the editing area to the real window; redraw on resize (SIGWINCH)
+    lines_editor_state.fit_to_terminal_window(query_terminal_window_size().ok());
+    if install_window_resize_handler().is_err() {
+        eprintln!("Warning: window resize detection unavailable");
+    }
+    if install_termination_handler().is_err() {
+        eprintln!("Warning: clean Ctrl-C handling unavailable");
+    }
+
     // Initialize session directory FIRST
     initialize_session_directory(
         &mut lines_editor_state,
@@ -23962,12 +24495,37 @@ pub fn lines_fullfile_editor_core(
     // Defensive: Limit loop iterations to prevent infinite loops
     let mut iteration_count = 0;
 
+    // Start each session with a full repaint; after that, only changed rows
+    // are redrawn until the mode changes (other modes draw their own screens).
+    invalidate_utf8txt_tui_frame();
+    let mut last_rendered_mode = lines_editor_state.mode;
+
     //  ===============================
     //  Main Loop for Full Lines Editor
     //  ===============================
     while keep_editor_loop_running && iteration_count < limits::MAIN_EDITOR_LOOP_COMMANDS {
         iteration_count += 1;
 
+        // ==================
+        // Ctrl-C and SIGTERM
+        // ==================
+        // Command reads return here when a signal arrives (poll wakes up; a
+        // keystroke-mode raw read fails with EINTR and its RawTerminal drop
+        // restores the terminal first).
+        if let Some(signal_number) = pending_termination_signal() {
+            exit_lines_on_termination_signal(&lines_editor_state, signal_number);
+        }
+
+        // =============
+        // Window Resize
+        // =============
+        // SIGWINCH since the last draw: re-fit to the new size (tall/wide
+        // offsets kept) and rebuild the window before rendering.
+        if take_window_resized() {
+            lines_editor_state.fit_to_terminal_window(query_terminal_window_size().ok());
+            build_windowmap_nowrap(&mut lines_editor_state, &read_copy)?;
+        }
+
         // ================
         // Bump on Main St.
         // ================
@@ -24021,6 +24579,11 @@ pub fn lines_fullfile_editor_core(
             }
         }
 
+        if lines_editor_state.mode != last_rendered_mode {
+            invalidate_utf8txt_tui_frame();
+            last_rendered_mode = lines_editor_state.mode;
+        }
+
         if lines_editor_state.mode == EditorMode::HexMode {
             //  ======================
             //  HEX Render a Flesh TUI
@@ -5,10 +5,12 @@ mod ff_file_fantastic_module;
 use ff_file_fantastic_module::file_fantastic;
 
 // import rows and columns helper module w/ these 3 lines
+mod csv_convert_module;
 mod csv_processor_module;
 mod error_types_module;
 mod ribbon_external_counter_module;
 mod rows_and_columns_module;
+mod spreadsheet_import_module;
 
 // lines
 mod buffy_format_write_module;
@@ -23,6 +25,9 @@ mod source_it_module;
 // show data & code file line counts
 mod row_line_count_tui_module;
 
+// color themes, NO_COLOR and monochrome output
+mod color_theme_module;
+
 // ff as a module for any project, e.g.
 fn main() {
     // Let's call File Fantastic
@@ -91,6 +91,7 @@ use core::arch::asm;
 use std::fs::{File, OpenOptions};
 use std::io::{self, Read, Write};
 use std::os::unix::io::AsRawFd;
+use std::sync::atomic::{AtomicBool, AtomicI32, Ordering};
 
 // ============================================================================
 // LINUX KERNEL CONSTANTS - x86_64 SPECIFIC
@@ -814,6 +815,9 @@ pub struct RawTerminal {
 
     /// Original terminal settings, saved for restoration on drop
     prev_ios: Termios,
+
+    /// True while SGR mouse reporting is on (turned off again on drop)
+    mouse_reporting_enabled: bool,
 }
 
 impl RawTerminal {
@@ -892,7 +896,92 @@ impl RawTerminal {
         // ---------------------------------------------------------------------
         set_terminal_attr(fd, &raw_ios)?;
 
-        Ok(RawTerminal { tty, prev_ios })
+        Ok(RawTerminal {
+            tty,
+            prev_ios,
+            mouse_reporting_enabled: false,
+        })
+    }
+
+    /// Ask the terminal to report mouse clicks and wheel turns (SGR 1006).
+    ///
+    /// ## Project Context
+    ///
+    /// Mouse reports arrive as escape sequences on the same input as keys
+    /// (`ESC [ < button ; column ; row M`); `parse_raw_key_bytes` decodes
+    /// them into `RawKey::Mouse`. Reporting is switched off again by
+    /// `disable_mouse_reporting` or on drop, so the shell never receives
+    /// stray mouse sequences after we exit.
+    ///
+    /// ## Returns
+    ///
+    /// * `Ok(())` - Enable sequence written
+    /// * `Err(io::Error)` - Writing to the terminal failed
+    pub fn enable_mouse_reporting(&mut self) -> io::Result<()> {
+        self.tty.write_all(SGR_MOUSE_REPORTING_ON)?;
+        self.tty.flush()?;
+        self.mouse_reporting_enabled = true;
+        Ok(())
+    }
+
+    /// Stop mouse reporting (the terminal's own selection works again).
+    ///
+    /// ## Returns
+    ///
+    /// * `Ok(())` - Disable sequence written (or reporting was already off)
+    /// * `Err(io::Error)` - Writing to the terminal failed
+    pub fn disable_mouse_reporting(&mut self) -> io::Result<()> {
+        if self.mouse_reporting_enabled {
+            self.tty.write_all(SGR_MOUSE_REPORTING_OFF)?;
+            self.tty.flush()?;
+            self.mouse_reporting_enabled = false;
+        }
+        Ok(())
+    }
+
+    /// Read the rest of an escape sequence whose first bytes are already read.
+    ///
+    /// ## Project Context
+    ///
+    /// The bytes of one key or mouse report arrive together; a lone ESC has
+    /// nothing after it. Reading stops at the sequence's final byte (a letter
+    /// or '~'), when no byte follows within `ESCAPE_SEQUENCE_TIMEOUT_MS`, or
+    /// when `key_bytes` is full.
+    ///
+    /// ## Arguments
+    ///
+    /// * `key_bytes` - Buffer holding the sequence read so far
+    /// * `key_length` - How many bytes of `key_bytes` are already filled (>= 1)
+    ///
+    /// ## Returns
+    ///
+    /// * `Ok(usize)` - The new filled length
+    /// * `Err(io::Error)` - Read failure
+    pub fn read_escape_sequence_rest(
+        &mut self,
+        key_bytes: &mut [u8],
+        key_length: usize,
+    ) -> io::Result<usize> {
+        let tty_fd = self.tty.as_raw_fd();
+        let mut key_length = key_length.min(key_bytes.len());
+
+        while key_length < key_bytes.len()
+            && is_fd_readable_within(tty_fd, ESCAPE_SEQUENCE_TIMEOUT_MS)
+        {
+            let bytes_read = self.read(&mut key_bytes[key_length..key_length + 1])?;
+            if bytes_read == 0 {
+                break;
+            }
+            key_length += bytes_read;
+
+            // CSI / SS3 sequences (and SGR mouse reports) end with a letter or '~'
+            let last_byte = key_bytes[key_length - 1];
+            if key_length >= 3 && (last_byte.is_ascii_alphabetic() || last_byte == b'~') {
+                break;
+            }
+        }
+
+        Ok(key_length)
     }
 
     /// Temporarily suspend raw mode, restoring normal terminal behavior.
@@ -1141,6 +1230,12 @@ impl Drop for RawTerminal {
     ///
     /// Per production rules: errors must be logged even when not propagatable.
     fn drop(&mut self) {
+        // Mouse reporting outlives the process unless switched off
+        if self.mouse_reporting_enabled {
+            let _ = self.tty.write_all(SGR_MOUSE_REPORTING_OFF);
+            let _ = self.tty.flush();
+        }
+
         let fd = self.tty.as_raw_fd();
 
         // Bounded retry: terminal restore is critical for shell usability, and
@@ -1217,6 +1312,818 @@ impl Write for RawTerminal {
     }
 }
 
+// ============================================================================
+// WINDOW SIZE - TIOCGWINSZ AND SIGWINCH
+// ============================================================================
+//
+// ## Project Context
+//
+// ff and Lines used to lay out their screens from fixed 80x24 defaults plus
+// manual tall+N / wide-N adjustments. This section lets them ask the kernel
+// for the real window size and notice when the window is resized:
+//
+// - `query_terminal_window_size()`: `ioctl(fd, TIOCGWINSZ, &winsize)` using
+//   the same inline-assembly syscall approach as TCGETS above.
+// - `install_window_resize_handler()`: a SIGWINCH handler that only sets an
+//   atomic flag. Installed with `sigaction` from the C library std already
+//   links (no crate); `SA_RESTART` keeps ordinary blocking reads working.
+// - `wait_for_stdin_or_resize()`: waits with `poll` (never restarted by
+//   the kernel) so a resize can trigger a redraw while waiting for input.
+// ============================================================================
+
+/// Get window size - reads winsize struct
+/// Equivalent to: ioctl(fd, TIOCGWINSZ, &winsize)
+const TIOCGWINSZ: u64 = 0x5413;
+
+/// poll syscall number on x86_64 Linux
+const SYS_POLL: u64 = 7;
+
+/// poll event: data to read
+const POLLIN: i16 = 0x0001;
+
+/// Window change signal number on Linux
+const SIGWINCH: i32 = 28;
+
+/// sigaction flag: restart interruptible syscalls (read, write) after the handler
+const SA_RESTART: i32 = 0x1000_0000;
+
+/// EINTR: a system call was interrupted by a signal handler
+const EINTR: i64 = 4;
+
+/// How long `wait_for_stdin_or_resize` sleeps between resize-flag checks
+const RESIZE_POLL_INTERVAL_MS: i32 = 250;
+
+/// Window size structure matching the Linux kernel `struct winsize` (8 bytes)
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Default)]
+struct Winsize {
+    /// Rows, in characters
+    ws_row: u16,
+    /// Columns, in characters
+    ws_col: u16,
+    /// Width in pixels (unused)
+    ws_xpixel: u16,
+    /// Height in pixels (unused)
+    ws_ypixel: u16,
+}
+
+/// One entry for the poll syscall, matching the kernel `struct pollfd`
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+struct PollFd {
+    /// File descriptor to watch
+    fd: i32,
+    /// Requested events
+    events: i16,
+    /// Returned events (written by the kernel)
+    revents: i16,
+}
+
+/// C library `struct sigaction` layout on Linux x86_64 (glibc and musl agree)
+///
+/// ```text
+/// Offset  Size  Field
+/// 0       8     sa_handler
+/// 8       128   sa_mask (sigset_t, 1024 bits)
+/// 136     4     sa_flags
+/// 140     4     (padding)
+/// 144     8     sa_restorer
+/// Total: 152 bytes
+/// ```
+#[repr(C)]
+struct SigAction {
+    /// Handler function address
+    sa_handler: usize,
+    /// Signals blocked while the handler runs
+    sa_mask: [u64; 16],
+    /// SA_* flags
+    sa_flags: i32,
+    /// Set by the C library; we pass 0
+    sa_restorer: usize,
+}
+
+// Compile-time verification of struct sizes
+const _: () = {
+    if core::mem::size_of::<Winsize>() != 8 {
+        panic!("Winsize struct size must be exactly 8 bytes");
+    }
+    if core::mem::size_of::<SigAction>() != 152 {
+        panic!("SigAction struct size must be exactly 152 bytes for Linux x86_64");
+    }
+};
+
+unsafe extern "C" {
+    /// C library sigaction(2) wrapper (std already links the C library)
+    fn sigaction(signum: i32, act: *const SigAction, oldact: *mut SigAction) -> i32;
+}
+
+/// Set by the SIGWINCH handler; cleared by `take_window_resized`
+static WINDOW_RESIZED: AtomicBool = AtomicBool::new(false);
+
+/// Set once the SIGWINCH handler is installed
+static RESIZE_HANDLER_INSTALLED: AtomicBool = AtomicBool::new(false);
+
+/// Terminal window size in character cells
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TerminalWindowSize {
+    /// Number of rows (lines)
+    pub rows: u16,
+    /// Number of columns (characters per line)
+    pub cols: u16,
+}
+
+/// Execute ioctl syscall for **reading** the window size (TIOCGWINSZ).
+///
+/// ## Safety Contract
+///
+/// - `arg` must point to a valid, properly aligned, **writable** `Winsize`.
+/// - The pointed-to memory must remain valid for the duration of the syscall.
+#[inline]
+unsafe fn ioctl_read_winsize(fd: i32, arg: *mut Winsize) -> i64 {
+    let ret: i64;
+
+    // SAFETY: Same register convention and reasoning as `ioctl_read_termios`;
+    // TIOCGWINSZ writes 8 bytes into the caller's Winsize.
+    unsafe {
+        asm!(
+            "syscall",
+            inlateout("rax") SYS_IOCTL => ret,
+            in("rdi") fd as u64,
+            in("rsi") TIOCGWINSZ,
+            in("rdx") arg as u64,
+            out("rcx") _,
+            out("r11") _,
+            options(nostack),
+        );
+    }
+
+    ret
+}
+
+/// Execute the poll syscall on a single file descriptor.
+///
+/// ## Safety Contract
+///
+/// - `poll_fd` must point to a valid, writable `PollFd` for the syscall duration.
+#[inline]
+unsafe fn poll_single_fd(poll_fd: *mut PollFd, timeout_ms: i32) -> i64 {
+    let ret: i64;
+
+    // SAFETY: poll(fds, nfds = 1, timeout) reads `fd`/`events` and writes
+    // `revents` of the one PollFd the caller owns.
+    unsafe {
+        asm!(
+            "syscall",
+            inlateout("rax") SYS_POLL => ret,
+            in("rdi") poll_fd as u64,
+            in("rsi") 1u64,
+            in("rdx") timeout_ms as i64 as u64,
+            out("rcx") _,
+            out("r11") _,
+            options(nostack),
+        );
+    }
+
+    ret
+}
+
+/// Read the window size of one file descriptor.
+///
+/// ## Returns
+///
+/// * `Ok(TerminalWindowSize)` - Rows and columns (both non-zero)
+/// * `Err(io::Error)` - ENOTTY if fd is not a terminal; EINVAL if the
+///   kernel reports a zero size (e.g. a serial line with no size set)
+fn get_window_size(fd: i32) -> io::Result<TerminalWindowSize> {
+    let mut winsize = Winsize::default();
+
+    // SAFETY: winsize is a local, properly aligned, writable Winsize.
+    let ret = unsafe { ioctl_read_winsize(fd, &mut winsize) };
+
+    if ret < 0 {
+        let errno = if (-4095..0).contains(&ret) {
+            -ret as i32
+        } else {
+            5
+        };
+        return Err(io::Error::from_raw_os_error(errno));
+    }
+
+    if winsize.ws_row == 0 || winsize.ws_col == 0 {
+        return Err(io::Error::from_raw_os_error(22));
+    }
+
+    Ok(TerminalWindowSize {
+        rows: winsize.ws_row,
+        cols: winsize.ws_col,
+    })
+}
+
+/// Query the real size of the terminal window.
+///
+/// ## Project Context
+///
+/// Used by ff and Lines to size their screens. Tries stdout, then stdin,
+/// then `/dev/tty`, so it works when one of the standard streams is
+/// redirected.
+///
+/// ## Returns
+///
+/// * `Ok(TerminalWindowSize)` - Current rows and columns
+/// * `Err(io::Error)` - Not running in a terminal; callers fall back to
+///   their 80x24 defaults
+pub fn query_terminal_window_size() -> io::Result<TerminalWindowSize> {
+    if let Ok(size) = get_window_size(1) {
+        return Ok(size);
+    }
+    if let Ok(size) = get_window_size(0) {
+        return Ok(size);
+    }
+
+    let tty = OpenOptions::new().read(true).open("/dev/tty")?;
+    get_window_size(tty.as_raw_fd())
+}
+
+/// SIGWINCH handler: only sets an atomic flag (async-signal-safe).
+extern "C" fn handle_window_resize_signal(_signal_number: i32) {
+    WINDOW_RESIZED.store(true, Ordering::SeqCst);
+}
+
+/// Install the SIGWINCH handler (safe to call more than once).
+///
+/// ## Returns
+///
+/// * `Ok(())` - Handler installed (or already installed)
+/// * `Err(io::Error)` - sigaction failed; resize detection is then limited
+///   to re-querying the size before each redraw
+pub fn install_window_resize_handler() -> io::Result<()> {
+    if RESIZE_HANDLER_INSTALLED.load(Ordering::SeqCst) {
+        return Ok(());
+    }
+
+    let action = SigAction {
+        sa_handler: handle_window_resize_signal as extern "C" fn(i32) as usize,
+        sa_mask: [0u64; 16],
+        sa_flags: SA_RESTART,
+        sa_restorer: 0,
+    };
+
+    // SAFETY: `action` is a fully initialized SigAction with the C library
+    // layout; a null `oldact` is allowed. The handler only touches an atomic.
+    let ret = unsafe { sigaction(SIGWINCH, &action, std::ptr::null_mut()) };
+    if ret != 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    RESIZE_HANDLER_INSTALLED.store(true, Ordering::SeqCst);
+    Ok(())
+}
+
+/// Returns true once per resize: reads and clears the SIGWINCH flag.
+pub fn take_window_resized() -> bool {
+    WINDOW_RESIZED.swap(false, Ordering::SeqCst)
+}
+
+/// Wait until stdin has input or the window is resized.
+///
+/// ## Project Context
+///
+/// Called right before a blocking line read so the screen can be redrawn
+/// at the new size without waiting for the next command. Only used when
+/// stdin is a terminal: in canonical mode a terminal read returns at most
+/// one line, so std's stdin buffer is empty between prompts and polling
+/// the file descriptor cannot miss typed-ahead input.
+///
+/// ## Returns
+///
+/// * `Ok(true)` - The window was resized; the flag stays set so the caller
+///   can re-layout, call `take_window_resized`, redraw, then wait again.
+///   Also returned when SIGINT/SIGTERM arrived (see
+///   `pending_termination_signal`), so the caller's loop can exit cleanly
+/// * `Ok(false)` - Input is ready, or stdin is not a terminal / the
+///   handler is not installed (read normally)
+/// * `Err(io::Error)` - poll failed for a reason other than EINTR
+pub fn wait_for_stdin_or_resize() -> io::Result<bool> {
+    if !RESIZE_HANDLER_INSTALLED.load(Ordering::SeqCst) || get_window_size(0).is_err() {
+        return Ok(false);
+    }
+
+    wait_for_fd_or_resize(0)
+}
+
+/// Poll one file descriptor until it is readable or a resize/termination flag is set.
+///
+/// ## Arguments
+///
+/// * `fd` - Descriptor to wait on (stdin, or the raw-mode `/dev/tty`)
+///
+/// ## Returns
+///
+/// * `Ok(true)` - The window was resized or SIGINT/SIGTERM arrived (flags are left set)
+/// * `Ok(false)` - The descriptor is readable (or hung up / in error: let the read report it)
+/// * `Err(io::Error)` - poll failed for a reason other than EINTR
+fn wait_for_fd_or_resize(fd: i32) -> io::Result<bool> {
+    loop {
+        if WINDOW_RESIZED.load(Ordering::SeqCst) || pending_termination_signal().is_some() {
+            return Ok(true);
+        }
+
+        let mut poll_fd = PollFd {
+            fd,
+            events: POLLIN,
+            revents: 0,
+        };
+
+        // SAFETY: poll_fd is a local, writable PollFd.
+        let ret = unsafe { poll_single_fd(&mut poll_fd, RESIZE_POLL_INTERVAL_MS) };
+
+        if ret > 0 {
+            return Ok(false); // Readable, hung up, or error: let the read report it
+        }
+        if ret < 0 && ret != -EINTR {
+            let errno = if (-4095..0).contains(&ret) {
+                -ret as i32
+            } else {
+                5
+            };
+            return Err(io::Error::from_raw_os_error(errno));
+        }
+        // Timeout or EINTR: check the resize and termination flags again
+    }
+}
+
+// ============================================================================
+// TERMINATION SIGNALS - SIGINT AND SIGTERM
+// ============================================================================
+//
+// ## Project Context
+//
+// With the default dispositions, Ctrl-C or `kill` ends ff/Lines at once:
+// `RawTerminal::drop` never runs (the terminal can stay raw) and Lines
+// session drafts are left behind. `install_termination_handler()` replaces
+// that with a handler that only records the signal number. The main loops
+// poll `pending_termination_signal()`, restore the terminal, print a notice
+// and exit with `termination_exit_code()`.
+//
+// - No SA_RESTART: a blocking raw-terminal read fails with EINTR so the
+//   caller returns to its main loop. (std's read_line retries EINTR; line
+//   prompts are woken by `wait_for_stdin_or_resize` instead.)
+// - SA_RESETHAND: the handler runs once, then the default disposition is
+//   back - a second Ctrl-C still ends the program immediately if some
+//   prompt does not return to a main loop.
+// - Cooked-mode prompts that never poll the flag (menus, y/N questions,
+//   the TUIs ff runs in-process) would swallow the first Ctrl-C, so ff
+//   calls `restore_default_termination_handling()` once its command line
+//   is read and re-arms the handler before the next main-loop wait.
+// ============================================================================
+
+/// Interrupt signal (Ctrl-C) number on Linux
+pub const SIGINT: i32 = 2;
+
+/// Termination signal (default `kill`) number on Linux
+pub const SIGTERM: i32 = 15;
+
+/// sigaction flag: restore the default disposition after one delivery
+const SA_RESETHAND: i32 = 0x8000_0000_u32 as i32;
+
+/// sigaction handler value for the default disposition
+const SIG_DFL: usize = 0;
+
+/// Signal number of the first SIGINT/SIGTERM received (0 = none)
+static TERMINATION_SIGNAL: AtomicI32 = AtomicI32::new(0);
+
+/// SIGINT/SIGTERM handler: records the signal number (async-signal-safe).
+extern "C" fn handle_termination_signal(signal_number: i32) {
+    // Keep the first signal if several arrive
+    let _ =
+        TERMINATION_SIGNAL.compare_exchange(0, signal_number, Ordering::SeqCst, Ordering::SeqCst);
+}
+
+/// Install the SIGINT and SIGTERM handlers (safe to call more than once).
+///
+/// ## Returns
+///
+/// * `Ok(())` - Both handlers installed
+/// * `Err(io::Error)` - sigaction failed; the default dispositions (stop
+///   immediately) remain for any signal not yet installed
+pub fn install_termination_handler() -> io::Result<()> {
+    let action = SigAction {
+        sa_handler: handle_termination_signal as extern "C" fn(i32) as usize,
+        sa_mask: [0u64; 16],
+        sa_flags: SA_RESETHAND,
+        sa_restorer: 0,
+    };
+
+    for signal_number in [SIGINT, SIGTERM] {
+        // SAFETY: `action` is a fully initialized SigAction with the C library
+        // layout; a null `oldact` is allowed. The handler only touches an atomic.
+        let ret = unsafe { sigaction(signal_number, &action, std::ptr::null_mut()) };
+        if ret != 0 {
+            return Err(io::Error::last_os_error());
+        }
+    }
+
+    Ok(())
+}
+
+/// Put SIGINT and SIGTERM back to their default dispositions (stop at once).
+///
+/// For stretches of cooked-mode `read_line` prompts that do not check
+/// `pending_termination_signal()`: std retries a read interrupted by the
+/// handler, so the first Ctrl-C would otherwise be swallowed there. Call
+/// `install_termination_handler()` again before the next main-loop wait.
+///
+/// ## Returns
+///
+/// * `Ok(())` - Both signals use the default disposition
+/// * `Err(io::Error)` - sigaction failed
+pub fn restore_default_termination_handling() -> io::Result<()> {
+    let action = SigAction {
+        sa_handler: SIG_DFL,
+        sa_mask: [0u64; 16],
+        sa_flags: 0,
+        sa_restorer: 0,
+    };
+
+    for signal_number in [SIGINT, SIGTERM] {
+        // SAFETY: `action` is a fully initialized SigAction with the C library
+        // layout; a null `oldact` is allowed.
+        let ret = unsafe { sigaction(signal_number, &action, std::ptr::null_mut()) };
+        if ret != 0 {
+            return Err(io::Error::last_os_error());
+        }
+    }
+
+    Ok(())
+}
+
+/// Returns the SIGINT/SIGTERM number if one has arrived (flag is not cleared).
+pub fn pending_termination_signal() -> Option<i32> {
+    match TERMINATION_SIGNAL.load(Ordering::SeqCst) {
+        0 => None,
+        signal_number => Some(signal_number),
+    }
+}
+
+/// Process exit status for a termination signal, following the shell
+/// convention of 128 + signal number.
+///
+/// ## Returns
+///
+/// * `130` for SIGINT, `143` for SIGTERM
+pub fn termination_exit_code(signal_number: i32) -> i32 {
+    128 + signal_number
+}
+
+/// Short name for messages ("SIGINT", "SIGTERM", or "signal").
+pub fn termination_signal_name(signal_number: i32) -> &'static str {
+    match signal_number {
+        SIGINT => "SIGINT",
+        SIGTERM => "SIGTERM",
+        _ => "signal",
+    }
+}
+
+/// Return a cooked-mode terminal to a clean visual state before exiting:
+/// reset colors/attributes, show the cursor, and end the current line.
+///
+/// Termios is restored separately by `RawTerminal::drop`; this covers the
+/// escape-sequence state a half-drawn screen can leave behind.
+pub fn reset_terminal_display_state() {
+    let mut stdout = io::stdout();
+    let _ = stdout.write_all(b"\x1b[0m\x1b[?25h\r\n");
+    let _ = stdout.flush();
+}
+
+// ============================================================================
+// SINGLE KEYPRESS INPUT
+// ============================================================================
+//
+// ## Project Context
+//
+// ff's optional raw-key navigation reads one key at a time instead of a
+// line. Each call to `read_raw_key()` owns a `RawTerminal` only for the
+// duration of one keypress, so the terminal is back in normal mode while
+// ff prints, opens files, or prompts with read_line - and is restored by
+// Drop on every exit path.
+//
+// Mouse: with SGR (1006) reporting enabled on the RawTerminal, clicks and
+// wheel turns arrive as `ESC [ < button ; column ; row M/m` and decode to
+// `RawKey::Mouse`. Reporting is only on while a raw read is waiting, so
+// the terminal's own text selection works the rest of the time.
+// ============================================================================
+
+/// How long to wait for the rest of an escape sequence after ESC
+const ESCAPE_SEQUENCE_TIMEOUT_MS: i32 = 30;
+
+/// How long `read_raw_key` waits for a click's release report
+const MOUSE_RELEASE_TIMEOUT_MS: i32 = 500;
+
+/// First bytes of every SGR mouse report: `ESC [ <`
+pub const SGR_MOUSE_REPORT_PREFIX: &[u8] = b"\x1b[<";
+
+/// Report button presses/releases (1000) using SGR encoding (1006)
+const SGR_MOUSE_REPORTING_ON: &[u8] = b"\x1b[?1000h\x1b[?1006h";
+
+/// Turn both mouse modes off again
+const SGR_MOUSE_REPORTING_OFF: &[u8] = b"\x1b[?1006l\x1b[?1000l";
+
+/// Longest key or mouse report read as one unit (`ESC [ < 64 ; 65535 ; 65535 M` is 19)
+pub const RAW_KEY_MAX_BYTES: usize = 24;
+
+/// What the mouse did
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MouseEventKind {
+    /// Left button pressed
+    LeftPress,
+    /// Middle button pressed
+    MiddlePress,
+    /// Right button pressed
+    RightPress,
+    /// Any button released
+    Release,
+    /// Wheel turned up (away from the user)
+    WheelUp,
+    /// Wheel turned down
+    WheelDown,
+}
+
+/// One decoded SGR mouse report
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MouseEvent {
+    /// What happened
+    pub kind: MouseEventKind,
+    /// Screen row, 1-based (row 1 is the top line)
+    pub row: u16,
+    /// Screen column, 1-based
+    pub column: u16,
+}
+
+/// Decode the body of an SGR mouse report (the bytes after `ESC [ <`).
+///
+/// ## Arguments
+///
+/// * `report_bytes` - `button ; column ; row` followed by `M` (press) or `m` (release)
+///
+/// ## Returns
+///
+/// * `Some(MouseEvent)` - A press, release, or wheel turn
+/// * `None` - Malformed report, motion/drag, or an unsupported button
+pub fn parse_sgr_mouse_report(report_bytes: &[u8]) -> Option<MouseEvent> {
+    // Step 1: Split off the final byte (M = press, m = release)
+    let (final_byte, fields) = report_bytes.split_last()?;
+    let is_release = match final_byte {
+        b'M' => false,
+        b'm' => true,
+        _ => return None,
+    };
+
+    // Step 2: Three decimal fields separated by ';'
+    let fields = std::str::from_utf8(fields).ok()?;
+    let mut field_values = fields.split(';').map(|field| field.parse::<u16>().ok());
+    let button_code = field_values.next()??;
+    let column = field_values.next()??;
+    let row = field_values.next()??;
+    if field_values.next().is_some() || row == 0 || column == 0 {
+        return None;
+    }
+
+    // Step 3: Button code: bits 0-1 button, 4/8/16 modifiers, 32 motion, 64 wheel
+    const MOTION_FLAG: u16 = 32;
+    const WHEEL_FLAG: u16 = 64;
+    let kind = if button_code & MOTION_FLAG != 0 {
+        return None;
+    } else if button_code & WHEEL_FLAG != 0 {
+        match button_code & 0b11 {
+            0 => MouseEventKind::WheelUp,
+            1 => MouseEventKind::WheelDown,
+            _ => return None,
+        }
+    } else if is_release {
+        MouseEventKind::Release
+    } else {
+        match button_code & 0b11 {
+            0 => MouseEventKind::LeftPress,
+            1 => MouseEventKind::MiddlePress,
+            2 => MouseEventKind::RightPress,
+            _ => MouseEventKind::Release,
+        }
+    };
+
+    Some(MouseEvent { kind, row, column })
+}
+
+/// One decoded keypress
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RawKey {
+    /// A printable character (including multi-byte UTF-8)
+    Char(char),
+    /// Arrow up
+    Up,
+    /// Arrow down
+    Down,
+    /// Arrow left
+    Left,
+    /// Arrow right
+    Right,
+    /// Page Up
+    PageUp,
+    /// Page Down
+    PageDown,
+    /// Home
+    Home,
+    /// End
+    End,
+    /// Enter / Return
+    Enter,
+    /// Backspace (DEL or Ctrl-H)
+    Backspace,
+    /// A lone Escape press
+    Escape,
+    /// Ctrl-C (arrives as a byte because raw mode disables ISIG)
+    CtrlC,
+    /// A mouse click or wheel turn (only while mouse reporting is on)
+    Mouse(MouseEvent),
+    /// Any other control byte or unrecognized escape sequence
+    Unknown,
+}
+
+/// Decode the bytes of one keypress.
+///
+/// ## Arguments
+///
+/// * `key_bytes` - Bytes read for one key: a single byte, a UTF-8 character,
+///   or an escape sequence starting with ESC (0x1b), including SGR mouse reports
+///
+/// ## Returns
+///
+/// * `RawKey` - The decoded key; `Unknown` for anything unrecognized
+pub fn parse_raw_key_bytes(key_bytes: &[u8]) -> RawKey {
+    match key_bytes {
+        [] => RawKey::Unknown,
+        [0x1b] => RawKey::Escape,
+        // CSI (ESC [) and SS3 (ESC O) cursor keys
+        [0x1b, b'[' | b'O', b'A'] => RawKey::Up,
+        [0x1b, b'[' | b'O', b'B'] => RawKey::Down,
+        [0x1b, b'[' | b'O', b'C'] => RawKey::Right,
+        [0x1b, b'[' | b'O', b'D'] => RawKey::Left,
+        [0x1b, b'[' | b'O', b'H'] | [0x1b, b'[', b'1' | b'7', b'~'] => RawKey::Home,
+        [0x1b, b'[' | b'O', b'F'] | [0x1b, b'[', b'4' | b'8', b'~'] => RawKey::End,
+        [0x1b, b'[', b'5', b'~'] => RawKey::PageUp,
+        [0x1b, b'[', b'6', b'~'] => RawKey::PageDown,
+        [0x1b, b'[', b'<', report_bytes @ ..] => match parse_sgr_mouse_report(report_bytes) {
+            Some(mouse_event) => RawKey::Mouse(mouse_event),
+            None => RawKey::Unknown,
+        },
+        [0x1b, ..] => RawKey::Unknown,
+        [b'\r'] | [b'\n'] => RawKey::Enter,
+        [0x7f] | [0x08] => RawKey::Backspace,
+        [0x03] => RawKey::CtrlC,
+        [single_byte] if single_byte.is_ascii_graphic() || *single_byte == b' ' => {
+            RawKey::Char(*single_byte as char)
+        }
+        multi_byte => match std::str::from_utf8(multi_byte) {
+            Ok(text) => {
+                let mut text_chars = text.chars();
+                match (text_chars.next(), text_chars.next()) {
+                    (Some(key_char), None) if !key_char.is_control() => RawKey::Char(key_char),
+                    _ => RawKey::Unknown,
+                }
+            }
+            Err(_) => RawKey::Unknown,
+        },
+    }
+}
+
+/// Returns true if `fd` has input within `timeout_ms` milliseconds.
+fn is_fd_readable_within(fd: i32, timeout_ms: i32) -> bool {
+    let mut poll_fd = PollFd {
+        fd,
+        events: POLLIN,
+        revents: 0,
+    };
+
+    // SAFETY: poll_fd is a local, writable PollFd.
+    let ret = unsafe { poll_single_fd(&mut poll_fd, timeout_ms) };
+    ret > 0 && (poll_fd.revents & POLLIN) != 0
+}
+
+/// Read a single keypress (or mouse report) from the terminal.
+///
+/// ## Project Context
+///
+/// Enters raw mode, reads one key (the whole escape sequence or UTF-8
+/// character), and restores the terminal before returning. With
+/// `mouse_reporting` on, clicks and wheel turns made while waiting arrive
+/// as `RawKey::Mouse`; reporting is switched off again before returning.
+///
+/// ## Arguments
+///
+/// * `mouse_reporting` - Also report mouse clicks and wheel turns
+///
+/// ## Returns
+///
+/// * `Ok(RawKey)` - The decoded key
+/// * `Err(io::Error)` - No terminal (ENOENT/ENOTTY), read failure, or
+///   UnexpectedEof if the terminal closed
+pub fn read_raw_key(mouse_reporting: bool) -> io::Result<RawKey> {
+    read_raw_key_waking_on_resize(mouse_reporting, false)
+        .map(|raw_key| raw_key.unwrap_or(RawKey::Unknown))
+}
+
+/// Read a single keypress, or return early when the window is resized.
+///
+/// ## Project Context
+///
+/// Like `read_raw_key`, but for a screen that should redraw at a new
+/// window size without waiting for a key. The SIGWINCH handler uses
+/// `SA_RESTART`, so a blocking read would not wake up; this waits with
+/// `poll` in raw mode first (a canonical-mode poll would not see a single
+/// keypress until Enter).
+///
+/// ## Arguments
+///
+/// * `mouse_reporting` - Also report mouse clicks and wheel turns
+///
+/// ## Returns
+///
+/// * `Ok(Some(RawKey))` - The decoded key
+/// * `Ok(None)` - The window was resized, or SIGINT/SIGTERM arrived, before
+///   any key; the flags stay set for the caller (see `wait_for_stdin_or_resize`)
+/// * `Err(io::Error)` - As for `read_raw_key`
+pub fn read_raw_key_or_resize(mouse_reporting: bool) -> io::Result<Option<RawKey>> {
+    read_raw_key_waking_on_resize(
+        mouse_reporting,
+        RESIZE_HANDLER_INSTALLED.load(Ordering::SeqCst),
+    )
+}
+
+/// Shared body of `read_raw_key` and `read_raw_key_or_resize`
+fn read_raw_key_waking_on_resize(
+    mouse_reporting: bool,
+    wake_on_resize: bool,
+) -> io::Result<Option<RawKey>> {
+    let mut term = RawTerminal::new()?;
+    if mouse_reporting {
+        term.enable_mouse_reporting()?;
+    }
+
+    // Step 0: Wait in raw mode, so one keypress (or a resize) ends the wait
+    if wake_on_resize && wait_for_fd_or_resize(term.tty.as_raw_fd())? {
+        return Ok(None);
+    }
+
+    // Step 1: First byte (blocks until a key is pressed)
+    let mut key_bytes = [0u8; RAW_KEY_MAX_BYTES];
+    if term.read(&mut key_bytes[..1])? == 0 {
+        return Err(io::Error::from(io::ErrorKind::UnexpectedEof));
+    }
+    let mut key_length = 1;
+
+    // Step 2: Rest of the key
+    let lead_byte = key_bytes[0];
+    if lead_byte == 0x1b {
+        key_length = term.read_escape_sequence_rest(&mut key_bytes, key_length)?;
+    } else if lead_byte >= 0xC0 {
+        // UTF-8 character: read its continuation bytes
+        let utf8_length = if lead_byte >= 0xF0 {
+            4
+        } else if lead_byte >= 0xE0 {
+            3
+        } else {
+            2
+        };
+        while key_length < utf8_length {
+            let bytes_read = term.read(&mut key_bytes[key_length..utf8_length])?;
+            if bytes_read == 0 {
+                break;
+            }
+            key_length += bytes_read;
+        }
+    }
+
+    let raw_key = parse_raw_key_bytes(&key_bytes[..key_length]);
+
+    // Step 3: A click's release report follows its press; read it now so it
+    // is not left in the input for the next (cooked) read_line
+    if let RawKey::Mouse(MouseEvent {
+        kind: MouseEventKind::LeftPress | MouseEventKind::MiddlePress | MouseEventKind::RightPress,
+        ..
+    }) = raw_key
+    {
+        let tty_fd = term.tty.as_raw_fd();
+        if is_fd_readable_within(tty_fd, MOUSE_RELEASE_TIMEOUT_MS) {
+            let mut release_bytes = [0u8; RAW_KEY_MAX_BYTES];
+            if term.read(&mut release_bytes[..1])? == 1 && release_bytes[0] == 0x1b {
+                let _ = term.read_escape_sequence_rest(&mut release_bytes, 1)?;
+            }
+        }
+    }
+
+    Ok(Some(raw_key))
+    // `term` drops here, turning mouse reporting off and restoring the terminal
+}
+
 // ============================================================================
 // TESTS
 // ============================================================================
@@ -1585,7 +2492,6 @@ mod tests {
 /// This function uses a macro for safe bounds-checked copying rather than
 /// relying on slice bounds checks that would panic. Returning 0 on overflow
 /// allows the caller to skip malformed output rather than crashing.
-#[allow(dead_code)]
 pub fn format_byte_info(output: &mut [u8; 64], byte: u8) -> usize {
     let mut pos: usize = 0;
 
@@ -2002,6 +2908,169 @@ mod format_tests {
     }
 }
 
+#[cfg(test)]
+mod window_size_tests {
+    use super::*;
+
+    unsafe extern "C" {
+        /// C library raise(3), used to deliver SIGWINCH to this process
+        fn raise(signum: i32) -> i32;
+    }
+
+    /// Verify kernel struct sizes and ioctl/syscall constants.
+    #[test]
+    fn test_window_size_constants() {
+        assert_eq!(core::mem::size_of::<Winsize>(), 8);
+        assert_eq!(core::mem::size_of::<PollFd>(), 8);
+        assert_eq!(core::mem::size_of::<SigAction>(), 152);
+        assert_eq!(TIOCGWINSZ, 0x5413);
+        assert_eq!(SYS_POLL, 7);
+        assert_eq!(SIGWINCH, 28);
+    }
+
+    /// TIOCGWINSZ on a bad fd or a regular file reports EBADF / ENOTTY.
+    #[test]
+    fn test_get_window_size_errors() {
+        let bad_fd_error = get_window_size(-1).unwrap_err();
+        assert_eq!(bad_fd_error.raw_os_error(), Some(9));
+
+        let regular_file = File::open("Cargo.toml").expect("Cargo.toml should exist");
+        let not_a_tty_error = get_window_size(regular_file.as_raw_fd()).unwrap_err();
+        assert_eq!(not_a_tty_error.raw_os_error(), Some(25));
+    }
+
+    /// A delivered SIGWINCH sets the flag once; taking it clears it.
+    #[test]
+    fn test_sigwinch_sets_resize_flag() {
+        install_window_resize_handler().expect("sigaction should succeed");
+        install_window_resize_handler().expect("second install is a no-op");
+        take_window_resized();
+
+        // SAFETY: raise only delivers a signal whose handler we just installed.
+        let ret = unsafe { raise(SIGWINCH) };
+        assert_eq!(ret, 0);
+
+        assert!(take_window_resized());
+        assert!(!take_window_resized());
+    }
+}
+
+#[cfg(test)]
+mod raw_key_tests {
+    use super::*;
+
+    /// Escape sequences, control bytes and characters decode to keys.
+    #[test]
+    fn test_parse_raw_key_bytes() {
+        assert_eq!(parse_raw_key_bytes(b"\x1b[A"), RawKey::Up);
+        assert_eq!(parse_raw_key_bytes(b"\x1bOB"), RawKey::Down);
+        assert_eq!(parse_raw_key_bytes(b"\x1b[C"), RawKey::Right);
+        assert_eq!(parse_raw_key_bytes(b"\x1b[D"), RawKey::Left);
+        assert_eq!(parse_raw_key_bytes(b"\x1b[5~"), RawKey::PageUp);
+        assert_eq!(parse_raw_key_bytes(b"\x1b[6~"), RawKey::PageDown);
+        assert_eq!(parse_raw_key_bytes(b"\x1b[H"), RawKey::Home);
+        assert_eq!(parse_raw_key_bytes(b"\x1b[4~"), RawKey::End);
+        assert_eq!(parse_raw_key_bytes(b"\x1b"), RawKey::Escape);
+        assert_eq!(parse_raw_key_bytes(b"\x1b[99Z"), RawKey::Unknown);
+        assert_eq!(parse_raw_key_bytes(b"\r"), RawKey::Enter);
+        assert_eq!(parse_raw_key_bytes(b"\x7f"), RawKey::Backspace);
+        assert_eq!(parse_raw_key_bytes(b"\x03"), RawKey::CtrlC);
+        assert_eq!(parse_raw_key_bytes(b"q"), RawKey::Char('q'));
+        assert_eq!(parse_raw_key_bytes(b":"), RawKey::Char(':'));
+        assert_eq!(parse_raw_key_bytes("é".as_bytes()), RawKey::Char('é'));
+        assert_eq!(parse_raw_key_bytes(b"\x01"), RawKey::Unknown);
+        assert_eq!(parse_raw_key_bytes(b"\xff"), RawKey::Unknown);
+        assert_eq!(parse_raw_key_bytes(b""), RawKey::Unknown);
+    }
+
+    /// SGR mouse reports decode to clicks, releases and wheel turns.
+    #[test]
+    fn test_parse_sgr_mouse_reports() {
+        assert_eq!(
+            parse_raw_key_bytes(b"\x1b[<0;12;7M"),
+            RawKey::Mouse(MouseEvent {
+                kind: MouseEventKind::LeftPress,
+                row: 7,
+                column: 12,
+            })
+        );
+        assert_eq!(
+            parse_sgr_mouse_report(b"0;12;7m").map(|event| event.kind),
+            Some(MouseEventKind::Release)
+        );
+        assert_eq!(
+            parse_sgr_mouse_report(b"2;1;1M").map(|event| event.kind),
+            Some(MouseEventKind::RightPress)
+        );
+        // Shift (4) and Ctrl (16) modifiers do not change the button
+        assert_eq!(
+            parse_sgr_mouse_report(b"20;3;4M").map(|event| event.kind),
+            Some(MouseEventKind::LeftPress)
+        );
+        assert_eq!(
+            parse_sgr_mouse_report(b"64;200;300M"),
+            Some(MouseEvent {
+                kind: MouseEventKind::WheelUp,
+                row: 300,
+                column: 200,
+            })
+        );
+        assert_eq!(
+            parse_sgr_mouse_report(b"65;1;1M").map(|event| event.kind),
+            Some(MouseEventKind::WheelDown)
+        );
+
+        // Motion, malformed and zero coordinates are not events
+        assert_eq!(parse_sgr_mouse_report(b"32;5;5M"), None);
+        assert_eq!(parse_sgr_mouse_report(b"0;5M"), None);
+        assert_eq!(parse_sgr_mouse_report(b"0;5;5;5M"), None);
+        assert_eq!(parse_sgr_mouse_report(b"0;0;5M"), None);
+        assert_eq!(parse_sgr_mouse_report(b"0;5;5X"), None);
+        assert_eq!(parse_sgr_mouse_report(b""), None);
+        assert_eq!(parse_raw_key_bytes(b"\x1b[<0;x;1M"), RawKey::Unknown);
+    }
+}
+
+#[cfg(test)]
+mod termination_signal_tests {
+    use super::*;
+
+    unsafe extern "C" {
+        /// C library raise(3), used to deliver SIGTERM to this process
+        fn raise(signum: i32) -> i32;
+    }
+
+    /// Exit codes follow the 128 + signal convention and are distinct.
+    #[test]
+    fn test_termination_exit_codes() {
+        assert_eq!(termination_exit_code(SIGINT), 130);
+        assert_eq!(termination_exit_code(SIGTERM), 143);
+        assert_eq!(termination_signal_name(SIGINT), "SIGINT");
+        assert_eq!(termination_signal_name(SIGTERM), "SIGTERM");
+        assert_eq!(termination_signal_name(9), "signal");
+    }
+
+    /// A delivered SIGTERM is recorded instead of ending the process.
+    ///
+    /// The only test that raises SIGTERM; it clears the flag and puts the
+    /// default dispositions back so the rest of the test run is unaffected.
+    #[test]
+    fn test_sigterm_sets_termination_flag() {
+        install_termination_handler().expect("sigaction should succeed");
+
+        // SAFETY: raise only delivers a signal to this process; the installed
+        // handler stores an atomic.
+        let ret = unsafe { raise(SIGTERM) };
+        assert_eq!(ret, 0);
+
+        assert_eq!(pending_termination_signal(), Some(SIGTERM));
+
+        TERMINATION_SIGNAL.store(0, Ordering::SeqCst);
+        restore_default_termination_handling().expect("sigaction should succeed");
+        assert_eq!(pending_termination_signal(), None);
+    }
+}
+
 /// Tests for error handling paths in terminal operations.
 ///
 /// ## Project Context
@@ -1,18 +1,84 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::io::{self, BufRead, BufReader, Write};
 use std::path::{Path, PathBuf};
 use std::sync::OnceLock;
 
+// Comment markers shared with the comment toggling feature
+use super::toggle_comment_indent_module::{determine_block_markers, determine_comment_flag};
+
+// Report export helpers shared with the CSV tools
+use super::csv_convert_module::format_delimited_field;
+use super::csv_processor_module::{escape_json_string, parse_csv_line_into_fields};
+
+// Timestamps in the same format as ff archives
+use super::ff_file_fantastic_module::{FF_DATA_DIRECTORY_NAME, createarchive_timestamp_with_precision};
+
+// Open a selected file in the Lines editor
+use super::lines_editor_module::lines_full_file_editor;
+
+/// Directory names never descended into in recursive mode
+const RECURSIVE_SCAN_IGNORED_DIRECTORIES: &[&str] = &[
+    "target",
+    ".git",
+    "node_modules",
+    ".hg",
+    ".svn",
+    "__pycache__",
+];
+
+/// Maximum directory depth for recursive scans
+const MAX_RECURSIVE_SCAN_DEPTH: usize = 64;
+
+/// Code, comment and blank line counts for a file or a group of files
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+struct LineKindCounts {
+    /// Lines containing anything outside a comment
+    code_lines: usize,
+    /// Non-blank lines containing only comment text
+    comment_lines: usize,
+    /// Empty or whitespace-only lines
+    blank_lines: usize,
+}
+
+impl LineKindCounts {
+    /// Total number of lines (code + comment + blank)
+    fn total_lines(&self) -> usize {
+        self.code_lines + self.comment_lines + self.blank_lines
+    }
+
+    /// Adds another set of counts into this one
+    fn add(&mut self, other: &LineKindCounts) {
+        self.code_lines += other.code_lines;
+        self.comment_lines += other.comment_lines;
+        self.blank_lines += other.blank_lines;
+    }
+}
+
 /// Represents a file with its metadata for display
 #[derive(Debug, Clone)]
 struct FileLineCount {
     /// Absolute path to the file
     file_path: PathBuf,
-    /// Display name (filename only)
+    /// Display name (filename, or path relative to the scanned directory in recursive mode)
     display_name: String,
     /// Number of lines in the file
     line_count: usize,
+    /// Language used for the per-language totals
+    language_name: &'static str,
+    /// Code / comment / blank breakdown of `line_count`
+    line_kind_counts: LineKindCounts,
+}
+
+/// Summed line counts for a group of files (a language or a directory)
+#[derive(Debug, Clone, PartialEq)]
+struct LineTotalsGroup {
+    /// Group name, e.g. "Rust" or "src/parser"
+    group_name: String,
+    /// Number of files in this group
+    file_count: usize,
+    /// Summed line counts for those files
+    line_kind_counts: LineKindCounts,
 }
 
 /// Sort modes for file display
@@ -26,6 +92,18 @@ enum SortMode {
     CountAscending,
     /// Sort by line count descending
     CountDescending,
+    /// Sort by code line count ascending
+    CodeAscending,
+    /// Sort by code line count descending
+    CodeDescending,
+    /// Sort by comment line count ascending
+    CommentAscending,
+    /// Sort by comment line count descending
+    CommentDescending,
+    /// Sort by blank line count ascending
+    BlankAscending,
+    /// Sort by blank line count descending
+    BlankDescending,
 }
 
 /// Display configuration for the TUI
@@ -37,6 +115,8 @@ struct DisplayConfig {
     show_header_in_line_count: bool,
     /// Terminal width (default 80)
     terminal_width: usize,
+    /// Whether subdirectories are scanned too (skipping `RECURSIVE_SCAN_IGNORED_DIRECTORIES`)
+    recursive_mode: bool,
     // Terminal height (default 24)
     // terminal_height: usize,
 }
@@ -47,6 +127,7 @@ impl Default for DisplayConfig {
             sort_mode: SortMode::NameAscending,
             show_header_in_line_count: true,
             terminal_width: 80,
+            recursive_mode: false,
             // terminal_height: 24,
         }
     }
@@ -400,6 +481,265 @@ fn get_extension_lookup() -> &'static HashSet<&'static str> {
     })
 }
 
+/// Returns the language name used to group a file in the totals footer
+///
+/// # Arguments
+/// * `file_path` - Path to the file
+///
+/// # Returns
+/// * `&'static str` - Language name, or "Other" for unrecognized files
+///
+/// # Note
+/// Groups follow the comments in `get_extension_lookup`; files matched by
+/// exact name (Makefile, .bashrc, ...) are grouped as Make, Docker or Config.
+fn language_name_for_file(file_path: &Path) -> &'static str {
+    let filename = file_path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    if get_exact_filename_lookup().contains(filename.as_str()) {
+        return match filename.as_str() {
+            "Makefile" | "makefile" | "GNUmakefile" | "BSDmakefile" => "Make",
+            "Dockerfile" | "dockerfile" | "Containerfile" => "Docker",
+            _ => "Config",
+        };
+    }
+
+    let extension = file_path
+        .extension()
+        .map(|ext| ext.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+
+    match extension.as_str() {
+        "py" | "pyw" | "pyx" | "pyi" => "Python",
+        "rs" | "rlib" => "Rust",
+        "js" | "mjs" | "cjs" | "jsx" => "JavaScript",
+        "ts" | "tsx" | "mts" | "cts" => "TypeScript",
+        "java" | "class" => "Java",
+        "go" => "Go",
+        "rb" | "erb" | "rake" => "Ruby",
+        "php" | "phtml" => "PHP",
+        "c" | "h" => "C",
+        "cpp" | "cc" | "cxx" | "hpp" | "hh" | "hxx" => "C++",
+        "cs" | "csx" => "C#",
+        "swift" => "Swift",
+        "kt" | "kts" => "Kotlin",
+        "scala" | "sc" => "Scala",
+        "r" | "rdata" | "rds" | "rda" => "R",
+        "m" | "mm" => "Objective-C",
+        "f90" | "f95" | "f03" | "f08" => "Fortran",
+        "lua" => "Lua",
+        "pl" | "pm" => "Perl",
+        "jl" => "Julia",
+        "nim" | "nims" => "Nim",
+        "cr" => "Crystal",
+        "dart" => "Dart",
+        "elm" => "Elm",
+        "ex" | "exs" => "Elixir",
+        "erl" | "hrl" => "Erlang",
+        "fs" | "fsi" | "fsx" => "F#",
+        "ml" | "mli" => "OCaml",
+        "clj" | "cljs" | "cljc" => "Clojure",
+        "rkt" => "Racket",
+        "scm" | "ss" => "Scheme",
+        "lisp" | "lsp" | "cl" => "Lisp",
+        "hs" | "lhs" => "Haskell",
+        "v" => "V/Verilog",
+        "vhd" | "vhdl" => "VHDL",
+        "pas" | "pp" => "Pascal",
+        "d" | "di" => "D",
+        "zig" => "Zig",
+        "ada" | "adb" | "ads" => "Ada",
+        "cob" | "cbl" => "COBOL",
+        "asm" | "s" => "Assembly",
+        "wat" | "wasm" => "WebAssembly",
+        "html" | "htm" | "xhtml" => "HTML",
+        "css" => "CSS",
+        "scss" | "sass" => "Sass",
+        "less" => "Less",
+        "styl" | "stylus" => "Stylus",
+        "vue" => "Vue",
+        "svelte" => "Svelte",
+        "json" | "jsonc" | "json5" => "JSON",
+        "xml" | "xsd" | "xsl" | "xslt" => "XML",
+        "yaml" | "yml" => "YAML",
+        "toml" | "tml" => "TOML",
+        "ini" | "cfg" | "cnf" | "conf" | "config" | "properties" | "env" => "Config",
+        "sh" | "bash" | "zsh" | "fish" | "ksh" | "tcsh" | "csh" => "Shell",
+        "ps1" | "psm1" | "psd1" => "PowerShell",
+        "bat" | "cmd" => "Batch",
+        "awk" => "AWK",
+        "vim" | "nvim" => "Vim",
+        "el" | "elc" => "Emacs Lisp",
+        "md" | "markdown" | "mdown" => "Markdown",
+        "rst" | "rest" => "reStructuredText",
+        "txt" | "text" => "Text",
+        "adoc" | "asciidoc" => "AsciiDoc",
+        "tex" | "latex" => "LaTeX",
+        "org" => "Org",
+        "textile" => "Textile",
+        "sql" | "mysql" | "psql" | "cql" => "SQL",
+        "sqlite" | "sqlite3" | "db" => "SQLite",
+        "gradle" => "Gradle",
+        "sbt" => "SBT",
+        "cmake" => "CMake",
+        "make" | "mak" | "mk" => "Make",
+        "ninja" => "Ninja",
+        "bazel" | "bzl" => "Bazel",
+        "pro" | "pri" => "Qt Project",
+        "csv" | "tsv" | "psv" => "Delimited Data",
+        "log" => "Log",
+        "csproj" | "vbproj" | "fsproj" | "sln" => ".NET Project",
+        "xcodeproj" | "xcworkspace" => "Xcode",
+        "lock" => "Lock File",
+        _ => "Other",
+    }
+}
+
+/// Comment syntax used to classify the lines of one file
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct CommentSyntax {
+    /// Line comment marker, e.g. "//" or "#"
+    line_marker: Option<&'static str>,
+    /// Block comment start and end markers, e.g. ("/*", "*/")
+    block_markers: Option<(&'static str, &'static str)>,
+}
+
+/// Looks up the comment syntax for a file from its extension
+///
+/// # Arguments
+/// * `file_path` - Path to the file
+///
+/// # Returns
+/// * `CommentSyntax` - Markers from `determine_comment_flag` and
+///   `determine_block_markers`; Makefiles use the `mk` markers
+///
+/// # Note
+/// Files with no known syntax get no markers, so every non-blank line is code.
+fn comment_syntax_for_file(file_path: &Path) -> CommentSyntax {
+    let is_makefile = file_path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_lowercase().ends_with("makefile"))
+        .unwrap_or(false);
+
+    let extension = if is_makefile {
+        "mk".to_string()
+    } else {
+        match file_path.extension() {
+            Some(ext) => ext.to_string_lossy().to_lowercase(),
+            None => String::new(),
+        }
+    };
+
+    // Block markers are stored as whole lines ("/*\n"); only the marker text is needed here
+    let marker_text = |marker_bytes: &'static [u8]| {
+        std::str::from_utf8(marker_bytes)
+            .ok()
+            .map(|marker| marker.trim_end_matches('\n'))
+    };
+
+    CommentSyntax {
+        line_marker: determine_comment_flag(&extension).map(|flag| flag.as_str()),
+        block_markers: determine_block_markers(&extension).and_then(|markers| {
+            Some((marker_text(markers.start)?, marker_text(markers.end)?))
+        }),
+    }
+}
+
+/// Kind of a single source line
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum LineKind {
+    Code,
+    Comment,
+    Blank,
+}
+
+/// Classifies one line as code, comment or blank
+///
+/// # Arguments
+/// * `line` - The line text (without its newline)
+/// * `syntax` - Comment markers for the file's language
+/// * `inside_block_comment` - Block comment state, carried from line to line
+///
+/// # Returns
+/// * `LineKind` - Code if any text lies outside comments, Comment if the
+///   line holds only comment text, Blank if it is empty or whitespace
+///
+/// # Note
+/// String literals are not parsed, so a marker inside a string is taken
+/// as a comment marker (the same trade-off most line counters make).
+fn classify_line(line: &str, syntax: &CommentSyntax, inside_block_comment: &mut bool) -> LineKind {
+    if line.trim().is_empty() {
+        return LineKind::Blank;
+    }
+
+    let mut has_code = false;
+    let mut remaining_text = line;
+
+    loop {
+        // Inside a block comment: skip to its end marker, if it is on this line
+        if *inside_block_comment {
+            let Some((_, block_end)) = syntax.block_markers else {
+                *inside_block_comment = false;
+                continue;
+            };
+            match remaining_text.find(block_end) {
+                Some(end_position) => {
+                    *inside_block_comment = false;
+                    remaining_text = &remaining_text[end_position + block_end.len()..];
+                    continue;
+                }
+                None => break,
+            }
+        }
+
+        remaining_text = remaining_text.trim_start();
+        if remaining_text.is_empty() {
+            break;
+        }
+
+        // Find whichever comment marker comes first
+        let line_marker_position = syntax
+            .line_marker
+            .and_then(|marker| remaining_text.find(marker));
+        let block_start_position = syntax
+            .block_markers
+            .and_then(|(block_start, _)| remaining_text.find(block_start).map(|position| (position, block_start)));
+
+        let (comment_position, block_start) = match (line_marker_position, block_start_position) {
+            (Some(line_position), Some((block_position, block_start))) => {
+                if block_position <= line_position {
+                    (block_position, Some(block_start))
+                } else {
+                    (line_position, None)
+                }
+            }
+            (Some(line_position), None) => (line_position, None),
+            (None, Some((block_position, block_start))) => (block_position, Some(block_start)),
+            (None, None) => {
+                has_code = true;
+                break;
+            }
+        };
+
+        if !remaining_text[..comment_position].trim().is_empty() {
+            has_code = true;
+        }
+
+        match block_start {
+            Some(block_start) => {
+                *inside_block_comment = true;
+                remaining_text = &remaining_text[comment_position + block_start.len()..];
+            }
+            // A line comment runs to the end of the line
+            None => break,
+        }
+    }
+
+    if has_code { LineKind::Code } else { LineKind::Comment }
+}
+
 /// Checks if a file should be included based on exact name or extension
 ///
 /// # Arguments
@@ -513,13 +853,15 @@ fn get_code_files_with_counts(directory_path: &Path) -> Vec<FileLineCount> {
             }
         };
 
-        // Count lines in file
+        // Count and classify lines in file
         match count_file_lines_efficiently(&absolute_path) {
-            Ok(line_count) => {
+            Ok(line_kind_counts) => {
                 file_counts.push(FileLineCount {
+                    language_name: language_name_for_file(&absolute_path),
                     file_path: absolute_path,
                     display_name,
-                    line_count,
+                    line_count: line_kind_counts.total_lines(),
+                    line_kind_counts,
                 });
             }
             Err(e) => {
@@ -577,19 +919,22 @@ fn validate_directory_path(directory_path: &Path) -> Result<PathBuf, String> {
 
 /// Efficiently counts lines in a file without loading entire content into memory
 ///
+/// Each line is classified as code, comment or blank using the comment
+/// markers for the file's language (see `comment_syntax_for_file`).
+///
 /// # Arguments
 /// * `file_path` - Absolute path to the file to count
 ///
 /// # Returns
-/// * `Ok(usize)` - Number of lines in the file
+/// * `Ok(LineKindCounts)` - Code, comment and blank line counts
 /// * `Err(String)` - Error message if counting fails
 ///
 /// # Examples
 /// ```
 /// use std::path::Path;
-/// let count = count_file_lines_efficiently(Path::new("/absolute/path/to/file.txt"));
+/// let counts = count_file_lines_efficiently(Path::new("/absolute/path/to/file.txt"));
 /// ```
-fn count_file_lines_efficiently(file_path: &Path) -> Result<usize, String> {
+fn count_file_lines_efficiently(file_path: &Path) -> Result<LineKindCounts, String> {
     // Open file with error handling
     let file = match fs::File::open(file_path) {
         Ok(f) => f,
@@ -597,26 +942,333 @@ fn count_file_lines_efficiently(file_path: &Path) -> Result<usize, String> {
     };
 
     // Use BufReader for memory-efficient line counting
-    let reader = BufReader::new(file);
-    let mut line_count = 0;
+    let mut reader = BufReader::new(file);
+    let comment_syntax = comment_syntax_for_file(file_path);
+    let mut line_kind_counts = LineKindCounts::default();
+    let mut inside_block_comment = false;
+    let mut line_bytes: Vec<u8> = Vec::new();
 
-    // Count lines using iterator to avoid loading entire file
-    for line_result in reader.lines() {
-        match line_result {
-            Ok(_) => line_count += 1,
+    // Read one line at a time; invalid UTF-8 is replaced rather than skipped
+    loop {
+        line_bytes.clear();
+        match reader.read_until(b'\n', &mut line_bytes) {
+            Ok(0) => break, // EOF
+            Ok(_) => {
+                let line_text = String::from_utf8_lossy(&line_bytes);
+                match classify_line(&line_text, &comment_syntax, &mut inside_block_comment) {
+                    LineKind::Code => line_kind_counts.code_lines += 1,
+                    LineKind::Comment => line_kind_counts.comment_lines += 1,
+                    LineKind::Blank => line_kind_counts.blank_lines += 1,
+                }
+            }
             Err(e) => {
-                // Log error but continue counting what we can
+                // Log error and stop: the rest of the file cannot be read
                 eprintln!(
                     "Warning: Error reading line in {}: {}",
                     file_path.display(),
                     e
                 );
+                break;
+            }
+        }
+    }
+
+    Ok(line_kind_counts)
+}
+
+/// Sums file line counts per group
+///
+/// # Arguments
+/// * `files` - Files with their line counts
+/// * `group_name_for_file` - Returns the group a file belongs to
+///
+/// # Returns
+/// * `Vec<LineTotalsGroup>` - One entry per group, most code lines first
+///   (ties broken by group name)
+fn summarize_line_counts<F>(files: &[FileLineCount], group_name_for_file: F) -> Vec<LineTotalsGroup>
+where
+    F: Fn(&FileLineCount) -> String,
+{
+    let mut totals_by_group: HashMap<String, LineTotalsGroup> = HashMap::new();
+
+    for file in files {
+        let group_name = group_name_for_file(file);
+        let group_totals = totals_by_group
+            .entry(group_name.clone())
+            .or_insert_with(|| LineTotalsGroup {
+                group_name,
+                file_count: 0,
+                line_kind_counts: LineKindCounts::default(),
+            });
+        group_totals.file_count += 1;
+        group_totals.line_kind_counts.add(&file.line_kind_counts);
+    }
+
+    let mut group_totals: Vec<LineTotalsGroup> = totals_by_group.into_values().collect();
+    group_totals.sort_by(|a, b| {
+        b.line_kind_counts
+            .code_lines
+            .cmp(&a.line_kind_counts.code_lines)
+            .then_with(|| a.group_name.cmp(&b.group_name))
+    });
+    group_totals
+}
+
+/// Sums file line counts per language for the totals footer
+///
+/// # Arguments
+/// * `files` - Files with their line counts
+///
+/// # Returns
+/// * `Vec<LineTotalsGroup>` - One entry per language, most code lines first
+fn summarize_counts_by_language(files: &[FileLineCount]) -> Vec<LineTotalsGroup> {
+    summarize_line_counts(files, |file| file.language_name.to_string())
+}
+
+/// Sums file line counts per directory for recursive-mode subtotals
+///
+/// # Arguments
+/// * `files` - Files with their line counts
+/// * `root_directory` - The scanned directory; subtotals are named relative to it
+///
+/// # Returns
+/// * `Vec<LineTotalsGroup>` - One entry per directory that holds counted
+///   files, in path order ("." is the scanned directory itself)
+fn summarize_counts_by_directory(files: &[FileLineCount], root_directory: &Path) -> Vec<LineTotalsGroup> {
+    let mut directory_totals = summarize_line_counts(files, |file| {
+        relative_directory_name(&file.file_path, root_directory)
+    });
+    directory_totals.sort_by(|a, b| a.group_name.cmp(&b.group_name));
+    directory_totals
+}
+
+/// Returns a file's path relative to the scanned directory, using `/` separators
+///
+/// # Arguments
+/// * `file_path` - Absolute path to the file
+/// * `root_directory` - The scanned directory
+///
+/// # Returns
+/// * `String` - e.g. "src/main.rs"; the full path if it is outside the root
+fn relative_display_path(file_path: &Path, root_directory: &Path) -> String {
+    let relative_path = file_path.strip_prefix(root_directory).unwrap_or(file_path);
+    relative_path
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Returns the directory of a file relative to the scanned directory
+///
+/// # Returns
+/// * `String` - e.g. "src/parser", or "." for files directly in the root
+fn relative_directory_name(file_path: &Path, root_directory: &Path) -> String {
+    match file_path.parent() {
+        Some(parent_directory) if parent_directory != root_directory => {
+            relative_display_path(parent_directory, root_directory)
+        }
+        _ => ".".to_string(),
+    }
+}
+
+/// Scans a directory tree and returns supported files with line counts
+///
+/// # Arguments
+/// * `root_directory` - Absolute path to the directory to scan
+///
+/// # Returns
+/// * `Vec<FileLineCount>` - Files from the whole tree, each with its path
+///   relative to `root_directory` as display name
+///
+/// # Note
+/// Directories named in `RECURSIVE_SCAN_IGNORED_DIRECTORIES` are skipped,
+/// symbolic links to directories are not followed, and the walk stops
+/// descending at `MAX_RECURSIVE_SCAN_DEPTH`.
+fn get_code_files_with_counts_recursive(root_directory: &Path) -> Vec<FileLineCount> {
+    let mut file_counts = Vec::new();
+    let mut directories_to_scan: Vec<(PathBuf, usize)> = vec![(root_directory.to_path_buf(), 0)];
+
+    while let Some((directory_path, depth)) = directories_to_scan.pop() {
+        // Files in this directory
+        for mut file_count in get_code_files_with_counts(&directory_path) {
+            file_count.display_name = relative_display_path(&file_count.file_path, root_directory);
+            file_counts.push(file_count);
+        }
+
+        if depth >= MAX_RECURSIVE_SCAN_DEPTH {
+            eprintln!(
+                "Warning: Not descending below {} (depth limit {})",
+                directory_path.display(),
+                MAX_RECURSIVE_SCAN_DEPTH
+            );
+            continue;
+        }
+
+        // Subdirectories, without following symlinks
+        let entries = match fs::read_dir(&directory_path) {
+            Ok(entries) => entries,
+            Err(_) => continue, // Already reported by get_code_files_with_counts
+        };
+
+        for entry in entries.flatten() {
+            let is_real_directory = entry
+                .file_type()
+                .map(|file_type| file_type.is_dir())
+                .unwrap_or(false);
+            if !is_real_directory {
+                continue;
+            }
+
+            let directory_name = entry.file_name().to_string_lossy().to_string();
+            if RECURSIVE_SCAN_IGNORED_DIRECTORIES.contains(&directory_name.as_str()) {
                 continue;
             }
+
+            directories_to_scan.push((entry.path(), depth + 1));
         }
     }
 
-    Ok(line_count)
+    file_counts
+}
+
+/// Export formats for the line-count table
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum LineCountExportFormat {
+    /// One row per file; readable by ff's `-rc` CSV analyzer
+    Csv,
+    /// Files plus per-directory and per-language totals
+    Json,
+}
+
+/// Renders the line-count table as CSV
+///
+/// # Arguments
+/// * `files` - Files in display order
+/// * `root_directory` - The scanned directory; paths are relative to it
+///
+/// # Returns
+/// * `String` - Header `path,directory,language,code,comment,blank,lines`
+///   and one row per file (no totals row, so every column keeps one type)
+fn render_line_counts_csv(files: &[FileLineCount], root_directory: &Path) -> String {
+    let mut csv_content = String::from("path,directory,language,code,comment,blank,lines\n");
+
+    for file in files {
+        csv_content.push_str(&format!(
+            "{},{},{},{},{},{},{}\n",
+            format_delimited_field(&relative_display_path(&file.file_path, root_directory), ','),
+            format_delimited_field(&relative_directory_name(&file.file_path, root_directory), ','),
+            format_delimited_field(file.language_name, ','),
+            file.line_kind_counts.code_lines,
+            file.line_kind_counts.comment_lines,
+            file.line_kind_counts.blank_lines,
+            file.line_count,
+        ));
+    }
+
+    csv_content
+}
+
+/// Formats one set of line counts as a JSON object body (without braces)
+fn format_line_kind_counts_json(line_kind_counts: &LineKindCounts) -> String {
+    format!(
+        "\"code\": {}, \"comment\": {}, \"blank\": {}, \"lines\": {}",
+        line_kind_counts.code_lines,
+        line_kind_counts.comment_lines,
+        line_kind_counts.blank_lines,
+        line_kind_counts.total_lines()
+    )
+}
+
+/// Renders the line-count table as JSON
+///
+/// # Arguments
+/// * `files` - Files in display order
+/// * `root_directory` - The scanned directory; paths are relative to it
+///
+/// # Returns
+/// * `String` - Object with `root`, `files`, `directories`, `languages` and `totals`
+fn render_line_counts_json(files: &[FileLineCount], root_directory: &Path) -> String {
+    let mut json_content = String::from("{\n");
+    json_content.push_str(&format!(
+        "  \"root\": \"{}\",\n",
+        escape_json_string(&root_directory.to_string_lossy())
+    ));
+
+    // Files
+    json_content.push_str("  \"files\": [");
+    for (file_index, file) in files.iter().enumerate() {
+        json_content.push_str(if file_index == 0 { "\n" } else { ",\n" });
+        json_content.push_str(&format!(
+            "    {{\"path\": \"{}\", \"directory\": \"{}\", \"language\": \"{}\", {}}}",
+            escape_json_string(&relative_display_path(&file.file_path, root_directory)),
+            escape_json_string(&relative_directory_name(&file.file_path, root_directory)),
+            escape_json_string(file.language_name),
+            format_line_kind_counts_json(&file.line_kind_counts),
+        ));
+    }
+    json_content.push_str(if files.is_empty() { "],\n" } else { "\n  ],\n" });
+
+    // Directory and language subtotals
+    let group_sections = [
+        ("directories", summarize_counts_by_directory(files, root_directory)),
+        ("languages", summarize_counts_by_language(files)),
+    ];
+    for (section_name, group_totals) in &group_sections {
+        json_content.push_str(&format!("  \"{}\": [", section_name));
+        for (group_index, group) in group_totals.iter().enumerate() {
+            json_content.push_str(if group_index == 0 { "\n" } else { ",\n" });
+            json_content.push_str(&format!(
+                "    {{\"name\": \"{}\", \"files\": {}, {}}}",
+                escape_json_string(&group.group_name),
+                group.file_count,
+                format_line_kind_counts_json(&group.line_kind_counts),
+            ));
+        }
+        json_content.push_str(if group_totals.is_empty() { "],\n" } else { "\n  ],\n" });
+    }
+
+    // Grand total
+    let mut all_files = LineKindCounts::default();
+    for file in files {
+        all_files.add(&file.line_kind_counts);
+    }
+    json_content.push_str(&format!(
+        "  \"totals\": {{\"files\": {}, {}}}\n}}\n",
+        files.len(),
+        format_line_kind_counts_json(&all_files)
+    ));
+
+    json_content
+}
+
+/// Writes the line-count table into the scanned directory
+///
+/// # Arguments
+/// * `files` - Files in display order
+/// * `root_directory` - The scanned directory (the report is saved here)
+/// * `export_format` - CSV or JSON
+///
+/// # Returns
+/// * `Ok(PathBuf)` - Path of the written `line_counts_{timestamp}.csv|json`
+/// * `Err(String)` - Error message if the file cannot be written
+fn export_line_counts(
+    files: &[FileLineCount],
+    root_directory: &Path,
+    export_format: LineCountExportFormat,
+) -> Result<PathBuf, String> {
+    let (report_content, file_extension) = match export_format {
+        LineCountExportFormat::Csv => (render_line_counts_csv(files, root_directory), "csv"),
+        LineCountExportFormat::Json => (render_line_counts_json(files, root_directory), "json"),
+    };
+
+    let timestamp = createarchive_timestamp_with_precision(std::time::SystemTime::now(), true);
+    let report_path = root_directory.join(format!("line_counts_{}.{}", timestamp, file_extension));
+
+    fs::write(&report_path, report_content)
+        .map_err(|e| format!("Cannot write report {}: {}", report_path.display(), e))?;
+
+    Ok(report_path)
 }
 
 /// Sorts file list according to specified sort mode
@@ -641,27 +1293,105 @@ fn sort_files_by_mode(files: &mut Vec<FileLineCount>, sort_mode: SortMode) {
         SortMode::CountDescending => {
             files.sort_by(|a, b| b.line_count.cmp(&a.line_count));
         }
+        SortMode::CodeAscending => {
+            files.sort_by_key(|file| file.line_kind_counts.code_lines);
+        }
+        SortMode::CodeDescending => {
+            files.sort_by_key(|file| std::cmp::Reverse(file.line_kind_counts.code_lines));
+        }
+        SortMode::CommentAscending => {
+            files.sort_by_key(|file| file.line_kind_counts.comment_lines);
+        }
+        SortMode::CommentDescending => {
+            files.sort_by_key(|file| std::cmp::Reverse(file.line_kind_counts.comment_lines));
+        }
+        SortMode::BlankAscending => {
+            files.sort_by_key(|file| file.line_kind_counts.blank_lines);
+        }
+        SortMode::BlankDescending => {
+            files.sort_by_key(|file| std::cmp::Reverse(file.line_kind_counts.blank_lines));
+        }
     }
 }
 
+/// Width of the four right-aligned count columns (Code, Comment, Blank, Lines)
+const COUNT_COLUMNS_WIDTH: usize = 32;
+
+/// Formats the code / comment / blank / total columns, right-aligned
+///
+/// # Arguments
+/// * `code` - Code line count (or column title)
+/// * `comment` - Comment line count (or column title)
+/// * `blank` - Blank line count (or column title)
+/// * `total` - Total line count (or column title)
+///
+/// # Returns
+/// * `String` - Exactly `COUNT_COLUMNS_WIDTH` characters for counts up to 7 digits
+fn format_count_columns(
+    code: &dyn std::fmt::Display,
+    comment: &dyn std::fmt::Display,
+    blank: &dyn std::fmt::Display,
+    total: &dyn std::fmt::Display,
+) -> String {
+    format!("{:>8}{:>8}{:>8}{:>8}", code, comment, blank, total)
+}
+
+/// Pads or truncates a name to exactly `name_width` characters
+fn fit_name_to_width(name: &str, name_width: usize) -> String {
+    let name_char_count = name.chars().count();
+    if name_char_count > name_width {
+        let truncated: String = name.chars().take(name_width.saturating_sub(3)).collect();
+        format!("{:<width$}", format!("{}...", truncated), width = name_width)
+    } else {
+        format!("{:<width$}", name, width = name_width)
+    }
+}
+
+/// Prints one subtotal row (blank index column, label, counts)
+fn print_totals_row(label: &str, line_kind_counts: &LineKindCounts, index_width: usize, name_width: usize) {
+    println!(
+        "{:<index_width$} {}{}",
+        "",
+        fit_name_to_width(label, name_width),
+        format_count_columns(
+            &line_kind_counts.code_lines,
+            &line_kind_counts.comment_lines,
+            &line_kind_counts.blank_lines,
+            &line_kind_counts.total_lines(),
+        ),
+    );
+}
+
 /// Formats and displays the TUI with file information
 ///
 /// # Arguments
 /// * `files` - List of files to display
 /// * `config` - Display configuration (sort mode, header visibility, etc.)
+/// * `root_directory` - The scanned directory (for per-directory subtotals)
 ///
 /// # Note
-/// Handles terminal size constraints gracefully
-fn display_file_list_tui(files: &[FileLineCount], config: &DisplayConfig) {
+/// Handles terminal size constraints gracefully. The per-language totals
+/// footer always reflects the full file list; recursive mode adds
+/// per-directory subtotals above it.
+fn display_file_list_tui(files: &[FileLineCount], config: &DisplayConfig, root_directory: &Path) {
     // Clear screen (simple approach)
     print!("\x1B[2J\x1B[H");
     let _ = io::stdout().flush();
 
+    // Width left for "# " + name after the count columns
+    let index_width = files.len().max(1).to_string().len();
+    let name_width = config
+        .terminal_width
+        .saturating_sub(COUNT_COLUMNS_WIDTH)
+        .saturating_sub(index_width + 1)
+        .max(8);
+
     // Display header/legend always
     println!(
-        "# File Name{:width$}Line Count",
-        "",
-        width = config.terminal_width.saturating_sub(25)
+        "{:<index_width$} {}{}",
+        "#",
+        fit_name_to_width("File Name", name_width),
+        format_count_columns(&"Code", &"Comment", &"Blank", &"Lines"),
     );
 
     // Display files up to terminal height limit
@@ -675,66 +1405,120 @@ fn display_file_list_tui(files: &[FileLineCount], config: &DisplayConfig) {
             continue;
         }
 
-        // Format: "# filename                    line_count"
-        let line_number = index + 1;
-        let available_width = config.terminal_width.saturating_sub(10); // Reserve space for numbers and count
-
-        // Truncate filename if necessary to fit terminal width
-        let display_name = if file.display_name.len() > available_width {
-            format!(
-                "{}...",
-                &file.display_name[..available_width.saturating_sub(3)]
-            )
+        // Header toggle only changes the total line count
+        let displayed_line_count = if config.show_header_in_line_count {
+            file.line_count
         } else {
-            file.display_name.clone()
+            file.line_count.saturating_sub(1)
         };
 
-        // Calculate padding for right-alignment of line count
-        let padding_width = config
-            .terminal_width
-            .saturating_sub(line_number.to_string().len())
-            .saturating_sub(2) // "# "
-            .saturating_sub(display_name.len())
-            .saturating_sub(file.line_count.to_string().len());
-
         println!(
-            "{} {}{:width$}{}",
-            line_number,
-            display_name,
-            "",
-            if config.show_header_in_line_count {
-                file.line_count
-            } else {
-                file.line_count.saturating_sub(1)
-            },
-            width = padding_width
+            "{:<index_width$} {}{}",
+            index + 1,
+            fit_name_to_width(&file.display_name, name_width),
+            format_count_columns(
+                &file.line_kind_counts.code_lines,
+                &file.line_kind_counts.comment_lines,
+                &file.line_kind_counts.blank_lines,
+                &displayed_line_count,
+            ),
+        );
+    }
+
+    // Per-directory subtotals (recursive mode only)
+    if config.recursive_mode && !files.is_empty() {
+        println!("{}", "-".repeat(config.terminal_width));
+        for directory in summarize_counts_by_directory(files, root_directory) {
+            let directory_label = format!("{}/ ({})", directory.group_name, directory.file_count);
+            print_totals_row(&directory_label, &directory.line_kind_counts, index_width, name_width);
+        }
+    }
+
+    // Per-language totals footer
+    let language_totals = summarize_counts_by_language(files);
+    if !language_totals.is_empty() {
+        println!("{}", "-".repeat(config.terminal_width));
+
+        let mut all_languages = LineKindCounts::default();
+        for language in &language_totals {
+            all_languages.add(&language.line_kind_counts);
+            let language_label = format!("{} ({})", language.group_name, language.file_count);
+            print_totals_row(&language_label, &language.line_kind_counts, index_width, name_width);
+        }
+
+        print_totals_row(
+            &format!("Total ({})", files.len()),
+            &all_languages,
+            index_width,
+            name_width,
         );
     }
 
     // Show command prompt
-    print!("\nCommands: (n)ame sort, (c)ount sort, (h)eader, (Enter) reset, (b/q)uit: ");
+    println!(
+        "\nSort: (n)ame, (c)ount, c(o)de, co(m)ment, b(l)ank | (h)eader, (r)ecursive{}",
+        if config.recursive_mode { " [on]" } else { "" }
+    );
+    println!("Export: (e) csv, (j) json | (s)napshot, (d)iff vs snapshot | [number] open in Lines");
+    print!("(Enter) reset, re(f)resh, (b/q)uit: ");
     let _ = io::stdout().flush();
 }
 
+/// What the main loop should do after a command
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum LineCountTuiAction {
+    /// Redisplay (settings may have changed; a recursive toggle re-scans)
+    Redisplay,
+    /// Scan the directory again to pick up added, removed or edited files
+    Rescan,
+    /// Leave the line-count view
+    Quit,
+    /// Write the current table to a report file
+    Export(LineCountExportFormat),
+    /// Open the file at this 0-based list index in Lines
+    OpenInLines(usize),
+    /// Save the current counts as a timestamped snapshot
+    SaveSnapshot,
+    /// Pick a snapshot and show growth since then
+    DiffAgainstSnapshot,
+}
+
 /// Processes user input commands and updates display configuration
 ///
 /// # Arguments
 /// * `config` - Mutable reference to display configuration
 ///
 /// # Returns
-/// * `Ok(true)` - Continue program execution
-/// * `Ok(false)` - Exit program (user chose quit)
+/// * `Ok(LineCountTuiAction)` - What to do next (Quit when the user chose quit)
 /// * `Err(String)` - Error reading input
-fn handle_user_command(config: &mut DisplayConfig) -> Result<bool, String> {
+fn handle_user_command(config: &mut DisplayConfig) -> Result<LineCountTuiAction, String> {
     let mut input = String::new();
 
     match io::stdin().read_line(&mut input) {
+        // End of input (stdin closed): nothing more will come, so leave
+        Ok(0) => Ok(LineCountTuiAction::Quit),
         Ok(_) => {
             let command = input.trim().to_lowercase();
 
             match command.as_str() {
                 // Exit commands
-                "q" | "b" => return Ok(false),
+                "q" | "b" => return Ok(LineCountTuiAction::Quit),
+
+                // Export commands
+                "e" => return Ok(LineCountTuiAction::Export(LineCountExportFormat::Csv)),
+                "j" => return Ok(LineCountTuiAction::Export(LineCountExportFormat::Json)),
+
+                // Snapshot commands
+                "s" => return Ok(LineCountTuiAction::SaveSnapshot),
+                "d" => return Ok(LineCountTuiAction::DiffAgainstSnapshot),
+
+                // Re-scan the directory
+                "f" => return Ok(LineCountTuiAction::Rescan),
+
+                // Recursive mode toggle
+                "r" => {
+                    config.recursive_mode = !config.recursive_mode;
+                }
 
                 // Name sort toggle
                 "n" => {
@@ -752,6 +1536,30 @@ fn handle_user_command(config: &mut DisplayConfig) -> Result<bool, String> {
                     };
                 }
 
+                // Code sort toggle
+                "o" => {
+                    config.sort_mode = match config.sort_mode {
+                        SortMode::CodeDescending => SortMode::CodeAscending,
+                        _ => SortMode::CodeDescending,
+                    };
+                }
+
+                // Comment sort toggle
+                "m" => {
+                    config.sort_mode = match config.sort_mode {
+                        SortMode::CommentDescending => SortMode::CommentAscending,
+                        _ => SortMode::CommentDescending,
+                    };
+                }
+
+                // Blank sort toggle
+                "l" => {
+                    config.sort_mode = match config.sort_mode {
+                        SortMode::BlankDescending => SortMode::BlankAscending,
+                        _ => SortMode::BlankDescending,
+                    };
+                }
+
                 // Toggle header visibility
                 "h" => {
                     config.show_header_in_line_count = !config.show_header_in_line_count;
@@ -763,18 +1571,535 @@ fn handle_user_command(config: &mut DisplayConfig) -> Result<bool, String> {
                     config.show_header_in_line_count = true;
                 }
 
+                // File number (as shown) - open in Lines
+                number_input if number_input.parse::<usize>().is_ok_and(|number| number > 0) => {
+                    let file_number = number_input.parse::<usize>().unwrap_or(1);
+                    return Ok(LineCountTuiAction::OpenInLines(file_number - 1));
+                }
+
                 // Unknown command - ignore and continue
                 _ => {
                     // Silently ignore unknown commands per requirements
                 }
             }
 
-            Ok(true) // Continue execution
+            Ok(LineCountTuiAction::Redisplay) // Continue execution
         }
         Err(e) => Err(format!("Error reading user input: {}", e)),
     }
 }
 
+/// Subdirectory of ff_data that holds line-count snapshots, one folder per project
+const LINE_COUNT_SNAPSHOTS_DIRECTORY_NAME: &str = "line_count_snapshots";
+
+/// Returns the snapshot folder name for a scanned directory
+///
+/// # Arguments
+/// * `root_directory` - Absolute path of the scanned directory
+///
+/// # Returns
+/// * `String` - The path with separators written as `_`, ASCII letters,
+///   digits, `-` and `.` kept, and every other byte (including `_` itself)
+///   written as `%XX`, e.g. "home_user_my%20code". The escape is reversible,
+///   so two directories never share a snapshot folder.
+fn snapshot_project_key(root_directory: &Path) -> String {
+    let mut project_key = String::new();
+    for &path_byte in root_directory.as_os_str().as_encoded_bytes() {
+        if path_byte.is_ascii_alphanumeric() || matches!(path_byte, b'-' | b'.') {
+            project_key.push(path_byte as char);
+        } else if path_byte.is_ascii() && std::path::is_separator(path_byte as char) {
+            project_key.push('_');
+        } else {
+            project_key.push_str(&format!("%{:02X}", path_byte));
+        }
+    }
+
+    let trimmed_key = project_key.trim_matches('_');
+    if trimmed_key.is_empty() {
+        "root".to_string()
+    } else {
+        trimmed_key.to_string()
+    }
+}
+
+/// Finds (and creates) the snapshot folder for a scanned directory
+///
+/// # Arguments
+/// * `root_directory` - Absolute path of the scanned directory
+///
+/// # Returns
+/// * `Ok(PathBuf)` - `{executable_directory}/ff_data/line_count_snapshots/{project_key}`
+/// * `Err(String)` - If the executable location is unknown or the folder cannot be created
+fn line_count_snapshot_directory(root_directory: &Path) -> Result<PathBuf, String> {
+    // Step 1: ff_data lives next to the executable, as for partner programs
+    let executable_path =
+        std::env::current_exe().map_err(|e| format!("Cannot locate executable: {}", e))?;
+    let executable_directory = executable_path
+        .parent()
+        .ok_or_else(|| "Cannot determine executable directory".to_string())?;
+
+    // Step 2: One folder per scanned project
+    let snapshot_directory = executable_directory
+        .join(FF_DATA_DIRECTORY_NAME)
+        .join(LINE_COUNT_SNAPSHOTS_DIRECTORY_NAME)
+        .join(snapshot_project_key(root_directory));
+
+    // Step 3: Create it on first use
+    fs::create_dir_all(&snapshot_directory).map_err(|e| {
+        format!(
+            "Cannot create snapshot directory {}: {}",
+            snapshot_directory.display(),
+            e
+        )
+    })?;
+
+    Ok(snapshot_directory)
+}
+
+/// Saves the current per-file counts as a timestamped snapshot
+///
+/// # Arguments
+/// * `files` - Files with their line counts
+/// * `root_directory` - The scanned directory; paths are stored relative to it
+/// * `snapshot_directory` - Folder to write into
+///
+/// # Returns
+/// * `Ok(PathBuf)` - Path of the written `{timestamp}.csv`
+/// * `Err(String)` - Error message if the file cannot be written
+///
+/// # Format
+/// Snapshots use the same CSV layout as the (e) export, so they can also be
+/// opened with ff's `-rc` analyzer. The timestamp is
+/// `createarchive_timestamp_with_precision` ("YY_MM_DD_HH_MM_SS"), with
+/// microseconds added only if a snapshot from the same second exists.
+fn save_line_count_snapshot(
+    files: &[FileLineCount],
+    root_directory: &Path,
+    snapshot_directory: &Path,
+) -> Result<PathBuf, String> {
+    let snapshot_time = std::time::SystemTime::now();
+    let mut snapshot_path = snapshot_directory.join(format!(
+        "{}.csv",
+        createarchive_timestamp_with_precision(snapshot_time, false)
+    ));
+    if snapshot_path.exists() {
+        snapshot_path = snapshot_directory.join(format!(
+            "{}.csv",
+            createarchive_timestamp_with_precision(snapshot_time, true)
+        ));
+    }
+
+    fs::write(&snapshot_path, render_line_counts_csv(files, root_directory))
+        .map_err(|e| format!("Cannot write snapshot {}: {}", snapshot_path.display(), e))?;
+
+    Ok(snapshot_path)
+}
+
+/// Lists saved snapshots, newest first
+///
+/// # Arguments
+/// * `snapshot_directory` - Folder holding the snapshots
+///
+/// # Returns
+/// * `Vec<PathBuf>` - `.csv` files; empty if the folder is missing or unreadable
+fn list_line_count_snapshots(snapshot_directory: &Path) -> Vec<PathBuf> {
+    let entries = match fs::read_dir(snapshot_directory) {
+        Ok(entries) => entries,
+        Err(_) => return Vec::new(),
+    };
+
+    let mut snapshot_paths: Vec<PathBuf> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "csv"))
+        .collect();
+
+    // Timestamp names sort chronologically
+    snapshot_paths.sort_by(|a, b| b.file_name().cmp(&a.file_name()));
+    snapshot_paths
+}
+
+/// Reads a snapshot back into per-file counts
+///
+/// # Arguments
+/// * `snapshot_path` - A file written by `save_line_count_snapshot`
+///
+/// # Returns
+/// * `Ok(HashMap)` - Relative path -> counts
+/// * `Err(String)` - If the file cannot be read or has an unexpected layout
+fn load_line_count_snapshot(snapshot_path: &Path) -> Result<HashMap<String, LineKindCounts>, String> {
+    let snapshot_content = fs::read_to_string(snapshot_path)
+        .map_err(|e| format!("Cannot read snapshot {}: {}", snapshot_path.display(), e))?;
+
+    let mut snapshot_lines = snapshot_content.lines();
+    let header_fields = parse_csv_line_into_fields(snapshot_lines.next().unwrap_or_default());
+    if header_fields.first().map(String::as_str) != Some("path") || header_fields.len() < 6 {
+        return Err(format!(
+            "Not a line-count snapshot: {}",
+            snapshot_path.display()
+        ));
+    }
+
+    let mut counts_by_path = HashMap::new();
+    for (line_index, snapshot_line) in snapshot_lines.enumerate() {
+        if snapshot_line.trim().is_empty() {
+            continue;
+        }
+
+        // path, directory, language, code, comment, blank, lines
+        let fields = parse_csv_line_into_fields(snapshot_line);
+        let parse_count = |field_index: usize| -> Result<usize, String> {
+            fields
+                .get(field_index)
+                .and_then(|field| field.trim().parse::<usize>().ok())
+                .ok_or_else(|| {
+                    format!(
+                        "Bad count on line {} of {}",
+                        line_index + 2,
+                        snapshot_path.display()
+                    )
+                })
+        };
+
+        counts_by_path.insert(
+            fields[0].clone(),
+            LineKindCounts {
+                code_lines: parse_count(3)?,
+                comment_lines: parse_count(4)?,
+                blank_lines: parse_count(5)?,
+            },
+        );
+    }
+
+    Ok(counts_by_path)
+}
+
+/// One file's change between a snapshot and the current tree
+#[derive(Debug, Clone, PartialEq)]
+struct LineCountDelta {
+    /// Path relative to the scanned directory
+    relative_path: String,
+    /// Counts in the snapshot (None if the file is new)
+    before: Option<LineKindCounts>,
+    /// Counts now (None if the file was removed)
+    after: Option<LineKindCounts>,
+}
+
+impl LineCountDelta {
+    /// Change in total lines (negative when the file shrank or was removed)
+    fn line_delta(&self) -> i64 {
+        let before_lines = self.before.map_or(0, |counts| counts.total_lines());
+        let after_lines = self.after.map_or(0, |counts| counts.total_lines());
+        after_lines as i64 - before_lines as i64
+    }
+
+    /// Change in code lines
+    fn code_delta(&self) -> i64 {
+        let before_code = self.before.map_or(0, |counts| counts.code_lines);
+        let after_code = self.after.map_or(0, |counts| counts.code_lines);
+        after_code as i64 - before_code as i64
+    }
+
+    /// True if the file is in both and its counts did not change
+    fn is_unchanged(&self) -> bool {
+        self.before.is_some() && self.before == self.after
+    }
+
+    /// One-character marker: `+` added, `-` removed, `~` changed, space unchanged
+    fn status_marker(&self) -> char {
+        match (self.before, self.after) {
+            (None, Some(_)) => '+',
+            (Some(_), None) => '-',
+            _ if self.is_unchanged() => ' ',
+            _ => '~',
+        }
+    }
+}
+
+/// Compares a snapshot with the current files
+///
+/// # Arguments
+/// * `snapshot_counts` - Counts loaded from a snapshot
+/// * `files` - Current files
+/// * `root_directory` - The scanned directory
+///
+/// # Returns
+/// * `Vec<LineCountDelta>` - One entry per path in either set, sorted by path
+fn diff_line_counts(
+    snapshot_counts: &HashMap<String, LineKindCounts>,
+    files: &[FileLineCount],
+    root_directory: &Path,
+) -> Vec<LineCountDelta> {
+    let mut deltas_by_path: HashMap<String, LineCountDelta> = snapshot_counts
+        .iter()
+        .map(|(relative_path, counts)| {
+            (
+                relative_path.clone(),
+                LineCountDelta {
+                    relative_path: relative_path.clone(),
+                    before: Some(*counts),
+                    after: None,
+                },
+            )
+        })
+        .collect();
+
+    for file in files {
+        let relative_path = relative_display_path(&file.file_path, root_directory);
+        deltas_by_path
+            .entry(relative_path.clone())
+            .or_insert_with(|| LineCountDelta {
+                relative_path,
+                before: None,
+                after: None,
+            })
+            .after = Some(file.line_kind_counts);
+    }
+
+    let mut deltas: Vec<LineCountDelta> = deltas_by_path.into_values().collect();
+    deltas.sort_by(|a, b| a.relative_path.cmp(&b.relative_path));
+    deltas
+}
+
+/// Sort orders for the snapshot diff
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum DiffSortMode {
+    /// Biggest change (either direction) first
+    ChangeDescending,
+    /// Smallest change first
+    ChangeAscending,
+    /// Path A-Z
+    NameAscending,
+}
+
+/// Sorts diff rows; ties are broken by path
+fn sort_line_count_deltas(deltas: &mut [LineCountDelta], sort_mode: DiffSortMode) {
+    match sort_mode {
+        DiffSortMode::ChangeDescending => deltas.sort_by(|a, b| {
+            b.line_delta()
+                .abs()
+                .cmp(&a.line_delta().abs())
+                .then_with(|| a.relative_path.cmp(&b.relative_path))
+        }),
+        DiffSortMode::ChangeAscending => deltas.sort_by(|a, b| {
+            a.line_delta()
+                .abs()
+                .cmp(&b.line_delta().abs())
+                .then_with(|| a.relative_path.cmp(&b.relative_path))
+        }),
+        DiffSortMode::NameAscending => {
+            deltas.sort_by(|a, b| a.relative_path.cmp(&b.relative_path));
+        }
+    }
+}
+
+/// Formats a growth line such as "Lines: 1200 -> 1350 (+150, +12.5%)"
+///
+/// # Arguments
+/// * `label` - Row label, e.g. "Lines"
+/// * `before_total` - Total in the snapshot
+/// * `after_total` - Total now
+///
+/// # Returns
+/// * `String` - The percentage is omitted when the snapshot total is zero
+fn format_growth_summary(label: &str, before_total: usize, after_total: usize) -> String {
+    let total_delta = after_total as i64 - before_total as i64;
+    if before_total == 0 {
+        format!("{}: {} -> {} ({:+})", label, before_total, after_total, total_delta)
+    } else {
+        let growth_percent = total_delta as f64 * 100.0 / before_total as f64;
+        format!(
+            "{}: {} -> {} ({:+}, {:+.1}%)",
+            label, before_total, after_total, total_delta, growth_percent
+        )
+    }
+}
+
+/// Width of the Before / After / Delta / Code delta columns in the diff view
+const DIFF_COLUMNS_WIDTH: usize = 32;
+
+/// Displays the diff between a snapshot and the current files
+///
+/// # Arguments
+/// * `deltas` - Sorted diff rows
+/// * `snapshot_name` - Snapshot file name shown in the title
+/// * `terminal_width` - Width to fit rows into
+///
+/// # Note
+/// Unchanged files are counted in the summary but not listed.
+fn display_line_count_diff(deltas: &[LineCountDelta], snapshot_name: &str, terminal_width: usize) {
+    // Clear screen (simple approach)
+    print!("\x1B[2J\x1B[H");
+    let _ = io::stdout().flush();
+
+    println!("Changes since snapshot {}", snapshot_name);
+
+    let name_width = terminal_width
+        .saturating_sub(DIFF_COLUMNS_WIDTH)
+        .saturating_sub(2)
+        .max(8);
+    println!(
+        "  {}{:>8}{:>8}{:>8}{:>8}",
+        fit_name_to_width("File", name_width),
+        "Before",
+        "After",
+        "Delta",
+        "Code"
+    );
+
+    // Changed files
+    let mut before_totals = LineKindCounts::default();
+    let mut after_totals = LineKindCounts::default();
+    let (mut added_count, mut removed_count, mut changed_count, mut unchanged_count) = (0, 0, 0, 0);
+
+    for delta in deltas {
+        if let Some(before) = &delta.before {
+            before_totals.add(before);
+        }
+        if let Some(after) = &delta.after {
+            after_totals.add(after);
+        }
+
+        match delta.status_marker() {
+            '+' => added_count += 1,
+            '-' => removed_count += 1,
+            ' ' => {
+                unchanged_count += 1;
+                continue;
+            }
+            _ => changed_count += 1,
+        }
+
+        let format_optional = |counts: Option<LineKindCounts>| match counts {
+            Some(counts) => counts.total_lines().to_string(),
+            None => "-".to_string(),
+        };
+        println!(
+            "{} {}{:>8}{:>8}{:>8}{:>8}",
+            delta.status_marker(),
+            fit_name_to_width(&delta.relative_path, name_width),
+            format_optional(delta.before),
+            format_optional(delta.after),
+            format!("{:+}", delta.line_delta()),
+            format!("{:+}", delta.code_delta()),
+        );
+    }
+
+    // Summary
+    println!("{}", "-".repeat(terminal_width));
+    println!(
+        "Added {}, removed {}, changed {}, unchanged {}",
+        added_count, removed_count, changed_count, unchanged_count
+    );
+    println!(
+        "{}",
+        format_growth_summary("Lines", before_totals.total_lines(), after_totals.total_lines())
+    );
+    println!(
+        "{}",
+        format_growth_summary("Code", before_totals.code_lines, after_totals.code_lines)
+    );
+
+    print!("\nSort: (c)hange size (toggle), (n)ame | (b/q) back: ");
+    let _ = io::stdout().flush();
+}
+
+/// Lets the user pick a snapshot and browses its diff against the current files
+///
+/// # Arguments
+/// * `files` - Current files (as scanned for the main view)
+/// * `root_directory` - The scanned directory
+/// * `terminal_width` - Width to fit rows into
+///
+/// # Returns
+/// * `Ok(())` - User went back to the main view
+/// * `Err(String)` - Snapshot folder or file could not be read
+fn show_line_count_diff_tui(
+    files: &[FileLineCount],
+    root_directory: &Path,
+    terminal_width: usize,
+) -> Result<(), String> {
+    // Step 1: Pick a snapshot (newest first)
+    let snapshot_directory = line_count_snapshot_directory(root_directory)?;
+    let snapshot_paths = list_line_count_snapshots(&snapshot_directory);
+    if snapshot_paths.is_empty() {
+        return Err("No snapshots yet for this directory; press 's' to save one".to_string());
+    }
+
+    println!("\nSnapshots in {}:", snapshot_directory.display());
+    for (snapshot_index, snapshot_path) in snapshot_paths.iter().enumerate() {
+        println!(
+            "{:>3}. {}",
+            snapshot_index + 1,
+            snapshot_path.file_name().unwrap_or_default().to_string_lossy()
+        );
+    }
+    print!("Snapshot number (Enter = newest, b = back): ");
+    let _ = io::stdout().flush();
+
+    let mut input = String::new();
+    let bytes_read = io::stdin()
+        .read_line(&mut input)
+        .map_err(|e| format!("Failed to read input: {}", e))?;
+    if bytes_read == 0 {
+        return Ok(()); // End of input: go back
+    }
+    let selection = input.trim().to_lowercase();
+    let snapshot_path = match selection.as_str() {
+        "b" | "q" => return Ok(()),
+        "" => &snapshot_paths[0],
+        number_input => match number_input.parse::<usize>() {
+            Ok(number) if (1..=snapshot_paths.len()).contains(&number) => &snapshot_paths[number - 1],
+            _ => return Err(format!("No snapshot number {}", number_input)),
+        },
+    };
+
+    // Step 2: Build the diff
+    let snapshot_counts = load_line_count_snapshot(snapshot_path)?;
+    let mut deltas = diff_line_counts(&snapshot_counts, files, root_directory);
+    let snapshot_name = snapshot_path
+        .file_name()
+        .unwrap_or_default()
+        .to_string_lossy()
+        .to_string();
+
+    // Step 3: Browse until the user goes back
+    let mut sort_mode = DiffSortMode::ChangeDescending;
+    loop {
+        sort_line_count_deltas(&mut deltas, sort_mode);
+        display_line_count_diff(&deltas, &snapshot_name, terminal_width);
+
+        let mut input = String::new();
+        let bytes_read = io::stdin()
+            .read_line(&mut input)
+            .map_err(|e| format!("Failed to read input: {}", e))?;
+        if bytes_read == 0 {
+            return Ok(()); // End of input: stop instead of redrawing forever
+        }
+
+        match input.trim().to_lowercase().as_str() {
+            "b" | "q" => return Ok(()),
+            "c" => {
+                sort_mode = if sort_mode == DiffSortMode::ChangeDescending {
+                    DiffSortMode::ChangeAscending
+                } else {
+                    DiffSortMode::ChangeDescending
+                };
+            }
+            "n" => sort_mode = DiffSortMode::NameAscending,
+            _ => {}
+        }
+    }
+}
+
+/// Waits for Enter so a message stays visible before the screen is redrawn
+fn wait_for_enter() {
+    print!("Press Enter to continue...");
+    let _ = io::stdout().flush();
+    let mut buf = String::new();
+    let _ = io::stdin().read_line(&mut buf);
+}
+
 /// Main TUI loop that coordinates file scanning, display, and user interaction
 ///
 /// # Arguments
@@ -791,24 +2116,80 @@ pub fn show_minimal_linecount_tui(directory_path: &Path) -> Result<(), String> {
     // Initialize display configuration
     let mut display_config = DisplayConfig::default();
 
+    // Scanned files are kept between commands: a recursive scan of a large
+    // tree is slow, so it only runs again on re(f)resh or a mode change
+    let mut files: Vec<FileLineCount> = Vec::new();
+    let mut scanned_recursive_mode: Option<bool> = None;
+
     // Main program loop
     loop {
-        // Scan directory for code files (re-scan each iteration to catch file changes)
-        let mut files = get_code_files_with_counts(&absolute_directory_path);
+        // Scan directory for code files
+        if scanned_recursive_mode != Some(display_config.recursive_mode) {
+            files = if display_config.recursive_mode {
+                get_code_files_with_counts_recursive(&absolute_directory_path)
+            } else {
+                get_code_files_with_counts(&absolute_directory_path)
+            };
+            scanned_recursive_mode = Some(display_config.recursive_mode);
+        }
 
         // Sort files according to current mode
         sort_files_by_mode(&mut files, display_config.sort_mode);
 
         // Display the TUI
-        display_file_list_tui(&files, &display_config);
+        display_file_list_tui(&files, &display_config, &absolute_directory_path);
 
         // Process user command
         match handle_user_command(&mut display_config) {
-            Ok(continue_program) => {
-                if !continue_program {
-                    break; // User chose to exit
+            Ok(LineCountTuiAction::Redisplay) => {}
+            Ok(LineCountTuiAction::Rescan) => scanned_recursive_mode = None,
+            Ok(LineCountTuiAction::Quit) => break, // User chose to exit
+            Ok(LineCountTuiAction::Export(export_format)) => {
+                match export_line_counts(&files, &absolute_directory_path, export_format) {
+                    Ok(report_path) => println!("Saved: {}", report_path.display()),
+                    Err(e) => eprintln!("Export failed: {}", e),
                 }
+                wait_for_enter();
             }
+            Ok(LineCountTuiAction::SaveSnapshot) => {
+                let save_result = line_count_snapshot_directory(&absolute_directory_path).and_then(
+                    |snapshot_directory| {
+                        save_line_count_snapshot(&files, &absolute_directory_path, &snapshot_directory)
+                    },
+                );
+                match save_result {
+                    Ok(snapshot_path) => println!("Snapshot saved: {}", snapshot_path.display()),
+                    Err(e) => eprintln!("Snapshot failed: {}", e),
+                }
+                wait_for_enter();
+            }
+            Ok(LineCountTuiAction::DiffAgainstSnapshot) => {
+                if let Err(e) = show_line_count_diff_tui(
+                    &files,
+                    &absolute_directory_path,
+                    display_config.terminal_width,
+                ) {
+                    eprintln!("Diff failed: {}", e);
+                    wait_for_enter();
+                }
+            }
+            Ok(LineCountTuiAction::OpenInLines(file_index)) => match files.get_mut(file_index) {
+                Some(file) => {
+                    if let Err(e) = lines_full_file_editor(Some(file.file_path.clone()), Some(1), None, false) {
+                        eprintln!("Could not open {} in Lines: {}", file.display_name, e);
+                        wait_for_enter();
+                    }
+                    // The file may have been edited: recount just that one
+                    if let Ok(line_kind_counts) = count_file_lines_efficiently(&file.file_path) {
+                        file.line_count = line_kind_counts.total_lines();
+                        file.line_kind_counts = line_kind_counts;
+                    }
+                }
+                None => {
+                    eprintln!("No file number {}", file_index + 1);
+                    wait_for_enter();
+                }
+            },
             Err(e) => {
                 eprintln!("Input error: {}", e);
                 // Continue program despite input errors
@@ -1122,7 +2503,7 @@ mod linecount_tui_tests {
             assert!(result.is_ok(), "Should count lines in {}", filename);
 
             // Verify count matches expected value
-            let actual_lines = result.unwrap();
+            let actual_lines = result.unwrap().total_lines();
             assert_eq!(
                 actual_lines, *expected_lines,
                 "Wrong line count for {}",
@@ -1286,16 +2667,31 @@ mod linecount_tui_tests {
                 file_path: PathBuf::from("/test/zebra.rs"),
                 display_name: "zebra.rs".to_string(),
                 line_count: 10,
+                language_name: "Rust",
+                line_kind_counts: LineKindCounts {
+                    code_lines: 10,
+                    ..LineKindCounts::default()
+                },
             },
             FileLineCount {
                 file_path: PathBuf::from("/test/apple.py"),
                 display_name: "apple.py".to_string(),
                 line_count: 5,
+                language_name: "Python",
+                line_kind_counts: LineKindCounts {
+                    code_lines: 5,
+                    ..LineKindCounts::default()
+                },
             },
             FileLineCount {
                 file_path: PathBuf::from("/test/mango.csv"),
                 display_name: "mango.csv".to_string(),
                 line_count: 15,
+                language_name: "Delimited Data",
+                line_kind_counts: LineKindCounts {
+                    code_lines: 15,
+                    ..LineKindCounts::default()
+                },
             },
         ];
 
@@ -1328,11 +2724,21 @@ mod linecount_tui_tests {
                 file_path: PathBuf::from("/test/apple.py"),
                 display_name: "apple.py".to_string(),
                 line_count: 5,
+                language_name: "Python",
+                line_kind_counts: LineKindCounts {
+                    code_lines: 5,
+                    ..LineKindCounts::default()
+                },
             },
             FileLineCount {
                 file_path: PathBuf::from("/test/zebra.rs"),
                 display_name: "zebra.rs".to_string(),
                 line_count: 10,
+                language_name: "Rust",
+                line_kind_counts: LineKindCounts {
+                    code_lines: 10,
+                    ..LineKindCounts::default()
+                },
             },
         ];
 
@@ -1364,16 +2770,31 @@ mod linecount_tui_tests {
                 file_path: PathBuf::from("/test/large.rs"),
                 display_name: "large.rs".to_string(),
                 line_count: 100,
+                language_name: "Rust",
+                line_kind_counts: LineKindCounts {
+                    code_lines: 100,
+                    ..LineKindCounts::default()
+                },
             },
             FileLineCount {
                 file_path: PathBuf::from("/test/small.py"),
                 display_name: "small.py".to_string(),
                 line_count: 5,
+                language_name: "Python",
+                line_kind_counts: LineKindCounts {
+                    code_lines: 5,
+                    ..LineKindCounts::default()
+                },
             },
             FileLineCount {
                 file_path: PathBuf::from("/test/medium.csv"),
                 display_name: "medium.csv".to_string(),
                 line_count: 50,
+                language_name: "Delimited Data",
+                line_kind_counts: LineKindCounts {
+                    code_lines: 50,
+                    ..LineKindCounts::default()
+                },
             },
         ];
 
@@ -1406,11 +2827,21 @@ mod linecount_tui_tests {
                 file_path: PathBuf::from("/test/small.py"),
                 display_name: "small.py".to_string(),
                 line_count: 5,
+                language_name: "Python",
+                line_kind_counts: LineKindCounts {
+                    code_lines: 5,
+                    ..LineKindCounts::default()
+                },
             },
             FileLineCount {
                 file_path: PathBuf::from("/test/large.rs"),
                 display_name: "large.rs".to_string(),
                 line_count: 100,
+                language_name: "Rust",
+                line_kind_counts: LineKindCounts {
+                    code_lines: 100,
+                    ..LineKindCounts::default()
+                },
             },
         ];
 
@@ -1442,6 +2873,7 @@ mod linecount_tui_tests {
         assert_eq!(config.sort_mode, SortMode::NameAscending);
         assert_eq!(config.show_header_in_line_count, true);
         assert_eq!(config.terminal_width, 80);
+        assert!(!config.recursive_mode);
         // assert_eq!(config.terminal_height, 24);
     }
 
@@ -1466,6 +2898,11 @@ mod linecount_tui_tests {
             file_path: PathBuf::from("/test/example.rs"),
             display_name: "example.rs".to_string(),
             line_count: 42,
+            language_name: "Rust",
+            line_kind_counts: LineKindCounts {
+                code_lines: 42,
+                ..LineKindCounts::default()
+            },
         };
 
         // Verify fields are stored correctly
@@ -1500,4 +2937,360 @@ mod linecount_tui_tests {
         assert_ne!(SortMode::NameAscending, SortMode::NameDescending);
         assert_ne!(SortMode::CountAscending, SortMode::CountDescending);
     }
+
+    /// Builds a FileLineCount with a given breakdown for sort/summary tests
+    fn make_file(name: &str, language_name: &'static str, code: usize, comment: usize, blank: usize) -> FileLineCount {
+        let line_kind_counts = LineKindCounts {
+            code_lines: code,
+            comment_lines: comment,
+            blank_lines: blank,
+        };
+        FileLineCount {
+            file_path: PathBuf::from(format!("/test/{}", name)),
+            display_name: name.to_string(),
+            line_count: line_kind_counts.total_lines(),
+            language_name,
+            line_kind_counts,
+        }
+    }
+
+    /// Tests classify_line with line comments, block comments and blanks
+    ///
+    /// # Expected Behavior
+    /// - Whitespace-only lines are blank, even inside a block comment
+    /// - Lines with only comment text are comments
+    /// - Lines with any text outside a comment are code
+    /// - Block comment state carries across lines
+    #[test]
+    fn test_classify_line_code_comment_blank() {
+        let rust_syntax = comment_syntax_for_file(Path::new("main.rs"));
+        assert_eq!(rust_syntax.line_marker, Some("//"));
+        assert_eq!(rust_syntax.block_markers, Some(("/*", "*/")));
+
+        let mut inside_block = false;
+        let rust_cases = [
+            ("fn main() {", LineKind::Code),
+            ("    ", LineKind::Blank),
+            ("    // note", LineKind::Comment),
+            ("/// docs", LineKind::Comment),
+            ("let x = 1; // trailing", LineKind::Code),
+            ("/* one-line block */", LineKind::Comment),
+            ("let y = /* inline */ 2;", LineKind::Code),
+            ("/* start of block", LineKind::Comment),
+            ("", LineKind::Blank),
+            ("   still inside", LineKind::Comment),
+            ("end of block */ let z = 3;", LineKind::Code),
+            ("}", LineKind::Code),
+        ];
+        for (line, expected_kind) in &rust_cases {
+            assert_eq!(
+                classify_line(line, &rust_syntax, &mut inside_block),
+                *expected_kind,
+                "Wrong kind for {:?}",
+                line
+            );
+        }
+        assert!(!inside_block);
+
+        // Python: '#' comments and triple-quote blocks
+        let python_syntax = comment_syntax_for_file(Path::new("script.py"));
+        let mut inside_block = false;
+        assert_eq!(classify_line("# comment", &python_syntax, &mut inside_block), LineKind::Comment);
+        assert_eq!(classify_line("\"\"\"Docstring.\"\"\"", &python_syntax, &mut inside_block), LineKind::Comment);
+        assert_eq!(classify_line("\"\"\"", &python_syntax, &mut inside_block), LineKind::Comment);
+        assert_eq!(classify_line("text", &python_syntax, &mut inside_block), LineKind::Comment);
+        assert_eq!(classify_line("\"\"\"", &python_syntax, &mut inside_block), LineKind::Comment);
+        assert_eq!(classify_line("print('hi')", &python_syntax, &mut inside_block), LineKind::Code);
+
+        // Lua: '--[[' opens a block even though '--' is also a line marker
+        let lua_syntax = comment_syntax_for_file(Path::new("init.lua"));
+        let mut inside_block = false;
+        assert_eq!(classify_line("--[[ start", &lua_syntax, &mut inside_block), LineKind::Comment);
+        assert!(inside_block);
+        assert_eq!(classify_line("]]", &lua_syntax, &mut inside_block), LineKind::Comment);
+        assert_eq!(classify_line("-- line", &lua_syntax, &mut inside_block), LineKind::Comment);
+
+        // No known syntax: every non-blank line is code
+        let text_syntax = comment_syntax_for_file(Path::new("readme.txt"));
+        let mut inside_block = false;
+        assert_eq!(classify_line("# not a comment here", &text_syntax, &mut inside_block), LineKind::Code);
+        assert_eq!(classify_line(" \t", &text_syntax, &mut inside_block), LineKind::Blank);
+    }
+
+    /// Tests that count_file_lines_efficiently returns a code/comment/blank breakdown
+    ///
+    /// # Expected Behavior
+    /// - Breakdown adds up to the total line count
+    /// - CRLF line endings and invalid UTF-8 do not break classification
+    #[test]
+    fn test_count_file_lines_efficiently_breakdown() {
+        let test_dir = PathBuf::from("test_temp_line_kinds");
+        fs::create_dir_all(&test_dir).expect("Failed to create test_temp_line_kinds");
+
+        let rust_path = test_dir.join("sample.rs");
+        fs::write(
+            &rust_path,
+            "// header\r\n\r\n/*\r\n block\r\n*/\r\nfn main() {}\r\n",
+        )
+        .expect("Failed to write sample.rs");
+
+        let text_path = test_dir.join("latin1.txt");
+        fs::write(&text_path, b"caf\xe9\n\nend\n").expect("Failed to write latin1.txt");
+
+        let rust_counts = count_file_lines_efficiently(&rust_path).unwrap();
+        let text_counts = count_file_lines_efficiently(&text_path).unwrap();
+        let _ = fs::remove_dir_all(&test_dir);
+
+        assert_eq!(
+            rust_counts,
+            LineKindCounts {
+                code_lines: 1,
+                comment_lines: 4,
+                blank_lines: 1,
+            }
+        );
+        assert_eq!(rust_counts.total_lines(), 6);
+        assert_eq!(
+            text_counts,
+            LineKindCounts {
+                code_lines: 2,
+                comment_lines: 0,
+                blank_lines: 1,
+            }
+        );
+    }
+
+    /// Tests sorting by code, comment and blank line columns
+    #[test]
+    fn test_sort_files_by_mode_line_kinds() {
+        let mut files = vec![
+            make_file("a.rs", "Rust", 10, 1, 7),
+            make_file("b.rs", "Rust", 30, 9, 2),
+            make_file("c.py", "Python", 20, 5, 4),
+        ];
+
+        let sorted_names = |files: &[FileLineCount]| -> Vec<String> {
+            files.iter().map(|file| file.display_name.clone()).collect()
+        };
+
+        sort_files_by_mode(&mut files, SortMode::CodeDescending);
+        assert_eq!(sorted_names(&files), ["b.rs", "c.py", "a.rs"]);
+        sort_files_by_mode(&mut files, SortMode::CodeAscending);
+        assert_eq!(sorted_names(&files), ["a.rs", "c.py", "b.rs"]);
+        sort_files_by_mode(&mut files, SortMode::CommentDescending);
+        assert_eq!(sorted_names(&files), ["b.rs", "c.py", "a.rs"]);
+        sort_files_by_mode(&mut files, SortMode::CommentAscending);
+        assert_eq!(sorted_names(&files), ["a.rs", "c.py", "b.rs"]);
+        sort_files_by_mode(&mut files, SortMode::BlankDescending);
+        assert_eq!(sorted_names(&files), ["a.rs", "c.py", "b.rs"]);
+        sort_files_by_mode(&mut files, SortMode::BlankAscending);
+        assert_eq!(sorted_names(&files), ["b.rs", "c.py", "a.rs"]);
+    }
+
+    /// Tests per-language totals used by the footer
+    ///
+    /// # Expected Behavior
+    /// - One entry per language with summed counts and file counts
+    /// - Languages ordered by code lines, most first
+    #[test]
+    fn test_summarize_counts_by_language() {
+        let files = vec![
+            make_file("a.rs", "Rust", 10, 1, 7),
+            make_file("c.py", "Python", 50, 5, 4),
+            make_file("b.rs", "Rust", 30, 9, 2),
+        ];
+
+        let totals = summarize_counts_by_language(&files);
+        assert_eq!(totals.len(), 2);
+        assert_eq!(totals[0].group_name, "Python");
+        assert_eq!(totals[0].file_count, 1);
+        assert_eq!(totals[1].group_name, "Rust");
+        assert_eq!(totals[1].file_count, 2);
+        assert_eq!(
+            totals[1].line_kind_counts,
+            LineKindCounts {
+                code_lines: 40,
+                comment_lines: 10,
+                blank_lines: 9,
+            }
+        );
+
+        assert_eq!(language_name_for_file(Path::new("/x/Makefile")), "Make");
+        assert_eq!(language_name_for_file(Path::new("/x/main.RS")), "Rust");
+        assert_eq!(language_name_for_file(Path::new("/x/data.unknown")), "Other");
+        assert!(summarize_counts_by_language(&[]).is_empty());
+    }
+
+    /// Tests the recursive scan with ignored directories
+    ///
+    /// # Expected Behavior
+    /// - Files in subdirectories are found, named relative to the root
+    /// - target/, .git/ and node_modules/ are skipped
+    /// - Directory subtotals use "." for the root itself
+    #[test]
+    fn test_get_code_files_with_counts_recursive() {
+        let test_dir = std::env::current_dir()
+            .expect("Failed to read current directory")
+            .join("test_temp_recursive_scan");
+        let _ = fs::remove_dir_all(&test_dir);
+        for directory in ["src/parser", "target/debug", ".git", "node_modules/pkg"] {
+            fs::create_dir_all(test_dir.join(directory)).expect("Failed to create test directory");
+        }
+        fs::write(test_dir.join("main.rs"), "fn main() {}\n").unwrap();
+        fs::write(test_dir.join("src/lib.rs"), "// lib\npub fn a() {}\n").unwrap();
+        fs::write(test_dir.join("src/parser/mod.rs"), "\npub fn b() {}\n").unwrap();
+        fs::write(test_dir.join("target/debug/build.rs"), "fn x() {}\n").unwrap();
+        fs::write(test_dir.join(".git/hook.sh"), "echo\n").unwrap();
+        fs::write(test_dir.join("node_modules/pkg/index.js"), "x();\n").unwrap();
+
+        let mut files = get_code_files_with_counts_recursive(&test_dir);
+        sort_files_by_mode(&mut files, SortMode::NameAscending);
+        let directory_totals = summarize_counts_by_directory(&files, &test_dir);
+        let _ = fs::remove_dir_all(&test_dir);
+
+        let names: Vec<&str> = files.iter().map(|f| f.display_name.as_str()).collect();
+        assert_eq!(names, ["main.rs", "src/lib.rs", "src/parser/mod.rs"]);
+
+        let directory_names: Vec<&str> = directory_totals.iter().map(|d| d.group_name.as_str()).collect();
+        assert_eq!(directory_names, [".", "src", "src/parser"]);
+        assert_eq!(directory_totals[1].line_kind_counts.comment_lines, 1);
+        assert_eq!(directory_totals[2].line_kind_counts.blank_lines, 1);
+    }
+
+    /// Tests CSV and JSON rendering of the line-count table
+    ///
+    /// # Expected Behavior
+    /// - CSV has one header and one row per file, quoting where needed
+    /// - JSON contains files, directory and language groups, and totals
+    #[test]
+    fn test_render_line_counts_csv_and_json() {
+        let files = vec![
+            make_file("main.rs", "Rust", 10, 2, 1),
+            make_file("odd,name.py", "Python", 3, 0, 0),
+        ];
+        let root = Path::new("/test");
+
+        let csv_content = render_line_counts_csv(&files, root);
+        let csv_lines: Vec<&str> = csv_content.lines().collect();
+        assert_eq!(csv_lines[0], "path,directory,language,code,comment,blank,lines");
+        assert_eq!(csv_lines[1], "main.rs,.,Rust,10,2,1,13");
+        assert_eq!(csv_lines[2], "\"odd,name.py\",.,Python,3,0,0,3");
+        assert_eq!(csv_lines.len(), 3);
+
+        let json_content = render_line_counts_json(&files, root);
+        assert!(json_content.contains("\"path\": \"main.rs\""));
+        assert!(json_content.contains("\"directories\": [\n    {\"name\": \".\", \"files\": 2"));
+        assert!(json_content.contains("{\"name\": \"Rust\", \"files\": 1, \"code\": 10"));
+        assert!(json_content.contains(
+            "\"totals\": {\"files\": 2, \"code\": 13, \"comment\": 2, \"blank\": 1, \"lines\": 16}"
+        ));
+
+        let empty_json = render_line_counts_json(&[], root);
+        assert!(empty_json.contains("\"files\": [],"));
+        assert!(empty_json.contains("\"languages\": [],"));
+    }
+
+    /// Tests saving a snapshot and reading it back
+    ///
+    /// # Expected Behavior
+    /// - The snapshot is a timestamped CSV in the given folder
+    /// - Loading it returns the same per-file counts, keyed by relative path
+    /// - A second save in the same second gets a distinct name
+    #[test]
+    fn test_save_and_load_line_count_snapshot() {
+        let snapshot_dir = std::env::current_dir()
+            .expect("Failed to read current directory")
+            .join("test_temp_line_count_snapshots");
+        let _ = fs::remove_dir_all(&snapshot_dir);
+        fs::create_dir_all(&snapshot_dir).expect("Failed to create snapshot directory");
+
+        let files = vec![
+            make_file("main.rs", "Rust", 10, 2, 1),
+            make_file("a,b.py", "Python", 3, 0, 0),
+        ];
+        let first_path = save_line_count_snapshot(&files, Path::new("/test"), &snapshot_dir).unwrap();
+        let second_path = save_line_count_snapshot(&files, Path::new("/test"), &snapshot_dir).unwrap();
+        let listed = list_line_count_snapshots(&snapshot_dir);
+        let loaded = load_line_count_snapshot(&first_path).unwrap();
+        let _ = fs::remove_dir_all(&snapshot_dir);
+
+        assert_ne!(first_path, second_path);
+        assert_eq!(listed.len(), 2);
+        assert_eq!(first_path.file_name().unwrap().len(), "26_01_01_00_00_00.csv".len());
+        assert_eq!(loaded.len(), 2);
+        assert_eq!(loaded["main.rs"], files[0].line_kind_counts);
+        assert_eq!(loaded["a,b.py"].code_lines, 3);
+
+        assert_eq!(snapshot_project_key(Path::new("/home/user/my code")), "home_user_my%20code");
+        assert_eq!(snapshot_project_key(Path::new("/")), "root");
+        // Paths that differ only in separators, underscores or spaces stay apart
+        let similar_keys: HashSet<String> = ["/a/b", "/a_b", "/a b", "/a%5Fb", "/a/b_"]
+            .iter()
+            .map(|path| snapshot_project_key(Path::new(path)))
+            .collect();
+        assert_eq!(similar_keys.len(), 5);
+    }
+
+    /// Tests diffing a snapshot against current files
+    ///
+    /// # Expected Behavior
+    /// - Added, removed, changed and unchanged files are told apart
+    /// - Change-size sorting ranks by absolute line delta
+    /// - Growth summary shows the percent change
+    #[test]
+    fn test_diff_line_counts_and_sort() {
+        let mut snapshot_counts = HashMap::new();
+        snapshot_counts.insert(
+            "kept.rs".to_string(),
+            LineKindCounts {
+                code_lines: 5,
+                comment_lines: 0,
+                blank_lines: 0,
+            },
+        );
+        snapshot_counts.insert(
+            "grew.rs".to_string(),
+            LineKindCounts {
+                code_lines: 10,
+                comment_lines: 0,
+                blank_lines: 0,
+            },
+        );
+        snapshot_counts.insert(
+            "gone.rs".to_string(),
+            LineKindCounts {
+                code_lines: 30,
+                comment_lines: 2,
+                blank_lines: 0,
+            },
+        );
+
+        let files = vec![
+            make_file("kept.rs", "Rust", 5, 0, 0),
+            make_file("grew.rs", "Rust", 14, 1, 0),
+            make_file("new.rs", "Rust", 2, 0, 0),
+        ];
+        let mut deltas = diff_line_counts(&snapshot_counts, &files, Path::new("/test"));
+
+        let markers: Vec<(&str, char)> = deltas
+            .iter()
+            .map(|d| (d.relative_path.as_str(), d.status_marker()))
+            .collect();
+        assert_eq!(
+            markers,
+            [("gone.rs", '-'), ("grew.rs", '~'), ("kept.rs", ' '), ("new.rs", '+')]
+        );
+        assert_eq!(deltas[0].line_delta(), -32);
+        assert_eq!(deltas[1].line_delta(), 5);
+        assert_eq!(deltas[1].code_delta(), 4);
+
+        sort_line_count_deltas(&mut deltas, DiffSortMode::ChangeDescending);
+        let order: Vec<&str> = deltas.iter().map(|d| d.relative_path.as_str()).collect();
+        assert_eq!(order, ["gone.rs", "grew.rs", "new.rs", "kept.rs"]);
+        sort_line_count_deltas(&mut deltas, DiffSortMode::ChangeAscending);
+        assert_eq!(deltas[0].relative_path, "kept.rs");
+
+        assert_eq!(format_growth_summary("Lines", 200, 250), "Lines: 200 -> 250 (+50, +25.0%)");
+        assert_eq!(format_growth_summary("Code", 0, 7), "Code: 0 -> 7 (+7)");
+    }
 }
@@ -25,16 +25,48 @@
 /// - No pre-loading: on-demand data processing for scalability
 /// - Clear error handling with comprehensive user feedback
 
-use std::path::PathBuf;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+// Import CSV conversion capabilities
+use super::csv_convert_module::{
+    convert_csv_file,
+    determine_conversion_output_path,
+    CsvConversionFormat,
+    CsvConversionOptions,
+    CsvConversionSummary,
+    RowFilter,
+};
+
+// Import spreadsheet (.xlsx / .ods) to CSV import
+use super::spreadsheet_import_module::{
+    detect_spreadsheet_format,
+    import_spreadsheet_sheet_to_csv,
+    open_spreadsheet_workbook,
+};
 
 // Import enhanced CSV analysis capabilities
 use super::csv_processor_module::{
     analyze_csv_file_structure_and_types,
-    // CsvAnalysisResults,
+    analyze_csv_file_without_saving_metadata,
+    CsvAnalysisResults,
+    EnhancedCsvAnalysisResults,
+    DEFAULT_HISTOGRAM_BIN_COUNT,
+    MAX_HISTOGRAM_BIN_COUNT,
     perform_enhanced_statistical_analysis,
+    perform_enhanced_statistical_analysis_with_histogram_bins,
+    render_column_distribution_plot_lines,
+    render_analysis_json,
+    render_analysis_text,
+    render_analysis_summary_toml,
+    render_analysis_details_toml,
     // display_enhanced_csv_analysis_results,
     save_analysis_details_to_file,
     save_analysis_summary_to_file,
+    determine_metadata_file_path,
+    validate_csv_against_metadata_file,
+    save_validation_report_to_file,
 };
 
 // Import our custom error types for comprehensive error handling
@@ -42,7 +74,10 @@ use super::error_types_module::{
     // RowsAndColumnsError,
     RowsAndColumnsResult,
     create_file_system_error,
-    create_configuration_error
+    create_configuration_error,
+    EXIT_CODE_SUCCESS,
+    EXIT_CODE_USAGE_ERROR,
+    EXIT_CODE_FILE_SYSTEM_ERROR,
 };
 
 /// Modified version of process_csv_file_from_command_line that saves reports to file
@@ -51,19 +86,25 @@ use super::error_types_module::{
 /// report file instead of printing to terminal. The report file is created in the
 /// same directory as the source CSV file.
 ///
+/// When `show_distribution_plots_view` is true, an interactive terminal view of
+/// per-column histograms and top-value bar charts follows the report. The view
+/// reuses the statistics already computed for the report; only changing the
+/// bin count re-reads the CSV.
+///
 /// # Arguments
 /// * `csv_file_path_argument` - The CSV file path provided as command line argument
-/// * `directory_paths` - The application directory structure for data storage
+/// * `show_distribution_plots_view` - Whether to show the distribution plots view
 ///
 /// # Returns
-/// * `RowsAndColumnsResult<()>` - Success or detailed error information
+/// * `RowsAndColumnsResult<PathBuf>` - Path to the saved analysis report
 ///
 /// # Errors
-/// * `RowsAndColumnsError::FileSystemError` - If file access or validation fails
+/// * `RowsAndColumnsError::FileSystemError` - If file access, validation or input fails
 /// * `RowsAndColumnsError::CsvProcessingError` - If CSV parsing fails
 /// * `RowsAndColumnsError::MetadataError` - If metadata operations fail
 pub fn rc_analyze_datafile_save_results_to_resultsfile(
     csv_file_path_argument: &str,
+    show_distribution_plots_view: bool,
 ) -> RowsAndColumnsResult<PathBuf> {
     // Step 1: Validate the provided file path
     let csv_file_absolute_path = validate_csv_file_path_from_argument(csv_file_path_argument)?;
@@ -93,10 +134,751 @@ pub fn rc_analyze_datafile_save_results_to_resultsfile(
     // Step 6: Notify user of report creation (minimal output to terminal)
     println!("Analysis report here: {}", report_file_path.display());
 
-    // Step 7: Return the report file path
+    // Step 7: Optionally browse the distribution plots
+    if show_distribution_plots_view {
+        show_distribution_plots_tui(
+            &csv_file_absolute_path,
+            &csv_analysis_results,
+            enhanced_analysis_results,
+        )?;
+    }
+
+    // Step 8: Return the report file path
     Ok(report_file_path)
 }
 
+/// Interactive view of per-column distribution plots
+///
+/// Shows one column per screen: a histogram for continuous columns or a
+/// top-values bar chart for categorical columns.
+///
+/// # Commands
+/// * Enter or `n` - next column
+/// * `p` - previous column
+/// * `{number}` - jump to column number (1-based)
+/// * `b {number}` - re-bin numerical histograms (re-reads the CSV)
+/// * `q` - leave the view
+///
+/// # Arguments
+/// * `csv_file_path` - Absolute path to the analyzed CSV file
+/// * `csv_analysis_results` - Basic analysis, reused when re-binning
+/// * `enhanced_analysis_results` - Statistics computed for the report
+///
+/// # Returns
+/// * `RowsAndColumnsResult<()>` - Success, or error if input or re-analysis fails
+fn show_distribution_plots_tui(
+    csv_file_path: &PathBuf,
+    csv_analysis_results: &CsvAnalysisResults,
+    mut enhanced_analysis_results: EnhancedCsvAnalysisResults,
+) -> RowsAndColumnsResult<()> {
+    if enhanced_analysis_results.column_results.is_empty() {
+        return Ok(());
+    }
+
+    let mut current_column_index: usize = 0;
+    let mut histogram_bin_count = DEFAULT_HISTOGRAM_BIN_COUNT;
+
+    loop {
+        let column_count = enhanced_analysis_results.column_results.len();
+        let column_info = &enhanced_analysis_results.column_results[current_column_index];
+
+        // Clear screen (simple approach)
+        print!("\x1B[2J\x1B[H");
+
+        println!(
+            "Distribution plots: {}  (column {} of {})  bins: {}",
+            csv_file_path.file_name().map(|name| name.to_string_lossy()).unwrap_or_default(),
+            current_column_index + 1,
+            column_count,
+            histogram_bin_count
+        );
+        println!(
+            "{}  [{:?}, {}]",
+            column_info.basic_info.column_name,
+            column_info.field_type,
+            column_info.basic_info.detected_data_type.to_toml_string()
+        );
+        println!();
+
+        for plot_line in render_column_distribution_plot_lines(column_info) {
+            println!("  {}", plot_line);
+        }
+
+        print!("\nCommands: (Enter/n)ext, (p)rev, column #, b N bins (1-{}), (q)uit: ", MAX_HISTOGRAM_BIN_COUNT);
+        io::stdout().flush()?;
+
+        let mut input = String::new();
+        io::stdin().read_line(&mut input).map_err(|io_error| {
+            create_file_system_error("Failed to read distribution view command", io_error)
+        })?;
+        let command = input.trim().to_lowercase();
+
+        match command.as_str() {
+            "q" | "b" => break,
+            "" | "n" => {
+                current_column_index = (current_column_index + 1) % column_count;
+            }
+            "p" => {
+                current_column_index = current_column_index
+                    .checked_sub(1)
+                    .unwrap_or(column_count - 1);
+            }
+            _ => {
+                if let Some(bins_argument) = command.strip_prefix('b') {
+                    // Re-bin: recompute statistics with the new histogram size
+                    if let Ok(requested_bins) = bins_argument.trim().parse::<usize>()
+                        && (1..=MAX_HISTOGRAM_BIN_COUNT).contains(&requested_bins)
+                    {
+                        histogram_bin_count = requested_bins;
+                        enhanced_analysis_results = perform_enhanced_statistical_analysis_with_histogram_bins(
+                            csv_file_path,
+                            csv_analysis_results,
+                            histogram_bin_count,
+                        )?;
+                    }
+                } else if let Ok(column_number) = command.parse::<usize>()
+                    && (1..=column_count).contains(&column_number)
+                {
+                    current_column_index = column_number - 1;
+                }
+                // Unknown commands are ignored, as in the line-count view
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Validates a CSV file against its saved metadata schema and saves a pass/fail report
+///
+/// The metadata file written by a previous `-rc` analysis is used as the schema.
+/// The metadata file is only read; it is not regenerated, so repeated validations
+/// keep comparing against the same saved schema.
+///
+/// # Arguments
+/// * `csv_file_path_argument` - The CSV file path provided as command line argument
+/// * `metadata_file_path_argument` - Optional explicit metadata file; when None, the
+///   `{stem}.csv_metadata.toml` file next to the CSV is used
+///
+/// # Returns
+/// * `RowsAndColumnsResult<PathBuf>` - Path to the validation report file
+///
+/// # Errors
+/// * `RowsAndColumnsError::FileSystemError` - If the CSV or metadata file is missing
+/// * `RowsAndColumnsError::ConfigurationError` - If the metadata cannot be parsed
+/// * `RowsAndColumnsError::CsvProcessingError` - If CSV parsing fails
+pub fn rc_validate_datafile_against_metadata(
+    csv_file_path_argument: &str,
+    metadata_file_path_argument: Option<&str>,
+) -> RowsAndColumnsResult<PathBuf> {
+    // Step 1: Validate the provided file path
+    let csv_file_absolute_path = validate_csv_file_path_from_argument(csv_file_path_argument)?;
+
+    // Step 2: Locate the saved metadata schema
+    let metadata_file_path = match metadata_file_path_argument {
+        Some(explicit_path) => PathBuf::from(explicit_path),
+        None => determine_metadata_file_path(&csv_file_absolute_path)?,
+    };
+
+    if !metadata_file_path.is_file() {
+        return Err(create_file_system_error(
+            &format!(
+                "No saved metadata found at {} (run -rc analysis first)",
+                metadata_file_path.display()
+            ),
+            std::io::Error::new(std::io::ErrorKind::NotFound, "Metadata file not found")
+        ));
+    }
+
+    // Step 3: Compare the CSV against the schema
+    let validation_report = validate_csv_against_metadata_file(
+        &csv_file_absolute_path,
+        &metadata_file_path
+    )?;
+
+    // Step 4: Save the pass/fail summary next to the CSV
+    let report_file_path = save_validation_report_to_file(
+        &csv_file_absolute_path,
+        &metadata_file_path,
+        &validation_report
+    )?;
+
+    // Step 5: Minimal terminal output
+    println!(
+        "Validation {}: {}",
+        if validation_report.passed() { "PASS" } else { "FAIL" },
+        report_file_path.display()
+    );
+
+    Ok(report_file_path)
+}
+
+/// Output formats for the headless `ff --rc` command
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HeadlessOutputFormat {
+    Json,
+    Toml,
+    Text,
+}
+
+/// Parsed arguments for the headless `ff --rc` command
+#[derive(Debug, Clone, PartialEq)]
+pub struct HeadlessRcOptions {
+    /// CSV file to analyze
+    pub csv_file_path_argument: String,
+    /// Output format written to stdout
+    pub output_format: HeadlessOutputFormat,
+    /// Number of histogram bins for numerical columns
+    pub histogram_bin_count: usize,
+    /// Sheet name or 1-based number, for .xlsx / .ods input
+    pub sheet_selector: Option<String>,
+}
+
+/// Usage text printed to stderr for invalid headless arguments
+const HEADLESS_RC_USAGE: &str = "\
+Usage: ff --rc FILE [--format json|toml|text] [--bins N] [--sheet NAME|N]
+
+Prints CSV analysis to stdout (default format: text).
+FILE may be .xlsx or .ods: the sheet (default: first) is saved as CSV first.
+Exit codes: 0 ok, 2 usage, 3 file system, 4 CSV processing, 5 configuration";
+
+/// Splits a `--flag=value` argument into its name and inline value
+///
+/// # Arguments
+/// * `argument` - One command line argument
+///
+/// # Returns
+/// * `(&str, Option<String>)` - Flag name (or the whole argument) and inline value
+fn split_headless_flag_argument(argument: &str) -> (&str, Option<String>) {
+    match argument.split_once('=') {
+        Some((flag_name, flag_value)) if flag_name.starts_with("--") => {
+            (flag_name, Some(flag_value.to_string()))
+        }
+        _ => (argument, None),
+    }
+}
+
+/// Returns a flag's value: the inline `=value`, or else the next argument
+///
+/// # Arguments
+/// * `flag_name` - Flag being read, for the error message
+/// * `inline_value` - Value from `--flag=value`, if any
+/// * `arguments` - All arguments being parsed
+/// * `argument_index` - Index of the flag; advanced past a separate value
+///
+/// # Returns
+/// * `Result<String, String>` - The value or a usage error message
+fn take_headless_flag_value(
+    flag_name: &str,
+    inline_value: Option<String>,
+    arguments: &[String],
+    argument_index: &mut usize,
+) -> Result<String, String> {
+    match inline_value {
+        Some(flag_value) => Ok(flag_value),
+        None => {
+            *argument_index += 1;
+            arguments
+                .get(*argument_index)
+                .cloned()
+                .ok_or_else(|| format!("Missing value for {}", flag_name))
+        }
+    }
+}
+
+/// Parses the arguments that follow `--rc` on the command line
+///
+/// # Arguments
+/// * `arguments` - Arguments after `--rc`, e.g. `["data.csv", "--format", "json"]`
+///
+/// # Returns
+/// * `Result<HeadlessRcOptions, String>` - Parsed options or a usage error message
+pub fn parse_headless_rc_arguments(arguments: &[String]) -> Result<HeadlessRcOptions, String> {
+    let mut csv_file_path_argument: Option<String> = None;
+    let mut output_format = HeadlessOutputFormat::Text;
+    let mut histogram_bin_count = DEFAULT_HISTOGRAM_BIN_COUNT;
+    let mut sheet_selector: Option<String> = None;
+
+    let mut argument_index = 0;
+    while argument_index < arguments.len() {
+        let current_argument = arguments[argument_index].as_str();
+
+        let (flag_name, inline_value) = split_headless_flag_argument(current_argument);
+
+        match flag_name {
+            "--format" | "--bins" | "--sheet" => {
+                let flag_value = take_headless_flag_value(
+                    flag_name,
+                    inline_value,
+                    arguments,
+                    &mut argument_index,
+                )?;
+
+                if flag_name == "--sheet" {
+                    sheet_selector = Some(flag_value);
+                } else if flag_name == "--format" {
+                    output_format = match flag_value.to_lowercase().as_str() {
+                        "json" => HeadlessOutputFormat::Json,
+                        "toml" => HeadlessOutputFormat::Toml,
+                        "text" => HeadlessOutputFormat::Text,
+                        other => return Err(format!("Unknown format: {}", other)),
+                    };
+                } else {
+                    histogram_bin_count = match flag_value.parse::<usize>() {
+                        Ok(count) if (1..=MAX_HISTOGRAM_BIN_COUNT).contains(&count) => count,
+                        _ => {
+                            return Err(format!(
+                                "--bins must be a number from 1 to {}",
+                                MAX_HISTOGRAM_BIN_COUNT
+                            ));
+                        }
+                    };
+                }
+            }
+            unknown_flag if unknown_flag.starts_with("--") => {
+                return Err(format!("Unknown option: {}", unknown_flag));
+            }
+            file_argument => {
+                if csv_file_path_argument.is_some() {
+                    return Err(format!("Unexpected extra argument: {}", file_argument));
+                }
+                csv_file_path_argument = Some(file_argument.to_string());
+            }
+        }
+
+        argument_index += 1;
+    }
+
+    let csv_file_path_argument = csv_file_path_argument
+        .ok_or_else(|| "Missing CSV file path".to_string())?;
+
+    Ok(HeadlessRcOptions {
+        csv_file_path_argument,
+        output_format,
+        histogram_bin_count,
+        sheet_selector,
+    })
+}
+
+/// Runs CSV analysis without any interactive view and prints it to stdout
+///
+/// Nothing is saved next to the CSV except the metadata file that every
+/// analysis writes (and the imported CSV, for .xlsx / .ods input). Progress
+/// and warnings go to stderr so stdout stays parseable.
+///
+/// # Arguments
+/// * `options` - Parsed headless options
+///
+/// # Returns
+/// * `RowsAndColumnsResult<String>` - The rendered analysis in the chosen format
+///
+/// # Errors
+/// * `RowsAndColumnsError::FileSystemError` - If the CSV cannot be accessed
+/// * `RowsAndColumnsError::CsvProcessingError` - If CSV parsing fails
+/// * `RowsAndColumnsError::ConfigurationError` - If the path is not a file
+pub fn rc_render_headless_analysis(options: &HeadlessRcOptions) -> RowsAndColumnsResult<String> {
+    // Step 1: Validate the provided file path, importing spreadsheets to CSV
+    let csv_file_absolute_path = if detect_spreadsheet_format(Path::new(&options.csv_file_path_argument)).is_some() {
+        let csv_file_path = import_spreadsheet_from_argument(
+            &options.csv_file_path_argument,
+            Some(options.sheet_selector.as_deref().unwrap_or("1")),
+        )?;
+        eprintln!("Imported sheet to: {}", csv_file_path.display());
+        csv_file_path
+    } else {
+        validate_csv_file_path_from_argument(&options.csv_file_path_argument)?
+    };
+
+    // Step 2: Basic and enhanced analysis (read-only: the metadata file,
+    // which `-validate` checks against, is not rewritten)
+    let csv_analysis_results = analyze_csv_file_without_saving_metadata(&csv_file_absolute_path)?;
+    let enhanced_analysis_results = perform_enhanced_statistical_analysis_with_histogram_bins(
+        &csv_file_absolute_path,
+        &csv_analysis_results,
+        options.histogram_bin_count,
+    )?;
+
+    // Step 3: Render in the requested format
+    let rendered_output = match options.output_format {
+        HeadlessOutputFormat::Json => render_analysis_json(
+            &csv_file_absolute_path,
+            &csv_analysis_results,
+            &enhanced_analysis_results,
+        ),
+        HeadlessOutputFormat::Text => render_analysis_text(
+            &csv_file_absolute_path,
+            &csv_analysis_results,
+            &enhanced_analysis_results,
+        ),
+        HeadlessOutputFormat::Toml => {
+            let timestamp = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|duration| duration.as_secs())
+                .unwrap_or(0)
+                .to_string();
+
+            let mut toml_content = render_analysis_summary_toml(
+                &csv_file_absolute_path,
+                &csv_analysis_results,
+                &timestamp,
+            );
+            toml_content.push_str(&render_analysis_details_toml(&enhanced_analysis_results));
+            toml_content
+        }
+    };
+
+    Ok(rendered_output)
+}
+
+/// Entry point for `ff --rc FILE [--format json|toml|text] [--bins N] [--sheet NAME|N]`
+///
+/// # Arguments
+/// * `arguments` - Command line arguments following `--rc`
+///
+/// # Returns
+/// * `i32` - Process exit code: `EXIT_CODE_SUCCESS`, `EXIT_CODE_USAGE_ERROR`,
+///   or the code for the `RowsAndColumnsError` variant that occurred
+pub fn rc_run_headless_cli(arguments: &[String]) -> i32 {
+    // Step 1: Parse arguments
+    let options = match parse_headless_rc_arguments(arguments) {
+        Ok(parsed_options) => parsed_options,
+        Err(usage_message) => {
+            eprintln!("ff --rc: {}", usage_message);
+            eprintln!("{}", HEADLESS_RC_USAGE);
+            return EXIT_CODE_USAGE_ERROR;
+        }
+    };
+
+    // Step 2: Analyze and print
+    match rc_render_headless_analysis(&options) {
+        Ok(rendered_output) => {
+            let mut stdout_handle = io::stdout().lock();
+            if stdout_handle
+                .write_all(rendered_output.as_bytes())
+                .and_then(|_| stdout_handle.flush())
+                .is_err()
+            {
+                // Closed pipe or full disk: nothing useful left to print to
+                return EXIT_CODE_FILE_SYSTEM_ERROR;
+            }
+            EXIT_CODE_SUCCESS
+        }
+        Err(analysis_error) => {
+            eprintln!("ff --rc: {}", analysis_error);
+            analysis_error.exit_code()
+        }
+    }
+}
+
+/// Splits a comma-separated column list such as `name, age`
+///
+/// # Arguments
+/// * `column_list` - User-entered column names
+///
+/// # Returns
+/// * `Vec<String>` - Trimmed, non-empty column names in the given order
+fn parse_column_name_list(column_list: &str) -> Vec<String> {
+    column_list
+        .split(',')
+        .map(|column_name| column_name.trim().to_string())
+        .filter(|column_name| !column_name.is_empty())
+        .collect()
+}
+
+/// Converts a CSV file into a new file, replacing the output only on success
+///
+/// The converted data is written to a temporary file beside the output
+/// and renamed into place once conversion finishes, so a failed run never
+/// leaves a partial file or clobbers an existing one. An output that is the
+/// input CSV itself is refused before anything is written.
+///
+/// # Arguments
+/// * `csv_file_absolute_path` - Validated path to the CSV file
+/// * `conversion_options` - Format, column selection and row filters
+/// * `output_file_path` - Where to write the converted file
+///
+/// # Returns
+/// * `RowsAndColumnsResult<CsvConversionSummary>` - Rows written and filtered out
+///
+/// # Errors
+/// * `RowsAndColumnsError::FileSystemError` - If the output cannot be created, written or renamed
+/// * `RowsAndColumnsError::CsvProcessingError` - If CSV parsing fails
+/// * `RowsAndColumnsError::ConfigurationError` - If the output is the input CSV,
+///   has no file name, or a selected or filtered column is unknown
+fn convert_csv_file_into_output_file(
+    csv_file_absolute_path: &PathBuf,
+    conversion_options: &CsvConversionOptions,
+    output_file_path: &Path,
+) -> RowsAndColumnsResult<CsvConversionSummary> {
+    // Step 1: Refuse to overwrite the CSV being read
+    if let (Ok(canonical_output_path), Ok(canonical_input_path)) = (
+        output_file_path.canonicalize(),
+        csv_file_absolute_path.canonicalize(),
+    ) && canonical_output_path == canonical_input_path
+    {
+        return Err(create_configuration_error(&format!(
+            "Output file is the input CSV: {}",
+            output_file_path.display()
+        )));
+    }
+
+    // Step 2: Write into a temporary file in the target directory
+    let output_file_name = output_file_path.file_name().ok_or_else(|| {
+        create_configuration_error(&format!(
+            "Output path has no file name: {}",
+            output_file_path.display()
+        ))
+    })?;
+    let mut temp_file_name = std::ffi::OsString::from(format!("converting_{}_", std::process::id()));
+    temp_file_name.push(output_file_name);
+    let temp_output_path = output_file_path.with_file_name(temp_file_name);
+
+    let temp_output_file = File::create(&temp_output_path)
+        .map_err(|io_error| {
+            create_file_system_error(
+                &format!("Failed to create converted file: {}", temp_output_path.display()),
+                io_error
+            )
+        })?;
+
+    let mut output_writer = BufWriter::new(temp_output_file);
+    let conversion_result = convert_csv_file(
+        csv_file_absolute_path,
+        conversion_options,
+        &mut output_writer,
+    )
+    .and_then(|summary| {
+        output_writer.flush().map_err(|io_error| {
+            create_file_system_error("Failed to flush converted file", io_error)
+        })?;
+        Ok(summary)
+    });
+    drop(output_writer);
+
+    // Step 3: Move the finished file into place, or discard it
+    let conversion_result = conversion_result.and_then(|summary| {
+        std::fs::rename(&temp_output_path, output_file_path).map_err(|io_error| {
+            create_file_system_error(
+                &format!("Failed to move converted file to: {}", output_file_path.display()),
+                io_error
+            )
+        })?;
+        Ok(summary)
+    });
+
+    if conversion_result.is_err() {
+        let _ = std::fs::remove_file(&temp_output_path);
+    }
+
+    conversion_result
+}
+
+/// Reads one trimmed line of user input after showing a prompt
+///
+/// # Arguments
+/// * `prompt_text` - Text shown before the cursor
+///
+/// # Returns
+/// * `RowsAndColumnsResult<String>` - The trimmed input line
+///
+/// # Errors
+/// * `RowsAndColumnsError::FileSystemError` - If stdin or stdout fails
+fn prompt_for_line(prompt_text: &str) -> RowsAndColumnsResult<String> {
+    print!("{}", prompt_text);
+    io::stdout().flush().map_err(|io_error| {
+        create_file_system_error("Failed to flush prompt", io_error)
+    })?;
+
+    let mut input_line = String::new();
+    io::stdin().read_line(&mut input_line).map_err(|io_error| {
+        create_file_system_error("Failed to read input", io_error)
+    })?;
+
+    Ok(input_line.trim().to_string())
+}
+
+/// Asks for conversion settings and writes the converted file next to the CSV
+///
+/// Prompts for the output format, an optional column list (which also sets
+/// the output order), and any number of row filters. The converted file is
+/// saved as `{stem}_converted_{timestamp}.{ext}`.
+///
+/// # Arguments
+/// * `csv_file_path_argument` - The CSV file path to convert
+///
+/// # Returns
+/// * `RowsAndColumnsResult<PathBuf>` - Path to the converted file
+///
+/// # Errors
+/// * `RowsAndColumnsError::FileSystemError` - If file access or input fails
+/// * `RowsAndColumnsError::CsvProcessingError` - If CSV parsing fails
+/// * `RowsAndColumnsError::ConfigurationError` - If the user cancels or names an unknown column
+pub fn rc_convert_datafile_interactive(csv_file_path_argument: &str) -> RowsAndColumnsResult<PathBuf> {
+    // Step 1: Validate the provided file path and show available columns
+    let csv_file_absolute_path = validate_csv_file_path_from_argument(csv_file_path_argument)?;
+    let csv_analysis_results = analyze_csv_file_without_saving_metadata(&csv_file_absolute_path)?;
+
+    println!();
+    println!("Columns:");
+    for column_info in &csv_analysis_results.column_information_list {
+        println!(
+            "  {:>3}. {} ({})",
+            column_info.column_index + 1,
+            column_info.column_name.trim(),
+            column_info.detected_data_type.to_toml_string()
+        );
+    }
+    println!();
+
+    // Step 2: Output format
+    let output_format = loop {
+        let format_input = prompt_for_line(
+            "Format: json, jsonl, md, csv, tsv, psv, ssv (Enter = json, q = cancel): "
+        )?;
+
+        if format_input.eq_ignore_ascii_case("q") {
+            return Err(create_configuration_error("Conversion cancelled"));
+        }
+        if format_input.is_empty() {
+            break CsvConversionFormat::JsonArray;
+        }
+        match CsvConversionFormat::from_name(&format_input) {
+            Some(chosen_format) => break chosen_format,
+            None => println!("Unknown format: {}", format_input),
+        }
+    };
+
+    // Step 3: Column selection and order
+    let selected_column_names = parse_column_name_list(&prompt_for_line(
+        "Columns in output order, comma separated (Enter = all): "
+    )?);
+
+    // Step 4: Row filters, all of which must match
+    let mut row_filters = Vec::new();
+    loop {
+        let filter_input = prompt_for_line("Row filter, e.g. age>=30 or name~lu (Enter = done): ")?;
+        if filter_input.is_empty() {
+            break;
+        }
+        match RowFilter::parse(&filter_input) {
+            Ok(row_filter) => row_filters.push(row_filter),
+            Err(filter_error) => println!("{}", filter_error),
+        }
+    }
+
+    let conversion_options = CsvConversionOptions {
+        output_format,
+        selected_column_names,
+        row_filters,
+    };
+
+    // Step 5: Convert into a new file next to the CSV
+    let output_file_path = determine_conversion_output_path(&csv_file_absolute_path, output_format)?;
+    let summary = convert_csv_file_into_output_file(
+        &csv_file_absolute_path,
+        &conversion_options,
+        &output_file_path,
+    )?;
+
+    // Step 6: Minimal terminal output
+    println!(
+        "Converted {} rows ({} filtered out): {}",
+        summary.rows_written,
+        summary.rows_filtered_out,
+        output_file_path.display()
+    );
+
+    Ok(output_file_path)
+}
+
+/// Saves one sheet of an .xlsx or .ods file as CSV next to the original
+///
+/// With no sheet selector and more than one sheet, the sheets are listed
+/// and the user picks one by number or name.
+///
+/// # Arguments
+/// * `spreadsheet_path_argument` - The spreadsheet file path
+/// * `sheet_selector` - Sheet name or 1-based number; None to ask
+///
+/// # Returns
+/// * `RowsAndColumnsResult<PathBuf>` - Absolute path to the written CSV file
+///
+/// # Errors
+/// * `RowsAndColumnsError::FileSystemError` - If the file cannot be read or the CSV written
+/// * `RowsAndColumnsError::CsvProcessingError` - If the spreadsheet is malformed or the sheet empty
+/// * `RowsAndColumnsError::ConfigurationError` - If the sheet does not exist or the user cancels
+fn import_spreadsheet_from_argument(
+    spreadsheet_path_argument: &str,
+    sheet_selector: Option<&str>,
+) -> RowsAndColumnsResult<PathBuf> {
+    // Step 1: Resolve the path and list sheets
+    let spreadsheet_path = PathBuf::from(spreadsheet_path_argument)
+        .canonicalize()
+        .map_err(|io_error| {
+            create_file_system_error(
+                &format!("Spreadsheet file does not exist: {}", spreadsheet_path_argument),
+                io_error
+            )
+        })?;
+    let spreadsheet_workbook = open_spreadsheet_workbook(&spreadsheet_path)?;
+
+    // Step 2: Pick the sheet
+    let sheet_index = match sheet_selector {
+        Some(selector) => spreadsheet_workbook.find_sheet_index(selector).ok_or_else(|| {
+            create_configuration_error(&format!(
+                "No sheet named or numbered '{}' (sheets: {})",
+                selector,
+                spreadsheet_workbook.sheet_names.join(", ")
+            ))
+        })?,
+        None if spreadsheet_workbook.sheet_names.len() == 1 => 0,
+        None => {
+            println!();
+            println!("Sheets:");
+            for (sheet_index, sheet_name) in spreadsheet_workbook.sheet_names.iter().enumerate() {
+                println!("  {:>3}. {}", sheet_index + 1, sheet_name);
+            }
+            println!();
+
+            loop {
+                let sheet_input = prompt_for_line("Sheet number or name (Enter = 1, q = cancel): ")?;
+                if sheet_input.eq_ignore_ascii_case("q") {
+                    return Err(create_configuration_error("Spreadsheet import cancelled"));
+                }
+                if sheet_input.is_empty() {
+                    break 0;
+                }
+                match spreadsheet_workbook.find_sheet_index(&sheet_input) {
+                    Some(chosen_index) => break chosen_index,
+                    None => println!("No such sheet: {}", sheet_input),
+                }
+            }
+        }
+    };
+
+    // Step 3: Write the CSV next to the spreadsheet
+    import_spreadsheet_sheet_to_csv(&spreadsheet_path, &spreadsheet_workbook, sheet_index)
+}
+
+/// Imports an .xlsx or .ods sheet to CSV, asking which sheet if there are several
+///
+/// The CSV is written next to the spreadsheet as `{stem}_{sheet}.csv` and
+/// can then be analyzed like any other CSV file.
+///
+/// # Arguments
+/// * `spreadsheet_path_argument` - The spreadsheet file path
+///
+/// # Returns
+/// * `RowsAndColumnsResult<PathBuf>` - Path to the written CSV file
+///
+/// # Errors
+/// * Same as the sheet import: file system, malformed spreadsheet, or cancelled
+pub fn rc_import_spreadsheet_interactive(spreadsheet_path_argument: &str) -> RowsAndColumnsResult<PathBuf> {
+    let csv_file_path = import_spreadsheet_from_argument(spreadsheet_path_argument, None)?;
+    println!("Imported sheet to: {}", csv_file_path.display());
+    Ok(csv_file_path)
+}
+
 /// Validates a CSV file path provided as command line argument
 ///
 /// This function checks if the provided path exists, is accessible, and appears
@@ -140,13 +922,13 @@ fn validate_csv_file_path_from_argument(csv_file_path_argument: &str) -> RowsAnd
             // File appears to be CSV format
         }
         Some(other_extension) => {
-            println!("Warning: File extension '{}' is not typical for CSV files.", other_extension);
-            println!("         Proceeding anyway, but ensure this is a comma-separated values file.");
-            println!();
+            eprintln!("Warning: File extension '{}' is not typical for CSV files.", other_extension);
+            eprintln!("         Proceeding anyway, but ensure this is a comma-separated values file.");
+            eprintln!();
         }
         None => {
-            println!("Warning: File has no extension. Ensure this is a comma-separated values file.");
-            println!();
+            eprintln!("Warning: File has no extension. Ensure this is a comma-separated values file.");
+            eprintln!();
         }
     }
 
@@ -161,3 +943,151 @@ fn validate_csv_file_path_from_argument(csv_file_path_argument: &str) -> RowsAnd
 
     Ok(absolute_file_path)
 }
+
+#[cfg(test)]
+mod headless_rc_argument_tests {
+    use super::*;
+
+    fn to_arguments(raw_arguments: &[&str]) -> Vec<String> {
+        raw_arguments.iter().map(|argument| argument.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_headless_rc_arguments() {
+        let options = parse_headless_rc_arguments(&to_arguments(&["data.csv"])).unwrap();
+        assert_eq!(options.csv_file_path_argument, "data.csv");
+        assert_eq!(options.output_format, HeadlessOutputFormat::Text);
+        assert_eq!(options.histogram_bin_count, DEFAULT_HISTOGRAM_BIN_COUNT);
+
+        let options = parse_headless_rc_arguments(
+            &to_arguments(&["--format", "json", "data.csv", "--bins=5"])
+        ).unwrap();
+        assert_eq!(options.output_format, HeadlessOutputFormat::Json);
+        assert_eq!(options.histogram_bin_count, 5);
+
+        let options = parse_headless_rc_arguments(&to_arguments(&["data.csv", "--format=TOML"])).unwrap();
+        assert_eq!(options.output_format, HeadlessOutputFormat::Toml);
+        assert_eq!(options.sheet_selector, None);
+
+        let options = parse_headless_rc_arguments(&to_arguments(&["book.xlsx", "--sheet", "Q3 Sales"])).unwrap();
+        assert_eq!(options.sheet_selector.as_deref(), Some("Q3 Sales"));
+    }
+
+    #[test]
+    fn test_parse_headless_rc_arguments_rejects_bad_input() {
+        assert!(parse_headless_rc_arguments(&to_arguments(&[])).is_err());
+        assert!(parse_headless_rc_arguments(&to_arguments(&["data.csv", "--format"])).is_err());
+        assert!(parse_headless_rc_arguments(&to_arguments(&["data.csv", "--format", "xml"])).is_err());
+        assert!(parse_headless_rc_arguments(&to_arguments(&["data.csv", "--bins", "0"])).is_err());
+        assert!(parse_headless_rc_arguments(&to_arguments(&["a.csv", "b.csv"])).is_err());
+        assert!(parse_headless_rc_arguments(&to_arguments(&["data.csv", "--verbose"])).is_err());
+    }
+
+    #[test]
+    fn test_headless_analysis_writes_no_metadata() {
+        let test_directory = std::env::temp_dir()
+            .join(format!("rc_headless_read_only_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&test_directory);
+        std::fs::create_dir_all(&test_directory).expect("create test directory");
+        let csv_path = test_directory.join("data.csv");
+        std::fs::write(&csv_path, "id,name\n1,Luna\n2,Max\n").expect("write test csv");
+        let metadata_path = test_directory.join("data.csv_metadata.toml");
+
+        let options = parse_headless_rc_arguments(&to_arguments(&[
+            csv_path.to_str().unwrap(),
+            "--format",
+            "json",
+        ])).unwrap();
+
+        // No metadata file is created...
+        assert!(rc_render_headless_analysis(&options).is_ok());
+        assert!(!metadata_path.exists());
+
+        // ...and a saved one is left exactly as it was
+        std::fs::write(&metadata_path, "saved schema").expect("write metadata");
+        assert!(rc_render_headless_analysis(&options).is_ok());
+        assert_eq!(std::fs::read_to_string(&metadata_path).unwrap(), "saved schema");
+
+        let _ = std::fs::remove_dir_all(&test_directory);
+    }
+
+    #[test]
+    fn test_headless_cli_exit_codes() {
+        assert_eq!(rc_run_headless_cli(&to_arguments(&[])), EXIT_CODE_USAGE_ERROR);
+        assert_eq!(
+            rc_run_headless_cli(&to_arguments(&["/nonexistent/ff_headless_missing.csv"])),
+            EXIT_CODE_FILE_SYSTEM_ERROR
+        );
+    }
+}
+
+#[cfg(test)]
+mod convert_output_file_tests {
+    use super::*;
+    use crate::error_types_module::EXIT_CODE_CONFIGURATION_ERROR;
+
+    #[test]
+    fn test_convert_into_output_file() {
+        let test_directory = std::env::temp_dir()
+            .join(format!("rc_convert_output_tests_{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&test_directory);
+        std::fs::create_dir_all(&test_directory).expect("create test directory");
+
+        let csv_path = test_directory.join("people.csv");
+        std::fs::write(&csv_path, "id,name,age\n1,Ann,34\n2,Bo,28\n3,Cy,41\n").expect("write test csv");
+        let output_path = test_directory.join("people.jsonl");
+        let conversion_options = |selected_column_names: &[&str]| CsvConversionOptions {
+            output_format: CsvConversionFormat::from_name("jsonl").unwrap(),
+            selected_column_names: selected_column_names.iter().map(|name| name.to_string()).collect(),
+            row_filters: vec![RowFilter::parse("age>30").unwrap()],
+        };
+
+        let summary = convert_csv_file_into_output_file(
+            &csv_path,
+            &conversion_options(&["name", "age"]),
+            &output_path,
+        ).unwrap();
+        assert_eq!((summary.rows_written, summary.rows_filtered_out), (2, 1));
+        assert_eq!(
+            std::fs::read_to_string(&output_path).unwrap(),
+            "{\"name\": \"Ann\", \"age\": 34}\n{\"name\": \"Cy\", \"age\": 41}\n"
+        );
+        // Converting only reads the CSV: no metadata/schema file is written
+        assert!(!test_directory.join("people.csv_metadata.toml").exists());
+
+        // Unknown column is a configuration error and leaves no partial file
+        let bad_output_path = test_directory.join("bad.json");
+        let conversion_error = convert_csv_file_into_output_file(
+            &csv_path,
+            &conversion_options(&["missing"]),
+            &bad_output_path,
+        ).unwrap_err();
+        assert_eq!(conversion_error.exit_code(), EXIT_CODE_CONFIGURATION_ERROR);
+        assert!(!bad_output_path.exists());
+
+        // Output naming the input is refused and the CSV survives untouched
+        let conversion_error = convert_csv_file_into_output_file(
+            &csv_path,
+            &conversion_options(&[]),
+            &test_directory.join(".").join("people.csv"),
+        ).unwrap_err();
+        assert_eq!(conversion_error.exit_code(), EXIT_CODE_CONFIGURATION_ERROR);
+        assert_eq!(
+            std::fs::read_to_string(&csv_path).unwrap(),
+            "id,name,age\n1,Ann,34\n2,Bo,28\n3,Cy,41\n"
+        );
+
+        // A failed run leaves an existing output file as it was
+        assert!(convert_csv_file_into_output_file(
+            &csv_path,
+            &conversion_options(&["missing"]),
+            &output_path,
+        ).is_err());
+        assert!(std::fs::read_to_string(&output_path).unwrap().contains("Ann"));
+        assert!(std::fs::read_dir(&test_directory).unwrap().all(|entry| {
+            !entry.unwrap().file_name().to_string_lossy().starts_with("converting_")
+        }));
+
+        let _ = std::fs::remove_dir_all(&test_directory);
+    }
+}
@@ -0,0 +1,2073 @@
+// src/spreadsheet_import_module.rs
+
+//! Spreadsheet (.xlsx / .ods) import to CSV for rows_and_columns
+//!
+//! Reads one sheet of an Office Open XML workbook (`.xlsx`) or an OpenDocument
+//! spreadsheet (`.ods`) and writes it as a CSV file next to the original, so
+//! the regular CSV analyzer can take it from there.
+//!
+//! Both formats are ZIP archives of XML parts, so this module contains the
+//! three small pieces needed to read them without external crates:
+//! - A ZIP central directory reader (stored and deflate entries, CRC-checked)
+//! - An inflate (RFC 1951) decompressor
+//! - A minimal XML pull parser (elements, attributes, text, entities, CDATA)
+//!
+//! # Scope
+//! - Cell values are written as stored: XLSX dates are serial numbers, ODS
+//!   dates are ISO text, formulas contribute their cached result
+//! - Line breaks inside a cell become spaces, so every record stays on one
+//!   line for the line-based CSV analyzer
+//! - ZIP64, encrypted and multi-disk archives are reported as unsupported
+//!
+//! # Design Philosophy
+//! - Whole archive in memory: spreadsheets are bounded in size, and this keeps
+//!   the ZIP reader simple
+//! - Clear errors naming the archive part that failed
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use super::csv_convert_module::format_delimited_field;
+
+// Existing CSV files are archived the same way ff archives replaced files
+use super::ff_file_fantastic_module::{
+    createarchive_timestamp_with_precision,
+    ensure_archive_directory_exists,
+    generate_archive_filename,
+};
+
+// Import our error handling system
+use super::error_types_module::{
+    RowsAndColumnsError,
+    RowsAndColumnsResult,
+    create_file_system_error,
+    create_csv_processing_error,
+    create_configuration_error,
+};
+
+/// Most cells (blank ones included) one imported sheet may expand to
+///
+/// ODS files describe repeated rows and cells as counts, and a workbook may
+/// pad rows out to its widest one; a sheet that would go past this is an
+/// error, never silently cut. Trailing blank areas cost nothing.
+const SPREADSHEET_MAX_CELL_COUNT: usize = 16_777_216;
+
+/// Upper bound on how many spaces one ODS `<text:s c="N"/>` expands to
+const ODS_MAX_SPACE_RUN: usize = 16_384;
+
+/// ZIP record signatures
+const ZIP_LOCAL_FILE_HEADER_SIGNATURE: u32 = 0x0403_4b50;
+const ZIP_CENTRAL_DIRECTORY_SIGNATURE: u32 = 0x0201_4b50;
+const ZIP_END_OF_CENTRAL_DIRECTORY_SIGNATURE: u32 = 0x0605_4b50;
+
+/// Fixed ZIP record sizes (without variable-length name/extra/comment)
+const ZIP_LOCAL_FILE_HEADER_SIZE: usize = 30;
+const ZIP_CENTRAL_DIRECTORY_HEADER_SIZE: usize = 46;
+const ZIP_END_OF_CENTRAL_DIRECTORY_SIZE: usize = 22;
+
+/// ZIP compression methods this reader supports
+const ZIP_METHOD_STORED: u16 = 0;
+const ZIP_METHOD_DEFLATE: u16 = 8;
+
+// ============================================================================
+// ZIP ARCHIVE
+// ============================================================================
+
+/// One file inside a ZIP archive, from the central directory
+#[derive(Debug, Clone)]
+struct ZipArchiveEntry {
+    entry_name: String,
+    compression_method: u16,
+    expected_crc32: u32,
+    compressed_size: usize,
+    uncompressed_size: usize,
+    local_header_offset: usize,
+}
+
+/// A ZIP archive held in memory
+#[derive(Debug)]
+pub struct ZipArchive {
+    archive_bytes: Vec<u8>,
+    entries: Vec<ZipArchiveEntry>,
+}
+
+/// Reads a little-endian u16 at an offset
+fn read_u16_le(bytes: &[u8], offset: usize) -> Option<u16> {
+    bytes.get(offset..offset + 2).map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+}
+
+/// Reads a little-endian u32 at an offset
+fn read_u32_le(bytes: &[u8], offset: usize) -> Option<u32> {
+    bytes
+        .get(offset..offset + 4)
+        .map(|quad| u32::from_le_bytes([quad[0], quad[1], quad[2], quad[3]]))
+}
+
+/// Builds the CRC-32 (IEEE 802.3) lookup table at compile time
+const fn build_crc32_table() -> [u32; 256] {
+    let mut crc_table = [0u32; 256];
+    let mut table_index = 0;
+    while table_index < 256 {
+        let mut crc_value = table_index as u32;
+        let mut bit_index = 0;
+        while bit_index < 8 {
+            crc_value = if crc_value & 1 != 0 {
+                0xEDB8_8320 ^ (crc_value >> 1)
+            } else {
+                crc_value >> 1
+            };
+            bit_index += 1;
+        }
+        crc_table[table_index] = crc_value;
+        table_index += 1;
+    }
+    crc_table
+}
+
+const CRC32_TABLE: [u32; 256] = build_crc32_table();
+
+/// Calculates the CRC-32 checksum used by ZIP
+///
+/// # Arguments
+/// * `data_bytes` - Uncompressed entry contents
+///
+/// # Returns
+/// * `u32` - The checksum
+pub fn calculate_crc32(data_bytes: &[u8]) -> u32 {
+    let mut crc_value = 0xFFFF_FFFFu32;
+    for &data_byte in data_bytes {
+        crc_value = CRC32_TABLE[((crc_value ^ data_byte as u32) & 0xFF) as usize] ^ (crc_value >> 8);
+    }
+    !crc_value
+}
+
+/// Creates the error for a sheet over `SPREADSHEET_MAX_CELL_COUNT` cells
+fn create_sheet_too_large_error() -> RowsAndColumnsError {
+    create_csv_processing_error(
+        &format!(
+            "Sheet is too large to import (more than {} cells)",
+            SPREADSHEET_MAX_CELL_COUNT
+        ),
+        None,
+        None,
+    )
+}
+
+/// Takes `cell_count` cells from what is left of the sheet's cell budget
+///
+/// # Returns
+/// * `bool` - false (budget unchanged) if that would go past the budget
+fn take_from_cell_budget(cell_budget_left: &mut usize, cell_count: usize) -> bool {
+    match cell_budget_left.checked_sub(cell_count) {
+        Some(budget_left) => {
+            *cell_budget_left = budget_left;
+            true
+        }
+        None => false,
+    }
+}
+
+/// Creates the error used for malformed archive or sheet content
+fn create_spreadsheet_format_error(problem_description: &str, part_name: &str) -> RowsAndColumnsError {
+    create_csv_processing_error(
+        &format!("Unreadable spreadsheet: {}", problem_description),
+        None,
+        Some(part_name.to_string()),
+    )
+}
+
+impl ZipArchive {
+    /// Reads a ZIP archive's central directory from bytes
+    ///
+    /// # Arguments
+    /// * `archive_bytes` - Entire archive contents
+    ///
+    /// # Returns
+    /// * `RowsAndColumnsResult<ZipArchive>` - Archive with its entry list
+    ///
+    /// # Errors
+    /// * `RowsAndColumnsError::CsvProcessingError` - If the bytes are not a valid ZIP archive
+    /// * `RowsAndColumnsError::ConfigurationError` - If the archive uses ZIP64, encryption or several disks
+    pub fn from_bytes(archive_bytes: Vec<u8>) -> RowsAndColumnsResult<ZipArchive> {
+        let not_zip_error = || create_spreadsheet_format_error("not a ZIP archive", "(archive)");
+
+        // Step 1: Find the end-of-central-directory record (it may be followed by a comment)
+        if archive_bytes.len() < ZIP_END_OF_CENTRAL_DIRECTORY_SIZE {
+            return Err(not_zip_error());
+        }
+        let last_possible_offset = archive_bytes.len() - ZIP_END_OF_CENTRAL_DIRECTORY_SIZE;
+        let first_possible_offset = last_possible_offset.saturating_sub(u16::MAX as usize);
+        let end_record_offset = (first_possible_offset..=last_possible_offset)
+            .rev()
+            .find(|&offset| read_u32_le(&archive_bytes, offset) == Some(ZIP_END_OF_CENTRAL_DIRECTORY_SIGNATURE))
+            .ok_or_else(not_zip_error)?;
+
+        let disk_number = read_u16_le(&archive_bytes, end_record_offset + 4).ok_or_else(not_zip_error)?;
+        let entry_count = read_u16_le(&archive_bytes, end_record_offset + 10).ok_or_else(not_zip_error)?;
+        let directory_offset = read_u32_le(&archive_bytes, end_record_offset + 16).ok_or_else(not_zip_error)?;
+
+        if disk_number != 0 {
+            return Err(create_configuration_error("Multi-disk ZIP archives are not supported"));
+        }
+        if entry_count == u16::MAX || directory_offset == u32::MAX {
+            return Err(create_configuration_error("ZIP64 archives are not supported"));
+        }
+
+        // Step 2: Walk the central directory
+        let mut entries = Vec::with_capacity(entry_count as usize);
+        let mut record_offset = directory_offset as usize;
+
+        for _ in 0..entry_count {
+            if read_u32_le(&archive_bytes, record_offset) != Some(ZIP_CENTRAL_DIRECTORY_SIGNATURE) {
+                return Err(create_spreadsheet_format_error("damaged ZIP central directory", "(archive)"));
+            }
+
+            let field_u16 = |relative_offset: usize| {
+                read_u16_le(&archive_bytes, record_offset + relative_offset).ok_or_else(not_zip_error)
+            };
+            let field_u32 = |relative_offset: usize| {
+                read_u32_le(&archive_bytes, record_offset + relative_offset).ok_or_else(not_zip_error)
+            };
+
+            let general_purpose_flags = field_u16(8)?;
+            let compression_method = field_u16(10)?;
+            let expected_crc32 = field_u32(16)?;
+            let compressed_size = field_u32(20)?;
+            let uncompressed_size = field_u32(24)?;
+            let name_length = field_u16(28)? as usize;
+            let extra_length = field_u16(30)? as usize;
+            let comment_length = field_u16(32)? as usize;
+            let local_header_offset = field_u32(42)?;
+
+            let name_start = record_offset + ZIP_CENTRAL_DIRECTORY_HEADER_SIZE;
+            let name_bytes = archive_bytes
+                .get(name_start..name_start + name_length)
+                .ok_or_else(not_zip_error)?;
+            let entry_name = String::from_utf8_lossy(name_bytes).to_string();
+
+            if general_purpose_flags & 1 != 0 {
+                return Err(create_configuration_error(&format!(
+                    "Encrypted ZIP entries are not supported: {}",
+                    entry_name
+                )));
+            }
+            if compressed_size == u32::MAX || uncompressed_size == u32::MAX || local_header_offset == u32::MAX {
+                return Err(create_configuration_error("ZIP64 archives are not supported"));
+            }
+
+            entries.push(ZipArchiveEntry {
+                entry_name,
+                compression_method,
+                expected_crc32,
+                compressed_size: compressed_size as usize,
+                uncompressed_size: uncompressed_size as usize,
+                local_header_offset: local_header_offset as usize,
+            });
+
+            record_offset = name_start + name_length + extra_length + comment_length;
+        }
+
+        Ok(ZipArchive {
+            archive_bytes,
+            entries,
+        })
+    }
+
+    /// Checks whether the archive contains an entry
+    ///
+    /// # Arguments
+    /// * `entry_name` - Path inside the archive, e.g. `xl/workbook.xml`
+    ///
+    /// # Returns
+    /// * `bool` - True if the entry exists
+    pub fn contains_entry(&self, entry_name: &str) -> bool {
+        self.entries.iter().any(|entry| entry.entry_name == entry_name)
+    }
+
+    /// Reads and decompresses one entry, verifying its CRC-32
+    ///
+    /// # Arguments
+    /// * `entry_name` - Path inside the archive
+    ///
+    /// # Returns
+    /// * `RowsAndColumnsResult<Vec<u8>>` - Uncompressed contents
+    ///
+    /// # Errors
+    /// * `RowsAndColumnsError::CsvProcessingError` - If the entry is missing, damaged or fails its checksum
+    /// * `RowsAndColumnsError::ConfigurationError` - If the compression method is unsupported
+    pub fn read_entry(&self, entry_name: &str) -> RowsAndColumnsResult<Vec<u8>> {
+        let entry = self.entries
+            .iter()
+            .find(|entry| entry.entry_name == entry_name)
+            .ok_or_else(|| create_spreadsheet_format_error("missing archive part", entry_name))?;
+        let damaged_error = || create_spreadsheet_format_error("damaged ZIP entry", entry_name);
+
+        // Step 1: Skip the local header to the entry data
+        let header_offset = entry.local_header_offset;
+        if read_u32_le(&self.archive_bytes, header_offset) != Some(ZIP_LOCAL_FILE_HEADER_SIGNATURE) {
+            return Err(damaged_error());
+        }
+        let name_length = read_u16_le(&self.archive_bytes, header_offset + 26).ok_or_else(damaged_error)? as usize;
+        let extra_length = read_u16_le(&self.archive_bytes, header_offset + 28).ok_or_else(damaged_error)? as usize;
+        let data_start = header_offset + ZIP_LOCAL_FILE_HEADER_SIZE + name_length + extra_length;
+        let compressed_bytes = self.archive_bytes
+            .get(data_start..data_start + entry.compressed_size)
+            .ok_or_else(damaged_error)?;
+
+        // Step 2: Decompress
+        let entry_bytes = match entry.compression_method {
+            ZIP_METHOD_STORED => compressed_bytes.to_vec(),
+            ZIP_METHOD_DEFLATE => inflate_deflate_stream(compressed_bytes, entry.uncompressed_size)
+                .map_err(|inflate_problem| create_spreadsheet_format_error(inflate_problem, entry_name))?,
+            other_method => {
+                return Err(create_configuration_error(&format!(
+                    "ZIP compression method {} is not supported ({})",
+                    other_method, entry_name
+                )));
+            }
+        };
+
+        // Step 3: Verify size and checksum
+        if entry_bytes.len() != entry.uncompressed_size || calculate_crc32(&entry_bytes) != entry.expected_crc32 {
+            return Err(create_spreadsheet_format_error("ZIP entry failed its checksum", entry_name));
+        }
+
+        Ok(entry_bytes)
+    }
+
+    /// Reads an entry as UTF-8 text
+    ///
+    /// # Arguments
+    /// * `entry_name` - Path inside the archive
+    ///
+    /// # Returns
+    /// * `RowsAndColumnsResult<String>` - Entry text (invalid UTF-8 is replaced)
+    ///
+    /// # Errors
+    /// * Same as `read_entry`
+    pub fn read_entry_text(&self, entry_name: &str) -> RowsAndColumnsResult<String> {
+        let entry_bytes = self.read_entry(entry_name)?;
+        Ok(String::from_utf8(entry_bytes)
+            .unwrap_or_else(|utf8_error| String::from_utf8_lossy(utf8_error.as_bytes()).to_string()))
+    }
+}
+
+// ============================================================================
+// INFLATE (RFC 1951)
+// ============================================================================
+
+/// Base lengths for length codes 257..285
+const INFLATE_LENGTH_BASE: [u16; 29] = [
+    3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31,
+    35, 43, 51, 59, 67, 83, 99, 115, 131, 163, 195, 227, 258,
+];
+
+/// Extra bits for length codes 257..285
+const INFLATE_LENGTH_EXTRA_BITS: [u8; 29] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2,
+    3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0,
+];
+
+/// Base distances for distance codes 0..29
+const INFLATE_DISTANCE_BASE: [u16; 30] = [
+    1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193,
+    257, 385, 513, 769, 1025, 1537, 2049, 3073, 4097, 6145, 8193, 12289, 16385, 24577,
+];
+
+/// Extra bits for distance codes 0..29
+const INFLATE_DISTANCE_EXTRA_BITS: [u8; 30] = [
+    0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6,
+    7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13, 13,
+];
+
+/// Order in which code length code lengths are stored in dynamic blocks
+const INFLATE_CODE_LENGTH_ORDER: [usize; 19] = [
+    16, 17, 18, 0, 8, 7, 9, 6, 10, 5, 11, 4, 12, 3, 13, 2, 14, 1, 15,
+];
+
+/// Longest Huffman code allowed by deflate
+const INFLATE_MAX_CODE_BITS: usize = 15;
+
+/// Largest decompressed size accepted for one archive part (256 MiB)
+///
+/// The sizes in a ZIP directory come from the file itself, so a damaged or
+/// hostile workbook could otherwise claim, or expand to, any size.
+const INFLATE_MAX_OUTPUT_BYTES: usize = 256 * 1024 * 1024;
+
+/// Most output space reserved up front before any data is decoded (16 MiB)
+const INFLATE_MAX_PREALLOCATED_BYTES: usize = 16 * 1024 * 1024;
+
+/// Reads bits least-significant first from a deflate stream
+struct InflateBitReader<'a> {
+    input_bytes: &'a [u8],
+    byte_position: usize,
+    bit_buffer: u32,
+    bits_in_buffer: u32,
+}
+
+impl<'a> InflateBitReader<'a> {
+    fn new(input_bytes: &'a [u8]) -> Self {
+        InflateBitReader {
+            input_bytes,
+            byte_position: 0,
+            bit_buffer: 0,
+            bits_in_buffer: 0,
+        }
+    }
+
+    /// Reads `bit_count` bits (at most 16) as an integer
+    fn read_bits(&mut self, bit_count: u32) -> Result<u32, &'static str> {
+        while self.bits_in_buffer < bit_count {
+            let next_byte = *self.input_bytes
+                .get(self.byte_position)
+                .ok_or("compressed data ends early")?;
+            self.byte_position += 1;
+            self.bit_buffer |= (next_byte as u32) << self.bits_in_buffer;
+            self.bits_in_buffer += 8;
+        }
+
+        let bit_value = self.bit_buffer & ((1u32 << bit_count) - 1);
+        self.bit_buffer >>= bit_count;
+        self.bits_in_buffer -= bit_count;
+        Ok(bit_value)
+    }
+
+    /// Drops the rest of the current byte (before stored blocks)
+    fn align_to_byte(&mut self) {
+        self.bit_buffer = 0;
+        self.bits_in_buffer = 0;
+    }
+}
+
+/// Canonical Huffman decoding table
+struct InflateHuffmanTable {
+    /// Number of codes of each bit length
+    code_length_counts: [u16; INFLATE_MAX_CODE_BITS + 1],
+    /// Symbols ordered by code length, then by symbol value
+    sorted_symbols: Vec<u16>,
+}
+
+impl InflateHuffmanTable {
+    /// Builds a decoding table from per-symbol code lengths (0 = unused)
+    fn from_code_lengths(code_lengths: &[u8]) -> Result<Self, &'static str> {
+        let mut code_length_counts = [0u16; INFLATE_MAX_CODE_BITS + 1];
+        for &code_length in code_lengths {
+            code_length_counts[code_length as usize] += 1;
+        }
+        code_length_counts[0] = 0;
+
+        // Reject over-subscribed code sets; incomplete sets are allowed
+        let mut codes_left: i32 = 1;
+        for &length_count in &code_length_counts[1..] {
+            codes_left = (codes_left << 1) - length_count as i32;
+            if codes_left < 0 {
+                return Err("invalid Huffman code lengths");
+            }
+        }
+
+        let mut next_offsets = [0u16; INFLATE_MAX_CODE_BITS + 2];
+        for bit_length in 1..=INFLATE_MAX_CODE_BITS {
+            next_offsets[bit_length + 1] = next_offsets[bit_length] + code_length_counts[bit_length];
+        }
+
+        let mut sorted_symbols = vec![0u16; code_lengths.len()];
+        for (symbol, &code_length) in code_lengths.iter().enumerate() {
+            if code_length != 0 {
+                let slot = &mut next_offsets[code_length as usize];
+                sorted_symbols[*slot as usize] = symbol as u16;
+                *slot += 1;
+            }
+        }
+
+        Ok(InflateHuffmanTable {
+            code_length_counts,
+            sorted_symbols,
+        })
+    }
+
+    /// Decodes one symbol, reading one bit at a time
+    fn decode_symbol(&self, bit_reader: &mut InflateBitReader) -> Result<u16, &'static str> {
+        let mut code: i32 = 0;
+        let mut first_code: i32 = 0;
+        let mut symbol_index: i32 = 0;
+
+        for bit_length in 1..=INFLATE_MAX_CODE_BITS {
+            code |= bit_reader.read_bits(1)? as i32;
+            let length_count = self.code_length_counts[bit_length] as i32;
+            if code - first_code < length_count {
+                return Ok(self.sorted_symbols[(symbol_index + code - first_code) as usize]);
+            }
+            symbol_index += length_count;
+            first_code = (first_code + length_count) << 1;
+            code <<= 1;
+        }
+
+        Err("invalid Huffman code")
+    }
+}
+
+/// Decompresses a raw deflate stream (no zlib or gzip header)
+///
+/// Decoding stops with an error as soon as the output would grow past
+/// `expected_size`, and sizes above `INFLATE_MAX_OUTPUT_BYTES` are refused
+/// outright, so a small stream cannot expand without bound.
+///
+/// # Arguments
+/// * `compressed_bytes` - Raw deflate data
+/// * `expected_size` - Expected output size; the output may not exceed it
+///
+/// # Returns
+/// * `Result<Vec<u8>, &'static str>` - Decompressed bytes or a description of the problem
+pub fn inflate_deflate_stream(compressed_bytes: &[u8], expected_size: usize) -> Result<Vec<u8>, &'static str> {
+    if expected_size > INFLATE_MAX_OUTPUT_BYTES {
+        return Err("archive part is too large");
+    }
+
+    let mut bit_reader = InflateBitReader::new(compressed_bytes);
+    let mut output_bytes: Vec<u8> = Vec::with_capacity(expected_size.min(INFLATE_MAX_PREALLOCATED_BYTES));
+
+    loop {
+        let is_final_block = bit_reader.read_bits(1)? == 1;
+        let block_type = bit_reader.read_bits(2)?;
+
+        match block_type {
+            0 => {
+                // Stored block: byte-aligned LEN, NLEN, then raw bytes
+                bit_reader.align_to_byte();
+                let header_start = bit_reader.byte_position;
+                let block_length = read_u16_le(compressed_bytes, header_start).ok_or("stored block ends early")?;
+                let block_length_complement = read_u16_le(compressed_bytes, header_start + 2)
+                    .ok_or("stored block ends early")?;
+                if block_length != !block_length_complement {
+                    return Err("stored block length check failed");
+                }
+
+                let data_start = header_start + 4;
+                let block_bytes = compressed_bytes
+                    .get(data_start..data_start + block_length as usize)
+                    .ok_or("stored block ends early")?;
+                if output_bytes.len() + block_bytes.len() > expected_size {
+                    return Err("data larger than its recorded size");
+                }
+                output_bytes.extend_from_slice(block_bytes);
+                bit_reader.byte_position = data_start + block_length as usize;
+            }
+            1 => {
+                let (literal_table, distance_table) = build_fixed_huffman_tables()?;
+                inflate_huffman_block(
+                    &mut bit_reader,
+                    &literal_table,
+                    &distance_table,
+                    &mut output_bytes,
+                    expected_size,
+                )?;
+            }
+            2 => {
+                let (literal_table, distance_table) = read_dynamic_huffman_tables(&mut bit_reader)?;
+                inflate_huffman_block(
+                    &mut bit_reader,
+                    &literal_table,
+                    &distance_table,
+                    &mut output_bytes,
+                    expected_size,
+                )?;
+            }
+            _ => return Err("invalid deflate block type"),
+        }
+
+        if is_final_block {
+            return Ok(output_bytes);
+        }
+    }
+}
+
+/// Builds the fixed literal/length and distance tables (block type 1)
+fn build_fixed_huffman_tables() -> Result<(InflateHuffmanTable, InflateHuffmanTable), &'static str> {
+    let mut literal_lengths = [0u8; 288];
+    for (symbol, code_length) in literal_lengths.iter_mut().enumerate() {
+        *code_length = match symbol {
+            0..=143 => 8,
+            144..=255 => 9,
+            256..=279 => 7,
+            _ => 8,
+        };
+    }
+
+    Ok((
+        InflateHuffmanTable::from_code_lengths(&literal_lengths)?,
+        InflateHuffmanTable::from_code_lengths(&[5u8; 30])?,
+    ))
+}
+
+/// Reads the code length header of a dynamic block (block type 2)
+fn read_dynamic_huffman_tables(
+    bit_reader: &mut InflateBitReader,
+) -> Result<(InflateHuffmanTable, InflateHuffmanTable), &'static str> {
+    let literal_code_count = bit_reader.read_bits(5)? as usize + 257;
+    let distance_code_count = bit_reader.read_bits(5)? as usize + 1;
+    let code_length_code_count = bit_reader.read_bits(4)? as usize + 4;
+
+    if literal_code_count > 286 || distance_code_count > 30 {
+        return Err("too many Huffman codes");
+    }
+
+    // Step 1: Lengths of the code length alphabet
+    let mut code_length_lengths = [0u8; 19];
+    for &symbol in INFLATE_CODE_LENGTH_ORDER.iter().take(code_length_code_count) {
+        code_length_lengths[symbol] = bit_reader.read_bits(3)? as u8;
+    }
+    let code_length_table = InflateHuffmanTable::from_code_lengths(&code_length_lengths)?;
+
+    // Step 2: Literal/length and distance code lengths, run-length coded
+    let total_code_count = literal_code_count + distance_code_count;
+    let mut code_lengths: Vec<u8> = Vec::with_capacity(total_code_count);
+
+    while code_lengths.len() < total_code_count {
+        let symbol = code_length_table.decode_symbol(bit_reader)?;
+        let (repeated_length, repeat_count) = match symbol {
+            0..=15 => (symbol as u8, 1),
+            16 => {
+                let previous_length = *code_lengths.last().ok_or("repeat with no previous length")?;
+                (previous_length, 3 + bit_reader.read_bits(2)? as usize)
+            }
+            17 => (0, 3 + bit_reader.read_bits(3)? as usize),
+            18 => (0, 11 + bit_reader.read_bits(7)? as usize),
+            _ => return Err("invalid code length symbol"),
+        };
+
+        if code_lengths.len() + repeat_count > total_code_count {
+            return Err("code lengths overflow");
+        }
+        code_lengths.extend(std::iter::repeat_n(repeated_length, repeat_count));
+    }
+
+    if code_lengths[256] == 0 {
+        return Err("missing end-of-block code");
+    }
+
+    Ok((
+        InflateHuffmanTable::from_code_lengths(&code_lengths[..literal_code_count])?,
+        InflateHuffmanTable::from_code_lengths(&code_lengths[literal_code_count..])?,
+    ))
+}
+
+/// Decodes literals and back-references until the end-of-block symbol
+///
+/// Fails once the output would exceed `output_limit` bytes.
+fn inflate_huffman_block(
+    bit_reader: &mut InflateBitReader,
+    literal_table: &InflateHuffmanTable,
+    distance_table: &InflateHuffmanTable,
+    output_bytes: &mut Vec<u8>,
+    output_limit: usize,
+) -> Result<(), &'static str> {
+    loop {
+        let symbol = literal_table.decode_symbol(bit_reader)? as usize;
+
+        match symbol {
+            0..=255 => {
+                if output_bytes.len() >= output_limit {
+                    return Err("data larger than its recorded size");
+                }
+                output_bytes.push(symbol as u8);
+            }
+            256 => return Ok(()),
+            257..=285 => {
+                let length_index = symbol - 257;
+                let copy_length = INFLATE_LENGTH_BASE[length_index] as usize
+                    + bit_reader.read_bits(INFLATE_LENGTH_EXTRA_BITS[length_index] as u32)? as usize;
+
+                let distance_index = distance_table.decode_symbol(bit_reader)? as usize;
+                if distance_index >= INFLATE_DISTANCE_BASE.len() {
+                    return Err("invalid distance code");
+                }
+                let copy_distance = INFLATE_DISTANCE_BASE[distance_index] as usize
+                    + bit_reader.read_bits(INFLATE_DISTANCE_EXTRA_BITS[distance_index] as u32)? as usize;
+
+                if copy_distance > output_bytes.len() {
+                    return Err("back-reference before start of data");
+                }
+                if output_bytes.len() + copy_length > output_limit {
+                    return Err("data larger than its recorded size");
+                }
+
+                // Byte by byte, since the copy may overlap its own output
+                let copy_start = output_bytes.len() - copy_distance;
+                for copy_offset in 0..copy_length {
+                    let copied_byte = output_bytes[copy_start + copy_offset];
+                    output_bytes.push(copied_byte);
+                }
+            }
+            _ => return Err("invalid literal/length code"),
+        }
+    }
+}
+
+// ============================================================================
+// XML PULL PARSER
+// ============================================================================
+
+/// One event from the XML pull parser
+#[derive(Debug, Clone, PartialEq)]
+pub enum XmlEvent {
+    /// Opening tag; self-closing tags have no matching `EndElement`
+    StartElement {
+        element_name: String,
+        attributes: Vec<(String, String)>,
+        is_self_closing: bool,
+    },
+
+    /// Closing tag
+    EndElement { element_name: String },
+
+    /// Character data with entities decoded (CDATA included verbatim)
+    Text(String),
+}
+
+/// Minimal XML pull parser for spreadsheet parts
+///
+/// Handles elements, attributes, text, the five predefined entities, numeric
+/// character references and CDATA. Declarations, comments, processing
+/// instructions and DOCTYPE are skipped. No validation is performed.
+pub struct XmlPullParser<'a> {
+    xml_text: &'a str,
+    position: usize,
+    part_name: &'a str,
+}
+
+/// Returns an element or attribute name without its namespace prefix
+///
+/// # Arguments
+/// * `qualified_name` - e.g. `table:table-cell`
+///
+/// # Returns
+/// * `&str` - e.g. `table-cell`
+pub fn xml_local_name(qualified_name: &str) -> &str {
+    qualified_name.rsplit(':').next().unwrap_or(qualified_name)
+}
+
+/// Looks up an attribute by local name (namespace prefix ignored)
+///
+/// # Arguments
+/// * `attributes` - Attributes of a start element
+/// * `local_name` - Name without prefix, e.g. `value-type`
+///
+/// # Returns
+/// * `Option<&str>` - The first matching attribute value
+pub fn xml_attribute<'a>(attributes: &'a [(String, String)], local_name: &str) -> Option<&'a str> {
+    attributes
+        .iter()
+        .find(|(attribute_name, _)| xml_local_name(attribute_name) == local_name)
+        .map(|(_, attribute_value)| attribute_value.as_str())
+}
+
+/// Replaces XML entities and character references with their characters
+///
+/// Unknown entities are kept as written.
+///
+/// # Arguments
+/// * `raw_text` - Text or attribute value as it appears in the XML
+///
+/// # Returns
+/// * `String` - Decoded text
+pub fn decode_xml_entities(raw_text: &str) -> String {
+    if !raw_text.contains('&') {
+        return raw_text.to_string();
+    }
+
+    let mut decoded_text = String::with_capacity(raw_text.len());
+    let mut remaining_text = raw_text;
+
+    while let Some(ampersand_position) = remaining_text.find('&') {
+        decoded_text.push_str(&remaining_text[..ampersand_position]);
+        let entity_text = &remaining_text[ampersand_position..];
+
+        let decoded_entity = entity_text.find(';').and_then(|semicolon_position| {
+            let entity_name = &entity_text[1..semicolon_position];
+            let decoded_char = match entity_name {
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "amp" => Some('&'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                _ => entity_name
+                    .strip_prefix("#x")
+                    .or_else(|| entity_name.strip_prefix("#X"))
+                    .map(|hex_digits| u32::from_str_radix(hex_digits, 16))
+                    .or_else(|| entity_name.strip_prefix('#').map(|digits| digits.parse::<u32>()))
+                    .and_then(|parse_result| parse_result.ok())
+                    .and_then(char::from_u32),
+            };
+            decoded_char.map(|character| (character, semicolon_position + 1))
+        });
+
+        match decoded_entity {
+            Some((character, consumed_length)) => {
+                decoded_text.push(character);
+                remaining_text = &entity_text[consumed_length..];
+            }
+            None => {
+                decoded_text.push('&');
+                remaining_text = &entity_text[1..];
+            }
+        }
+    }
+
+    decoded_text.push_str(remaining_text);
+    decoded_text
+}
+
+impl<'a> XmlPullParser<'a> {
+    /// Creates a parser over XML text
+    ///
+    /// # Arguments
+    /// * `xml_text` - Complete XML document
+    /// * `part_name` - Archive part name, used in error messages
+    pub fn new(xml_text: &'a str, part_name: &'a str) -> Self {
+        XmlPullParser {
+            xml_text,
+            position: 0,
+            part_name,
+        }
+    }
+
+    /// Skips past the next occurrence of `terminator`
+    fn skip_past(&mut self, terminator: &str) -> RowsAndColumnsResult<()> {
+        match self.xml_text[self.position..].find(terminator) {
+            Some(relative_position) => {
+                self.position += relative_position + terminator.len();
+                Ok(())
+            }
+            None => Err(create_spreadsheet_format_error(
+                &format!("unterminated XML construct (expected '{}')", terminator),
+                self.part_name,
+            )),
+        }
+    }
+
+    /// Returns the next event, or None at the end of the document
+    ///
+    /// # Returns
+    /// * `RowsAndColumnsResult<Option<XmlEvent>>` - Next event
+    ///
+    /// # Errors
+    /// * `RowsAndColumnsError::CsvProcessingError` - If a tag is unterminated or malformed
+    pub fn next_event(&mut self) -> RowsAndColumnsResult<Option<XmlEvent>> {
+        loop {
+            let remaining_text = &self.xml_text[self.position..];
+            if remaining_text.is_empty() {
+                return Ok(None);
+            }
+
+            // Character data up to the next tag
+            if !remaining_text.starts_with('<') {
+                let text_length = remaining_text.find('<').unwrap_or(remaining_text.len());
+                self.position += text_length;
+                return Ok(Some(XmlEvent::Text(decode_xml_entities(&remaining_text[..text_length]))));
+            }
+
+            if remaining_text.starts_with("<?") {
+                self.skip_past("?>")?;
+            } else if remaining_text.starts_with("<!--") {
+                self.skip_past("-->")?;
+            } else if let Some(cdata_text) = remaining_text.strip_prefix("<![CDATA[") {
+                let cdata_length = cdata_text.find("]]>").ok_or_else(|| {
+                    create_spreadsheet_format_error("unterminated CDATA section", self.part_name)
+                })?;
+                self.position += "<![CDATA[".len() + cdata_length + "]]>".len();
+                return Ok(Some(XmlEvent::Text(cdata_text[..cdata_length].to_string())));
+            } else if remaining_text.starts_with("<!") {
+                self.skip_past(">")?;
+            } else if let Some(end_tag_text) = remaining_text.strip_prefix("</") {
+                let tag_length = end_tag_text.find('>').ok_or_else(|| {
+                    create_spreadsheet_format_error("unterminated end tag", self.part_name)
+                })?;
+                self.position += 2 + tag_length + 1;
+                return Ok(Some(XmlEvent::EndElement {
+                    element_name: end_tag_text[..tag_length].trim().to_string(),
+                }));
+            } else {
+                return self.parse_start_element().map(Some);
+            }
+        }
+    }
+
+    /// Parses `<name attr="value" ...>` or `<name ... />` at the current position
+    fn parse_start_element(&mut self) -> RowsAndColumnsResult<XmlEvent> {
+        let malformed_error = || create_spreadsheet_format_error("malformed start tag", self.part_name);
+        let tag_text = &self.xml_text[self.position + 1..];
+
+        let name_length = tag_text
+            .find(|character: char| character.is_whitespace() || character == '/' || character == '>')
+            .ok_or_else(malformed_error)?;
+        let element_name = tag_text[..name_length].to_string();
+        if element_name.is_empty() {
+            return Err(malformed_error());
+        }
+
+        let mut attributes = Vec::new();
+        let mut cursor = name_length;
+
+        loop {
+            let rest = &tag_text[cursor..];
+            let trimmed_rest = rest.trim_start();
+            cursor += rest.len() - trimmed_rest.len();
+
+            if trimmed_rest.starts_with("/>") {
+                self.position += 1 + cursor + 2;
+                return Ok(XmlEvent::StartElement {
+                    element_name,
+                    attributes,
+                    is_self_closing: true,
+                });
+            }
+            if trimmed_rest.starts_with('>') {
+                self.position += 1 + cursor + 1;
+                return Ok(XmlEvent::StartElement {
+                    element_name,
+                    attributes,
+                    is_self_closing: false,
+                });
+            }
+
+            // attribute_name = "value" (or 'value')
+            let equals_position = trimmed_rest.find('=').ok_or_else(malformed_error)?;
+            let attribute_name = trimmed_rest[..equals_position].trim().to_string();
+            let after_equals = trimmed_rest[equals_position + 1..].trim_start();
+            let quote_char = after_equals.chars().next().ok_or_else(malformed_error)?;
+            if quote_char != '"' && quote_char != '\'' {
+                return Err(malformed_error());
+            }
+            let value_length = after_equals[1..].find(quote_char).ok_or_else(malformed_error)?;
+            let attribute_value = decode_xml_entities(&after_equals[1..1 + value_length]);
+
+            attributes.push((attribute_name, attribute_value));
+            let consumed_length = trimmed_rest.len() - after_equals.len() + 1 + value_length + 1;
+            cursor += consumed_length;
+        }
+    }
+}
+
+// ============================================================================
+// WORKBOOKS
+// ============================================================================
+
+/// Spreadsheet container formats this module reads
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SpreadsheetFormat {
+    /// Office Open XML workbook (Excel)
+    Xlsx,
+    /// OpenDocument spreadsheet (LibreOffice / OpenOffice)
+    Ods,
+}
+
+/// Detects a spreadsheet format from the file extension
+///
+/// # Arguments
+/// * `file_path` - Path to check
+///
+/// # Returns
+/// * `Option<SpreadsheetFormat>` - The format, or None if not a supported spreadsheet
+pub fn detect_spreadsheet_format(file_path: &Path) -> Option<SpreadsheetFormat> {
+    let file_extension = file_path.extension()?.to_str()?.to_lowercase();
+    match file_extension.as_str() {
+        "xlsx" | "xlsm" => Some(SpreadsheetFormat::Xlsx),
+        "ods" => Some(SpreadsheetFormat::Ods),
+        _ => None,
+    }
+}
+
+/// An opened spreadsheet and the names of its sheets
+#[derive(Debug)]
+pub struct SpreadsheetWorkbook {
+    spreadsheet_format: SpreadsheetFormat,
+    zip_archive: ZipArchive,
+    /// Sheet names in workbook order
+    pub sheet_names: Vec<String>,
+    /// Archive part holding each sheet (XLSX only; ODS keeps all sheets in content.xml)
+    sheet_part_names: Vec<String>,
+}
+
+/// Location of the OpenDocument sheet data inside the archive
+const ODS_CONTENT_PART: &str = "content.xml";
+
+/// Locations of the XLSX workbook parts inside the archive
+const XLSX_WORKBOOK_PART: &str = "xl/workbook.xml";
+const XLSX_WORKBOOK_RELATIONSHIPS_PART: &str = "xl/_rels/workbook.xml.rels";
+const XLSX_SHARED_STRINGS_PART: &str = "xl/sharedStrings.xml";
+
+/// Columns in an XLSX worksheet (`A` through `XFD`)
+const XLSX_MAX_COLUMN_COUNT: usize = 16_384;
+
+/// Rows in an XLSX worksheet
+const XLSX_MAX_ROW_COUNT: usize = 1_048_576;
+
+/// Opens an `.xlsx` or `.ods` file and lists its sheets
+///
+/// # Arguments
+/// * `spreadsheet_path` - Path to the spreadsheet
+///
+/// # Returns
+/// * `RowsAndColumnsResult<SpreadsheetWorkbook>` - Workbook ready to read sheets from
+///
+/// # Errors
+/// * `RowsAndColumnsError::FileSystemError` - If the file cannot be read
+/// * `RowsAndColumnsError::ConfigurationError` - If the extension or archive features are unsupported
+/// * `RowsAndColumnsError::CsvProcessingError` - If the archive or XML is malformed
+pub fn open_spreadsheet_workbook(spreadsheet_path: &Path) -> RowsAndColumnsResult<SpreadsheetWorkbook> {
+    let spreadsheet_format = detect_spreadsheet_format(spreadsheet_path).ok_or_else(|| {
+        create_configuration_error(&format!(
+            "Not an .xlsx or .ods file: {}",
+            spreadsheet_path.display()
+        ))
+    })?;
+
+    let archive_bytes = std::fs::read(spreadsheet_path).map_err(|io_error| {
+        create_file_system_error(
+            &format!("Failed to read spreadsheet: {}", spreadsheet_path.display()),
+            io_error
+        )
+    })?;
+    let zip_archive = ZipArchive::from_bytes(archive_bytes)?;
+
+    let (sheet_names, sheet_part_names) = match spreadsheet_format {
+        SpreadsheetFormat::Xlsx => read_xlsx_sheet_list(&zip_archive)?,
+        SpreadsheetFormat::Ods => (read_ods_sheet_names(&zip_archive)?, Vec::new()),
+    };
+
+    if sheet_names.is_empty() {
+        return Err(create_spreadsheet_format_error("workbook has no sheets", "(workbook)"));
+    }
+
+    Ok(SpreadsheetWorkbook {
+        spreadsheet_format,
+        zip_archive,
+        sheet_names,
+        sheet_part_names,
+    })
+}
+
+impl SpreadsheetWorkbook {
+    /// Finds a sheet by name (case-insensitive) or 1-based number
+    ///
+    /// # Arguments
+    /// * `sheet_selector` - e.g. `Sales` or `2`
+    ///
+    /// # Returns
+    /// * `Option<usize>` - 0-based sheet index
+    pub fn find_sheet_index(&self, sheet_selector: &str) -> Option<usize> {
+        let wanted_sheet = sheet_selector.trim();
+
+        self.sheet_names
+            .iter()
+            .position(|sheet_name| sheet_name == wanted_sheet)
+            .or_else(|| {
+                self.sheet_names
+                    .iter()
+                    .position(|sheet_name| sheet_name.eq_ignore_ascii_case(wanted_sheet))
+            })
+            .or_else(|| {
+                wanted_sheet
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|&sheet_number| sheet_number >= 1 && sheet_number <= self.sheet_names.len())
+                    .map(|sheet_number| sheet_number - 1)
+            })
+    }
+
+    /// Reads all cell values of one sheet as rectangular rows
+    ///
+    /// Trailing empty rows are dropped and every row is padded to the width
+    /// of the widest row.
+    ///
+    /// # Arguments
+    /// * `sheet_index` - 0-based sheet index
+    ///
+    /// # Returns
+    /// * `RowsAndColumnsResult<Vec<Vec<String>>>` - Cell text by row and column
+    ///
+    /// # Errors
+    /// * `RowsAndColumnsError::ConfigurationError` - If the index is out of range
+    /// * `RowsAndColumnsError::CsvProcessingError` - If the sheet data is malformed
+    pub fn read_sheet_rows(&self, sheet_index: usize) -> RowsAndColumnsResult<Vec<Vec<String>>> {
+        if sheet_index >= self.sheet_names.len() {
+            return Err(create_configuration_error(&format!(
+                "Sheet number {} does not exist (workbook has {})",
+                sheet_index + 1,
+                self.sheet_names.len()
+            )));
+        }
+
+        let mut sheet_rows = match self.spreadsheet_format {
+            SpreadsheetFormat::Xlsx => {
+                let shared_strings = if self.zip_archive.contains_entry(XLSX_SHARED_STRINGS_PART) {
+                    read_xlsx_shared_strings(&self.zip_archive)?
+                } else {
+                    Vec::new()
+                };
+                read_xlsx_sheet_rows(&self.zip_archive, &self.sheet_part_names[sheet_index], &shared_strings)?
+            }
+            SpreadsheetFormat::Ods => read_ods_sheet_rows(&self.zip_archive, sheet_index)?,
+        };
+
+        // Make the table rectangular, ignoring trailing blank rows and cells
+        for sheet_row in sheet_rows.iter_mut() {
+            while sheet_row.last().is_some_and(|cell_text| cell_text.is_empty()) {
+                sheet_row.pop();
+            }
+        }
+        while sheet_rows.last().is_some_and(|sheet_row| sheet_row.is_empty()) {
+            sheet_rows.pop();
+        }
+
+        let column_count = sheet_rows.iter().map(Vec::len).max().unwrap_or(0);
+        if sheet_rows.len().checked_mul(column_count).is_none_or(|cell_count| cell_count > SPREADSHEET_MAX_CELL_COUNT) {
+            return Err(create_sheet_too_large_error());
+        }
+        for sheet_row in sheet_rows.iter_mut() {
+            sheet_row.resize(column_count, String::new());
+        }
+
+        Ok(sheet_rows)
+    }
+}
+
+/// Parses an XML part fully, calling `handle_event` for each event
+fn for_each_xml_event<F>(xml_text: &str, part_name: &str, mut handle_event: F) -> RowsAndColumnsResult<()>
+where
+    F: FnMut(XmlEvent) -> bool,
+{
+    let mut xml_parser = XmlPullParser::new(xml_text, part_name);
+    while let Some(xml_event) = xml_parser.next_event()? {
+        if !handle_event(xml_event) {
+            break;
+        }
+    }
+    Ok(())
+}
+
+/// Reads sheet names and their part paths from an XLSX workbook
+fn read_xlsx_sheet_list(zip_archive: &ZipArchive) -> RowsAndColumnsResult<(Vec<String>, Vec<String>)> {
+    // Step 1: Relationship id -> target part
+    let relationships_text = zip_archive.read_entry_text(XLSX_WORKBOOK_RELATIONSHIPS_PART)?;
+    let mut relationship_targets: HashMap<String, String> = HashMap::new();
+    for_each_xml_event(&relationships_text, XLSX_WORKBOOK_RELATIONSHIPS_PART, |xml_event| {
+        if let XmlEvent::StartElement { element_name, attributes, .. } = xml_event
+            && xml_local_name(&element_name) == "Relationship"
+            && let (Some(relationship_id), Some(target_path)) =
+                (xml_attribute(&attributes, "Id"), xml_attribute(&attributes, "Target"))
+        {
+            let part_name = match target_path.strip_prefix('/') {
+                Some(absolute_path) => absolute_path.to_string(),
+                None => format!("xl/{}", target_path),
+            };
+            relationship_targets.insert(relationship_id.to_string(), part_name);
+        }
+        true
+    })?;
+
+    // Step 2: Sheets in workbook order
+    let workbook_text = zip_archive.read_entry_text(XLSX_WORKBOOK_PART)?;
+    let mut sheet_names = Vec::new();
+    let mut sheet_part_names = Vec::new();
+    let mut missing_relationship: Option<String> = None;
+
+    for_each_xml_event(&workbook_text, XLSX_WORKBOOK_PART, |xml_event| {
+        if let XmlEvent::StartElement { element_name, attributes, .. } = xml_event
+            && xml_local_name(&element_name) == "sheet"
+        {
+            let sheet_name = xml_attribute(&attributes, "name").unwrap_or("").to_string();
+            match xml_attribute(&attributes, "id").and_then(|relationship_id| relationship_targets.get(relationship_id)) {
+                Some(part_name) => {
+                    sheet_names.push(sheet_name);
+                    sheet_part_names.push(part_name.clone());
+                }
+                None => {
+                    missing_relationship = Some(sheet_name);
+                    return false;
+                }
+            }
+        }
+        true
+    })?;
+
+    if let Some(sheet_name) = missing_relationship {
+        return Err(create_spreadsheet_format_error(
+            &format!("sheet '{}' has no data part", sheet_name),
+            XLSX_WORKBOOK_PART,
+        ));
+    }
+
+    Ok((sheet_names, sheet_part_names))
+}
+
+/// Reads the XLSX shared string table
+///
+/// Rich text runs are concatenated; phonetic hints (`rPh`) are skipped.
+fn read_xlsx_shared_strings(zip_archive: &ZipArchive) -> RowsAndColumnsResult<Vec<String>> {
+    let shared_strings_text = zip_archive.read_entry_text(XLSX_SHARED_STRINGS_PART)?;
+    let mut shared_strings = Vec::new();
+    let mut current_string = String::new();
+    let mut inside_text = false;
+    let mut phonetic_depth = 0usize;
+
+    for_each_xml_event(&shared_strings_text, XLSX_SHARED_STRINGS_PART, |xml_event| {
+        match xml_event {
+            XmlEvent::StartElement { element_name, is_self_closing, .. } => {
+                match xml_local_name(&element_name) {
+                    "si" if !is_self_closing => current_string.clear(),
+                    "si" => shared_strings.push(String::new()),
+                    "rPh" if !is_self_closing => phonetic_depth += 1,
+                    "t" if !is_self_closing => inside_text = phonetic_depth == 0,
+                    _ => {}
+                }
+            }
+            XmlEvent::EndElement { element_name } => {
+                match xml_local_name(&element_name) {
+                    "si" => shared_strings.push(std::mem::take(&mut current_string)),
+                    "rPh" => phonetic_depth = phonetic_depth.saturating_sub(1),
+                    "t" => inside_text = false,
+                    _ => {}
+                }
+            }
+            XmlEvent::Text(text) => {
+                if inside_text {
+                    current_string.push_str(&text);
+                }
+            }
+        }
+        true
+    })?;
+
+    Ok(shared_strings)
+}
+
+/// Converts an XLSX cell reference like `AB12` to a 0-based column index
+///
+/// # Returns
+/// * `Option<usize>` - The column index, or `None` when the reference has no
+///   column letters or names a column past `XFD`
+fn xlsx_column_index_from_reference(cell_reference: &str) -> Option<usize> {
+    let column_letters: Vec<char> = cell_reference
+        .chars()
+        .take_while(|character| character.is_ascii_alphabetic())
+        .collect();
+    if column_letters.is_empty() {
+        return None;
+    }
+
+    let mut column_number = 0usize;
+    for letter in column_letters {
+        let letter_value = letter.to_ascii_uppercase() as usize - 'A' as usize + 1;
+        column_number = column_number.checked_mul(26)?.checked_add(letter_value)?;
+        if column_number > XLSX_MAX_COLUMN_COUNT {
+            return None;
+        }
+    }
+    Some(column_number - 1)
+}
+
+/// Reads cell values from one XLSX worksheet part
+///
+/// Rows skipped by the `r` attribute of `<row>` come back as blank rows, as
+/// they do for ODS, so row positions match the spreadsheet.
+fn read_xlsx_sheet_rows(
+    zip_archive: &ZipArchive,
+    sheet_part_name: &str,
+    shared_strings: &[String],
+) -> RowsAndColumnsResult<Vec<Vec<String>>> {
+    let sheet_text = zip_archive.read_entry_text(sheet_part_name)?;
+
+    let mut sheet_rows: Vec<Vec<String>> = Vec::new();
+    let mut current_row: Vec<String> = Vec::new();
+    let mut next_column_index = 0usize;
+    let mut cell_column_index = 0usize;
+    let mut cell_type = String::new();
+    let mut cell_text = String::new();
+    let mut capturing_text = false;
+    let mut bad_shared_string: Option<String> = None;
+    let mut bad_cell_reference: Option<String> = None;
+
+    for_each_xml_event(&sheet_text, sheet_part_name, |xml_event| {
+        match xml_event {
+            XmlEvent::StartElement { element_name, attributes, is_self_closing } => {
+                match xml_local_name(&element_name) {
+                    "row" => {
+                        current_row.clear();
+                        next_column_index = 0;
+
+                        // Pad rows the sheet leaves out before this one
+                        if let Some(row_reference) = xml_attribute(&attributes, "r") {
+                            match row_reference.trim().parse::<usize>() {
+                                Ok(row_number) if (1..=XLSX_MAX_ROW_COUNT).contains(&row_number) => {
+                                    if sheet_rows.len() < row_number - 1 {
+                                        sheet_rows.resize(row_number - 1, Vec::new());
+                                    }
+                                }
+                                _ => {
+                                    bad_cell_reference = Some(row_reference.to_string());
+                                    return false;
+                                }
+                            }
+                        }
+
+                        if is_self_closing {
+                            sheet_rows.push(Vec::new());
+                        }
+                    }
+                    "c" => {
+                        let column_index = match xml_attribute(&attributes, "r") {
+                            Some(cell_reference) => xlsx_column_index_from_reference(cell_reference),
+                            None => Some(next_column_index).filter(|&index| index < XLSX_MAX_COLUMN_COUNT),
+                        };
+                        cell_column_index = match column_index {
+                            Some(column_index) => column_index,
+                            None => {
+                                bad_cell_reference = Some(
+                                    xml_attribute(&attributes, "r").unwrap_or("(past XFD)").to_string()
+                                );
+                                return false;
+                            }
+                        };
+                        cell_type = xml_attribute(&attributes, "t").unwrap_or("n").to_string();
+                        cell_text.clear();
+                        next_column_index = cell_column_index + 1;
+                    }
+                    // <v> holds the value; <t> holds inline string text
+                    "v" | "t" => capturing_text = !is_self_closing,
+                    _ => {}
+                }
+            }
+            XmlEvent::EndElement { element_name } => {
+                match xml_local_name(&element_name) {
+                    "v" | "t" => capturing_text = false,
+                    "c" => {
+                        let cell_value = match cell_type.as_str() {
+                            "s" => match cell_text.trim().parse::<usize>().ok().and_then(|index| shared_strings.get(index)) {
+                                Some(shared_string) => shared_string.clone(),
+                                None => {
+                                    bad_shared_string = Some(cell_text.clone());
+                                    return false;
+                                }
+                            },
+                            "b" => match cell_text.trim() {
+                                "1" => "true".to_string(),
+                                "0" => "false".to_string(),
+                                other_text => other_text.to_string(),
+                            },
+                            _ => std::mem::take(&mut cell_text),
+                        };
+
+                        if current_row.len() <= cell_column_index {
+                            current_row.resize(cell_column_index + 1, String::new());
+                        }
+                        current_row[cell_column_index] = cell_value;
+                    }
+                    "row" => sheet_rows.push(std::mem::take(&mut current_row)),
+                    _ => {}
+                }
+            }
+            XmlEvent::Text(text) => {
+                if capturing_text {
+                    cell_text.push_str(&text);
+                }
+            }
+        }
+        true
+    })?;
+
+    if let Some(bad_index) = bad_shared_string {
+        return Err(create_spreadsheet_format_error(
+            &format!("shared string index '{}' out of range", bad_index),
+            sheet_part_name,
+        ));
+    }
+    if let Some(bad_reference) = bad_cell_reference {
+        return Err(create_spreadsheet_format_error(
+            &format!("cell reference '{}' out of range", bad_reference),
+            sheet_part_name,
+        ));
+    }
+
+    Ok(sheet_rows)
+}
+
+/// Reads sheet (table) names from an ODS content part
+fn read_ods_sheet_names(zip_archive: &ZipArchive) -> RowsAndColumnsResult<Vec<String>> {
+    let content_text = zip_archive.read_entry_text(ODS_CONTENT_PART)?;
+    let mut sheet_names = Vec::new();
+
+    for_each_xml_event(&content_text, ODS_CONTENT_PART, |xml_event| {
+        if let XmlEvent::StartElement { element_name, attributes, .. } = xml_event
+            && element_name == "table:table"
+        {
+            sheet_names.push(xml_attribute(&attributes, "name").unwrap_or("").to_string());
+        }
+        true
+    })?;
+
+    Ok(sheet_names)
+}
+
+/// Reads a repeat count attribute (at least 1; a count too big for `usize`
+/// reads as `usize::MAX`, which the cell budget then refuses)
+fn read_ods_repeat_count(attributes: &[(String, String)], attribute_name: &str) -> usize {
+    xml_attribute(attributes, attribute_name)
+        .and_then(|repeat_text| {
+            repeat_text.parse::<usize>().ok().or_else(|| {
+                (!repeat_text.is_empty() && repeat_text.bytes().all(|byte| byte.is_ascii_digit()))
+                    .then_some(usize::MAX)
+            })
+        })
+        .unwrap_or(1)
+        .max(1)
+}
+
+/// Returns a cell's typed value from its attributes, if it has one
+///
+/// Strings (and untyped cells) return None; their value is the paragraph text.
+fn read_ods_typed_cell_value(attributes: &[(String, String)]) -> Option<String> {
+    let value_attribute = match xml_attribute(attributes, "value-type")? {
+        "float" | "percentage" | "currency" => "value",
+        "date" => "date-value",
+        "time" => "time-value",
+        "boolean" => "boolean-value",
+        _ => return None,
+    };
+    xml_attribute(attributes, value_attribute).map(str::to_string)
+}
+
+/// Reads cell values from one ODS table
+///
+/// Repeated blank cells and rows are only expanded when something non-blank
+/// follows them, so the huge trailing repeats ODS writers use cost nothing.
+/// Everything that is expanded counts against `SPREADSHEET_MAX_CELL_COUNT`
+/// (an empty row as one cell), and going past it is an error.
+fn read_ods_sheet_rows(zip_archive: &ZipArchive, sheet_index: usize) -> RowsAndColumnsResult<Vec<Vec<String>>> {
+    let content_text = zip_archive.read_entry_text(ODS_CONTENT_PART)?;
+
+    let mut sheet_rows: Vec<Vec<String>> = Vec::new();
+    let mut tables_seen = 0usize;
+    let mut inside_target_table = false;
+
+    let mut current_row: Vec<String> = Vec::new();
+    let mut row_repeat_count = 1usize;
+    let mut pending_blank_rows = 0usize;
+    let mut pending_blank_cells = 0usize;
+
+    let mut inside_cell = false;
+    let mut cell_repeat_count = 1usize;
+    let mut cell_typed_value: Option<String> = None;
+    let mut cell_text = String::new();
+    let mut cell_paragraph_count = 0usize;
+
+    let mut cell_budget_left = SPREADSHEET_MAX_CELL_COUNT;
+    let mut is_over_cell_budget = false;
+
+    // Appends one finished cell (possibly repeated) to the current row;
+    // false if that would go past the cell budget
+    let finish_cell = |current_row: &mut Vec<String>,
+                       pending_blank_cells: &mut usize,
+                       cell_budget_left: &mut usize,
+                       cell_value: String,
+                       repeat_count: usize| {
+        if cell_value.is_empty() {
+            *pending_blank_cells = pending_blank_cells.saturating_add(repeat_count);
+            return true;
+        }
+        if !take_from_cell_budget(cell_budget_left, pending_blank_cells.saturating_add(repeat_count)) {
+            return false;
+        }
+        current_row.extend(std::iter::repeat_n(String::new(), *pending_blank_cells));
+        *pending_blank_cells = 0;
+        current_row.extend(std::iter::repeat_n(cell_value, repeat_count));
+        true
+    };
+
+    for_each_xml_event(&content_text, ODS_CONTENT_PART, |xml_event| {
+        match xml_event {
+            XmlEvent::StartElement { element_name, attributes, is_self_closing } => {
+                if element_name == "table:table" {
+                    inside_target_table = tables_seen == sheet_index;
+                    tables_seen += 1;
+                    return true;
+                }
+                if !inside_target_table {
+                    return true;
+                }
+
+                match element_name.as_str() {
+                    "table:table-row" => {
+                        current_row.clear();
+                        pending_blank_cells = 0;
+                        row_repeat_count = read_ods_repeat_count(&attributes, "number-rows-repeated");
+                        if is_self_closing {
+                            pending_blank_rows = pending_blank_rows.saturating_add(row_repeat_count);
+                        }
+                    }
+                    "table:table-cell" | "table:covered-table-cell" => {
+                        cell_repeat_count = read_ods_repeat_count(&attributes, "number-columns-repeated");
+                        cell_typed_value = read_ods_typed_cell_value(&attributes);
+                        cell_text.clear();
+                        cell_paragraph_count = 0;
+
+                        if is_self_closing {
+                            let cell_value = cell_typed_value.take().unwrap_or_default();
+                            let fits_budget = finish_cell(
+                                &mut current_row,
+                                &mut pending_blank_cells,
+                                &mut cell_budget_left,
+                                cell_value,
+                                cell_repeat_count,
+                            );
+                            if !fits_budget {
+                                is_over_cell_budget = true;
+                                return false;
+                            }
+                        } else {
+                            inside_cell = true;
+                        }
+                    }
+                    "text:p" | "text:h" if inside_cell => {
+                        if cell_paragraph_count > 0 {
+                            cell_text.push('\n');
+                        }
+                        cell_paragraph_count += 1;
+                    }
+                    "text:s" if inside_cell => {
+                        let space_count = xml_attribute(&attributes, "c")
+                            .and_then(|count_text| count_text.parse::<usize>().ok())
+                            .unwrap_or(1)
+                            .min(ODS_MAX_SPACE_RUN);
+                        cell_text.extend(std::iter::repeat_n(' ', space_count));
+                    }
+                    "text:tab" if inside_cell => cell_text.push('\t'),
+                    "text:line-break" if inside_cell => cell_text.push('\n'),
+                    _ => {}
+                }
+            }
+            XmlEvent::EndElement { element_name } => {
+                if !inside_target_table {
+                    return true;
+                }
+
+                match element_name.as_str() {
+                    "table:table" => return false,
+                    "table:table-cell" | "table:covered-table-cell" => {
+                        inside_cell = false;
+                        let cell_value = cell_typed_value.take().unwrap_or_else(|| std::mem::take(&mut cell_text));
+                        let fits_budget = finish_cell(
+                            &mut current_row,
+                            &mut pending_blank_cells,
+                            &mut cell_budget_left,
+                            cell_value,
+                            cell_repeat_count,
+                        );
+                        if !fits_budget {
+                            is_over_cell_budget = true;
+                            return false;
+                        }
+                    }
+                    "table:table-row" => {
+                        if current_row.is_empty() {
+                            pending_blank_rows = pending_blank_rows.saturating_add(row_repeat_count);
+                        } else {
+                            // The row's own cells are already counted once
+                            let repeated_cell_count = (row_repeat_count - 1).checked_mul(current_row.len());
+                            let expanded_cell_count =
+                                repeated_cell_count.and_then(|cell_count| cell_count.checked_add(pending_blank_rows));
+                            let fits_budget = expanded_cell_count
+                                .is_some_and(|cell_count| take_from_cell_budget(&mut cell_budget_left, cell_count));
+                            if !fits_budget {
+                                is_over_cell_budget = true;
+                                return false;
+                            }
+                            sheet_rows.extend(std::iter::repeat_n(Vec::new(), pending_blank_rows));
+                            pending_blank_rows = 0;
+                            sheet_rows.extend(std::iter::repeat_n(std::mem::take(&mut current_row), row_repeat_count));
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            XmlEvent::Text(text) => {
+                if inside_target_table && inside_cell && cell_paragraph_count > 0 {
+                    cell_text.push_str(&text);
+                }
+            }
+        }
+        true
+    })?;
+
+    if is_over_cell_budget {
+        return Err(create_sheet_too_large_error());
+    }
+    Ok(sheet_rows)
+}
+
+// ============================================================================
+// CSV OUTPUT
+// ============================================================================
+
+/// Determines the CSV path for an imported sheet
+///
+/// The CSV goes next to the spreadsheet as `{stem}_{sheet}.csv`, with
+/// characters that are awkward in file names replaced by `_`.
+///
+/// # Arguments
+/// * `spreadsheet_path` - Path to the `.xlsx` or `.ods` file
+/// * `sheet_name` - Name of the imported sheet
+///
+/// # Returns
+/// * `RowsAndColumnsResult<PathBuf>` - Path for the CSV file
+///
+/// # Errors
+/// * `RowsAndColumnsError::ConfigurationError` - If the spreadsheet name cannot be determined
+pub fn determine_imported_csv_path(spreadsheet_path: &Path, sheet_name: &str) -> RowsAndColumnsResult<PathBuf> {
+    let spreadsheet_directory = spreadsheet_path.parent()
+        .ok_or_else(|| create_configuration_error("Cannot determine spreadsheet directory"))?;
+    let spreadsheet_stem = spreadsheet_path.file_stem()
+        .and_then(|stem| stem.to_str())
+        .ok_or_else(|| create_configuration_error("Cannot determine spreadsheet filename"))?;
+
+    let safe_sheet_name: String = sheet_name
+        .trim()
+        .chars()
+        .map(|character| if character.is_alphanumeric() || character == '-' { character } else { '_' })
+        .collect();
+
+    let csv_file_name = if safe_sheet_name.is_empty() {
+        format!("{}.csv", spreadsheet_stem)
+    } else {
+        format!("{}_{}.csv", spreadsheet_stem, safe_sheet_name)
+    };
+
+    Ok(spreadsheet_directory.join(csv_file_name))
+}
+
+/// Moves an existing file into the `archive/` directory beside it
+///
+/// The archived copy gets a timestamped name, as when ff replaces a file.
+///
+/// # Arguments
+/// * `file_path` - The file about to be replaced
+///
+/// # Returns
+/// * `RowsAndColumnsResult<PathBuf>` - Where the file was archived
+///
+/// # Errors
+/// * `RowsAndColumnsError::FileSystemError` - If the archive directory cannot be
+///   prepared, the archive name is taken, or the move fails
+fn archive_existing_file(file_path: &Path) -> RowsAndColumnsResult<PathBuf> {
+    let archive_error = |problem: String| {
+        create_file_system_error(
+            &format!("Failed to archive existing file: {}", file_path.display()),
+            std::io::Error::other(problem),
+        )
+    };
+
+    let (parent_directory, file_name) = match (file_path.parent(), file_path.file_name()) {
+        (Some(parent_directory), Some(file_name)) => (parent_directory.to_path_buf(), file_name),
+        _ => return Err(archive_error("no parent directory or file name".to_string())),
+    };
+    let archive_directory = ensure_archive_directory_exists(&parent_directory)
+        .map_err(|archive_problem| archive_error(archive_problem.to_string()))?;
+
+    let timestamp = createarchive_timestamp_with_precision(std::time::SystemTime::now(), true);
+    let archive_path = archive_directory.join(generate_archive_filename(file_name, &timestamp));
+    if archive_path.exists() {
+        return Err(archive_error(format!("{} already exists", archive_path.display())));
+    }
+
+    std::fs::rename(file_path, &archive_path)
+        .map_err(|io_error| archive_error(io_error.to_string()))?;
+    Ok(archive_path)
+}
+
+/// Writes rows as a comma-separated file
+///
+/// Fields are quoted when needed; line breaks inside a cell become spaces so
+/// each row is exactly one line. A file already at the destination is moved
+/// to `archive/` first rather than overwritten.
+///
+/// # Arguments
+/// * `sheet_rows` - Cell text by row and column
+/// * `csv_file_path` - Destination path
+///
+/// # Returns
+/// * `RowsAndColumnsResult<()>` - Success or write error
+///
+/// # Errors
+/// * `RowsAndColumnsError::FileSystemError` - If an existing file cannot be
+///   archived, or the new file cannot be created or written
+pub fn write_rows_as_csv_file(sheet_rows: &[Vec<String>], csv_file_path: &Path) -> RowsAndColumnsResult<()> {
+    let write_error = |io_error| {
+        create_file_system_error(
+            &format!("Failed to write CSV file: {}", csv_file_path.display()),
+            io_error
+        )
+    };
+
+    // Step 1: Keep any earlier file by archiving it
+    if csv_file_path.exists() {
+        archive_existing_file(csv_file_path)?;
+    }
+
+    // Step 2: Write rows; create_new never replaces a file that appeared since
+    let csv_file = File::options()
+        .write(true)
+        .create_new(true)
+        .open(csv_file_path)
+        .map_err(write_error)?;
+    let mut csv_writer = BufWriter::new(csv_file);
+
+    for sheet_row in sheet_rows {
+        let csv_fields: Vec<String> = sheet_row
+            .iter()
+            .map(|cell_text| format_delimited_field(&cell_text.replace(['\r', '\n'], " "), ','))
+            .collect();
+        writeln!(csv_writer, "{}", csv_fields.join(",")).map_err(write_error)?;
+    }
+
+    csv_writer.flush().map_err(write_error)
+}
+
+/// Imports one sheet of a workbook into a CSV file next to the spreadsheet
+///
+/// # Arguments
+/// * `spreadsheet_path` - Path to the `.xlsx` or `.ods` file
+/// * `spreadsheet_workbook` - The opened workbook
+/// * `sheet_index` - 0-based sheet index
+///
+/// # Returns
+/// * `RowsAndColumnsResult<PathBuf>` - Path to the written CSV file
+///
+/// # Errors
+/// * `RowsAndColumnsError::CsvProcessingError` - If the sheet is malformed or empty
+/// * `RowsAndColumnsError::FileSystemError` - If the CSV cannot be written
+pub fn import_spreadsheet_sheet_to_csv(
+    spreadsheet_path: &Path,
+    spreadsheet_workbook: &SpreadsheetWorkbook,
+    sheet_index: usize,
+) -> RowsAndColumnsResult<PathBuf> {
+    let sheet_rows = spreadsheet_workbook.read_sheet_rows(sheet_index)?;
+    let sheet_name = &spreadsheet_workbook.sheet_names[sheet_index];
+
+    if sheet_rows.is_empty() {
+        return Err(create_csv_processing_error(
+            &format!("Sheet '{}' is empty", sheet_name),
+            None,
+            None,
+        ));
+    }
+
+    let csv_file_path = determine_imported_csv_path(spreadsheet_path, sheet_name)?;
+    write_rows_as_csv_file(&sheet_rows, &csv_file_path)?;
+
+    Ok(csv_file_path)
+}
+
+#[cfg(test)]
+mod spreadsheet_import_tests {
+    use super::*;
+
+    /// Builds an uncompressed ZIP archive from (name, contents) pairs
+    fn build_stored_zip(zip_entries: &[(&str, &str)]) -> Vec<u8> {
+        let mut archive_bytes = Vec::new();
+        let mut central_directory = Vec::new();
+
+        for (entry_name, entry_text) in zip_entries {
+            let entry_bytes = entry_text.as_bytes();
+            let entry_crc32 = calculate_crc32(entry_bytes);
+            let local_header_offset = archive_bytes.len() as u32;
+
+            archive_bytes.extend_from_slice(&ZIP_LOCAL_FILE_HEADER_SIGNATURE.to_le_bytes());
+            archive_bytes.extend_from_slice(&[20, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+            archive_bytes.extend_from_slice(&entry_crc32.to_le_bytes());
+            archive_bytes.extend_from_slice(&(entry_bytes.len() as u32).to_le_bytes());
+            archive_bytes.extend_from_slice(&(entry_bytes.len() as u32).to_le_bytes());
+            archive_bytes.extend_from_slice(&(entry_name.len() as u16).to_le_bytes());
+            archive_bytes.extend_from_slice(&0u16.to_le_bytes());
+            archive_bytes.extend_from_slice(entry_name.as_bytes());
+            archive_bytes.extend_from_slice(entry_bytes);
+
+            central_directory.extend_from_slice(&ZIP_CENTRAL_DIRECTORY_SIGNATURE.to_le_bytes());
+            central_directory.extend_from_slice(&[20, 0, 20, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+            central_directory.extend_from_slice(&entry_crc32.to_le_bytes());
+            central_directory.extend_from_slice(&(entry_bytes.len() as u32).to_le_bytes());
+            central_directory.extend_from_slice(&(entry_bytes.len() as u32).to_le_bytes());
+            central_directory.extend_from_slice(&(entry_name.len() as u16).to_le_bytes());
+            central_directory.extend_from_slice(&[0; 12]);
+            central_directory.extend_from_slice(&local_header_offset.to_le_bytes());
+            central_directory.extend_from_slice(entry_name.as_bytes());
+        }
+
+        let directory_offset = archive_bytes.len() as u32;
+        archive_bytes.extend_from_slice(&central_directory);
+        archive_bytes.extend_from_slice(&ZIP_END_OF_CENTRAL_DIRECTORY_SIGNATURE.to_le_bytes());
+        archive_bytes.extend_from_slice(&[0, 0, 0, 0]);
+        archive_bytes.extend_from_slice(&(zip_entries.len() as u16).to_le_bytes());
+        archive_bytes.extend_from_slice(&(zip_entries.len() as u16).to_le_bytes());
+        archive_bytes.extend_from_slice(&(central_directory.len() as u32).to_le_bytes());
+        archive_bytes.extend_from_slice(&directory_offset.to_le_bytes());
+        archive_bytes.extend_from_slice(&0u16.to_le_bytes());
+        archive_bytes
+    }
+
+    /// Writes bytes to a fresh temporary directory and returns the file path
+    fn write_temp_file(test_name: &str, file_name: &str, file_bytes: &[u8]) -> PathBuf {
+        let test_directory = std::env::temp_dir()
+            .join(format!("rc_spreadsheet_tests_{}_{}", test_name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&test_directory);
+        std::fs::create_dir_all(&test_directory).expect("create test directory");
+
+        let file_path = test_directory.join(file_name);
+        std::fs::write(&file_path, file_bytes).expect("write test file");
+        file_path
+    }
+
+    #[test]
+    fn test_crc32_known_value() {
+        assert_eq!(calculate_crc32(b"123456789"), 0xCBF4_3926);
+        assert_eq!(calculate_crc32(b""), 0);
+    }
+
+    #[test]
+    fn test_inflate_stored_fixed_and_dynamic_blocks() {
+        // Stored block: "abc"
+        let stored_stream = [0x01, 0x03, 0x00, 0xFC, 0xFF, b'a', b'b', b'c'];
+        assert_eq!(inflate_deflate_stream(&stored_stream, 3).unwrap(), b"abc");
+
+        // Fixed Huffman block with a back-reference: "hello hello hello"
+        let fixed_stream = [
+            0xcb, 0x48, 0xcd, 0xc9, 0xc9, 0x57, 0xc8, 0x40, 0x90, 0x00,
+        ];
+        assert_eq!(inflate_deflate_stream(&fixed_stream, 17).unwrap(), b"hello hello hello");
+
+        // Dynamic Huffman block over 50 pseudo-random letters from "aaaabbc"
+        let dynamic_stream = [
+            0x25, 0x8a, 0xc1, 0x11, 0x00, 0x00, 0x0c, 0xc1, 0x66, 0x4d, 0xec, 0xbf,
+            0x43, 0xb5, 0xe5, 0xe1, 0x04, 0x94, 0x84, 0xa0, 0xa9, 0x79, 0x2d, 0x38,
+            0xf6, 0x65, 0x2f, 0xdd, 0x9b, 0xea, 0x00,
+        ];
+        let mut generator_state: u64 = 1;
+        let expected_bytes: Vec<u8> = (0..50)
+            .map(|_| {
+                generator_state = (generator_state * 1_103_515_245 + 12_345) % (1 << 31);
+                b"aaaabbc"[((generator_state >> 16) % 7) as usize]
+            })
+            .collect();
+        assert_eq!(inflate_deflate_stream(&dynamic_stream, 50).unwrap(), expected_bytes);
+
+        assert!(inflate_deflate_stream(&[0x07], 0).is_err());
+
+        // Output past the recorded size, or a size over the hard cap, is refused
+        assert!(inflate_deflate_stream(&stored_stream, 2).is_err());
+        assert!(inflate_deflate_stream(&fixed_stream, 16).is_err());
+        assert!(inflate_deflate_stream(&dynamic_stream, 49).is_err());
+        assert!(inflate_deflate_stream(&stored_stream, INFLATE_MAX_OUTPUT_BYTES + 1).is_err());
+    }
+
+    #[test]
+    fn test_xml_pull_parser_events() {
+        let xml_text = "<?xml version=\"1.0\"?><!-- note --><a x=\"1 &amp; 2\" y='q'><b/>t&lt;x&#65;<![CDATA[<raw>]]></a>";
+        let mut xml_parser = XmlPullParser::new(xml_text, "test.xml");
+        let mut xml_events = Vec::new();
+        while let Some(xml_event) = xml_parser.next_event().unwrap() {
+            xml_events.push(xml_event);
+        }
+
+        assert_eq!(xml_events, vec![
+            XmlEvent::StartElement {
+                element_name: "a".to_string(),
+                attributes: vec![("x".to_string(), "1 & 2".to_string()), ("y".to_string(), "q".to_string())],
+                is_self_closing: false,
+            },
+            XmlEvent::StartElement {
+                element_name: "b".to_string(),
+                attributes: Vec::new(),
+                is_self_closing: true,
+            },
+            XmlEvent::Text("t<xA".to_string()),
+            XmlEvent::Text("<raw>".to_string()),
+            XmlEvent::EndElement { element_name: "a".to_string() },
+        ]);
+
+        assert!(XmlPullParser::new("<a", "bad.xml").next_event().is_err());
+        assert_eq!(xml_local_name("table:table-cell"), "table-cell");
+    }
+
+    #[test]
+    fn test_xlsx_sheet_import() {
+        let archive_bytes = build_stored_zip(&[
+            ("xl/workbook.xml", r#"<workbook xmlns:r="rel"><sheets>
+                <sheet name="Summary" sheetId="1" r:id="rId1"/>
+                <sheet name="Raw Data" sheetId="2" r:id="rId2"/></sheets></workbook>"#),
+            ("xl/_rels/workbook.xml.rels", r#"<Relationships>
+                <Relationship Id="rId1" Target="worksheets/sheet1.xml"/>
+                <Relationship Id="rId2" Target="/xl/worksheets/sheet2.xml"/></Relationships>"#),
+            ("xl/sharedStrings.xml", r#"<sst><si><t>name</t></si><si><t>score</t></si>
+                <si><r><t>Lu, </t></r><r><t>Luna</t></r><rPh><t>x</t></rPh></si><si><t>Max</t></si></sst>"#),
+            ("xl/worksheets/sheet1.xml", "<worksheet><sheetData/></worksheet>"),
+            ("xl/worksheets/sheet2.xml", r#"<worksheet><sheetData>
+                <row r="1"><c r="A1" t="s"><v>0</v></c><c r="B1" t="s"><v>1</v></c><c r="C1" t="inlineStr"><is><t>ok</t></is></c></row>
+                <row r="2"><c r="A2" t="s"><v>2</v></c><c r="B2"><v>9.5</v></c><c r="C2" t="b"><v>1</v></c></row>
+                <row r="3"><c r="A3" t="s"><v>3</v></c><c r="C3" t="b"><v>0</v></c></row>
+                </sheetData></worksheet>"#),
+        ]);
+        let xlsx_path = write_temp_file("xlsx", "book.xlsx", &archive_bytes);
+
+        let workbook = open_spreadsheet_workbook(&xlsx_path).unwrap();
+        assert_eq!(workbook.sheet_names, vec!["Summary", "Raw Data"]);
+        assert_eq!(workbook.find_sheet_index("raw data"), Some(1));
+        assert_eq!(workbook.find_sheet_index("1"), Some(0));
+        assert_eq!(workbook.find_sheet_index("3"), None);
+
+        assert!(import_spreadsheet_sheet_to_csv(&xlsx_path, &workbook, 0).is_err());
+
+        let csv_path = import_spreadsheet_sheet_to_csv(&xlsx_path, &workbook, 1).unwrap();
+        assert_eq!(csv_path.file_name().unwrap(), "book_Raw_Data.csv");
+        assert_eq!(
+            std::fs::read_to_string(&csv_path).unwrap(),
+            "name,score,ok\n\"Lu, Luna\",9.5,true\nMax,,false\n"
+        );
+
+        // Importing again archives the earlier CSV instead of overwriting it
+        std::fs::write(&csv_path, "edited by hand\n").unwrap();
+        assert_eq!(import_spreadsheet_sheet_to_csv(&xlsx_path, &workbook, 1).unwrap(), csv_path);
+        assert!(std::fs::read_to_string(&csv_path).unwrap().starts_with("name,score,ok\n"));
+        let archived_files: Vec<PathBuf> = std::fs::read_dir(xlsx_path.parent().unwrap().join("archive"))
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .collect();
+        assert_eq!(archived_files.len(), 1);
+        assert_eq!(std::fs::read_to_string(&archived_files[0]).unwrap(), "edited by hand\n");
+
+        let _ = std::fs::remove_dir_all(xlsx_path.parent().unwrap());
+    }
+
+    #[test]
+    fn test_xlsx_row_gaps_and_reference_limits() {
+        assert_eq!(xlsx_column_index_from_reference("A1"), Some(0));
+        assert_eq!(xlsx_column_index_from_reference("xfd7"), Some(XLSX_MAX_COLUMN_COUNT - 1));
+        assert_eq!(xlsx_column_index_from_reference("XFE1"), None);
+        assert_eq!(xlsx_column_index_from_reference("ZZZZZZZZZZZZZZZZZZZZ1"), None);
+        assert_eq!(xlsx_column_index_from_reference("12"), None);
+
+        let build_workbook = |sheet_xml: &str| build_stored_zip(&[
+            ("xl/workbook.xml", r#"<workbook><sheets><sheet name="Only" r:id="rId1"/></sheets></workbook>"#),
+            ("xl/_rels/workbook.xml.rels", r#"<Relationships><Relationship Id="rId1" Target="worksheets/sheet1.xml"/></Relationships>"#),
+            ("xl/worksheets/sheet1.xml", sheet_xml),
+        ]);
+
+        // Rows 2 and 4 are absent and come back blank, matching ODS
+        let gap_path = write_temp_file("xlsx_gaps", "gaps.xlsx", &build_workbook(r#"<worksheet><sheetData>
+            <row r="1"><c r="A1" t="inlineStr"><is><t>id</t></is></c></row>
+            <row r="3"><c r="B3"><v>3</v></c></row>
+            <row r="5"><c r="A5"><v>5</v></c></row>
+            </sheetData></worksheet>"#));
+        let workbook = open_spreadsheet_workbook(&gap_path).unwrap();
+        let blank_row = vec![String::new(), String::new()];
+        assert_eq!(workbook.read_sheet_rows(0).unwrap(), vec![
+            vec!["id".to_string(), String::new()],
+            blank_row.clone(),
+            vec![String::new(), "3".to_string()],
+            blank_row,
+            vec!["5".to_string(), String::new()],
+        ]);
+        let _ = std::fs::remove_dir_all(gap_path.parent().unwrap());
+
+        // Columns past XFD and rows outside the sheet are format errors
+        for (test_name, sheet_xml) in [
+            ("xlsx_wide", r#"<worksheet><sheetData><row r="1"><c r="XFE1"><v>1</v></c></row></sheetData></worksheet>"#),
+            ("xlsx_many", &format!("<worksheet><sheetData><row>{}</row></sheetData></worksheet>", "<c/>".repeat(XLSX_MAX_COLUMN_COUNT + 1))),
+            ("xlsx_row0", r#"<worksheet><sheetData><row r="0"><c r="A1"><v>1</v></c></row></sheetData></worksheet>"#),
+            ("xlsx_tall", r#"<worksheet><sheetData><row r="1048577"><c><v>1</v></c></row></sheetData></worksheet>"#),
+        ] {
+            let bad_path = write_temp_file(test_name, "bad.xlsx", &build_workbook(sheet_xml));
+            let workbook = open_spreadsheet_workbook(&bad_path).unwrap();
+            assert!(workbook.read_sheet_rows(0).is_err(), "{} should be rejected", test_name);
+            let _ = std::fs::remove_dir_all(bad_path.parent().unwrap());
+        }
+    }
+
+    #[test]
+    fn test_ods_sheet_import_with_repeats() {
+        let archive_bytes = build_stored_zip(&[
+            ("mimetype", "application/vnd.oasis.opendocument.spreadsheet"),
+            ("content.xml", r#"<office:document-content><office:body><office:spreadsheet>
+                <table:table table:name="First"><table:table-row><table:table-cell office:value-type="string"><text:p>skip</text:p></table:table-cell></table:table-row></table:table>
+                <table:table table:name="Second">
+                  <table:table-row>
+                    <table:table-cell office:value-type="string"><text:p>id</text:p></table:table-cell>
+                    <table:table-cell office:value-type="string"><text:p>note</text:p></table:table-cell>
+                    <table:table-cell table:number-columns-repeated="1020"/>
+                  </table:table-row>
+                  <table:table-row table:number-rows-repeated="2">
+                    <table:table-cell office:value-type="float" office:value="7"><text:p>7.00</text:p></table:table-cell>
+                    <table:table-cell office:value-type="string"><text:p>a<text:s text:c="2"/>b</text:p><text:p>c</text:p></table:table-cell>
+                  </table:table-row>
+                  <table:table-row table:number-rows-repeated="1048570"><table:table-cell table:number-columns-repeated="1024"/></table:table-row>
+                </table:table>
+              </office:spreadsheet></office:body></office:document-content>"#),
+        ]);
+        let ods_path = write_temp_file("ods", "sheet.ods", &archive_bytes);
+
+        let workbook = open_spreadsheet_workbook(&ods_path).unwrap();
+        assert_eq!(workbook.sheet_names, vec!["First", "Second"]);
+
+        let sheet_rows = workbook.read_sheet_rows(1).unwrap();
+        assert_eq!(sheet_rows, vec![
+            vec!["id".to_string(), "note".to_string()],
+            vec!["7".to_string(), "a  b\nc".to_string()],
+            vec!["7".to_string(), "a  b\nc".to_string()],
+        ]);
+
+        let csv_path = import_spreadsheet_sheet_to_csv(&ods_path, &workbook, 1).unwrap();
+        assert_eq!(std::fs::read_to_string(&csv_path).unwrap(), "id,note\n7,a  b c\n7,a  b c\n");
+
+        let _ = std::fs::remove_dir_all(ods_path.parent().unwrap());
+    }
+
+    #[test]
+    fn test_repeats_count_against_a_total_cell_budget() {
+        let build_ods = |table_xml: &str| build_stored_zip(&[
+            ("mimetype", "application/vnd.oasis.opendocument.spreadsheet"),
+            ("content.xml", &format!(
+                r#"<office:document-content><office:body><office:spreadsheet><table:table table:name="Only">{}</table:table></office:spreadsheet></office:body></office:document-content>"#,
+                table_xml
+            )),
+        ]);
+
+        // Past the old per-attribute cap, but within the budget: nothing is cut
+        let long_path = write_temp_file("ods_long", "long.ods", &build_ods(
+            r#"<table:table-row table:number-rows-repeated="20000"><table:table-cell office:value-type="float" office:value="1"/></table:table-row>"#,
+        ));
+        let workbook = open_spreadsheet_workbook(&long_path).unwrap();
+        assert_eq!(workbook.read_sheet_rows(0).unwrap().len(), 20_000);
+        let _ = std::fs::remove_dir_all(long_path.parent().unwrap());
+
+        // Row and column repeats multiply; too many cells is an error, not a clamp
+        for (test_name, table_xml) in [
+            ("ods_square", r#"<table:table-row table:number-rows-repeated="16384"><table:table-cell table:number-columns-repeated="16384" office:value-type="float" office:value="1"/></table:table-row>"#),
+            ("ods_huge", r#"<table:table-row><table:table-cell table:number-columns-repeated="99999999999999999999999" office:value-type="float" office:value="1"/></table:table-row>"#),
+        ] {
+            let big_path = write_temp_file(test_name, "big.ods", &build_ods(table_xml));
+            let workbook = open_spreadsheet_workbook(&big_path).unwrap();
+            assert!(workbook.read_sheet_rows(0).is_err(), "{} should be rejected", test_name);
+            let _ = std::fs::remove_dir_all(big_path.parent().unwrap());
+        }
+
+        // Padding rows out to the widest one counts too
+        let padded_path = write_temp_file("xlsx_padded", "padded.xlsx", &build_stored_zip(&[
+            ("xl/workbook.xml", r#"<workbook><sheets><sheet name="Only" r:id="rId1"/></sheets></workbook>"#),
+            ("xl/_rels/workbook.xml.rels", r#"<Relationships><Relationship Id="rId1" Target="worksheets/sheet1.xml"/></Relationships>"#),
+            ("xl/worksheets/sheet1.xml", r#"<worksheet><sheetData><row r="1048576"><c r="XFD1048576"><v>1</v></c></row></sheetData></worksheet>"#),
+        ]));
+        let workbook = open_spreadsheet_workbook(&padded_path).unwrap();
+        assert!(workbook.read_sheet_rows(0).is_err());
+        let _ = std::fs::remove_dir_all(padded_path.parent().unwrap());
+    }
+}
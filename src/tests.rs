@@ -1,7 +1,9 @@
 // tests.rs (keen this in src/ with main.rs)
 
 #[cfg(test)]
-use crate::lines_editor_module::double_width::is_double_width;
+use crate::lines_editor_module::double_width::{
+    char_display_width, is_double_width, is_zero_width, str_display_width,
+};
 
 #[cfg(test)]
 use crate::lines_editor_module::*;
@@ -56,8 +58,8 @@ pub fn calculate_display_width(text: &str) -> Option<usize> {
         }
         char_count += 1;
 
-        // Add 2 for double-width, 1 for single-width
-        let char_width = if is_double_width(c) { 2 } else { 1 };
+        // Add 2 for double-width, 1 for single-width, 0 for combining marks
+        let char_width = char_display_width(c);
 
         // Check for overflow before adding
         width = width.checked_add(char_width)?;
@@ -364,6 +366,27 @@ mod char_width_tests {
         assert_eq!(calculate_display_width("ＡＢＣ"), Some(6));
     }
 
+    #[test]
+    fn test_zero_width_and_emoji() {
+        // Combining marks and joiners take no column of their own
+        assert!(is_zero_width('\u{0301}')); // combining acute accent
+        assert!(is_zero_width('\u{200D}')); // zero-width joiner
+        assert!(is_zero_width('\u{FE0F}')); // emoji presentation selector
+        assert!(!is_zero_width('e'));
+        assert!(!is_zero_width('中'));
+
+        assert_eq!(char_display_width('a'), 1);
+        assert_eq!(char_display_width('中'), 2);
+        assert_eq!(char_display_width('😀'), 2);
+        assert_eq!(char_display_width('\u{0301}'), 0);
+
+        // "été" written with combining accents is 3 columns, not 5 chars
+        assert_eq!(str_display_width("e\u{0301}te\u{0301}"), 3);
+        assert_eq!(str_display_width("📁notes"), 7);
+        assert_eq!(calculate_display_width("caf\u{00E9}"), Some(4));
+        assert_eq!(calculate_display_width("cafe\u{0301}"), Some(4));
+    }
+
     #[test]
     fn test_mixed_width_string() {
         let mixed = "Hello 世界 World";
@@ -2013,6 +2036,8 @@ mod hexedit_tests {
             read_copy_path: Some(file_path),
             effective_rows: 40, // ??? What value?
             effective_cols: 77, // ??? What value?
+            tui_rows_offset: 0,
+            tui_cols_offset: 0,
             windowmap_line_byte_start_end_position_pairs: [None; MAX_TUI_ROWS],
             security_mode: false,
 
@@ -3201,3 +3226,72 @@ mod tempname_tests {
         assert!(true);
     }
 }
+
+#[cfg(test)]
+mod window_size_tests {
+    use super::*;
+    use crate::raw_terminal_x86_module::TerminalWindowSize;
+
+    /// Editing area follows the window; tall/wide offsets stay on top
+    #[test]
+    fn test_fit_to_terminal_window_keeps_offsets() {
+        let mut state = EditorState::new();
+
+        // No window size: same as the 80x24 defaults
+        state.fit_to_terminal_window(None);
+        assert_eq!(state.effective_rows, DEFAULT_ROWS - 3);
+        assert_eq!(state.effective_cols, DEFAULT_COLS - 3);
+
+        // Larger window, with tall-2 and wide+5 offsets
+        state.tui_rows_offset = -2;
+        state.tui_cols_offset = 5;
+        state.fit_to_terminal_window(Some(TerminalWindowSize { rows: 30, cols: 100 }));
+        assert_eq!(state.effective_rows, 30 - 3 - 2);
+        assert_eq!(state.effective_cols, 100 - 3 + 5);
+
+        // Huge window clamps to the supported maximum
+        state.fit_to_terminal_window(Some(TerminalWindowSize { rows: 500, cols: 500 }));
+        assert_eq!(state.effective_rows, MAX_TUI_ROWS);
+        assert_eq!(state.effective_cols, MAX_TUI_VIZ_COLS);
+
+        // Shrinking pulls the cursor back on screen
+        state.cursor.tui_row = 40;
+        state.cursor.tui_visual_col = 150;
+        state.fit_to_terminal_window(Some(TerminalWindowSize { rows: 10, cols: 20 }));
+        assert_eq!(state.effective_rows, 5);
+        assert_eq!(state.effective_cols, 22);
+        assert_eq!(state.cursor.tui_row, 4);
+        assert_eq!(state.cursor.tui_visual_col, 21);
+
+        // Tiny window never goes below the minimum
+        state.tui_rows_offset = 0;
+        state.tui_cols_offset = 0;
+        state.fit_to_terminal_window(Some(TerminalWindowSize { rows: 1, cols: 1 }));
+        assert_eq!(state.effective_rows, MIN_TUI_ROWS);
+        assert_eq!(state.effective_cols, MIN_TUI_VIZ_COLS);
+    }
+}
+
+#[cfg(test)]
+mod mouse_click_tests {
+    use super::*;
+
+    /// Clicks map to TUI rows only where the window map shows a file line
+    #[test]
+    fn test_tui_row_for_mouse_click_uses_windowmap() {
+        let mut state = EditorState::new();
+        state.windowmap_line_byte_start_end_position_pairs[0] = Some((0, 5));
+        state.windowmap_line_byte_start_end_position_pairs[1] = Some((6, 12));
+
+        // Screen row 1 is the legend; row 2 is TUI row 0
+        assert_eq!(tui_row_for_mouse_click(&state, 1), None);
+        assert_eq!(tui_row_for_mouse_click(&state, 2), Some(0));
+        assert_eq!(tui_row_for_mouse_click(&state, 3), Some(1));
+
+        // Below the end of the file, and below the text area
+        assert_eq!(tui_row_for_mouse_click(&state, 4), None);
+        let below_text_area = (state.effective_rows + FIRST_TEXT_ROW_ON_SCREEN) as u16;
+        assert_eq!(tui_row_for_mouse_click(&state, below_text_area), None);
+        assert_eq!(tui_row_for_mouse_click(&state, u16::MAX), None);
+    }
+}
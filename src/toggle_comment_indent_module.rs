@@ -414,8 +414,11 @@ fn print_usage() {
     eprintln!();
 
     eprintln!("SUPPORTED EXTENSIONS:");
-    eprintln!("  //  : rs, c, cpp, js, ts, java, go, swift");
-    eprintln!("  #   : py, sh, toml, yaml, rb, pl, r");
+    eprintln!("  //  : rs, c, cpp, js, ts, java, go, swift, cs, kt, scala, dart, php, zig, ...");
+    eprintln!("  #   : py, sh, toml, yaml, rb, pl, r, jl, ex, cmake, conf, ...");
+    eprintln!("  --  : sql, lua, hs, elm, ada, vhdl");
+    eprintln!("  ;   : lisp, clj, scm, rkt, el, ini");
+    eprintln!("  %   : erl, tex");
     eprintln!();
 
     eprintln!("EXIT CODES:");
@@ -1280,7 +1283,7 @@ impl std::error::Error for ToggleCommentError {}
 
 /// Comment flag type for different language syntaxes
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum CommentFlag {
+pub enum CommentFlag {
     /// Tripple Slash for Rust-Docstrings
     TripppleSlash,
 
@@ -1289,27 +1292,35 @@ enum CommentFlag {
 
     /// Hash/pound comments (Python, Shell, TOML, etc.)
     Hash,
+
+    /// Double-dash comments (SQL, Lua, Haskell, Ada, etc.)
+    DoubleDash,
+
+    /// Semicolon comments (Lisp family, WebAssembly text, INI)
+    Semicolon,
+
+    /// Percent comments (Erlang, LaTeX)
+    Percent,
 }
 
-// impl CommentFlag {
-//     /// Get the byte slice representation of the comment flag
-//     fn as_bytes(&self) -> &'static [u8] {
-//         match self {
-//             CommentFlag::TripppleSlash => b"///",
-//             CommentFlag::DoubleSlash => b"//",
-//             CommentFlag::Hash => b"#",
-//         }
-//     }
+impl CommentFlag {
+    /// Get the byte slice representation of the comment flag
+    fn as_bytes(&self) -> &'static [u8] {
+        self.as_str().as_bytes()
+    }
 
-//     /// Get the string representation of the comment flag
-//     fn as_str(&self) -> &'static str {
-//         match self {
-//             CommentFlag::TripppleSlash => "///",
-//             CommentFlag::DoubleSlash => "//",
-//             CommentFlag::Hash => "#",
-//         }
-//     }
-// }
+    /// Get the string representation of the comment flag
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CommentFlag::TripppleSlash => "///",
+            CommentFlag::DoubleSlash => "//",
+            CommentFlag::Hash => "#",
+            CommentFlag::DoubleDash => "--",
+            CommentFlag::Semicolon => ";",
+            CommentFlag::Percent => "%",
+        }
+    }
+}
 
 /// Determine comment flag based on file extension
 ///
@@ -1321,20 +1332,52 @@ enum CommentFlag {
 /// * `None` if extension is not recognized
 ///
 /// # Supported Extensions
-/// - `//` : rs, c, cpp, cc, cxx, h, hpp, js, ts, java, go, swift
-/// - `#`  : py, sh, bash, toml, yaml, yml, rb, pl, r
-fn determine_comment_flag(extension: &str) -> Option<CommentFlag> {
+/// - `//` : rs, c, cpp, cc, cxx, h, hpp, hh, hxx, js, mjs, cjs, jsx, ts, tsx, mts, cts,
+///   java, go, swift, cs, csx, kt, kts, scala, sc, mm, dart, php, phtml, zig, d, di,
+///   fs, fsi, fsx, scss, sass, less, styl, stylus, jsonc, json5, gradle, sbt
+/// - `#`  : py, pyw, pyx, pyi, sh, bash, zsh, fish, ksh, tcsh, csh, ps1, psm1, psd1,
+///   toml, tml, yaml, yml, rb, rake, pl, pm, r, jl, nim, nims, cr, ex, exs, awk,
+///   cmake, make, mak, mk, bazel, bzl, ninja, pri, cfg, cnf, conf, config,
+///   properties, env
+/// - `--` : sql, mysql, psql, cql, lua, hs, elm, ada, adb, ads, vhd, vhdl
+/// - `;`  : lisp, lsp, el, clj, cljs, cljc, rkt, scm, ss, wat, ini
+/// - `%`  : erl, hrl, tex, latex
+///
+/// Extensions shared by languages with different comment syntax (`m`:
+/// Objective-C or MATLAB, `s`/`asm`: GNU or NASM assembly, `pp`: Pascal or
+/// Puppet, `cl`: Common Lisp or OpenCL, `v`: V, Verilog or Coq, `pro`: Qt
+/// project or Prolog) are left out, so toggling never writes the wrong marker.
+pub fn determine_comment_flag(extension: &str) -> Option<CommentFlag> {
     match extension.to_lowercase().as_str() {
         // Double-slash languages
-        "rs" | "c" | "cpp" | "cc" | "cxx" | "h" | "hpp" | "js" | "ts" | "java" | "go" | "swift" => {
+        "rs" | "c" | "cpp" | "cc" | "cxx" | "h" | "hpp" | "hh" | "hxx" | "js" | "mjs"
+        | "cjs" | "jsx" | "ts" | "tsx" | "mts" | "cts" | "java" | "go" | "swift" | "cs"
+        | "csx" | "kt" | "kts" | "scala" | "sc" | "mm" | "dart" | "php" | "phtml"
+        | "zig" | "d" | "di" | "fs" | "fsi" | "fsx" | "scss" | "sass" | "less"
+        | "styl" | "stylus" | "jsonc" | "json5" | "gradle" | "sbt" => {
             Some(CommentFlag::DoubleSlash)
         }
 
         // Hash languages
-        "py" | "sh" | "bash" | "toml" | "yaml" | "yml" | "rb" | "pl" | "r" => {
+        "py" | "pyw" | "pyx" | "pyi" | "sh" | "bash" | "zsh" | "fish" | "ksh" | "tcsh"
+        | "csh" | "ps1" | "psm1" | "psd1" | "toml" | "tml" | "yaml" | "yml" | "rb"
+        | "rake" | "pl" | "pm" | "r" | "jl" | "nim" | "nims" | "cr" | "ex" | "exs"
+        | "awk" | "cmake" | "make" | "mak" | "mk" | "bazel" | "bzl" | "ninja" | "pri"
+        | "cfg" | "cnf" | "conf" | "config" | "properties" | "env" => {
             Some(CommentFlag::Hash)
         }
 
+        // Double-dash languages
+        "sql" | "mysql" | "psql" | "cql" | "lua" | "hs" | "elm" | "ada" | "adb" | "ads"
+        | "vhd" | "vhdl" => Some(CommentFlag::DoubleDash),
+
+        // Semicolon languages
+        "lisp" | "lsp" | "el" | "clj" | "cljs" | "cljc" | "rkt" | "scm" | "ss"
+        | "wat" | "ini" => Some(CommentFlag::Semicolon),
+
+        // Percent languages
+        "erl" | "hrl" | "tex" | "latex" => Some(CommentFlag::Percent),
+
         // Unknown extension
         _ => None,
     }
@@ -1345,10 +1388,12 @@ fn determine_comment_flag(extension: &str) -> Option<CommentFlag> {
 // ================
 
 /// Block comment markers for different languages
+///
+/// Each marker is a whole line, including its trailing newline.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-struct BlockMarkers {
-    start: &'static [u8],
-    end: &'static [u8],
+pub struct BlockMarkers {
+    pub start: &'static [u8],
+    pub end: &'static [u8],
 }
 
 /// Determine block comment markers from file extension
@@ -1359,10 +1404,14 @@ struct BlockMarkers {
 /// # Returns
 /// * `Some(BlockMarkers)` - Start and end markers for this language
 /// * `None` - Extension not supported for block comments
-fn determine_block_markers(extension: &str) -> Option<BlockMarkers> {
+pub fn determine_block_markers(extension: &str) -> Option<BlockMarkers> {
     match extension.to_lowercase().as_str() {
         // C-style block comments: /* */
-        "rs" | "c" | "cpp" | "cc" | "cxx" | "h" | "hpp" | "js" | "ts" | "java" | "go" | "swift" => {
+        "rs" | "c" | "cpp" | "cc" | "cxx" | "h" | "hpp" | "hh" | "hxx" | "js" | "mjs"
+        | "cjs" | "jsx" | "ts" | "tsx" | "mts" | "cts" | "java" | "go" | "swift" | "cs"
+        | "csx" | "kt" | "kts" | "scala" | "sc" | "mm" | "dart" | "php" | "phtml"
+        | "zig" | "d" | "di" | "css" | "scss" | "less" | "styl" | "stylus"
+        | "jsonc" | "json5" | "gradle" | "sbt" | "sql" | "mysql" | "psql" | "cql" => {
             Some(BlockMarkers {
                 start: b"/*\n",
                 end: b"*/\n",
@@ -1370,11 +1419,36 @@ fn determine_block_markers(extension: &str) -> Option<BlockMarkers> {
         }
 
         // Python triple-quote: """ """
-        "py" => Some(BlockMarkers {
+        "py" | "pyw" | "pyx" | "pyi" => Some(BlockMarkers {
             start: b"\"\"\"\n",
             end: b"\"\"\"\n",
         }),
 
+        // Markup comments: <!-- -->
+        "html" | "htm" | "xhtml" | "xml" | "xsd" | "xsl" | "xslt" | "vue" | "svelte"
+        | "md" | "markdown" | "mdown" => Some(BlockMarkers {
+            start: b"<!--\n",
+            end: b"-->\n",
+        }),
+
+        // Lua long comments: --[[ ]]
+        "lua" => Some(BlockMarkers {
+            start: b"--[[\n",
+            end: b"]]\n",
+        }),
+
+        // Haskell / Elm nested comments: {- -}
+        "hs" | "elm" => Some(BlockMarkers {
+            start: b"{-\n",
+            end: b"-}\n",
+        }),
+
+        // ML family / Pascal: (* *)
+        "ml" | "mli" | "fs" | "fsi" | "fsx" | "pas" => Some(BlockMarkers {
+            start: b"(*\n",
+            end: b"*)\n",
+        }),
+
         // Shell/TOML/YAML don't have block comments
         _ => None,
     }
@@ -1586,6 +1660,23 @@ fn find_and_detect_tag_state(
                 }
             }
         }
+
+        CommentFlag::DoubleDash | CommentFlag::Semicolon | CommentFlag::Percent => {
+            // Pattern: flag then space at column 0, e.g. "-- ", "; ", "% "
+            let flag_bytes = comment_flag.as_bytes();
+            let mut matched = byte_bucket[0] == flag_bytes[0];
+            for &expected_byte in flag_bytes[1..].iter().chain(b" ") {
+                if !matched {
+                    break;
+                }
+                matched = match file.read(&mut byte_bucket) {
+                    Ok(0) => false,
+                    Ok(_) => byte_bucket[0] == expected_byte,
+                    Err(_) => return Err(ToggleCommentError::IoError(IoOperation::Read)),
+                };
+            }
+            matched
+        }
     };
 
     Ok(Some((line_start_pos, has_tag)))
@@ -1667,6 +1758,9 @@ fn write_toggled_file_bytewise(
             CommentFlag::Hash => 2,          // "# "
             CommentFlag::DoubleSlash => 3,   // "// "
             CommentFlag::TripppleSlash => 4, // "/// "
+            CommentFlag::DoubleDash | CommentFlag::Semicolon | CommentFlag::Percent => {
+                comment_flag.as_bytes().len() + 1 // flag + " "
+            }
         };
 
         // Skip the tag bytes
@@ -1722,6 +1816,13 @@ fn write_toggled_file_bytewise(
                     return Err(ToggleCommentError::IoError(IoOperation::Write));
                 }
             }
+            CommentFlag::DoubleDash | CommentFlag::Semicolon | CommentFlag::Percent => {
+                if writer.write_all(comment_flag.as_bytes()).is_err()
+                    || writer.write_all(b" ").is_err()
+                {
+                    return Err(ToggleCommentError::IoError(IoOperation::Write));
+                }
+            }
         }
 
         // Copy rest of line
@@ -2293,6 +2394,25 @@ mod bytewise_tests {
         ]);
     }
 
+    #[test]
+    fn test_bytewise_toggle_sql_roundtrip() {
+        let content = "SELECT 1;\n";
+        let test_file = create_test_file("test_bytewise_sql.sql", content);
+
+        let result = toggle_basic_singleline_comment_bytewise(test_file.to_str().unwrap(), 0);
+        assert!(result.is_ok());
+        assert_eq!(read_file_content(&test_file), "-- SELECT 1;\n");
+
+        let result = toggle_basic_singleline_comment_bytewise(test_file.to_str().unwrap(), 0);
+        assert!(result.is_ok());
+        assert_eq!(read_file_content(&test_file), content);
+
+        cleanup_files(&[
+            &test_file,
+            &PathBuf::from("backup_toggle_comment_test_bytewise_sql.sql"),
+        ]);
+    }
+
     #[test]
     fn test_bytewise_line_not_found_error() {
         let content = "line 0\nline 1\n";
@@ -3696,6 +3816,62 @@ mod range_toggle_bytewise_tests {
         ]);
     }
 
+    #[test]
+    fn test_range_bytewise_other_line_markers() {
+        // (file name, lines, lines with 1..=2 toggled)
+        let cases = [
+            ("test_range_bw_markers.lisp", "a\nb\nc\n", "a\n; b\n; c\n"),
+            ("test_range_bw_markers.erl", "a\nb\nc\n", "a\n% b\n% c\n"),
+            ("test_range_bw_markers.lua", "a\n-- b\nc\n", "a\nb\n-- c\n"),
+            ("test_range_bw_markers.kt", "a\nb\nc\n", "a\n// b\n// c\n"),
+            ("test_range_bw_markers.jl", "a\nb\nc\n", "a\n# b\n# c\n"),
+        ];
+
+        for (file_name, content, expected) in cases {
+            let test_file = create_test_file(file_name, content);
+
+            let result = toggle_range_basic_comments_bytewise(test_file.to_str().unwrap(), 1, 2);
+            assert!(result.is_ok(), "{}", file_name);
+            assert_eq!(read_file_content(&test_file), expected, "{}", file_name);
+
+            // Toggling again restores the original lines
+            let result = toggle_range_basic_comments_bytewise(test_file.to_str().unwrap(), 1, 2);
+            assert!(result.is_ok(), "{}", file_name);
+            assert_eq!(read_file_content(&test_file), content, "{}", file_name);
+
+            cleanup_files(&[
+                &test_file,
+                &PathBuf::from(format!("backup_toggle_comment_{}", file_name)),
+            ]);
+        }
+    }
+
+    #[test]
+    fn test_range_bytewise_ambiguous_extensions_unchanged() {
+        // These extensions name more than one language, so no marker is guessed
+        for file_name in [
+            "test_range_bw_ambiguous.m",
+            "test_range_bw_ambiguous.s",
+            "test_range_bw_ambiguous.asm",
+            "test_range_bw_ambiguous.pp",
+            "test_range_bw_ambiguous.cl",
+            "test_range_bw_ambiguous.v",
+            "test_range_bw_ambiguous.pro",
+        ] {
+            let content = "a\nb\nc\n";
+            let test_file = create_test_file(file_name, content);
+
+            let result = toggle_range_basic_comments_bytewise(test_file.to_str().unwrap(), 0, 2);
+            assert!(result.is_ok(), "{}", file_name);
+            assert_eq!(read_file_content(&test_file), content, "{}", file_name);
+
+            cleanup_files(&[
+                &test_file,
+                &PathBuf::from(format!("backup_toggle_comment_{}", file_name)),
+            ]);
+        }
+    }
+
     #[test]
     fn test_range_bytewise_roundtrip() {
         let original = "line 0\nline 1\nline 2\n";